@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Entities without a player nearby only tick every few ticks, saving time on idle mobs
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ActivationRangeConfig {
+    /// Should entities far away from players tick less often?
+    pub enabled: bool,
+    /// Distance in blocks a player has to be within for monsters to tick every tick
+    pub monsters: f64,
+    /// Distance in blocks a player has to be within for all other mobs to tick every tick
+    pub creatures: f64,
+    /// Inactive entities only tick once every this many ticks
+    pub inactive_tick_interval: u64,
+}
+
+impl Default for ActivationRangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            monsters: 32.0,
+            creatures: 32.0,
+            inactive_tick_interval: 20,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AutosaveConfig {
+    /// Should online players and modified chunks be saved periodically?
+    pub enabled: bool,
+    /// The interval in seconds between two autosaves
+    pub interval: u64,
+    /// How many players are saved per tick, spreading the work over multiple ticks
+    pub players_per_tick: usize,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: 300,
+            players_per_tick: 1,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct BroadcastConfig {
+    /// Put in front of every `/broadcast` message. Supports `&` formatting codes
+    pub prefix: String,
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self {
+            prefix: "&6[&cBroadcast&6]&r ".to_string(),
+        }
+    }
+}
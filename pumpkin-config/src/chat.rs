@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// The placeholders that can be used in the chat format
+const PLACEHOLDERS: [&str; 4] = ["player", "displayname", "world", "message"];
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChatConfig {
+    /// How chat messages are displayed. Supports `{player}`, `{displayname}`, `{world}` and `{message}`.
+    /// Signed chat is only kept when this is left at the vanilla format `<{displayname}> {message}`
+    pub format: String,
+    /// Only deliver chat messages to players within `local_radius` blocks of the sender
+    pub local: bool,
+    /// Worlds which use local chat even when `local` is off, e.g. `overworld`
+    pub local_worlds: Vec<String>,
+    /// How far, in blocks, local chat messages can be heard
+    pub local_radius: f64,
+    /// Messages starting with this prefix are sent to the whole world even when chat is local
+    pub global_prefix: String,
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            format: ChatConfig::VANILLA_FORMAT.to_string(),
+            local: false,
+            local_worlds: Vec::new(),
+            local_radius: 100.0,
+            global_prefix: "!".to_string(),
+        }
+    }
+}
+
+impl ChatConfig {
+    pub const VANILLA_FORMAT: &str = "<{displayname}> {message}";
+
+    /// Whether messages are displayed like in vanilla, so they can be sent as signed player chat
+    pub fn is_vanilla_format(&self) -> bool {
+        self.format == Self::VANILLA_FORMAT
+    }
+
+    /// Fills the placeholders of the format
+    pub fn format(&self, player: &str, display_name: &str, world: &str, message: &str) -> String {
+        // The message is replaced last, so placeholders typed by players are not expanded
+        self.format
+            .replace("{player}", player)
+            .replace("{displayname}", display_name)
+            .replace("{world}", world)
+            .replace("{message}", message)
+    }
+
+    /// Whether chat messages in the given world only reach nearby players
+    pub fn is_local(&self, world: &str) -> bool {
+        self.local || self.local_worlds.iter().any(|local| local == world)
+    }
+
+    pub fn validate(&self) {
+        assert!(
+            self.format.contains("{message}"),
+            "Chat format must contain {{message}}"
+        );
+        assert!(
+            self.local_radius > 0.0,
+            "Local chat radius must be greater than 0"
+        );
+        let mut rest = self.format.as_str();
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                panic!("Chat format has an unclosed placeholder: {}", self.format);
+            };
+            let placeholder = &rest[start + 1..start + end];
+            assert!(
+                PLACEHOLDERS.contains(&placeholder),
+                "Unknown chat format placeholder {{{placeholder}}}, supported are {}",
+                PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+            );
+            rest = &rest[start + end + 1..];
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use pumpkin_util::PermissionLvl;
 use serde::{Deserialize, Serialize};
 
@@ -8,8 +10,31 @@ pub struct CommandsConfig {
     pub use_console: bool,
     /// Should be commands from players be logged in console?
     pub log_console: bool, // TODO: commands...
+    /// Prints the feedback of console commands to stdout instead of logging it. By default the
+    /// feedback is logged, so it also ends up wherever the log is piped to
+    pub console_feedback_stdout: bool,
     /// The op permission level of everyone that is not in the ops file
     pub default_op_level: PermissionLvl,
+    /// Seconds a player has to wait before using a command again, keyed by the command name
+    pub cooldowns: HashMap<String, u64>,
+    /// Players with at least this permission level are not affected by command cooldowns
+    pub cooldown_bypass_level: PermissionLvl,
+    /// Permission nodes granted to players with the `group.<name>` node, keyed by the group name.
+    /// The nodes may be wildcards like `pumpkin.build.*`
+    pub permission_groups: HashMap<String, Vec<String>>,
+    /// How many commands of a single player may run at the same time, more are rejected
+    pub max_running_commands: usize,
+    /// How many block edits of a player can be undone with `/undo`
+    pub max_undo_edits: usize,
+    /// How many blocks the undo history of a player may hold, larger edits can not be undone
+    pub max_undo_blocks: usize,
+    /// How many blocks a selection edited with commands like `/set` may have
+    pub max_selection_blocks: usize,
+    /// Functions that run once the server started, like the `#minecraft:load` tag.
+    /// Functions that do not exist are skipped
+    pub load_functions: Vec<String>,
+    /// Functions that run every tick, like the `#minecraft:tick` tag
+    pub tick_functions: Vec<String>,
 }
 
 impl Default for CommandsConfig {
@@ -17,7 +42,17 @@ impl Default for CommandsConfig {
         Self {
             use_console: true,
             log_console: true,
+            console_feedback_stdout: false,
             default_op_level: PermissionLvl::Zero,
+            cooldowns: HashMap::new(),
+            cooldown_bypass_level: PermissionLvl::Two,
+            permission_groups: HashMap::new(),
+            max_running_commands: 4,
+            max_undo_edits: 10,
+            max_undo_blocks: 262_144,
+            max_selection_blocks: 262_144,
+            load_functions: vec!["minecraft:load".to_string()],
+            tick_functions: vec!["minecraft:tick".to_string()],
         }
     }
 }
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Hard limits on how many mobs may exist, protecting the server from spawning too many by accident or on purpose
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityLimitsConfig {
+    /// How many mobs may be in a single chunk
+    pub per_chunk: usize,
+    /// How many mobs may be in a single world
+    pub per_world: usize,
+}
+
+impl Default for EntityLimitsConfig {
+    fn default() -> Self {
+        Self {
+            per_chunk: 1000,
+            per_world: 20000,
+        }
+    }
+}
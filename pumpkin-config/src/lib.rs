@@ -17,17 +17,31 @@ pub mod networking;
 
 pub mod resource_pack;
 
+pub use activation_range::ActivationRangeConfig;
+pub use autosave::AutosaveConfig;
+pub use broadcast::BroadcastConfig;
+pub use chat::ChatConfig;
 pub use commands::CommandsConfig;
+pub use entity_limits::EntityLimitsConfig;
+pub use mob_spawning::MobSpawningConfig;
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
 pub use networking::rcon::RCONConfig;
+pub use nick::NickConfig;
 pub use pvp::PVPConfig;
 pub use server_links::ServerLinksConfig;
 
+mod activation_range;
+mod autosave;
+mod broadcast;
+mod chat;
 mod commands;
+mod entity_limits;
+mod mob_spawning;
 
 pub mod chunk;
+mod nick;
 pub mod op;
 mod pvp;
 mod server_links;
@@ -57,6 +71,13 @@ pub struct AdvancedConfiguration {
     pub commands: CommandsConfig,
     pub pvp: PVPConfig,
     pub server_links: ServerLinksConfig,
+    pub autosave: AutosaveConfig,
+    pub chat: ChatConfig,
+    pub nick: NickConfig,
+    pub activation_range: ActivationRangeConfig,
+    pub broadcast: BroadcastConfig,
+    pub mob_spawning: MobSpawningConfig,
+    pub entity_limits: EntityLimitsConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -80,6 +101,20 @@ pub struct BasicConfiguration {
     pub allow_nether: bool,
     /// Whether the server is in hardcore mode.
     pub hardcore: bool,
+    /// Bans players who die in hardcore mode instead of letting them spectate
+    pub hardcore_ban_on_death: bool,
+    /// Hides coordinates and other details from the debug screen (F3)
+    pub reduced_debug_info: bool,
+    /// Players joining for the first time are spread within this many blocks around the spawn, 0 places them exactly at it
+    pub spawn_radius: u32,
+    /// Radius in blocks around the spawn in which only operators can change the world, 0 disables it
+    pub spawn_protection: u32,
+    /// Players with at least this permission level are not affected by the spawn protection
+    pub spawn_protection_bypass_level: PermissionLvl,
+    /// The y coordinate above the highest block players can place, the top of each world when not set
+    pub build_limit: Option<i32>,
+    /// Permission node that allows building above `build_limit`, up to the top of the world. Operators always can
+    pub build_limit_bypass_permission: String,
     /// Whether online mode is enabled. Requires valid Minecraft accounts.
     pub online_mode: bool,
     /// Whether packet encryption is enabled. Required when online mode is enabled.
@@ -109,6 +144,13 @@ impl Default for BasicConfiguration {
             op_permission_level: PermissionLvl::Four,
             allow_nether: true,
             hardcore: false,
+            hardcore_ban_on_death: false,
+            reduced_debug_info: false,
+            spawn_radius: 10,
+            spawn_protection: 0,
+            spawn_protection_bypass_level: PermissionLvl::One,
+            build_limit: None,
+            build_limit_bypass_permission: "pumpkin.build.bypass_limit".to_string(),
             online_mode: true,
             encryption: true,
             motd: "A Blazing fast Pumpkin Server!".to_string(),
@@ -173,7 +215,8 @@ impl LoadConfiguration for AdvancedConfiguration {
     }
 
     fn validate(&self) {
-        self.resource_pack.validate()
+        self.resource_pack.validate();
+        self.chat.validate();
     }
 }
 
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Mobs spawning on their own in the chunks around players
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct MobSpawningConfig {
+    /// How many monsters may exist in the chunks around a single player
+    pub monsters: usize,
+    /// How many passive mobs may exist in the chunks around a single player
+    pub creatures: usize,
+    /// Passive mobs only try to spawn once every this many ticks, monsters try every tick
+    pub creature_spawn_interval: u64,
+}
+
+impl Default for MobSpawningConfig {
+    fn default() -> Self {
+        Self {
+            monsters: 70,
+            creatures: 10,
+            creature_spawn_interval: 400,
+        }
+    }
+}
@@ -13,7 +13,8 @@ pub mod proxy;
 pub mod query;
 pub mod rcon;
 
-#[derive(Deserialize, Serialize, Default)]
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
 pub struct NetworkingConfig {
     pub authentication: AuthenticationConfig,
     pub query: QueryConfig,
@@ -21,4 +22,34 @@ pub struct NetworkingConfig {
     pub proxy: ProxyConfig,
     pub packet_compression: CompressionConfig,
     pub lan_broadcast: LANBroadcastConfig,
+    /// Seconds a connection has to finish logging in before it gets closed, 0 to disable
+    pub login_timeout: u64,
+    /// How many received packets of a single connection may wait to be processed
+    pub max_packet_queue: usize,
+    /// What happens to a connection that sends packets faster than they are processed
+    pub packet_queue_overflow: QueueOverflowPolicy,
+}
+
+impl Default for NetworkingConfig {
+    fn default() -> Self {
+        Self {
+            authentication: AuthenticationConfig::default(),
+            query: QueryConfig::default(),
+            rcon: RCONConfig::default(),
+            proxy: ProxyConfig::default(),
+            packet_compression: CompressionConfig::default(),
+            lan_broadcast: LANBroadcastConfig::default(),
+            login_timeout: 30,
+            max_packet_queue: 4096,
+            packet_queue_overflow: QueueOverflowPolicy::Kick,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QueueOverflowPolicy {
+    /// Drops the packets that do not fit into the queue
+    Drop,
+    /// Disconnects the client
+    Kick,
 }
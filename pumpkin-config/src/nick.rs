@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct NickConfig {
+    /// Prevents nicknames that match the username or nickname of another online player
+    pub unique: bool,
+    /// The maximum length of a nickname, not counting formatting codes
+    pub max_length: usize,
+}
+
+impl Default for NickConfig {
+    fn default() -> Self {
+        Self {
+            unique: true,
+            max_length: 16,
+        }
+    }
+}
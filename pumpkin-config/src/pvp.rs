@@ -13,6 +13,13 @@ pub struct PVPConfig {
     pub knockback: bool,
     /// Should player swing when attacking?
     pub swing: bool,
+    /// Should players push each other apart when they walk into each other?
+    pub player_collision: bool,
+    /// The strongest push in blocks per tick a collision can cause, higher values make players jitter
+    pub max_collision_push: f64,
+    /// How many ticks players can not be hurt by other players after joining or respawning, 0 disables it.
+    /// Attacking someone ends it early
+    pub spawn_grace_ticks: u32,
 }
 
 impl Default for PVPConfig {
@@ -23,6 +30,9 @@ impl Default for PVPConfig {
             protect_creative: true,
             knockback: true,
             swing: true,
+            player_collision: false,
+            max_collision_push: 0.1,
+            spawn_grace_ticks: 100,
         }
     }
 }
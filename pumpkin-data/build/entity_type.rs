@@ -17,7 +17,7 @@ pub(crate) fn build() -> TokenStream {
 
     let json: HashMap<String, JSONStruct> =
         serde_json::from_str(include_str!("../../assets/entities.json"))
-            .expect("Failed to parse sound_category.json");
+            .expect("Failed to parse entities.json");
     let mut variants = TokenStream::new();
 
     for (item, id) in json.iter() {
@@ -30,9 +30,9 @@ pub(crate) fn build() -> TokenStream {
 
     let type_from_raw_id_arms = json
         .iter()
-        .map(|sound| {
-            let id = &sound.1.id;
-            let name = ident(sound.0.to_pascal_case());
+        .map(|entity| {
+            let id = &entity.1.id;
+            let name = ident(entity.0.to_pascal_case());
 
             quote! {
                 #id => Some(Self::#name),
@@ -42,9 +42,9 @@ pub(crate) fn build() -> TokenStream {
 
     let type_from_name = json
         .iter()
-        .map(|sound| {
-            let id = &sound.0;
-            let name = ident(sound.0.to_pascal_case());
+        .map(|entity| {
+            let id = &entity.0;
+            let name = ident(entity.0.to_pascal_case());
 
             quote! {
                 #id => Some(Self::#name),
@@ -52,6 +52,18 @@ pub(crate) fn build() -> TokenStream {
         })
         .collect::<TokenStream>();
 
+    let type_to_name = json
+        .iter()
+        .map(|entity| {
+            let id = &entity.0;
+            let name = ident(entity.0.to_pascal_case());
+
+            quote! {
+                Self::#name => #id,
+            }
+        })
+        .collect::<TokenStream>();
+
     quote! {
         #[derive(Clone, Copy, PartialEq, Eq, Debug)]
         #[repr(u8)]
@@ -73,6 +85,12 @@ pub(crate) fn build() -> TokenStream {
                     _ => None
                 }
             }
+
+            pub const fn to_name(&self) -> &'static str {
+                match self {
+                    #type_to_name
+                }
+            }
         }
     }
 }
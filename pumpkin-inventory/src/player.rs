@@ -1,6 +1,7 @@
 use crate::container_click::MouseClick;
 use crate::crafting::check_if_matches_crafting;
 use crate::{handle_item_change, Container, InventoryError, WindowType};
+use pumpkin_world::item::item_registry::Item;
 use pumpkin_world::item::ItemStack;
 use std::iter::Chain;
 use std::slice::IterMut;
@@ -120,6 +121,10 @@ impl PlayerInventory {
         &mut self.items[self.selected as usize + 36 - 9]
     }
 
+    pub fn offhand_mut(&mut self) -> &mut Option<ItemStack> {
+        &mut self.offhand
+    }
+
     pub fn get_slot_with_item(&self, item_id: u16) -> Option<usize> {
         for slot in 9..=44 {
             match &self.items[slot - 9] {
@@ -177,6 +182,40 @@ impl PlayerInventory {
         let (items, hotbar) = self.items.split_at_mut(27);
         hotbar.iter_mut().chain(items)
     }
+
+    /// Fills up existing stacks of the item first, then empty slots, the hotbar before the rest of the inventory.
+    /// Returns how many items did not fit
+    pub fn add_item_stack(&mut self, item: &Item, mut amount: u32) -> u32 {
+        let max_stack = item.components.max_stack_size;
+
+        let matching_slots = self.slots_with_hotbar_first().filter_map(|slot| {
+            slot.as_mut().filter(|item_slot| {
+                item_slot.item_id == item.id && item_slot.item_count < max_stack
+            })
+        });
+        for item_slot in matching_slots {
+            if amount == 0 {
+                return 0;
+            }
+            let amount_to_add = u32::from(max_stack - item_slot.item_count).min(amount);
+            item_slot.item_count += amount_to_add as u8;
+            amount -= amount_to_add;
+        }
+
+        let empty_slots = self.slots_with_hotbar_first().filter(|slot| slot.is_none());
+        for slot in empty_slots {
+            if amount == 0 {
+                return 0;
+            }
+            let item_count = u32::from(max_stack).min(amount);
+            *slot = Some(ItemStack {
+                item_id: item.id,
+                item_count: item_count as u8,
+            });
+            amount -= item_count;
+        }
+        amount
+    }
 }
 
 impl Container for PlayerInventory {
@@ -250,3 +289,52 @@ impl Container for PlayerInventory {
         (1..=4).contains(slot)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::item_registry::{Item, ItemComponents};
+
+    use super::PlayerInventory;
+
+    fn item(id: u16, max_stack_size: u8) -> Item {
+        Item {
+            id,
+            components: ItemComponents {
+                item_name: String::new(),
+                max_stack_size,
+                jukebox_playable: None,
+                damage: None,
+                max_damage: None,
+                attribute_modifiers: None,
+                food: None,
+                consumable: None,
+            },
+        }
+    }
+
+    #[test]
+    fn add_item_stack_fills_existing_stacks_first() {
+        let mut inventory = PlayerInventory::new();
+        let stone = item(1, 64);
+        assert_eq!(inventory.add_item_stack(&stone, 10), 0);
+        assert_eq!(inventory.add_item_stack(&stone, 60), 0);
+
+        let stacks: Vec<_> = inventory.slots().into_iter().flatten().collect();
+        assert_eq!(stacks.len(), 2);
+        assert_eq!(stacks[0].item_count + stacks[1].item_count, 70);
+        // the hotbar is filled first
+        assert_eq!(
+            inventory.held_item().map(|stack| stack.item_count),
+            Some(64)
+        );
+    }
+
+    #[test]
+    fn add_item_stack_returns_leftover() {
+        let mut inventory = PlayerInventory::new();
+        let pearl = item(2, 16);
+        // 36 slots of 16 pearls each
+        assert_eq!(inventory.add_item_stack(&pearl, 36 * 16 + 5), 5);
+        assert_eq!(inventory.add_item_stack(&item(1, 64), 1), 1);
+    }
+}
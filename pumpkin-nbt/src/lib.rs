@@ -15,6 +15,7 @@ use thiserror::Error;
 pub mod compound;
 pub mod deserializer;
 pub mod serializer;
+pub mod snbt;
 pub mod tag;
 
 // This NBT crate is inspired from CrabNBT
@@ -45,6 +46,8 @@ pub enum Error {
     SerdeError(String),
     #[error("NBT doesn't support this type {0}")]
     UnsupportedType(String),
+    #[error("Invalid SNBT at position {0}: {1}")]
+    InvalidSnbt(usize, String),
 }
 
 impl ser::Error for Error {
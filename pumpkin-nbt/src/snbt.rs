@@ -0,0 +1,246 @@
+//! The text form of NBT used in commands, like `{CustomName:'"Bob"',CustomNameVisible:1b}`
+
+use bytes::Bytes;
+
+use crate::{compound::NbtCompound, tag::NbtTag, Error};
+
+/// Parses a compound tag written as SNBT
+pub fn parse_compound(input: &str) -> Result<NbtCompound, Error> {
+    let mut parser = Parser { input, pos: 0 };
+    let compound = parser.compound()?;
+    parser.skip_whitespace();
+    if parser.pos != input.len() {
+        return Err(parser.error("Expected the end of the compound"));
+    }
+    Ok(compound)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, reason: &str) -> Error {
+        Error::InvalidSnbt(self.pos, reason.to_string())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// Skips `c` if it comes next, ignoring whitespace before it
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Error> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{c}'")))
+        }
+    }
+
+    fn compound(&mut self) -> Result<NbtCompound, Error> {
+        self.expect('{')?;
+        let mut compound = NbtCompound::new();
+        if self.eat('}') {
+            return Ok(compound);
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            let value = self.value()?;
+            compound.put(&key, value);
+            if self.eat('}') {
+                return Ok(compound);
+            }
+            self.expect(',')?;
+        }
+    }
+
+    /// A list like `[1,2]` or an array like `[I;1,2]`
+    fn list(&mut self) -> Result<NbtTag, Error> {
+        self.expect('[')?;
+        let array = ["B;", "I;", "L;"]
+            .into_iter()
+            .find(|prefix| self.input[self.pos..].starts_with(prefix));
+        if array.is_some() {
+            self.pos += 2;
+        }
+        let mut values = Vec::new();
+        if !self.eat(']') {
+            loop {
+                values.push(self.value()?);
+                if self.eat(']') {
+                    break;
+                }
+                self.expect(',')?;
+            }
+        }
+        let Some(array) = array else {
+            return Ok(NbtTag::List(values));
+        };
+        let numbers = values
+            .iter()
+            .map(|value| match value {
+                NbtTag::Byte(number) => Some(i64::from(*number)),
+                NbtTag::Short(number) => Some(i64::from(*number)),
+                NbtTag::Int(number) => Some(i64::from(*number)),
+                NbtTag::Long(number) => Some(*number),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| self.error("Arrays can only hold numbers"))?;
+        Ok(match array {
+            "B;" => NbtTag::ByteArray(Bytes::from(
+                numbers.into_iter().map(|n| n as u8).collect::<Vec<_>>(),
+            )),
+            "I;" => NbtTag::IntArray(numbers.into_iter().map(|n| n as i32).collect()),
+            _ => NbtTag::LongArray(numbers),
+        })
+    }
+
+    fn value(&mut self) -> Result<NbtTag, Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => Ok(NbtTag::Compound(self.compound()?)),
+            Some('[') => self.list(),
+            Some('"' | '\'') => Ok(NbtTag::String(self.quoted()?)),
+            _ => Ok(word_value(self.unquoted()?)),
+        }
+    }
+
+    /// A key or a string value, either quoted or a single word
+    fn string(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"' | '\'') => self.quoted(),
+            _ => self.unquoted().map(str::to_string),
+        }
+    }
+
+    /// A string in single or double quotes, the other quote needs no escaping inside of it
+    fn quoted(&mut self) -> Result<String, Error> {
+        let quote = self.peek().ok_or_else(|| self.error("Expected a string"))?;
+        let start = self.pos + quote.len_utf8();
+        let mut string = String::new();
+        let mut chars = self.input[start..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            if c == quote {
+                self.pos = start + i + c.len_utf8();
+                return Ok(string);
+            }
+            if c == '\\' {
+                let Some((_, escaped)) = chars.next() else {
+                    break;
+                };
+                string.push(escaped);
+            } else {
+                string.push(c);
+            }
+        }
+        Err(self.error("Unterminated string"))
+    }
+
+    fn unquoted(&mut self) -> Result<&'a str, Error> {
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "_-.+".contains(c)))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("Expected a value"));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+}
+
+/// Unquoted values are numbers with an optional type suffix like `1b`, booleans or strings
+fn word_value(word: &str) -> NbtTag {
+    let suffixed = |suffix: char| {
+        word.strip_suffix(suffix)
+            .or_else(|| word.strip_suffix(suffix.to_ascii_uppercase()))
+    };
+    if let Some(number) = suffixed('b').and_then(|n| n.parse().ok()) {
+        return NbtTag::Byte(number);
+    }
+    if let Some(number) = suffixed('s').and_then(|n| n.parse().ok()) {
+        return NbtTag::Short(number);
+    }
+    if let Some(number) = suffixed('l').and_then(|n| n.parse().ok()) {
+        return NbtTag::Long(number);
+    }
+    if let Some(number) = suffixed('f').and_then(|n| n.parse().ok()) {
+        return NbtTag::Float(number);
+    }
+    if let Some(number) = suffixed('d').and_then(|n| n.parse().ok()) {
+        return NbtTag::Double(number);
+    }
+    if let Ok(number) = word.parse() {
+        return NbtTag::Int(number);
+    }
+    // Numbers with a decimal point and no suffix are doubles
+    if word.contains('.') {
+        if let Ok(number) = word.parse() {
+            return NbtTag::Double(number);
+        }
+    }
+    match word {
+        "true" => NbtTag::Byte(1),
+        "false" => NbtTag::Byte(0),
+        _ => NbtTag::String(word.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::tag::NbtTag;
+
+    use super::parse_compound;
+
+    #[test]
+    fn parses_values() {
+        let nbt = parse_compound(
+            r#"{CustomName:'"Bob"', CustomNameVisible:1b, Health:20.5f, Pos:[1.0d,2.5,3d],
+            "quoted key":"say \"hi\"", Ids:[I;1,2], Nested:{Age:-3}, Id:zombie}"#,
+        )
+        .unwrap();
+        assert_eq!(nbt.get_string("CustomName").unwrap(), "\"Bob\"");
+        assert_eq!(nbt.get_bool("CustomNameVisible"), Some(true));
+        assert_eq!(nbt.get_float("Health"), Some(20.5));
+        assert_eq!(
+            nbt.get_list("Pos").unwrap(),
+            &vec![
+                NbtTag::Double(1.0),
+                NbtTag::Double(2.5),
+                NbtTag::Double(3.0)
+            ]
+        );
+        assert_eq!(nbt.get_string("quoted key").unwrap(), "say \"hi\"");
+        assert_eq!(nbt.get_int_array("Ids").unwrap(), &vec![1, 2]);
+        assert_eq!(nbt.get_compound("Nested").unwrap().get_int("Age"), Some(-3));
+        assert_eq!(nbt.get_string("Id").unwrap(), "zombie");
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(parse_compound("{}").unwrap().child_tags.is_empty());
+        for input in ["", "{", "{a:}", "{a:1", "{a:'open}", "{a:1} extra", "[1]"] {
+            assert!(parse_compound(input).is_err(), "{input}");
+        }
+    }
+}
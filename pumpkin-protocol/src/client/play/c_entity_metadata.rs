@@ -8,12 +8,19 @@ use crate::VarInt;
 #[client_packet(PLAY_SET_ENTITY_DATA)]
 pub struct CSetEntityMetadata<T> {
     entity_id: VarInt,
-    metadata: Metadata<T>,
+    metadata: T,
     end: u8,
 }
 
-impl<T> CSetEntityMetadata<T> {
+impl<T> CSetEntityMetadata<Metadata<T>> {
     pub fn new(entity_id: VarInt, metadata: Metadata<T>) -> Self {
+        Self::with_entries(entity_id, metadata)
+    }
+}
+
+impl<T> CSetEntityMetadata<T> {
+    /// Sends multiple entries at once, `metadata` is a tuple of [`Metadata`] entries
+    pub fn with_entries(entity_id: VarInt, metadata: T) -> Self {
         Self {
             entity_id,
             metadata,
@@ -60,10 +60,22 @@ impl<'a> CPlayerChatMessage<'a> {
     }
 }
 
+/// A message the sender has seen before sending theirs, the client needs these to verify the signature
 #[derive(Serialize)]
 pub struct PreviousMessage<'a> {
+    /// The id of a cached signature + 1, or 0 if the full signature follows
     message_id: VarInt,
-    signature: Option<&'a [u8]>,
+    /// Empty when referencing a cached signature, the length is not prefixed
+    signature: &'a [u8],
+}
+
+impl<'a> PreviousMessage<'a> {
+    pub fn new(signature: &'a [u8]) -> Self {
+        Self {
+            message_id: VarInt(0),
+            signature,
+        }
+    }
 }
 
 #[derive(Serialize)]
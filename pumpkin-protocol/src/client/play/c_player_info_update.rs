@@ -38,11 +38,22 @@ impl ClientPacket for CPlayerInfoUpdate<'_> {
                             p.put_option(&v.signature, |p, v| p.put_string(v));
                         });
                     }
-                    PlayerAction::InitializeChat(_) => todo!(),
+                    PlayerAction::InitializeChat(session) => {
+                        p.put_option(session, |p, v| {
+                            p.put_uuid(&v.session_id);
+                            p.put_i64(v.expires_at);
+                            p.put_var_int(&v.public_key.len().into());
+                            p.put_slice(v.public_key);
+                            p.put_var_int(&v.key_signature.len().into());
+                            p.put_slice(v.key_signature);
+                        });
+                    }
                     PlayerAction::UpdateGameMode(gamemode) => p.put_var_int(gamemode),
                     PlayerAction::UpdateListed(listed) => p.put_bool(*listed),
                     PlayerAction::UpdateLatency(_) => todo!(),
-                    PlayerAction::UpdateDisplayName(_) => todo!(),
+                    PlayerAction::UpdateDisplayName(display_name) => {
+                        p.put_option(display_name, |p, v| p.put_slice(&v.encode()));
+                    }
                     PlayerAction::UpdateListOrder => todo!(),
                 }
             }
@@ -0,0 +1,40 @@
+use pumpkin_data::packet::clientbound::PLAY_SECTION_BLOCKS_UPDATE;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::{codec::var_long::VarLong, VarInt};
+
+/// Updates multiple blocks inside a single chunk section at once
+#[derive(Serialize)]
+#[client_packet(PLAY_SECTION_BLOCKS_UPDATE)]
+pub struct CSectionBlocksUpdate {
+    section_position: i64,
+    block_count: VarInt,
+    blocks: Vec<VarLong>,
+}
+
+impl CSectionBlocksUpdate {
+    /// `section` is the position of the chunk section in section coordinates, every block
+    /// position in `blocks` must be inside of it
+    pub fn new(section: Vector3<i32>, blocks: &[(BlockPos, u16)]) -> Self {
+        let section_position = ((i64::from(section.x) & 0x3FFFFF) << 42)
+            | ((i64::from(section.z) & 0x3FFFFF) << 20)
+            | (i64::from(section.y) & 0xFFFFF);
+        let blocks: Vec<VarLong> = blocks
+            .iter()
+            .map(|(position, block_state_id)| {
+                let relative = (i64::from(position.0.x & 15) << 8)
+                    | (i64::from(position.0.z & 15) << 4)
+                    | i64::from(position.0.y & 15);
+                VarLong((i64::from(*block_state_id) << 12) | relative)
+            })
+            .collect();
+        Self {
+            section_position,
+            block_count: VarInt(blocks.len() as i32),
+            blocks,
+        }
+    }
+}
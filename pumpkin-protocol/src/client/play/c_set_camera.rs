@@ -0,0 +1,18 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_CAMERA;
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Makes the client view the world from another entity, or from the player again when given their own id
+#[derive(Serialize)]
+#[client_packet(PLAY_SET_CAMERA)]
+pub struct CSetCamera {
+    camera_id: VarInt,
+}
+
+impl CSetCamera {
+    pub fn new(camera_id: VarInt) -> Self {
+        Self { camera_id }
+    }
+}
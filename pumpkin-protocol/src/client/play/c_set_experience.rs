@@ -0,0 +1,24 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_EXPERIENCE;
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+#[derive(Serialize)]
+#[client_packet(PLAY_SET_EXPERIENCE)]
+pub struct CSetExperience {
+    /// The progress towards the next level, from 0 to 1
+    progress: f32,
+    level: VarInt,
+    total_experience: VarInt,
+}
+
+impl CSetExperience {
+    pub fn new(progress: f32, level: VarInt, total_experience: VarInt) -> Self {
+        Self {
+            progress,
+            level,
+            total_experience,
+        }
+    }
+}
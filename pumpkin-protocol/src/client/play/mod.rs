@@ -39,17 +39,21 @@ mod c_player_info_update;
 mod c_player_position;
 mod c_player_remove;
 mod c_remove_entities;
+mod c_remove_mob_effect;
 mod c_reset_score;
 mod c_respawn;
+mod c_section_blocks_update;
 mod c_server_links;
 mod c_set_border_center;
 mod c_set_border_lerp_size;
 mod c_set_border_size;
 mod c_set_border_warning_delay;
 mod c_set_border_warning_distance;
+mod c_set_camera;
 mod c_set_container_content;
 mod c_set_container_property;
 mod c_set_container_slot;
+mod c_set_experience;
 mod c_set_health;
 mod c_set_held_item;
 mod c_set_time;
@@ -65,6 +69,7 @@ mod c_unload_chunk;
 mod c_update_entity_pos;
 mod c_update_entity_pos_rot;
 mod c_update_entity_rot;
+mod c_update_mob_effect;
 mod c_update_objectives;
 mod c_update_score;
 mod c_worldevent;
@@ -111,17 +116,21 @@ pub use c_player_info_update::*;
 pub use c_player_position::*;
 pub use c_player_remove::*;
 pub use c_remove_entities::*;
+pub use c_remove_mob_effect::*;
 pub use c_reset_score::*;
 pub use c_respawn::*;
+pub use c_section_blocks_update::*;
 pub use c_server_links::*;
 pub use c_set_border_center::*;
 pub use c_set_border_lerp_size::*;
 pub use c_set_border_size::*;
 pub use c_set_border_warning_delay::*;
 pub use c_set_border_warning_distance::*;
+pub use c_set_camera::*;
 pub use c_set_container_content::*;
 pub use c_set_container_property::*;
 pub use c_set_container_slot::*;
+pub use c_set_experience::*;
 pub use c_set_health::*;
 pub use c_set_held_item::*;
 pub use c_set_time::*;
@@ -137,6 +146,7 @@ pub use c_unload_chunk::*;
 pub use c_update_entity_pos::*;
 pub use c_update_entity_pos_rot::*;
 pub use c_update_entity_rot::*;
+pub use c_update_mob_effect::*;
 pub use c_update_objectives::*;
 pub use c_update_score::*;
 pub use c_worldevent::*;
@@ -1,3 +1,5 @@
+use pumpkin_util::text::TextComponent;
+
 use crate::{Property, VarInt};
 
 pub enum PlayerAction<'a> {
@@ -5,12 +7,22 @@ pub enum PlayerAction<'a> {
         name: &'a str,
         properties: &'a [Property],
     },
-    InitializeChat(u8),
+    /// The chat session of the player, `None` if they don't sign their messages
+    InitializeChat(Option<ChatSession<'a>>),
     /// Gamemode ?
     UpdateGameMode(VarInt),
     /// Listed ?
     UpdateListed(bool),
     UpdateLatency(u8),
-    UpdateDisplayName(u8),
+    /// The name shown in the tab list, `None` shows the username
+    UpdateDisplayName(Option<&'a TextComponent>),
     UpdateListOrder,
 }
+
+/// The public key a player signs their chat messages with
+pub struct ChatSession<'a> {
+    pub session_id: uuid::Uuid,
+    pub expires_at: i64,
+    pub public_key: &'a [u8],
+    pub key_signature: &'a [u8],
+}
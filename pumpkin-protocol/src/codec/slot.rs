@@ -129,11 +129,12 @@ impl Serialize for Slot {
 }
 
 impl Slot {
+    /// Returns `None` for empty slots and for ids or counts that do not fit an item stack
     pub fn to_item(self) -> Option<ItemStack> {
-        let item_id = self.item_id?.0.try_into().unwrap();
+        let item_id = self.item_id?.0.try_into().ok()?;
         Some(ItemStack {
             item_id,
-            item_count: self.item_count.0.try_into().unwrap(),
+            item_count: self.item_count.0.try_into().ok()?,
         })
     }
 
@@ -0,0 +1,24 @@
+use bytes::Buf;
+use pumpkin_data::packet::serverbound::CONFIG_RESOURCE_PACK;
+use pumpkin_macros::server_packet;
+
+use crate::{
+    bytebuf::{ByteBuf, ReadingError},
+    ServerPacket, VarInt,
+};
+
+/// Sent by the client to report the progress of loading a resource pack
+#[server_packet(CONFIG_RESOURCE_PACK)]
+pub struct SConfigResourcePackResponse {
+    pub uuid: uuid::Uuid,
+    pub result: VarInt,
+}
+
+impl ServerPacket for SConfigResourcePackResponse {
+    fn read(bytebuf: &mut impl Buf) -> Result<Self, ReadingError> {
+        Ok(Self {
+            uuid: bytebuf.try_get_uuid()?,
+            result: bytebuf.try_get_var_int()?,
+        })
+    }
+}
@@ -1,5 +1,7 @@
+mod s_chat_ack;
 mod s_chat_command;
 mod s_chat_message;
+mod s_chat_session_update;
 mod s_click_container;
 mod s_client_command;
 mod s_client_information;
@@ -21,14 +23,17 @@ mod s_player_loaded;
 mod s_player_position;
 mod s_player_position_rotation;
 mod s_player_rotation;
+mod s_resource_pack_response;
 mod s_set_creative_slot;
 mod s_set_held_item;
 mod s_swing_arm;
 mod s_use_item;
 mod s_use_item_on;
 
+pub use s_chat_ack::*;
 pub use s_chat_command::*;
 pub use s_chat_message::*;
+pub use s_chat_session_update::*;
 pub use s_click_container::*;
 pub use s_client_command::*;
 pub use s_client_information::*;
@@ -50,6 +55,7 @@ pub use s_player_loaded::*;
 pub use s_player_position::*;
 pub use s_player_position_rotation::*;
 pub use s_player_rotation::*;
+pub use s_resource_pack_response::*;
 pub use s_set_creative_slot::*;
 pub use s_set_held_item::*;
 pub use s_swing_arm::*;
@@ -0,0 +1,11 @@
+use pumpkin_data::packet::serverbound::PLAY_CHAT_ACK;
+use pumpkin_macros::server_packet;
+
+use crate::VarInt;
+
+/// Sent by the client to acknowledge received chat messages when it did not send a message itself for a while
+#[derive(serde::Deserialize)]
+#[server_packet(PLAY_CHAT_ACK)]
+pub struct SChatAck {
+    pub offset: VarInt,
+}
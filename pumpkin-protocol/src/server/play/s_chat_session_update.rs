@@ -0,0 +1,35 @@
+use bytes::{Buf, Bytes};
+use pumpkin_data::packet::serverbound::PLAY_CHAT_SESSION_UPDATE;
+use pumpkin_macros::server_packet;
+
+use crate::{
+    bytebuf::{ByteBuf, ReadingError},
+    ServerPacket,
+};
+
+/// Sent by the client to share the public key it signs its chat messages with
+#[server_packet(PLAY_CHAT_SESSION_UPDATE)]
+pub struct SChatSessionUpdate {
+    pub session_id: uuid::Uuid,
+    /// Expiry time of the public key, in epoch milliseconds
+    pub expires_at: i64,
+    pub public_key: Bytes,
+    pub key_signature: Bytes,
+}
+
+impl ServerPacket for SChatSessionUpdate {
+    fn read(bytebuf: &mut impl Buf) -> Result<Self, ReadingError> {
+        Ok(Self {
+            session_id: bytebuf.try_get_uuid()?,
+            expires_at: bytebuf.try_get_i64()?,
+            public_key: {
+                let len = bytebuf.try_get_var_int()?.0 as usize;
+                bytebuf.try_copy_to_bytes_len(len, 512)?
+            },
+            key_signature: {
+                let len = bytebuf.try_get_var_int()?.0 as usize;
+                bytebuf.try_copy_to_bytes_len(len, 4096)?
+            },
+        })
+    }
+}
@@ -5,5 +5,15 @@ use pumpkin_macros::server_packet;
 #[server_packet(PLAY_PLAYER_INPUT)]
 pub struct SPlayerInput {
     // Yep exactly how it looks like
-    _input: i8,
+    pub input: i8,
+}
+
+impl SPlayerInput {
+    pub const FORWARD: i8 = 0x01;
+    pub const BACKWARD: i8 = 0x02;
+    pub const LEFT: i8 = 0x04;
+    pub const RIGHT: i8 = 0x08;
+    pub const JUMP: i8 = 0x10;
+    pub const SNEAK: i8 = 0x20;
+    pub const SPRINT: i8 = 0x40;
 }
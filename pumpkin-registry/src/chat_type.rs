@@ -14,3 +14,21 @@ pub struct Decoration {
     style: Option<Style>,
     parameters: Vec<String>,
 }
+
+impl ChatType {
+    /// Shows the message as it is, for messages the server already formatted
+    pub(crate) fn preformatted() -> Self {
+        Self {
+            chat: Decoration {
+                translation_key: "%s".to_string(),
+                style: None,
+                parameters: vec!["content".to_string()],
+            },
+            narration: Decoration {
+                translation_key: "chat.type.text.narrate".to_string(),
+                style: None,
+                parameters: vec!["sender".to_string(), "content".to_string()],
+            },
+        }
+    }
+}
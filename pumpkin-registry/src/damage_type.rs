@@ -10,3 +10,10 @@ pub struct DamageType {
     message_id: String,
     scaling: String,
 }
+
+impl DamageType {
+    /// Death messages use the translation key `death.attack.<message_id>`
+    pub fn message_id(&self) -> &str {
+        &self.message_id
+    }
+}
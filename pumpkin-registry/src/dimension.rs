@@ -11,10 +11,10 @@ pub struct Dimension {
     has_ceiling: bool,
     has_raids: bool,
     has_skylight: bool,
-    height: i32,
+    pub(crate) height: i32,
     infiniburn: String,
     logical_height: i32,
-    min_y: i32,
+    pub(crate) min_y: i32,
     monster_spawn_block_light_limit: i32,
     monster_spawn_light_level: MonsterSpawnLightLevel,
     natural: bool,
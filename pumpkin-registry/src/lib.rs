@@ -3,7 +3,7 @@ use std::sync::LazyLock;
 use banner_pattern::BannerPattern;
 use biome::Biome;
 use chat_type::ChatType;
-use damage_type::DamageType;
+pub use damage_type::DamageType;
 use dimension::Dimension;
 use enchantment::Enchantment;
 use indexmap::IndexMap;
@@ -62,7 +62,40 @@ pub struct SyncedRegistry {
     instrument: IndexMap<String, Instrument>,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Looks up a damage type by name, returning its protocol id and the damage type itself
+pub fn get_damage_type(name: &str) -> Option<(u8, &'static DamageType)> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    let (id, _, damage_type) = SYNCED_REGISTRIES.damage_type.get_full(name)?;
+    Some((id.try_into().ok()?, damage_type))
+}
+
+/// Whether the damage type with the given protocol id is in a damage type tag, like `bypasses_invulnerability`
+pub fn damage_type_has_tag(id: u8, tag: &str) -> bool {
+    let Some((name, _)) = SYNCED_REGISTRIES.damage_type.get_index(id.into()) else {
+        return false;
+    };
+    get_tag_values(TagCategory::DamageType, tag).is_some_and(|values| {
+        values
+            .iter()
+            .any(|value| matches!(value, TagType::Item(item) if item == name))
+    })
+}
+
+/// The id of the chat type for messages the server already formatted, like chat in a custom format.
+/// It is sent after the vanilla chat types
+pub fn preformatted_chat_type() -> u32 {
+    SYNCED_REGISTRIES.chat_type.len() as u32
+}
+
+/// Looks up a damage type by its protocol id
+pub fn get_damage_type_by_id(id: u8) -> Option<&'static DamageType> {
+    SYNCED_REGISTRIES
+        .damage_type
+        .get_index(id.into())
+        .map(|(_, damage_type)| damage_type)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum DimensionType {
     Overworld,
@@ -86,6 +119,20 @@ impl DimensionType {
             Self::TheNether => Identifier::vanilla("the_nether"),
         }
     }
+
+    fn dimension(&self) -> &'static Dimension {
+        &SYNCED_REGISTRIES.dimension_type[&self.name().path]
+    }
+
+    /// The lowest y coordinate blocks can exist at in this dimension
+    pub fn min_y(&self) -> i32 {
+        self.dimension().min_y
+    }
+
+    /// The total height in blocks of this dimension, starting at `min_y`
+    pub fn height(&self) -> i32 {
+        self.dimension().height
+    }
 }
 
 impl Registry {
@@ -110,6 +157,16 @@ impl Registry {
                 entry_id: Identifier::vanilla(s.0),
                 data: Some(pumpkin_nbt::serializer::to_bytes_unnamed(&s.1).unwrap()),
             })
+            // Comes last, see `preformatted_chat_type`
+            .chain([RegistryEntry {
+                entry_id: Identifier {
+                    namespace: "pumpkin".to_string(),
+                    path: "preformatted".to_string(),
+                },
+                data: Some(
+                    pumpkin_nbt::serializer::to_bytes_unnamed(&ChatType::preformatted()).unwrap(),
+                ),
+            }])
             .collect();
         let chat_type = Registry {
             registry_id: Identifier::vanilla("chat_type"),
@@ -266,3 +323,51 @@ impl Registry {
         ]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::codec::identifier::Identifier;
+
+    use crate::{
+        damage_type_has_tag, get_damage_type, get_damage_type_by_id, preformatted_chat_type,
+        DimensionType, Registry,
+    };
+
+    #[test]
+    // This test assures that every dimension type is found in the synced registries, with its vanilla height
+    fn dimension_heights() {
+        assert_eq!(DimensionType::Overworld.min_y(), -64);
+        assert_eq!(DimensionType::Overworld.height(), 384);
+        assert_eq!(DimensionType::OverworldCaves.min_y(), -64);
+        assert_eq!(DimensionType::OverworldCaves.height(), 384);
+        assert_eq!(DimensionType::TheNether.min_y(), 0);
+        assert_eq!(DimensionType::TheNether.height(), 256);
+        assert_eq!(DimensionType::TheEnd.min_y(), 0);
+        assert_eq!(DimensionType::TheEnd.height(), 256);
+    }
+
+    #[test]
+    fn preformatted_chat_type_comes_last() {
+        let chat_types = Registry::get_synced()
+            .into_iter()
+            .find(|registry| registry.registry_id == Identifier::vanilla("chat_type"))
+            .unwrap();
+        let entry = &chat_types.registry_entries[preformatted_chat_type() as usize];
+        assert_eq!(entry.entry_id.namespace, "pumpkin");
+        assert_eq!(entry.entry_id.path, "preformatted");
+    }
+
+    #[test]
+    fn damage_type_ids() {
+        let (id, fall) = get_damage_type("fall").unwrap();
+        assert_eq!(id, 10);
+        assert_eq!(fall.message_id(), "fall");
+        let (id, generic_kill) = get_damage_type("minecraft:generic_kill").unwrap();
+        assert_eq!(id, 19);
+        assert_eq!(generic_kill.message_id(), "genericKill");
+        assert!(get_damage_type("not_a_damage_type").is_none());
+        assert_eq!(get_damage_type_by_id(10).unwrap().message_id(), "fall");
+        assert!(damage_type_has_tag(19, "bypasses_invulnerability"));
+        assert!(!damage_type_has_tag(10, "bypasses_invulnerability"));
+    }
+}
@@ -1,6 +1,6 @@
 use super::{position::BlockPos, vector3::Vector3};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct BoundingBox {
     pub min_x: f64,
     pub min_y: f64,
@@ -59,21 +59,35 @@ impl BoundingBox {
             && self.max_z > other.min_z
     }
 
+    /// Whether this box intersects with one of the collision shapes of the block at `position`,
+    /// see [`BoundingBox::from_collision_shapes`]
     pub fn intersects_block(&self, position: &BlockPos, bounding_box: &[f32]) -> bool {
-        for i in 0..bounding_box.len() / 6 {
-            let other = BoundingBox {
-                min_x: position.0.x as f64 + bounding_box[i * 6] as f64,
-                min_y: position.0.y as f64 + bounding_box[i * 6 + 1] as f64,
-                min_z: position.0.z as f64 + bounding_box[i * 6 + 2] as f64,
-                max_x: position.0.x as f64 + bounding_box[i * 6 + 3] as f64,
-                max_y: position.0.y as f64 + bounding_box[i * 6 + 4] as f64,
-                max_z: position.0.z as f64 + bounding_box[i * 6 + 5] as f64,
-            };
-            if self.intersects(&other) {
-                return true;
-            }
-        }
-        false
+        self.intersects_any(&Self::from_collision_shapes(position, bounding_box))
+    }
+
+    pub fn intersects_any(&self, boxes: &[BoundingBox]) -> bool {
+        boxes.iter().any(|other| self.intersects(other))
+    }
+
+    /// Places the collision shapes of a block at its position. The shapes are stored flat, with
+    /// the minimum and then the maximum corner of every box
+    pub fn from_collision_shapes(position: &BlockPos, shapes: &[f32]) -> Vec<Self> {
+        let (x, y, z) = (
+            position.0.x as f64,
+            position.0.y as f64,
+            position.0.z as f64,
+        );
+        shapes
+            .chunks_exact(6)
+            .map(|shape| Self {
+                min_x: x + shape[0] as f64,
+                min_y: y + shape[1] as f64,
+                min_z: z + shape[2] as f64,
+                max_x: x + shape[3] as f64,
+                max_y: y + shape[4] as f64,
+                max_z: z + shape[5] as f64,
+            })
+            .collect()
     }
 
     pub fn squared_magnitude(&self, pos: Vector3<f64>) -> f64 {
@@ -82,6 +96,64 @@ impl BoundingBox {
         let f = f64::max(f64::max(self.min_z - pos.z, pos.z - self.max_z), 0.0);
         super::squared_magnitude(d, e, f)
     }
+
+    /// Moves the box by the given distances
+    pub fn offset(&self, dx: f64, dy: f64, dz: f64) -> Self {
+        Self {
+            min_x: self.min_x + dx,
+            min_y: self.min_y + dy,
+            min_z: self.min_z + dz,
+            max_x: self.max_x + dx,
+            max_y: self.max_y + dy,
+            max_z: self.max_z + dz,
+        }
+    }
+
+    /// Grows the box on both sides of every axis
+    pub fn expand(&self, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            min_x: self.min_x - x,
+            min_y: self.min_y - y,
+            min_z: self.min_z - z,
+            max_x: self.max_x + x,
+            max_y: self.max_y + y,
+            max_z: self.max_z + z,
+        }
+    }
+
+    /// Shrinks the box on both sides of every axis, see [`BoundingBox::expand`]
+    pub fn contract(&self, x: f64, y: f64, z: f64) -> Self {
+        self.expand(-x, -y, -z)
+    }
+
+    /// Grows the box only in the direction of `velocity`, so it covers everything the box passes
+    /// through while moving
+    pub fn stretch(&self, velocity: Vector3<f64>) -> Self {
+        let mut stretched = *self;
+        if velocity.x < 0.0 {
+            stretched.min_x += velocity.x;
+        } else {
+            stretched.max_x += velocity.x;
+        }
+        if velocity.y < 0.0 {
+            stretched.min_y += velocity.y;
+        } else {
+            stretched.max_y += velocity.y;
+        }
+        if velocity.z < 0.0 {
+            stretched.min_z += velocity.z;
+        } else {
+            stretched.max_z += velocity.z;
+        }
+        stretched
+    }
+
+    /// Whether the point is inside of the box, points on the faces are inside too
+    pub fn contains(&self, point: Vector3<f64>) -> bool {
+        (self.min_x..=self.max_x).contains(&point.x)
+            && (self.min_y..=self.max_y).contains(&point.y)
+            && (self.min_z..=self.max_z).contains(&point.z)
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -89,3 +161,86 @@ pub struct BoundingBoxSize {
     pub width: f64,
     pub height: f64,
 }
+
+#[cfg(test)]
+mod test {
+    use crate::math::{position::BlockPos, vector3::Vector3};
+
+    use super::BoundingBox;
+
+    fn unit() -> BoundingBox {
+        BoundingBox::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn offset() {
+        assert_eq!(
+            unit().offset(2.0, -1.0, 0.5),
+            BoundingBox::new(Vector3::new(2.0, -1.0, 0.5), Vector3::new(3.0, 0.0, 1.5))
+        );
+        assert_eq!(
+            unit().offset(-3.0, 4.0, -0.5),
+            BoundingBox::new(Vector3::new(-3.0, 4.0, -0.5), Vector3::new(-2.0, 5.0, 0.5))
+        );
+    }
+
+    #[test]
+    fn expand_and_contract() {
+        let expanded = unit().expand(1.0, 0.5, 0.0);
+        assert_eq!(
+            expanded,
+            BoundingBox::new(Vector3::new(-1.0, -0.5, 0.0), Vector3::new(2.0, 1.5, 1.0))
+        );
+        assert_eq!(expanded.contract(1.0, 0.5, 0.0), unit());
+    }
+
+    #[test]
+    fn stretch_grows_faces_in_movement_direction() {
+        assert_eq!(
+            unit().stretch(Vector3::new(2.0, 0.0, 0.0)),
+            BoundingBox::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(3.0, 1.0, 1.0))
+        );
+        assert_eq!(
+            unit().stretch(Vector3::new(0.0, -2.0, 0.5)),
+            BoundingBox::new(Vector3::new(0.0, -2.0, 0.0), Vector3::new(1.0, 1.0, 1.5))
+        );
+        assert_eq!(
+            unit().stretch(Vector3::new(-1.0, 1.0, -1.0)),
+            BoundingBox::new(Vector3::new(-1.0, 0.0, -1.0), Vector3::new(1.0, 2.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn contains() {
+        assert!(unit().contains(Vector3::new(0.5, 0.5, 0.5)));
+        assert!(unit().contains(Vector3::new(1.0, 0.0, 1.0)));
+        assert!(!unit().contains(Vector3::new(1.5, 0.5, 0.5)));
+        assert!(!unit().contains(Vector3::new(0.5, -0.1, 0.5)));
+    }
+
+    #[test]
+    fn collision_shapes_are_placed_at_the_block() {
+        // A bottom slab with a thin post on top of it
+        let shapes = [
+            0.0, 0.0, 0.0, 1.0, 0.5, 1.0, 0.25, 0.5, 0.25, 0.75, 1.0, 0.75,
+        ];
+        let position = BlockPos(Vector3::new(2, -1, 3));
+        let boxes = BoundingBox::from_collision_shapes(&position, &shapes);
+        assert_eq!(
+            boxes,
+            [
+                BoundingBox::new(Vector3::new(2.0, -1.0, 3.0), Vector3::new(3.0, -0.5, 4.0)),
+                BoundingBox::new(
+                    Vector3::new(2.25, -0.5, 3.25),
+                    Vector3::new(2.75, 0.0, 3.75)
+                ),
+            ]
+        );
+
+        let above_slab = unit().offset(2.0, -0.5, 3.0);
+        assert!(above_slab.intersects_block(&position, &shapes));
+        assert!(!above_slab
+            .offset(0.0, 0.5, 0.0)
+            .intersects_block(&position, &shapes));
+    }
+}
@@ -58,4 +58,4 @@ impl<'de> Deserialize<'de> for PermissionLvl {
             ))),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,12 @@
 use core::str;
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
-use crate::{text::color::ARGBColor, translation::get_translation_en_us};
+use crate::{
+    text::color::ARGBColor,
+    translation::{format_translation, get_translation_en_us},
+};
 use click::ClickEvent;
-use color::Color;
+use color::{Color, NamedColor};
 use colored::Colorize;
 use hover::HoverEvent;
 use serde::{Deserialize, Serialize};
@@ -78,6 +81,36 @@ impl TextComponentBase {
         }
         text
     }
+
+    fn localize(self, language: &HashMap<String, String>) -> Self {
+        let content = match self.content {
+            TextContent::Translate { translate, with } => match language.get(translate.as_ref()) {
+                Some(translation) => {
+                    let args: Vec<_> = with
+                        .into_iter()
+                        .map(|arg| TextComponent(arg.localize(language)).to_plain_text())
+                        .collect();
+                    TextContent::Text {
+                        text: format_translation(translation, &args).into(),
+                    }
+                }
+                None => TextContent::Translate {
+                    translate,
+                    with: with.into_iter().map(|arg| arg.localize(language)).collect(),
+                },
+            },
+            content => content,
+        };
+        Self {
+            content,
+            style: self.style,
+            extra: self
+                .extra
+                .into_iter()
+                .map(|child| child.localize(language))
+                .collect(),
+        }
+    }
 }
 
 impl TextComponent {
@@ -148,9 +181,108 @@ impl TextComponent {
         }
     }
 
+    /// Translates the text with the translations of `language` on the server, so it is shown in that
+    /// language regardless of the client's. Keys the language does not have are left for the client to translate
+    #[must_use]
+    pub fn localize(self, language: &HashMap<String, String>) -> Self {
+        Self(self.0.localize(language))
+    }
+
+    /// Returns the text of this component and all of its children, without any styling
+    pub fn to_plain_text(self) -> String {
+        let extra = self.0.extra.clone();
+        let mut text = self.get_text();
+        for child in extra {
+            text.push_str(&TextComponent(child).to_plain_text());
+        }
+        text
+    }
+
     pub fn to_pretty_console(self) -> String {
         self.0.to_pretty_console()
     }
+
+    /// Parses text using `&` formatting codes, e.g. `&cRed &lbold`. `&r` resets the style
+    pub fn from_legacy(text: &str) -> Self {
+        let mut component = Self::text("");
+        let mut style = Style::default();
+        let mut current = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '&' {
+                current.push(c);
+                continue;
+            }
+            let Some(code) = chars.next() else {
+                current.push(c);
+                break;
+            };
+            let mut new_style = style.clone();
+            match code.to_ascii_lowercase() {
+                'k' => new_style.obfuscated = Some(true),
+                'l' => new_style.bold = Some(true),
+                'm' => new_style.strikethrough = Some(true),
+                'n' => new_style.underlined = Some(true),
+                'o' => new_style.italic = Some(true),
+                'r' => new_style = Style::default(),
+                lowercase => match legacy_color(lowercase) {
+                    // Colors reset the formatting, like in vanilla
+                    Some(color) => {
+                        new_style = Style::default();
+                        new_style.color = Some(Color::Named(color));
+                    }
+                    None => {
+                        current.push(c);
+                        current.push(code);
+                        continue;
+                    }
+                },
+            }
+            if !current.is_empty() {
+                component.0.extra.push(TextComponentBase {
+                    content: TextContent::Text {
+                        text: std::mem::take(&mut current).into(),
+                    },
+                    style: style.clone(),
+                    extra: vec![],
+                });
+            }
+            style = new_style;
+        }
+        if !current.is_empty() {
+            component.0.extra.push(TextComponentBase {
+                content: TextContent::Text {
+                    text: current.into(),
+                },
+                style,
+                extra: vec![],
+            });
+        }
+        component
+    }
+}
+
+fn legacy_color(code: char) -> Option<NamedColor> {
+    let color = match code {
+        '0' => NamedColor::Black,
+        '1' => NamedColor::DarkBlue,
+        '2' => NamedColor::DarkGreen,
+        '3' => NamedColor::DarkAqua,
+        '4' => NamedColor::DarkRed,
+        '5' => NamedColor::DarkPurple,
+        '6' => NamedColor::Gold,
+        '7' => NamedColor::Gray,
+        '8' => NamedColor::DarkGray,
+        '9' => NamedColor::Blue,
+        'a' => NamedColor::Green,
+        'b' => NamedColor::Aqua,
+        'c' => NamedColor::Red,
+        'd' => NamedColor::LightPurple,
+        'e' => NamedColor::Yellow,
+        'f' => NamedColor::White,
+        _ => return None,
+    };
+    Some(color)
 }
 
 impl serde::Serialize for TextComponent {
@@ -274,3 +406,42 @@ pub enum TextContent {
     /// https://minecraft.wiki/w/Controls#Configurable_controls
     Keybind { keybind: Cow<'static, str> },
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{Color, NamedColor, TextComponent};
+
+    #[test]
+    fn from_legacy() {
+        let component = TextComponent::from_legacy("&cRed&lBold &rplain &zkept");
+        let extra = &component.0.extra;
+        assert_eq!(extra.len(), 3);
+        assert_eq!(extra[0].style.color, Some(Color::Named(NamedColor::Red)));
+        assert_eq!(extra[1].style.bold, Some(true));
+        assert_eq!(extra[1].style.color, Some(Color::Named(NamedColor::Red)));
+        assert_eq!(extra[2].style.color, None);
+        assert_eq!(component.to_plain_text(), "RedBold plain &zkept");
+    }
+
+    #[test]
+    fn localize() {
+        let language = HashMap::from([(
+            "multiplayer.player.joined".to_string(),
+            "%s hat das Spiel betreten".to_string(),
+        )]);
+        let joined = TextComponent::translate(
+            "multiplayer.player.joined",
+            vec![TextComponent::text("Alex")],
+        )
+        .color_named(NamedColor::Yellow)
+        .localize(&language);
+        assert_eq!(joined.0.style.color, Some(Color::Named(NamedColor::Yellow)));
+        assert_eq!(joined.to_plain_text(), "Alex hat das Spiel betreten");
+
+        // Left for the client
+        let left = TextComponent::translate("multiplayer.player.left", vec![]);
+        assert_eq!(left.clone().localize(&language), left);
+    }
+}
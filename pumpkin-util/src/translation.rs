@@ -14,3 +14,56 @@ pub fn get_translation_en_us(key: &str, with: Vec<TextComponentBase>) -> Option<
     }
     Some(translation)
 }
+
+/// Fills in the arguments of a translation, both `%s` and positional ones like `%2$s`.
+/// `%%` is a literal percent sign
+pub fn format_translation(translation: &str, args: &[String]) -> String {
+    let mut formatted = String::with_capacity(translation.len());
+    let mut next_arg = 0;
+    let mut rest = translation;
+    while let Some(start) = rest.find('%') {
+        formatted.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        if let Some(after) = rest.strip_prefix('%') {
+            formatted.push('%');
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix('s') {
+            formatted.push_str(args.get(next_arg).map_or("", String::as_str));
+            next_arg += 1;
+            rest = after;
+            continue;
+        }
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let position = rest[..digits].parse::<usize>().ok();
+        match (position, rest[digits..].strip_prefix("$s")) {
+            (Some(position), Some(after)) => {
+                let arg = position.checked_sub(1).and_then(|index| args.get(index));
+                formatted.push_str(arg.map_or("", String::as_str));
+                rest = after;
+            }
+            // Not a placeholder, keep it as it is
+            _ => formatted.push('%'),
+        }
+    }
+    formatted.push_str(rest);
+    formatted
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_translation;
+
+    #[test]
+    fn format_arguments() {
+        let args = ["Alex".to_string(), "Steve".to_string()];
+        assert_eq!(format_translation("%s joined", &args), "Alex joined");
+        assert_eq!(format_translation("%2$s, %1$s", &args), "Steve, Alex");
+        assert_eq!(
+            format_translation("100%% %s %s %s", &args),
+            "100% Alex Steve "
+        );
+        assert_eq!(format_translation("50% off", &args), "50% off");
+    }
+}
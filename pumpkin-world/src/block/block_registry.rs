@@ -107,7 +107,14 @@ pub fn get_block_collision_shapes(block_id: u16) -> Option<Vec<f32>> {
     Some(shapes)
 }
 
-#[expect(dead_code)]
+/// Returns the name of a block entity type, e.g. `sign` for the type of a state's `block_entity_type`
+pub fn get_block_entity_type_name(id: u32) -> Option<&'static str> {
+    BLOCKS
+        .block_entity_types
+        .get(id as usize)
+        .map(String::as_str)
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct TopLevel {
     block_entity_types: Vec<String>,
@@ -126,7 +133,44 @@ pub struct Block {
     pub default_state_id: u16,
     pub states: Vec<State>,
 }
-#[expect(dead_code)]
+
+impl Block {
+    /// Where the property is in the states, every `stride` states its next value follows
+    fn property_index(&self, state_id: u16, property: &str) -> Option<(usize, usize, usize)> {
+        let index = usize::from(state_id.checked_sub(self.states.first()?.id)?);
+        if index >= self.states.len() {
+            return None;
+        }
+        // The states list every combination of the properties, the last property changes fastest
+        let mut stride = 1;
+        for current in self.properties.iter().rev() {
+            if current.name == property {
+                return Some((index, stride, index / stride % current.values.len()));
+            }
+            stride *= current.values.len();
+        }
+        None
+    }
+
+    /// The value of a property in a state of this block, e.g. `true` for `powered`
+    #[must_use]
+    pub fn property_value(&self, state_id: u16, property: &str) -> Option<&str> {
+        let (_, _, value_index) = self.property_index(state_id, property)?;
+        let property = self.properties.iter().find(|p| p.name == property)?;
+        Some(&property.values[value_index])
+    }
+
+    /// The state with the property set to the value and every other property kept
+    #[must_use]
+    pub fn state_with(&self, state_id: u16, property: &str, value: &str) -> Option<u16> {
+        let (index, stride, value_index) = self.property_index(state_id, property)?;
+        let property = self.properties.iter().find(|p| p.name == property)?;
+        let new_value_index = property.values.iter().position(|v| v == value)?;
+        let new_index = index - value_index * stride + new_value_index * stride;
+        Some(self.states[new_index].id)
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct Property {
     name: String,
@@ -148,3 +192,29 @@ struct Shape {
     min: [f32; 3],
     max: [f32; 3],
 }
+
+#[cfg(test)]
+mod test {
+    use super::get_block;
+
+    #[test]
+    fn block_state_properties() {
+        let lever = get_block("minecraft:lever").unwrap();
+        let state = lever.default_state_id;
+        assert_eq!(lever.property_value(state, "powered"), Some("false"));
+        let powered = lever.state_with(state, "powered", "true").unwrap();
+        assert_eq!(lever.property_value(powered, "powered"), Some("true"));
+        // the other properties are kept
+        for property in ["face", "facing"] {
+            assert_eq!(
+                lever.property_value(powered, property),
+                lever.property_value(state, property)
+            );
+        }
+        assert_eq!(lever.state_with(powered, "powered", "false"), Some(state));
+        assert_eq!(lever.state_with(state, "powered", "maybe"), None);
+        assert_eq!(lever.property_value(state, "waterlogged"), None);
+        // a state of another block
+        assert_eq!(lever.property_value(0, "powered"), None);
+    }
+}
@@ -80,6 +80,8 @@ pub struct ChunkData {
     /// See `https://minecraft.wiki/w/Heightmap` for more info
     pub heightmap: ChunkHeightmaps,
     pub position: Vector2<i32>,
+    /// Whether the chunk was modified since it was last written to disk
+    pub dirty: bool,
 }
 
 /// # Subchunks
@@ -429,6 +431,7 @@ impl ChunkData {
             subchunks,
             heightmap: chunk_data.heightmaps,
             position,
+            dirty: false,
         })
     }
 }
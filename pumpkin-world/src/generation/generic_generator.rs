@@ -76,6 +76,7 @@ impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGen
             subchunks,
             heightmap: Default::default(),
             position: at,
+            dirty: true,
         }
     }
 }
@@ -78,6 +78,7 @@ impl<B: BiomeGenerator, T: TerrainGenerator> WorldGenerator for TestGenerator<B,
             subchunks,
             heightmap: Default::default(),
             position: at,
+            dirty: true,
         }
     }
 }
@@ -63,6 +63,32 @@ pub struct ItemComponents {
     pub max_damage: Option<u16>,
     #[serde(rename = "minecraft:attribute_modifiers")]
     pub attribute_modifiers: Option<AttributeModifiers>,
+    #[serde(rename = "minecraft:food")]
+    pub food: Option<FoodComponent>,
+    #[serde(rename = "minecraft:consumable")]
+    pub consumable: Option<Consumable>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct FoodComponent {
+    pub nutrition: i32,
+    /// The saturation restored, not the saturation modifier
+    pub saturation: f32,
+    /// Whether the food can be eaten without being hungry
+    #[serde(default)]
+    pub can_always_eat: bool,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Consumable {
+    #[serde(default = "Consumable::default_consume_seconds")]
+    pub consume_seconds: f32,
+}
+
+impl Consumable {
+    const fn default_consume_seconds() -> f32 {
+        1.6
+    }
 }
 
 #[derive(Deserialize, Clone, Debug)]
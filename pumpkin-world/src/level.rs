@@ -1,4 +1,4 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use dashmap::{DashMap, Entry};
 use num_traits::Zero;
@@ -86,7 +86,8 @@ impl Level {
         }
     }
 
-    pub async fn save(&self) {
+    /// Saves every loaded chunk and the world info, with the game rules the world has now
+    pub async fn save(&self, game_rules: HashMap<String, String>) {
         log::info!("Saving level...");
         // lets first save all chunks
         for chunk in self.loaded_chunks.iter() {
@@ -94,17 +95,57 @@ impl Level {
             self.clean_chunk(&chunk.position).await;
         }
         // then lets save the world info
+        let level_info = LevelData {
+            game_rules,
+            ..self.level_info.clone()
+        };
         self.world_info_writer
-            .write_world_info(self.level_info.clone(), &self.level_folder)
+            .write_world_info(level_info, &self.level_folder)
             .expect("Failed to save world info");
     }
 
+    /// Writes every loaded chunk that was modified since it was last saved, keeping it in memory.
+    /// Returns the amount of chunks written
+    pub async fn save_dirty_chunks(&self) -> usize {
+        let chunks: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+
+        let mut saved = 0;
+        for (position, chunk) in chunks {
+            let mut data = chunk.write().await;
+            if !data.dirty {
+                continue;
+            }
+            match self
+                .chunk_writer
+                .write_chunk(&data, &self.level_folder, &position)
+            {
+                Ok(()) => {
+                    data.dirty = false;
+                    saved += 1;
+                }
+                Err(error) => log::error!("Failed writing Chunk to disk {error}"),
+            }
+        }
+        saved
+    }
+
     pub fn get_block() {}
 
     pub fn loaded_chunk_count(&self) -> usize {
         self.loaded_chunks.len()
     }
 
+    /// Returns the chunk only if it is already loaded, never reads or generates it
+    pub fn get_loaded_chunk(&self, chunk: &Vector2<i32>) -> Option<Arc<RwLock<ChunkData>>> {
+        self.loaded_chunks
+            .get(chunk)
+            .map(|chunk| chunk.value().clone())
+    }
+
     pub fn list_cached(&self) {
         for entry in self.loaded_chunks.iter() {
             log::debug!("In map: {:?}", entry.key());
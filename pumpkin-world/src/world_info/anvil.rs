@@ -61,6 +61,7 @@ impl WorldInfoWriter for AnvilLevelInfo {
                 spawn_z: info.spawn_z,
                 nbt_version: info.nbt_version,
                 version: info.version,
+                game_rules: info.game_rules,
             },
         };
         // convert it into nbt
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use pumpkin_config::BASIC_CONFIG;
 use pumpkin_util::Difficulty;
 use serde::{Deserialize, Serialize};
@@ -45,6 +47,9 @@ pub struct LevelData {
     pub nbt_version: i32,
     #[serde(rename = "Version")]
     pub version: WorldVersion,
+    // The game rules by their name, every value is stored as a string.
+    #[serde(default)]
+    pub game_rules: HashMap<String, String>,
     // TODO: Implement the rest of the fields
 }
 
@@ -106,6 +111,7 @@ impl Default for LevelData {
             spawn_z: 0,
             nbt_version: -1,
             version: Default::default(),
+            game_rules: HashMap::new(),
         }
     }
 }
@@ -1,9 +1,10 @@
 use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
 use crate::entity::player::Player;
 use crate::server::Server;
+use crate::world::World;
 use pumpkin_inventory::OpenContainer;
 use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::block::block_registry::{Block, BLOCKS};
 use pumpkin_world::item::item_registry::Item;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -25,6 +26,21 @@ impl BlockManager {
         self.blocks.insert(block.name(), Arc::new(block));
     }
 
+    /// Registers the same behavior for every block whose name matches, like all kinds of buttons
+    pub fn register_matching<T: PumpkinBlock + 'static>(
+        &mut self,
+        block: T,
+        matches: impl Fn(&str) -> bool,
+    ) {
+        let block: Arc<dyn PumpkinBlock> = Arc::new(block);
+        for registered in &BLOCKS.blocks {
+            if matches(&registered.name) {
+                self.blocks
+                    .insert(format!("minecraft:{}", registered.name), block.clone());
+            }
+        }
+    }
+
     pub async fn on_use(
         &self,
         block: &Block,
@@ -101,6 +117,36 @@ impl BlockManager {
         }
     }
 
+    pub async fn on_scheduled_tick(
+        &self,
+        block: &Block,
+        world: &World,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_scheduled_tick(block, world, location, server)
+                .await;
+        }
+    }
+
+    pub async fn on_entity_inside(
+        &self,
+        block: &Block,
+        world: &World,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_entity_inside(block, world, location, server)
+                .await;
+        }
+    }
+
     #[must_use]
     pub fn get_pumpkin_block(&self, block: &Block) -> Option<&Arc<dyn PumpkinBlock>> {
         self.blocks
@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::{block::block_registry::Block, item::item_registry::Item};
+
+use crate::block::block_manager::BlockActionResult;
+use crate::block::blocks::redstone::{current, is_powered, set_power};
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::{scheduled_tick::TickPriority, World};
+
+/// Buttons stay pressed for a moment and then turn off again on their own
+pub struct ButtonBlock;
+
+impl ButtonBlock {
+    pub fn matches(name: &str) -> bool {
+        name.ends_with("_button")
+    }
+}
+
+fn is_stone(name: &str) -> bool {
+    matches!(name, "stone_button" | "polished_blackstone_button")
+}
+
+/// Ticks until a pressed button turns off, wooden buttons stay pressed longer
+fn press_duration(name: &str) -> u32 {
+    if is_stone(name) {
+        20
+    } else {
+        30
+    }
+}
+
+fn click_sound(name: &str, powered: bool) -> Sound {
+    match (name, powered) {
+        (name, true) if is_stone(name) => Sound::BlockStoneButtonClickOn,
+        (name, false) if is_stone(name) => Sound::BlockStoneButtonClickOff,
+        ("bamboo_button", true) => Sound::BlockBambooWoodButtonClickOn,
+        ("bamboo_button", false) => Sound::BlockBambooWoodButtonClickOff,
+        ("cherry_button", true) => Sound::BlockCherryWoodButtonClickOn,
+        ("cherry_button", false) => Sound::BlockCherryWoodButtonClickOff,
+        ("crimson_button" | "warped_button", true) => Sound::BlockNetherWoodButtonClickOn,
+        ("crimson_button" | "warped_button", false) => Sound::BlockNetherWoodButtonClickOff,
+        (_, true) => Sound::BlockWoodenButtonClickOn,
+        (_, false) => Sound::BlockWoodenButtonClickOff,
+    }
+}
+
+/// Turns the button on or off, with its click sound
+async fn set_pressed(
+    world: &World,
+    block: &Block,
+    location: BlockPos,
+    state_id: u16,
+    pressed: bool,
+) -> bool {
+    let Some(new_state) = block.state_with(state_id, "powered", &pressed.to_string()) else {
+        return false;
+    };
+    if !set_power(
+        world,
+        block,
+        location,
+        new_state,
+        current(!pressed),
+        current(pressed),
+    )
+    .await
+    {
+        return false;
+    }
+    world
+        .play_block_sound(
+            click_sound(&block.name, pressed),
+            SoundCategory::Blocks,
+            location,
+        )
+        .await;
+    true
+}
+
+impl ButtonBlock {
+    /// Presses the button, unless it is still pressed
+    async fn press(&self, block: &Block, player: &Player, location: BlockPos) {
+        let world = player.world();
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        if is_powered(block, state_id) {
+            return;
+        }
+        if set_pressed(world, block, location, state_id, true).await {
+            world
+                .schedule_block_tick(&location, press_duration(&block.name), TickPriority::Normal)
+                .await;
+        }
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for ButtonBlock {
+    async fn on_use<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+    ) {
+        self.press(block, player, location).await;
+    }
+
+    async fn on_use_with_item<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+    ) -> BlockActionResult {
+        self.press(block, player, location).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_scheduled_tick<'a>(
+        &self,
+        block: &Block,
+        world: &World,
+        location: BlockPos,
+        _server: &Server,
+    ) {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        if is_powered(block, state_id) {
+            set_pressed(world, block, location, state_id, false).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::sound::Sound;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::{
+        block::block_registry::get_block, dimension::Dimension, item::item_registry::get_item,
+    };
+
+    use super::{click_sound, press_duration, ButtonBlock};
+    use crate::block::{block_manager::BlockActionResult, blocks::redstone::is_powered};
+    use crate::data::test_folder::TestFolder;
+    use crate::{entity::player::test_player_in, server::Server, world::World};
+
+    #[test]
+    fn button_kinds() {
+        assert!(ButtonBlock::matches("oak_button"));
+        assert!(!ButtonBlock::matches("lever"));
+        assert_eq!(press_duration("stone_button"), 20);
+        assert_eq!(press_duration("oak_button"), 30);
+        assert_eq!(
+            click_sound("polished_blackstone_button", true),
+            Sound::BlockStoneButtonClickOn
+        );
+        assert_eq!(
+            click_sound("warped_button", false),
+            Sound::BlockNetherWoodButtonClickOff
+        );
+        assert_eq!(
+            click_sound("birch_button", true),
+            Sound::BlockWoodenButtonClickOn
+        );
+    }
+
+    #[tokio::test]
+    async fn pressed_buttons_turn_off_again() {
+        let folder = TestFolder::new("button");
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        let server = Server::with_world_folder(folder.join("server"));
+        let player = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        let button = get_block("stone_button").unwrap();
+        let location = BlockPos(Vector3::new(8, 100, 8));
+        world
+            .set_block_state(&location, button.default_state_id)
+            .await;
+
+        // Pressing it with an item in hand does not place the item
+        let stone = get_item("stone").unwrap();
+        let result = server
+            .block_manager
+            .on_use_with_item(button, &player, location, stone, &server)
+            .await;
+        assert!(matches!(result, BlockActionResult::Consume));
+        let state_id = world.get_block_state_id(&location).await.unwrap();
+        assert!(is_powered(button, state_id));
+
+        server
+            .block_manager
+            .on_scheduled_tick(button, &world, location, &server)
+            .await;
+        let state_id = world.get_block_state_id(&location).await.unwrap();
+        assert!(!is_powered(button, state_id));
+    }
+}
@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{block::block_registry::Block, item::item_registry::Item};
+
+use crate::block::block_manager::BlockActionResult;
+use crate::block::blocks::redstone::{current, is_powered, set_power};
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+
+#[pumpkin_block("minecraft:lever")]
+pub struct LeverBlock;
+
+impl LeverBlock {
+    /// Switches the lever on or off
+    async fn toggle(&self, block: &Block, player: &Player, location: BlockPos) {
+        let world = player.world();
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let powered = !is_powered(block, state_id);
+        let Some(new_state) = block.state_with(state_id, "powered", &powered.to_string()) else {
+            return;
+        };
+        if !set_power(
+            world,
+            block,
+            location,
+            new_state,
+            current(!powered),
+            current(powered),
+        )
+        .await
+        {
+            return;
+        }
+        // The click sounds higher when the lever is turned on
+        let pitch = if powered { 0.6 } else { 0.5 };
+        let center = Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y) + 0.5,
+            f64::from(location.0.z) + 0.5,
+        );
+        world
+            .play_sound_raw(
+                Sound::BlockLeverClick as u16,
+                SoundCategory::Blocks,
+                &center,
+                0.3,
+                pitch,
+            )
+            .await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for LeverBlock {
+    async fn on_use<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+    ) {
+        self.toggle(block, player, location).await;
+    }
+
+    async fn on_use_with_item<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+    ) -> BlockActionResult {
+        self.toggle(block, player, location).await;
+        BlockActionResult::Consume
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::{
+        block::block_registry::get_block, dimension::Dimension, item::item_registry::get_item,
+    };
+
+    use crate::block::{block_manager::BlockActionResult, blocks::redstone::is_powered};
+    use crate::data::test_folder::TestFolder;
+    use crate::{entity::player::test_player_in, server::Server, world::World};
+
+    #[tokio::test]
+    async fn levers_toggle_with_and_without_items() {
+        let folder = TestFolder::new("lever");
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        let server = Server::with_world_folder(folder.join("server"));
+        let player = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        let lever = get_block("lever").unwrap();
+        let location = BlockPos(Vector3::new(8, 100, 8));
+        world
+            .set_block_state(&location, lever.default_state_id)
+            .await;
+
+        server
+            .block_manager
+            .on_use(lever, &player, location, &server)
+            .await;
+        let state_id = world.get_block_state_id(&location).await.unwrap();
+        assert!(is_powered(lever, state_id));
+
+        // Using it with an item in hand switches it too, instead of placing the item
+        let stone = get_item("stone").unwrap();
+        let result = server
+            .block_manager
+            .on_use_with_item(lever, &player, location, stone, &server)
+            .await;
+        assert!(matches!(result, BlockActionResult::Consume));
+        let state_id = world.get_block_state_id(&location).await.unwrap();
+        assert!(!is_powered(lever, state_id));
+    }
+}
@@ -5,10 +5,14 @@ use pumpkin_world::block::block_registry::Block;
 
 use crate::{entity::player::Player, server::Server};
 
+pub(crate) mod button;
 pub(crate) mod chest;
 pub(crate) mod crafting_table;
 pub(crate) mod furnace;
 pub(crate) mod jukebox;
+pub(crate) mod lever;
+pub(crate) mod pressure_plate;
+mod redstone;
 
 /// The standard destroy with container removes the player forcibly from the container,
 /// drops items to the floor, and back to the player's inventory if the item stack is in movement.
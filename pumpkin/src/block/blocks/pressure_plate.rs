@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::block_registry::Block;
+
+use crate::block::blocks::redstone::{current, is_powered, set_power};
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::server::Server;
+use crate::world::{scheduled_tick::TickPriority, World};
+
+/// Pressure plates are powered while entities stand on them, weighted plates by how many there are
+pub struct PressurePlateBlock;
+
+impl PressurePlateBlock {
+    pub fn matches(name: &str) -> bool {
+        name.ends_with("_pressure_plate")
+    }
+}
+
+fn is_weighted(name: &str) -> bool {
+    name.ends_with("_weighted_pressure_plate")
+}
+
+/// How often an active plate checks whether entities are still on it, in ticks
+fn check_delay(name: &str) -> u32 {
+    if is_weighted(name) {
+        10
+    } else {
+        20
+    }
+}
+
+/// The current of the plate with the given amount of entities on it
+fn plate_current(name: &str, entities: usize) -> i32 {
+    let current = match name {
+        "light_weighted_pressure_plate" => entities,
+        "heavy_weighted_pressure_plate" => entities.div_ceil(10),
+        _ => return current(entities > 0),
+    };
+    current.min(15) as i32
+}
+
+/// The current the plate has in its state
+fn state_current(block: &Block, state_id: u16) -> i32 {
+    if is_weighted(&block.name) {
+        block
+            .property_value(state_id, "power")
+            .and_then(|power| power.parse().ok())
+            .unwrap_or(0)
+    } else {
+        current(is_powered(block, state_id))
+    }
+}
+
+fn click_sound(name: &str, powered: bool) -> Sound {
+    match (name, powered) {
+        ("stone_pressure_plate" | "polished_blackstone_pressure_plate", true) => {
+            Sound::BlockStonePressurePlateClickOn
+        }
+        ("stone_pressure_plate" | "polished_blackstone_pressure_plate", false) => {
+            Sound::BlockStonePressurePlateClickOff
+        }
+        (name, true) if is_weighted(name) => Sound::BlockMetalPressurePlateClickOn,
+        (name, false) if is_weighted(name) => Sound::BlockMetalPressurePlateClickOff,
+        ("bamboo_pressure_plate", true) => Sound::BlockBambooWoodPressurePlateClickOn,
+        ("bamboo_pressure_plate", false) => Sound::BlockBambooWoodPressurePlateClickOff,
+        ("cherry_pressure_plate", true) => Sound::BlockCherryWoodPressurePlateClickOn,
+        ("cherry_pressure_plate", false) => Sound::BlockCherryWoodPressurePlateClickOff,
+        ("crimson_pressure_plate" | "warped_pressure_plate", true) => {
+            Sound::BlockNetherWoodPressurePlateClickOn
+        }
+        ("crimson_pressure_plate" | "warped_pressure_plate", false) => {
+            Sound::BlockNetherWoodPressurePlateClickOff
+        }
+        (_, true) => Sound::BlockWoodenPressurePlateClickOn,
+        (_, false) => Sound::BlockWoodenPressurePlateClickOff,
+    }
+}
+
+/// Updates the current of the plate to the entities on it, and checks again later while it is active
+async fn update_plate(world: &World, block: &Block, location: BlockPos) {
+    let Ok(state_id) = world.get_block_state_id(&location).await else {
+        return;
+    };
+    let old_current = state_current(block, state_id);
+    let (x, y, z) = (
+        f64::from(location.0.x),
+        f64::from(location.0.y),
+        f64::from(location.0.z),
+    );
+    let area = BoundingBox::new(
+        Vector3::new(x + 0.0625, y, z + 0.0625),
+        Vector3::new(x + 0.9375, y + 0.25, z + 0.9375),
+    );
+    let entities = world
+        .get_entities_in_box(&area)
+        .await
+        .iter()
+        .filter(|entity| !entity.is_spectator())
+        .count();
+    let new_current = plate_current(&block.name, entities);
+
+    if new_current != old_current {
+        let new_state = if is_weighted(&block.name) {
+            block.state_with(state_id, "power", &new_current.to_string())
+        } else {
+            block.state_with(state_id, "powered", &(new_current > 0).to_string())
+        };
+        if let Some(new_state) = new_state {
+            let changed =
+                set_power(world, block, location, new_state, old_current, new_current).await;
+            if changed && (old_current > 0) != (new_current > 0) {
+                world
+                    .play_block_sound(
+                        click_sound(&block.name, new_current > 0),
+                        SoundCategory::Blocks,
+                        location,
+                    )
+                    .await;
+            }
+        }
+    }
+    if new_current > 0 {
+        world
+            .schedule_block_tick(&location, check_delay(&block.name), TickPriority::Normal)
+            .await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for PressurePlateBlock {
+    async fn on_entity_inside<'a>(
+        &self,
+        block: &Block,
+        world: &World,
+        location: BlockPos,
+        _server: &Server,
+    ) {
+        update_plate(world, block, location).await;
+    }
+
+    async fn on_scheduled_tick<'a>(
+        &self,
+        block: &Block,
+        world: &World,
+        location: BlockPos,
+        _server: &Server,
+    ) {
+        update_plate(world, block, location).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::{
+        math::{position::BlockPos, vector3::Vector3},
+        GameMode,
+    };
+    use pumpkin_world::{block::block_registry::get_block, dimension::Dimension};
+
+    use super::{check_delay, plate_current, PressurePlateBlock};
+    use crate::data::test_folder::TestFolder;
+    use crate::{
+        block::blocks::redstone::is_powered,
+        entity::player::test_player_in,
+        server::Server,
+        world::{entity_query::WorldEntity, World},
+    };
+
+    #[test]
+    fn plate_current_by_entities() {
+        assert!(PressurePlateBlock::matches("oak_pressure_plate"));
+        assert_eq!(plate_current("stone_pressure_plate", 0), 0);
+        assert_eq!(plate_current("stone_pressure_plate", 3), 15);
+        assert_eq!(plate_current("light_weighted_pressure_plate", 3), 3);
+        assert_eq!(plate_current("light_weighted_pressure_plate", 40), 15);
+        assert_eq!(plate_current("heavy_weighted_pressure_plate", 11), 2);
+        assert_eq!(plate_current("heavy_weighted_pressure_plate", 0), 0);
+        assert_eq!(check_delay("heavy_weighted_pressure_plate"), 10);
+        assert_eq!(check_delay("oak_pressure_plate"), 20);
+    }
+
+    async fn plate_powered(world: &World, location: &BlockPos) -> bool {
+        let state_id = world.get_block_state_id(location).await.unwrap();
+        is_powered(world.get_block(location).await.unwrap(), state_id)
+    }
+
+    #[tokio::test]
+    async fn mobs_press_plates_but_spectators_do_not() {
+        let folder = TestFolder::new("pressure-plate");
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        let server = Server::with_world_folder(folder.join("server"));
+        let plate = get_block("oak_pressure_plate").unwrap();
+        let location = BlockPos(Vector3::new(8, 100, 8));
+        world
+            .set_block_state(&location, plate.default_state_id)
+            .await;
+
+        let spectator = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        spectator.gamemode.store(GameMode::Spectator);
+        spectator
+            .living_entity
+            .set_pos(Vector3::new(8.5, 100.0, 8.5));
+        world
+            .current_players
+            .lock()
+            .await
+            .insert(spectator.gameprofile.id, spectator.clone());
+        assert!(WorldEntity::Player(spectator).is_spectator());
+        world.enter_block(location, &server).await;
+        assert!(!plate_powered(&world, &location).await);
+
+        server
+            .add_mob_entity(EntityType::Zombie, Vector3::new(8.5, 100.0, 8.5), &world)
+            .await;
+        world.enter_block(location, &server).await;
+        assert!(plate_powered(&world, &location).await);
+    }
+}
@@ -0,0 +1,49 @@
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::block_registry::Block;
+
+use crate::{
+    plugin::{block::redstone::BlockRedstoneEventImpl, CancellableEvent},
+    world::World,
+    PLUGIN_MANAGER,
+};
+
+/// The current of a power source that is either on or off
+pub const fn current(powered: bool) -> i32 {
+    if powered {
+        15
+    } else {
+        0
+    }
+}
+
+/// Whether the power source is turned on, false for blocks without a `powered` property
+pub fn is_powered(block: &Block, state_id: u16) -> bool {
+    block.property_value(state_id, "powered") == Some("true")
+}
+
+/// Changes a power source to `new_state`, unless a plugin cancels the `BlockRedstoneEvent`.
+/// Returns whether the block was changed
+pub async fn set_power(
+    world: &World,
+    block: &Block,
+    location: BlockPos,
+    new_state: u16,
+    old_current: i32,
+    new_current: i32,
+) -> bool {
+    let event = PLUGIN_MANAGER
+        .lock()
+        .await
+        .fire_cancellable(BlockRedstoneEventImpl::new(
+            block.clone(),
+            location,
+            old_current,
+            new_current,
+        ))
+        .await;
+    if event.is_cancelled() {
+        return false;
+    }
+    world.set_block_state(&location, new_state).await;
+    true
+}
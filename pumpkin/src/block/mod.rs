@@ -4,6 +4,9 @@ use blocks::furnace::FurnaceBlock;
 use crate::block::block_manager::BlockManager;
 use crate::block::blocks::crafting_table::CraftingTableBlock;
 use crate::block::blocks::jukebox::JukeboxBlock;
+use blocks::button::ButtonBlock;
+use blocks::lever::LeverBlock;
+use blocks::pressure_plate::PressurePlateBlock;
 use std::sync::Arc;
 
 pub mod block_manager;
@@ -18,6 +21,9 @@ pub fn default_block_manager() -> Arc<BlockManager> {
     manager.register(CraftingTableBlock);
     manager.register(FurnaceBlock);
     manager.register(ChestBlock);
+    manager.register(LeverBlock);
+    manager.register_matching(ButtonBlock, ButtonBlock::matches);
+    manager.register_matching(PressurePlateBlock, PressurePlateBlock::matches);
 
     Arc::new(manager)
 }
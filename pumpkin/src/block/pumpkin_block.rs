@@ -1,6 +1,7 @@
 use crate::block::block_manager::BlockActionResult;
 use crate::entity::player::Player;
 use crate::server::Server;
+use crate::world::World;
 use async_trait::async_trait;
 use pumpkin_inventory::OpenContainer;
 use pumpkin_util::math::position::BlockPos;
@@ -63,4 +64,24 @@ pub trait PumpkinBlock: Send + Sync {
         _container: &mut OpenContainer,
     ) {
     }
+
+    /// Called when a tick scheduled with [`World::schedule_block_tick`] is due
+    async fn on_scheduled_tick<'a>(
+        &self,
+        _block: &Block,
+        _world: &World,
+        _location: BlockPos,
+        _server: &Server,
+    ) {
+    }
+
+    /// Called when an entity moves into the block
+    async fn on_entity_inside<'a>(
+        &self,
+        _block: &Block,
+        _world: &World,
+        _location: BlockPos,
+        _server: &Server,
+    ) {
+    }
 }
@@ -1,7 +1,11 @@
 use std::str::FromStr;
 
+use pumpkin_protocol::client::play::CommandSuggestion;
+use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::{WORLD_LOWEST_Y, WORLD_MAX_Y};
 
+use crate::command::CommandSender;
+
 pub enum MaybeRelativeCoordinate<const IS_Y: bool> {
     Absolute(f64),
     Relative(f64),
@@ -69,3 +73,112 @@ impl<const IS_Y: bool> MaybeRelativeBlockCoordinate<IS_Y> {
         Some(abs)
     }
 }
+
+/// A position in local coordinates (`^left ^up ^forwards`), which are relative to where the
+/// sender is looking. Local coordinates can't be mixed with other coordinates
+#[derive(Debug)]
+pub struct LocalCoordinates {
+    left: f64,
+    up: f64,
+    forwards: f64,
+}
+
+impl LocalCoordinates {
+    pub fn try_new(left: &str, up: &str, forwards: &str) -> Option<Self> {
+        fn parse(s: &str) -> Option<f64> {
+            let offset = s.strip_prefix('^')?;
+            if offset.is_empty() {
+                Some(0.0)
+            } else {
+                offset.parse().ok()
+            }
+        }
+
+        Some(Self {
+            left: parse(left)?,
+            up: parse(up)?,
+            forwards: parse(forwards)?,
+        })
+    }
+
+    /// Resolves the coordinates from `origin`, facing the direction of `yaw` and `pitch`
+    pub fn into_absolute(self, origin: Vector3<f64>, (yaw, pitch): (f32, f32)) -> Vector3<f64> {
+        let (yaw, pitch) = (
+            (f64::from(yaw) + 90.0).to_radians(),
+            f64::from(-pitch).to_radians(),
+        );
+        let forwards = Vector3::new(
+            yaw.cos() * pitch.cos(),
+            pitch.sin(),
+            yaw.sin() * pitch.cos(),
+        );
+        let up_pitch = pitch + std::f64::consts::FRAC_PI_2;
+        let up = Vector3::new(
+            yaw.cos() * up_pitch.cos(),
+            up_pitch.sin(),
+            yaw.sin() * up_pitch.cos(),
+        );
+        // left = up x forwards
+        let left = Vector3::new(
+            up.y * forwards.z - up.z * forwards.y,
+            up.z * forwards.x - up.x * forwards.z,
+            up.x * forwards.y - up.y * forwards.x,
+        );
+
+        origin + forwards * self.forwards + up * self.up + left * self.left
+    }
+}
+
+/// Suggests the coordinates of the block a player is looking at, the block they are standing in
+/// and `~ ~ ~` for an argument made of three coordinates. The console gets `0 0 0` as it has no position.
+pub(crate) async fn suggest_coordinates(
+    sender: &CommandSender<'_>,
+    input: &str,
+) -> Vec<CommandSuggestion> {
+    let mut candidates = Vec::new();
+    if let CommandSender::Player(player) = sender {
+        if let Some(target) = player.target_block().await {
+            candidates.push([
+                target.0.x.to_string(),
+                target.0.y.to_string(),
+                target.0.z.to_string(),
+            ]);
+        }
+        let pos = player.living_entity.entity.block_pos.load();
+        let current = [
+            pos.0.x.to_string(),
+            pos.0.y.to_string(),
+            pos.0.z.to_string(),
+        ];
+        if !candidates.contains(&current) {
+            candidates.push(current);
+        }
+        candidates.push(["~".into(), "~".into(), "~".into()]);
+    } else {
+        candidates.push(["0".into(), "0".into(), "0".into()]);
+    }
+
+    // The client replaces the word currently being typed, so only suggest the coordinates that
+    // are still missing. We can't know where the argument started, so assume every coordinate
+    // directly in front of the current word belongs to it
+    let (partial, previous) = if input.ends_with(char::is_whitespace) {
+        ("", input)
+    } else {
+        input
+            .rsplit_once(char::is_whitespace)
+            .map_or(("", ""), |(previous, partial)| (partial, previous))
+    };
+    let typed = previous
+        .split_whitespace()
+        .rev()
+        .take_while(|word| MaybeRelativeCoordinate::<false>::try_from(*word).is_ok())
+        .count()
+        % 3;
+
+    candidates
+        .into_iter()
+        .map(|coordinates| coordinates[typed..].join(" "))
+        .filter(|suggestion| suggestion.starts_with(partial))
+        .map(|suggestion| CommandSuggestion::new(suggestion, None))
+        .collect()
+}
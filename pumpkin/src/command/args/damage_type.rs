@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_registry::{get_damage_type, DamageType};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+pub struct DamageTypeArgumentConsumer;
+
+impl GetClientSideArgParser for DamageTypeArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        // The client suggests damage types from the synced registry
+        ArgumentType::Resource {
+            identifier: "damage_type",
+        }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for DamageTypeArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::ResourceLocation(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for DamageTypeArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "damageType"
+    }
+}
+
+impl<'a> FindArg<'a> for DamageTypeArgumentConsumer {
+    type Data = (u8, &'static DamageType);
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ResourceLocation(name)) => get_damage_type(name).map_or_else(
+                || {
+                    Err(CommandError::GeneralCommandIssue(format!(
+                        "Damage type {name} does not exist."
+                    )))
+                },
+                Result::Ok,
+            ),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
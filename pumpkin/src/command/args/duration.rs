@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    ArgumentType, CommandSuggestion, StringProtoArgBehavior, SuggestionProviders,
+};
+
+use crate::command::args::{Arg, ArgumentConsumer, FindArg, GetClientSideArgParser};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::server::Server;
+
+/// A real time duration like `30m` or `7d`, see [`parse_duration`]
+pub struct DurationArgumentConsumer;
+
+impl GetClientSideArgParser for DurationArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::String(StringProtoArgBehavior::SingleWord)
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for DurationArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        parse_duration(args.pop()?).map(Arg::Duration)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl<'a> FindArg<'a> for DurationArgumentConsumer {
+    type Data = Duration;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Duration(data)) => Ok(*data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
+
+/// Parses a positive whole number of seconds `s`, minutes `m`, hours `h`, days `d` or weeks `w`, like `30m`
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (number, seconds_per_unit) = match s.char_indices().last()? {
+        (i, 's') => (&s[..i], 1),
+        (i, 'm') => (&s[..i], 60),
+        (i, 'h') => (&s[..i], 60 * 60),
+        (i, 'd') => (&s[..i], 24 * 60 * 60),
+        (i, 'w') => (&s[..i], 7 * 24 * 60 * 60),
+        _ => return None,
+    };
+    let seconds = number.parse::<u64>().ok()?.checked_mul(seconds_per_unit)?;
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::parse_duration;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("30m"), Some(Duration::from_secs(1800)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("7d"), Some(Duration::from_secs(604_800)));
+        assert_eq!(parse_duration("1w"), Some(Duration::from_secs(604_800)));
+        assert_eq!(parse_duration("0d"), None);
+        assert_eq!(parse_duration("-1d"), None);
+        assert_eq!(parse_duration("1.5d"), None);
+        assert_eq!(parse_duration("30"), None);
+        assert_eq!(parse_duration("griefing"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+}
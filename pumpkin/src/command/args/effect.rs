@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::{command::dispatcher::CommandError, entity::effect::Effect, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+pub struct EffectArgumentConsumer;
+
+impl GetClientSideArgParser for EffectArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        // The client knows the effects from its built-in registry
+        ArgumentType::Resource {
+            identifier: "mob_effect",
+        }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for EffectArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::ResourceLocation(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for EffectArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "effect"
+    }
+}
+
+impl<'a> FindArg<'a> for EffectArgumentConsumer {
+    type Data = Effect;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ResourceLocation(name)) => Effect::from_name(name).ok_or_else(|| {
+                CommandError::GeneralCommandIssue(format!("Effect {name} does not exist."))
+            }),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -1,20 +1,16 @@
-use std::sync::Arc;
-
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 
 use crate::command::dispatcher::CommandError;
 use crate::command::tree::RawArgs;
 use crate::command::CommandSender;
-use crate::entity::player::Player;
 use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
 
 use super::super::args::ArgumentConsumer;
-use super::players::PlayersArgumentConsumer;
+use super::selector::TargetSelector;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
-/// todo: implement (currently just calls [`super::arg_player::PlayerArgumentConsumer`])
-///
 /// For selecting zero, one or multiple entities, eg. using @s, a player name, @a or @e
 pub struct EntitiesArgumentConsumer;
 
@@ -37,11 +33,20 @@ impl ArgumentConsumer for EntitiesArgumentConsumer {
         server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        // todo
-        match PlayersArgumentConsumer.consume(src, server, args).await {
-            Some(Arg::Players(p)) => Some(Arg::Entities(p)),
-            _ => None,
-        }
+        self.try_consume(src, server, args).await.ok().flatten()
+    }
+
+    async fn try_consume<'a>(
+        &'a self,
+        src: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Result<Option<Arg<'a>>, CommandError> {
+        let Some(arg) = args.pop() else {
+            return Ok(None);
+        };
+        let selector = TargetSelector::parse(arg)?;
+        Ok(selector.resolve(src, server).await.map(Arg::Entities))
     }
 
     async fn suggest<'a>(
@@ -61,7 +66,7 @@ impl DefaultNameArgConsumer for EntitiesArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for EntitiesArgumentConsumer {
-    type Data = &'a [Arc<Player>];
+    type Data = &'a [WorldEntity];
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
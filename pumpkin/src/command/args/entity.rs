@@ -1,19 +1,16 @@
-use std::sync::Arc;
-
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 
 use crate::command::dispatcher::CommandError;
 use crate::command::tree::RawArgs;
 use crate::command::CommandSender;
-use crate::entity::player::Player;
 use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
 
 use super::super::args::ArgumentConsumer;
+use super::selector::TargetSelector;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
-/// todo: implement for entities that aren't players
-///
 /// For selecting a single entity, eg. using @s, a player name or entity uuid.
 ///
 /// Use [`super::arg_entities::EntitiesArgumentConsumer`] when there may be multiple targets.
@@ -40,30 +37,29 @@ impl ArgumentConsumer for EntityArgumentConsumer {
         server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let s = args.pop()?;
+        self.try_consume(src, server, args).await.ok().flatten()
+    }
 
-        let entity = match s {
-            // @s is always valid when sender is a player
-            "@s" => match src {
-                CommandSender::Player(p) => Some(p.clone()),
-                _ => None,
-            },
-            // @n/@p are always valid when sender is a player
-            #[allow(clippy::match_same_arms)] // todo: implement for non-players
-            "@n" | "@p" => match src {
-                CommandSender::Player(p) => Some(p.clone()),
-                // todo: implement for non-players: how should this behave when sender is console/rcon?
-                _ => None,
-            },
-            // @r is valid when there is at least one player
-            "@r" => server.get_random_player().await,
-            // @a/@e/@r are not valid because we're looking for a single entity
-            "@a" | "@e" => None,
-            // player name is only valid if player is online
-            name => server.get_player_by_name(name).await,
+    async fn try_consume<'a>(
+        &'a self,
+        src: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Result<Option<Arg<'a>>, CommandError> {
+        let Some(arg) = args.pop() else {
+            return Ok(None);
         };
+        let selector = TargetSelector::parse(arg)?;
+        // @a/@e are not valid because we're looking for a single entity
+        if !selector.is_single() {
+            return Ok(None);
+        }
+        let entity = selector
+            .resolve(src, server)
+            .await
+            .and_then(|mut entities| entities.pop());
 
-        entity.map(Arg::Entity)
+        Ok(entity.map(Arg::Entity))
     }
 
     async fn suggest<'a>(
@@ -83,7 +79,7 @@ impl DefaultNameArgConsumer for EntityArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for EntityArgumentConsumer {
-    type Data = Arc<Player>;
+    type Data = WorldEntity;
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
@@ -0,0 +1,70 @@
+use crate::command::args::{
+    Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+use crate::command::dispatcher::CommandError;
+use crate::command::function::{FunctionId, FUNCTIONS};
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+/// The id of a loaded function, suggesting all functions that are currently loaded
+pub struct FunctionArgumentConsumer;
+
+impl GetClientSideArgParser for FunctionArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::ResourceLocation
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for FunctionArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::ResourceLocation(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        let suggestions = FUNCTIONS
+            .read()
+            .await
+            .ids()
+            .into_iter()
+            .map(|id| CommandSuggestion::new(id, None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for FunctionArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "name"
+    }
+}
+
+impl<'a> FindArg<'a> for FunctionArgumentConsumer {
+    type Data = FunctionId;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ResourceLocation(id)) => FunctionId::parse(id).ok_or_else(|| {
+                CommandError::GeneralCommandIssue(format!("Invalid function id {id}"))
+            }),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
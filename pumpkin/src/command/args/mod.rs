@@ -1,7 +1,8 @@
-use std::{collections::HashMap, hash::Hash, sync::Arc};
+use std::{collections::HashMap, hash::Hash, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use bounded_num::{NotInBounds, Number};
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::{
@@ -15,6 +16,7 @@ use super::{
     CommandSender,
 };
 use crate::world::bossbar::{BossbarColor, BossbarDivisions};
+use crate::world::entity_query::WorldEntity;
 use crate::{entity::player::Player, server::Server};
 
 pub mod block;
@@ -24,21 +26,28 @@ pub mod bossbar_style;
 pub mod bounded_num;
 pub mod command;
 mod coordinate;
+pub mod damage_type;
+pub mod duration;
+pub mod effect;
 pub mod entities;
 pub mod entity;
+pub mod function;
 pub mod gamemode;
 pub mod item;
 pub mod message;
+pub mod nbt;
 pub mod players;
 pub mod position_2d;
 pub mod position_3d;
 pub mod position_block;
 pub mod resource_location;
 pub mod rotation;
+mod selector;
 pub mod simple;
 pub mod sound;
 pub mod summonable_entities;
 pub mod textcomponent;
+pub mod time;
 
 /// see [`crate::commands::tree_builder::argument`]
 #[async_trait]
@@ -50,6 +59,17 @@ pub trait ArgumentConsumer: Sync + GetClientSideArgParser {
         args: &mut RawArgs<'a>,
     ) -> Option<Arg>;
 
+    /// Like [`ArgumentConsumer::consume`], but an error explains to the sender why the argument is
+    /// invalid, instead of only trying the next path through the command tree
+    async fn try_consume<'a>(
+        &'a self,
+        sender: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Result<Option<Arg<'a>>, CommandError> {
+        Ok(self.consume(sender, server, args).await)
+    }
+
     /// Used for tab completion (but only if argument suggestion type is "minecraft:ask_server"!).
     ///
     /// NOTE: This is called after this consumer's [`ArgumentConsumer::consume`] method returned None, so if args is used here, make sure [`ArgumentConsumer::consume`] never returns None after mutating args.
@@ -74,8 +94,8 @@ pub trait DefaultNameArgConsumer: ArgumentConsumer {
 
 #[derive(Clone)]
 pub enum Arg<'a> {
-    Entities(Vec<Arc<Player>>),
-    Entity(Arc<Player>),
+    Entities(Vec<WorldEntity>),
+    Entity(WorldEntity),
     Players(Vec<Arc<Player>>),
     BlockPos(BlockPos),
     Pos3D(Vector3<f64>),
@@ -89,9 +109,12 @@ pub enum Arg<'a> {
     BossbarColor(BossbarColor),
     BossbarStyle(BossbarDivisions),
     Msg(String),
+    Nbt(NbtCompound),
     TextComponent(TextComponent),
     Num(Result<Number, NotInBounds>),
     Bool(bool),
+    Time(i32),
+    Duration(Duration),
     #[allow(unused)]
     Simple(&'a str),
 }
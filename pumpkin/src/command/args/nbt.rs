@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use pumpkin_nbt::{compound::NbtCompound, snbt};
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// Consumes a compound tag written as SNBT, like `{CustomName:'"Bob"'}`. It may contain spaces,
+/// so all remaining words are consumed
+pub struct NbtArgConsumer;
+
+impl GetClientSideArgParser for NbtArgConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::Nbt
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for NbtArgConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let mut nbt = args.pop()?.to_string();
+        while let Some(word) = args.pop() {
+            nbt.push(' ');
+            nbt.push_str(word);
+        }
+
+        snbt::parse_compound(&nbt).ok().map(Arg::Nbt)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for NbtArgConsumer {
+    fn default_name(&self) -> &'static str {
+        "nbt"
+    }
+}
+
+impl<'a> FindArg<'a> for NbtArgConsumer {
+    type Data = &'a NbtCompound;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Nbt(data)) => Ok(data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
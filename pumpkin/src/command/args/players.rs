@@ -8,8 +8,10 @@ use crate::command::tree::RawArgs;
 use crate::command::CommandSender;
 use crate::entity::player::Player;
 use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
 
 use super::super::args::ArgumentConsumer;
+use super::selector::TargetSelector;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// Select zero, one or multiple players
@@ -36,28 +38,31 @@ impl ArgumentConsumer for PlayersArgumentConsumer {
         server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let s = args.pop()?;
+        self.try_consume(src, server, args).await.ok().flatten()
+    }
 
-        let players = match s {
-            "@s" => match src {
-                CommandSender::Player(p) => Some(vec![p.clone()]),
-                _ => None,
-            },
-            #[allow(clippy::match_same_arms)]
-            // todo: implement for non-players and remove this line
-            "@n" | "@p" => match src {
-                CommandSender::Player(p) => Some(vec![p.clone()]),
-                // todo: implement for non-players: how should this behave when sender is console/rcon?
-                _ => None,
-            },
-            "@r" => {
-                (server.get_random_player().await).map_or_else(|| Some(vec![]), |p| Some(vec![p]))
-            }
-            "@a" | "@e" => Some(server.get_all_players().await),
-            name => server.get_player_by_name(name).await.map(|p| vec![p]),
+    async fn try_consume<'a>(
+        &'a self,
+        src: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Result<Option<Arg<'a>>, CommandError> {
+        let Some(arg) = args.pop() else {
+            return Ok(None);
         };
+        let selector = TargetSelector::parse(arg)?;
+        // Entity selectors like `@e` only select the players
+        let players = selector.resolve(src, server).await.map(|entities| {
+            entities
+                .into_iter()
+                .filter_map(|entity| match entity {
+                    WorldEntity::Player(player) => Some(player),
+                    WorldEntity::Mob(_) => None,
+                })
+                .collect()
+        });
 
-        players.map(Arg::Players)
+        Ok(players.map(Arg::Players))
     }
 
     async fn suggest<'a>(
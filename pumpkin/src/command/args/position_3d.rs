@@ -8,7 +8,7 @@ use crate::command::CommandSender;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
-use super::coordinate::MaybeRelativeCoordinate;
+use super::coordinate::{suggest_coordinates, MaybeRelativeCoordinate};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// x, y and z coordinates
@@ -20,7 +20,7 @@ impl GetClientSideArgParser for Position3DArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        None
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -41,11 +41,11 @@ impl ArgumentConsumer for Position3DArgumentConsumer {
 
     async fn suggest<'a>(
         &'a self,
-        _sender: &CommandSender<'a>,
+        sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_coordinates(sender, input).await))
     }
 }
 
@@ -9,10 +9,11 @@ use crate::command::CommandSender;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
-use super::coordinate::MaybeRelativeBlockCoordinate;
+use super::coordinate::{suggest_coordinates, LocalCoordinates, MaybeRelativeBlockCoordinate};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
-/// x, y and z coordinates
+/// x, y and z coordinates, either absolute, relative to the sender (`~`) or local to where the
+/// sender is looking (`^`)
 pub struct BlockPosArgumentConsumer;
 
 impl GetClientSideArgParser for BlockPosArgumentConsumer {
@@ -21,7 +22,7 @@ impl GetClientSideArgParser for BlockPosArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        None
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -35,42 +36,67 @@ impl ArgumentConsumer for BlockPosArgumentConsumer {
     ) -> Option<Arg<'a>> {
         let pos = MaybeRelativeBlockPos::try_new(args.pop()?, args.pop()?, args.pop()?)?;
 
-        let vec3 = pos.try_to_absolute(src.position())?;
+        let vec3 = pos.try_to_absolute(src.position(), src.rotation())?;
 
         Some(Arg::BlockPos(vec3))
     }
 
     async fn suggest<'a>(
         &'a self,
-        _sender: &CommandSender<'a>,
+        sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_coordinates(sender, input).await))
     }
 }
 
-struct MaybeRelativeBlockPos(
-    MaybeRelativeBlockCoordinate<false>,
-    MaybeRelativeBlockCoordinate<true>,
-    MaybeRelativeBlockCoordinate<false>,
-);
+enum MaybeRelativeBlockPos {
+    Coordinates(
+        MaybeRelativeBlockCoordinate<false>,
+        MaybeRelativeBlockCoordinate<true>,
+        MaybeRelativeBlockCoordinate<false>,
+    ),
+    Local(LocalCoordinates),
+}
 
 impl MaybeRelativeBlockPos {
     fn try_new(x: &str, y: &str, z: &str) -> Option<Self> {
-        Some(Self(
+        if x.starts_with('^') {
+            return LocalCoordinates::try_new(x, y, z).map(Self::Local);
+        }
+        Some(Self::Coordinates(
             x.try_into().ok()?,
             y.try_into().ok()?,
             z.try_into().ok()?,
         ))
     }
 
-    fn try_to_absolute(self, origin: Option<Vector3<f64>>) -> Option<BlockPos> {
-        Some(BlockPos(Vector3::new(
-            self.0.into_absolute(origin.map(|o| o.x))?,
-            self.1.into_absolute(origin.map(|o| o.y))?,
-            self.2.into_absolute(origin.map(|o| o.z))?,
-        )))
+    /// Returns `None` if the position is out of the world's height range, or is relative and the
+    /// sender has no position or rotation
+    fn try_to_absolute(
+        self,
+        origin: Option<Vector3<f64>>,
+        rotation: Option<(f32, f32)>,
+    ) -> Option<BlockPos> {
+        match self {
+            Self::Coordinates(x, y, z) => Some(BlockPos(Vector3::new(
+                x.into_absolute(origin.map(|o| o.x))?,
+                y.into_absolute(origin.map(|o| o.y))?,
+                z.into_absolute(origin.map(|o| o.z))?,
+            ))),
+            Self::Local(local) => {
+                let pos = local.into_absolute(origin?, rotation?);
+                // The y coordinate is validated like an absolute one
+                let y = MaybeRelativeBlockCoordinate::<true>::Absolute(pos.y.floor() as i32)
+                    .into_absolute(None)?;
+                Some(BlockPos(Vector3::new(
+                    pos.x.floor() as i32,
+                    y,
+                    pos.z.floor() as i32,
+                )))
+            }
+        }
     }
 }
 
@@ -90,3 +116,51 @@ impl<'a> FindArg<'a> for BlockPosArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::MaybeRelativeBlockPos;
+
+    fn resolve(input: &str, origin: Option<Vector3<f64>>, yaw: f32) -> Option<BlockPos> {
+        let [x, y, z] = input.split(' ').collect::<Vec<_>>()[..] else {
+            panic!("expected three coordinates");
+        };
+        MaybeRelativeBlockPos::try_new(x, y, z)?.try_to_absolute(origin, origin.map(|_| (yaw, 0.0)))
+    }
+
+    fn pos(x: i32, y: i32, z: i32) -> BlockPos {
+        BlockPos(Vector3::new(x, y, z))
+    }
+
+    #[test]
+    fn absolute_and_relative() {
+        let origin = Some(Vector3::new(10.7, 64.0, -3.2));
+        assert_eq!(resolve("1 2 3", None, 0.0), Some(pos(1, 2, 3)));
+        assert_eq!(resolve("~ ~ ~", origin, 0.0), Some(pos(10, 64, -4)));
+        assert_eq!(resolve("~1 ~-2 ~3", origin, 0.0), Some(pos(11, 62, -1)));
+        assert_eq!(resolve("5 ~1 ~", origin, 0.0), Some(pos(5, 65, -4)));
+
+        // The console has no position to be relative to
+        assert_eq!(resolve("~ 64 ~", None, 0.0), None);
+        assert_eq!(resolve("1 ~ 3", None, 0.0), None);
+    }
+
+    #[test]
+    fn local() {
+        let origin = Some(Vector3::new(0.5, 64.0, 0.5));
+        assert_eq!(resolve("^ ^ ^", origin, 0.0), Some(pos(0, 64, 0)));
+        // Yaw 0 faces south (+z), so left is east (+x)
+        assert_eq!(resolve("^ ^ ^2", origin, 0.0), Some(pos(0, 64, 2)));
+        assert_eq!(resolve("^1 ^ ^", origin, 0.0), Some(pos(1, 64, 0)));
+        assert_eq!(resolve("^ ^3 ^", origin, 0.0), Some(pos(0, 67, 0)));
+        // Yaw 90 faces west (-x)
+        assert_eq!(resolve("^ ^ ^2", origin, 90.0), Some(pos(-2, 64, 0)));
+
+        // Local coordinates can't be mixed and need a sender that is looking somewhere
+        assert!(resolve("^ ~ ^", origin, 0.0).is_none());
+        assert!(resolve("~ ^ ~", origin, 0.0).is_none());
+        assert!(resolve("^ ^ ^", None, 0.0).is_none());
+    }
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_util::math::wrap_degrees;
 
 use crate::command::dispatcher::CommandError;
 use crate::command::tree::RawArgs;
@@ -9,7 +10,7 @@ use crate::server::Server;
 use super::super::args::ArgumentConsumer;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
-/// yaw and pitch
+/// yaw and pitch, each either absolute or relative to the sender's rotation (`~`)
 pub struct RotationArgumentConsumer;
 
 impl GetClientSideArgParser for RotationArgumentConsumer {
@@ -26,25 +27,14 @@ impl GetClientSideArgParser for RotationArgumentConsumer {
 impl ArgumentConsumer for RotationArgumentConsumer {
     async fn consume<'a>(
         &'a self,
-        _src: &CommandSender<'a>,
+        src: &CommandSender<'a>,
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
         let yaw = args.pop()?;
         let pitch = args.pop()?;
 
-        let mut yaw = yaw.parse::<f32>().ok()?;
-        let mut pitch = pitch.parse::<f32>().ok()?;
-
-        yaw %= 360.0;
-        if yaw >= 180.0 {
-            yaw -= 360.0;
-        };
-        pitch %= 360.0;
-        if pitch >= 180.0 {
-            pitch -= 360.0;
-        };
-
+        let (yaw, pitch) = parse_rotation(yaw, pitch, src.rotation())?;
         Some(Arg::Rotation(yaw, pitch))
     }
 
@@ -58,6 +48,25 @@ impl ArgumentConsumer for RotationArgumentConsumer {
     }
 }
 
+/// Parses a single angle, returns `None` if it is relative and there is no angle to be relative to
+fn parse_angle(s: &str, origin: Option<f32>) -> Option<f32> {
+    match s.strip_prefix('~') {
+        Some("") => origin,
+        Some(offset) => Some(origin? + offset.parse::<f32>().ok()?),
+        None => s.parse().ok(),
+    }
+}
+
+/// Resolves a yaw and pitch, wrapping the yaw to `-180..180` and clamping the pitch to `-90..=90`
+fn parse_rotation(yaw: &str, pitch: &str, origin: Option<(f32, f32)>) -> Option<(f32, f32)> {
+    let yaw = parse_angle(yaw, origin.map(|(yaw, _)| yaw))?;
+    let pitch = parse_angle(pitch, origin.map(|(_, pitch)| pitch))?;
+    if !yaw.is_finite() || !pitch.is_finite() {
+        return None;
+    }
+    Some((wrap_degrees(yaw), pitch.clamp(-90.0, 90.0)))
+}
+
 impl DefaultNameArgConsumer for RotationArgumentConsumer {
     fn default_name(&self) -> &'static str {
         "rotation"
@@ -74,3 +83,34 @@ impl<'a> FindArg<'a> for RotationArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::parse_rotation;
+
+    #[test]
+    fn absolute() {
+        assert_eq!(parse_rotation("90", "45", None), Some((90.0, 45.0)));
+        assert_eq!(parse_rotation("-30.5", "0", None), Some((-30.5, 0.0)));
+        assert_eq!(parse_rotation("north", "0", None), None);
+    }
+
+    #[test]
+    fn wraps_yaw_and_clamps_pitch() {
+        assert_eq!(parse_rotation("270", "100", None), Some((-90.0, 90.0)));
+        assert_eq!(parse_rotation("-540", "-95", None), Some((-180.0, -90.0)));
+        assert_eq!(parse_rotation("720", "0", None), Some((0.0, 0.0)));
+    }
+
+    #[test]
+    fn relative() {
+        let origin = Some((170.0, 80.0));
+        assert_eq!(parse_rotation("~", "~", origin), Some((170.0, 80.0)));
+        assert_eq!(parse_rotation("~20", "~-30", origin), Some((-170.0, 50.0)));
+        assert_eq!(parse_rotation("0", "~20", origin), Some((0.0, 90.0)));
+
+        // The console has no rotation to be relative to
+        assert_eq!(parse_rotation("~", "0", None), None);
+        assert_eq!(parse_rotation("0", "~5", None), None);
+    }
+}
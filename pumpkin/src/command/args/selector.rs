@@ -0,0 +1,418 @@
+use std::fmt;
+
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::{math::vector3::Vector3, GameMode};
+use rand::seq::SliceRandom;
+
+use crate::command::dispatcher::CommandError;
+use crate::command::CommandSender;
+use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
+
+/// Which entities a selector starts out with, before its filters are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorKind<'a> {
+    /// `@s`, the entity running the command
+    Sender,
+    /// `@p`, the player nearest to the sender
+    NearestPlayer,
+    /// `@n`, the entity nearest to the sender
+    NearestEntity,
+    /// `@r`, a random player
+    RandomPlayer,
+    /// `@a`, every player
+    AllPlayers,
+    /// `@e`, every entity
+    AllEntities,
+    /// An online player with this name
+    Player(&'a str),
+}
+
+impl SelectorKind<'_> {
+    const fn selects_players(self) -> bool {
+        matches!(
+            self,
+            Self::NearestPlayer | Self::RandomPlayer | Self::AllPlayers | Self::Player(_)
+        )
+    }
+
+    const fn default_sort(self) -> Sort {
+        match self {
+            Self::NearestPlayer | Self::NearestEntity => Sort::Nearest,
+            Self::RandomPlayer => Sort::Random,
+            _ => Sort::Arbitrary,
+        }
+    }
+
+    const fn default_limit(self) -> Option<usize> {
+        match self {
+            Self::AllPlayers | Self::AllEntities => None,
+            _ => Some(1),
+        }
+    }
+}
+
+/// Selects entities for an argument, either by a target selector like `@e[type=zombie]` or by a player name
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetSelector<'a> {
+    pub kind: SelectorKind<'a>,
+    pub filters: SelectorFilters<'a>,
+}
+
+impl<'a> TargetSelector<'a> {
+    pub fn parse(s: &'a str) -> Result<Self, SelectorError> {
+        let (kind, options) = s
+            .split_once('[')
+            .map_or((s, None), |(kind, options)| (kind, Some(options)));
+        let options = options
+            .map(|options| options.strip_suffix(']').ok_or(SelectorError::Unterminated))
+            .transpose()?;
+        let kind = match kind {
+            "@s" => SelectorKind::Sender,
+            "@p" => SelectorKind::NearestPlayer,
+            "@n" => SelectorKind::NearestEntity,
+            "@r" => SelectorKind::RandomPlayer,
+            "@a" => SelectorKind::AllPlayers,
+            "@e" => SelectorKind::AllEntities,
+            _ if kind.starts_with('@') => return Err(SelectorError::UnknownSelector),
+            _ if options.is_some() => return Err(SelectorError::NotApplicable("[")),
+            name => SelectorKind::Player(name),
+        };
+        let filters =
+            options.map_or_else(|| Ok(SelectorFilters::default()), SelectorFilters::parse)?;
+        if filters.entity_type.is_some() && kind.selects_players() {
+            return Err(SelectorError::NotApplicable("type"));
+        }
+        Ok(Self { kind, filters })
+    }
+
+    /// Whether at most one entity is selected
+    #[must_use]
+    pub fn is_single(&self) -> bool {
+        self.filters.limit.or(self.kind.default_limit()) == Some(1)
+    }
+
+    /// The selected entities, `None` if the selector can't be used by the sender or the player is offline.
+    /// Like in vanilla, players and entities of every world are selected unless a distance is given
+    pub async fn resolve(
+        &self,
+        sender: &CommandSender<'_>,
+        server: &Server,
+    ) -> Option<Vec<WorldEntity>> {
+        // Senders without a position, like the console, select relative to the world origin
+        let origin = sender
+            .position()
+            .unwrap_or_else(|| Vector3::new(0.0, 0.0, 0.0));
+
+        let mut entities = match self.kind {
+            SelectorKind::Sender => vec![sender_entity(sender)?],
+            SelectorKind::Player(name) => {
+                vec![WorldEntity::Player(server.get_player_by_name(name).await?)]
+            }
+            kind if kind.selects_players() => server
+                .get_all_players()
+                .await
+                .into_iter()
+                .map(WorldEntity::Player)
+                .collect(),
+            _ => server.get_all_entities().await,
+        };
+        if let (Some(_), Some(world)) = (self.filters.distance, sender.world()) {
+            entities.retain(|entity| std::ptr::eq(entity.entity().world.as_ref(), world));
+        }
+        entities.retain(|entity| self.filters.matches(entity, origin));
+
+        self.filters.sort.unwrap_or(self.kind.default_sort()).apply(
+            origin,
+            &mut entities,
+            |entity| entity.entity().pos.load(),
+        );
+        if let Some(limit) = self.filters.limit.or(self.kind.default_limit()) {
+            entities.truncate(limit);
+        }
+        Some(entities)
+    }
+}
+
+/// The order of the selected entities, which matters when limiting them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    Nearest,
+    Furthest,
+    Random,
+    Arbitrary,
+}
+
+impl Sort {
+    fn apply<T>(
+        self,
+        origin: Vector3<f64>,
+        candidates: &mut [T],
+        pos: impl Fn(&T) -> Vector3<f64>,
+    ) {
+        let distance = |candidate: &T| pos(candidate).squared_distance_to_vec(origin);
+        match self {
+            Self::Nearest => candidates.sort_by(|a, b| distance(a).total_cmp(&distance(b))),
+            Self::Furthest => candidates.sort_by(|a, b| distance(b).total_cmp(&distance(a))),
+            Self::Random => candidates.shuffle(&mut rand::thread_rng()),
+            Self::Arbitrary => (),
+        }
+    }
+}
+
+/// A range of distances like `..10`, either end can be left out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl DistanceRange {
+    fn parse(s: &str) -> Option<Self> {
+        let parse_bound = |bound: &str| -> Option<Option<f64>> {
+            if bound.is_empty() {
+                return Some(None);
+            }
+            let bound: f64 = bound.parse().ok()?;
+            (bound.is_finite() && bound >= 0.0).then_some(Some(bound))
+        };
+        // A single value like `5` is a range of exactly that distance
+        let (min, max) = s.split_once("..").unwrap_or((s, s));
+        let range = Self {
+            min: parse_bound(min)?,
+            max: parse_bound(max)?,
+        };
+        match (range.min, range.max) {
+            (None, None) => None,
+            (Some(min), Some(max)) if min > max => None,
+            _ => Some(range),
+        }
+    }
+
+    /// Compares squared, so the distance does not need a square root
+    fn contains_squared(self, distance_squared: f64) -> bool {
+        self.min.is_none_or(|min| distance_squared >= min * min)
+            && self.max.is_none_or(|max| distance_squared <= max * max)
+    }
+}
+
+/// The options of a selector in brackets, like `[type=zombie,limit=3]`.
+/// `type`, `name` and `gamemode` can be negated with a leading `!`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SelectorFilters<'a> {
+    pub distance: Option<DistanceRange>,
+    pub limit: Option<usize>,
+    pub sort: Option<Sort>,
+    pub entity_type: Option<(EntityType, bool)>,
+    pub name: Option<(&'a str, bool)>,
+    pub gamemode: Option<(GameMode, bool)>,
+}
+
+impl<'a> SelectorFilters<'a> {
+    fn parse(options: &'a str) -> Result<Self, SelectorError> {
+        let mut filters = Self::default();
+        for option in options.split(',').filter(|option| !option.is_empty()) {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or(SelectorError::InvalidValue(option.to_string()))?;
+            let invalid = || SelectorError::InvalidValue(option.to_string());
+            let negated = |value: &'a str| {
+                value
+                    .strip_prefix('!')
+                    .map_or((value, false), |value| (value, true))
+            };
+            match key {
+                "distance" => {
+                    filters.distance = Some(DistanceRange::parse(value).ok_or_else(invalid)?);
+                }
+                "limit" => {
+                    let limit = value.parse().ok().filter(|limit| *limit > 0);
+                    filters.limit = Some(limit.ok_or_else(invalid)?);
+                }
+                "sort" => {
+                    filters.sort = Some(match value {
+                        "nearest" => Sort::Nearest,
+                        "furthest" => Sort::Furthest,
+                        "random" => Sort::Random,
+                        "arbitrary" => Sort::Arbitrary,
+                        _ => return Err(invalid()),
+                    });
+                }
+                "type" => {
+                    let (name, negated) = negated(value);
+                    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+                    let entity_type = EntityType::from_name(name).ok_or_else(invalid)?;
+                    filters.entity_type = Some((entity_type, negated));
+                }
+                "name" => filters.name = Some(negated(value)),
+                "gamemode" => {
+                    let (gamemode, negated) = negated(value);
+                    let gamemode = gamemode.parse().map_err(|_| invalid())?;
+                    filters.gamemode = Some((gamemode, negated));
+                }
+                _ => return Err(SelectorError::UnknownOption(key.to_string())),
+            }
+        }
+        Ok(filters)
+    }
+
+    fn matches(&self, entity: &WorldEntity, origin: Vector3<f64>) -> bool {
+        let inner = entity.entity();
+        let player = match entity {
+            WorldEntity::Player(player) => Some(player),
+            WorldEntity::Mob(_) => None,
+        };
+        // Mobs have no names or gamemodes, so they only match these filters when negated
+        self.distance.is_none_or(|distance| {
+            distance.contains_squared(inner.pos.load().squared_distance_to_vec(origin))
+        }) && self
+            .entity_type
+            .is_none_or(|(entity_type, negated)| (inner.entity_type == entity_type) != negated)
+            && self.name.is_none_or(|(name, negated)| {
+                player.is_some_and(|player| player.gameprofile.name == name) != negated
+            })
+            && self.gamemode.is_none_or(|(gamemode, negated)| {
+                player.is_some_and(|player| player.gamemode.load() == gamemode) != negated
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorError {
+    UnknownSelector,
+    Unterminated,
+    UnknownOption(String),
+    InvalidValue(String),
+    /// The option can't be used with this kind of selector
+    NotApplicable(&'static str),
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownSelector => write!(f, "Unknown selector type"),
+            Self::Unterminated => write!(f, "Expected end of options"),
+            Self::UnknownOption(key) => write!(f, "Unknown option '{key}'"),
+            Self::InvalidValue(option) => write!(f, "Invalid value in '{option}'"),
+            Self::NotApplicable(option) => write!(f, "Option '{option}' isn't applicable here"),
+        }
+    }
+}
+
+impl From<SelectorError> for CommandError {
+    fn from(error: SelectorError) -> Self {
+        Self::GeneralCommandIssue(error.to_string())
+    }
+}
+
+/// The entity running the command, only players are entities for now
+fn sender_entity(sender: &CommandSender<'_>) -> Option<WorldEntity> {
+    sender.as_player().map(WorldEntity::Player)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use crate::command::CommandSender;
+
+    use super::{sender_entity, DistanceRange, SelectorError, SelectorKind, Sort, TargetSelector};
+
+    fn range(s: &str) -> Option<(Option<f64>, Option<f64>)> {
+        DistanceRange::parse(s).map(|range| (range.min, range.max))
+    }
+
+    #[test]
+    fn parses_selectors() {
+        let kind = |s| TargetSelector::parse(s).map(|selector| selector.kind);
+        assert_eq!(kind("@a"), Ok(SelectorKind::AllPlayers));
+        assert_eq!(kind("@e[]"), Ok(SelectorKind::AllEntities));
+        assert_eq!(kind("Notch"), Ok(SelectorKind::Player("Notch")));
+        assert_eq!(kind("@x"), Err(SelectorError::UnknownSelector));
+        assert!(TargetSelector::parse("@p").unwrap().is_single());
+        assert!(!TargetSelector::parse("@a").unwrap().is_single());
+        assert!(TargetSelector::parse("@e[limit=1]").unwrap().is_single());
+    }
+
+    #[test]
+    fn parses_filters() {
+        let selector = TargetSelector::parse("@e[type=zombie,distance=..10,limit=3]").unwrap();
+        let filters = selector.filters;
+        assert_eq!(filters.entity_type, Some((EntityType::Zombie, false)));
+        assert_eq!(filters.distance.map(|range| range.max), Some(Some(10.0)));
+        assert_eq!(filters.limit, Some(3));
+
+        let filters = TargetSelector::parse("@a[sort=furthest,gamemode=!creative,name=Notch]")
+            .unwrap()
+            .filters;
+        assert_eq!(filters.sort, Some(Sort::Furthest));
+        assert!(filters.gamemode.is_some_and(|(_, negated)| negated));
+        assert_eq!(filters.name, Some(("Notch", false)));
+    }
+
+    #[test]
+    fn rejects_invalid_filters() {
+        let error = |s| TargetSelector::parse(s).err();
+        assert_eq!(
+            error("@e[color=red]"),
+            Some(SelectorError::UnknownOption("color".to_string()))
+        );
+        assert_eq!(
+            error("@e[limit=0]"),
+            Some(SelectorError::InvalidValue("limit=0".to_string()))
+        );
+        assert_eq!(error("@e[limit=3"), Some(SelectorError::Unterminated));
+        assert_eq!(
+            error("@a[type=zombie]"),
+            Some(SelectorError::NotApplicable("type"))
+        );
+        assert!(error("@e[type=dragon]").is_some());
+        assert!(error("@e[sort=alphabetical]").is_some());
+        assert!(error("Notch[limit=1]").is_some());
+    }
+
+    #[test]
+    fn parses_distance_ranges() {
+        assert_eq!(range("5"), Some((Some(5.0), Some(5.0))));
+        assert_eq!(range("..10"), Some((None, Some(10.0))));
+        assert_eq!(range("3.."), Some((Some(3.0), None)));
+        assert_eq!(range("2..8"), Some((Some(2.0), Some(8.0))));
+        assert_eq!(range("1.5..2.5"), Some((Some(1.5), Some(2.5))));
+        assert_eq!(range(".."), None);
+        assert_eq!(range("8..2"), None);
+        assert_eq!(range("-1"), None);
+        assert_eq!(range("far"), None);
+    }
+
+    #[test]
+    fn distance_compares_squared() {
+        let range = DistanceRange::parse("2..8").unwrap();
+        assert!(!range.contains_squared(3.9));
+        assert!(range.contains_squared(4.0));
+        assert!(range.contains_squared(64.0));
+        assert!(!range.contains_squared(64.1));
+    }
+
+    #[test]
+    fn sender_selector_fails_for_console() {
+        assert!(sender_entity(&CommandSender::Console).is_none());
+    }
+
+    #[test]
+    fn nearest_by_squared_distance() {
+        let origin = Vector3::new(0.0, 64.0, 0.0);
+        let mut candidates = [
+            ("far", Vector3::new(10.0, 64.0, 0.0)),
+            ("diagonal", Vector3::new(4.0, 60.0, 4.0)),
+            ("near", Vector3::new(0.0, 64.0, -6.0)),
+        ];
+        let names = |candidates: &[(&'static str, Vector3<f64>)]| {
+            candidates.iter().map(|(name, _)| *name).collect::<Vec<_>>()
+        };
+        Sort::Nearest.apply(origin, &mut candidates, |(_, pos)| *pos);
+        assert_eq!(names(&candidates), ["near", "diagonal", "far"]);
+        Sort::Furthest.apply(origin, &mut candidates, |(_, pos)| *pos);
+        assert_eq!(names(&candidates), ["far", "diagonal", "near"]);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::command::args::{Arg, ArgumentConsumer, FindArg, GetClientSideArgParser};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+/// A duration in ticks, which may also be given in days with `d` or seconds with `s`, like `1.5d`
+pub struct TimeArgumentConsumer {
+    /// The shortest allowed duration in ticks
+    pub min: i32,
+}
+
+impl GetClientSideArgParser for TimeArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::Time { min: self.min }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for TimeArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let ticks = parse_ticks(args.pop()?)?;
+        (ticks >= self.min).then_some(Arg::Time(ticks))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl<'a> FindArg<'a> for TimeArgumentConsumer {
+    type Data = i32;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Time(data)) => Ok(*data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
+
+/// Parses a duration like `100`, `100t`, `5s` or `0.5d` into ticks
+fn parse_ticks(s: &str) -> Option<i32> {
+    let (number, ticks_per_unit) = match s.char_indices().last()? {
+        (i, 'd') => (&s[..i], 24000.0),
+        (i, 's') => (&s[..i], 20.0),
+        (i, 't') => (&s[..i], 1.0),
+        _ => (s, 1.0),
+    };
+    let ticks = (number.parse::<f64>().ok()? * ticks_per_unit).round();
+    (ticks.is_finite() && ticks >= 0.0 && ticks <= f64::from(i32::MAX)).then_some(ticks as i32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_ticks;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_ticks("100"), Some(100));
+        assert_eq!(parse_ticks("100t"), Some(100));
+        assert_eq!(parse_ticks("5s"), Some(100));
+        assert_eq!(parse_ticks("2d"), Some(48000));
+        assert_eq!(parse_ticks("0.5d"), Some(12000));
+        assert_eq!(parse_ticks("-5s"), None);
+        assert_eq!(parse_ticks("5m"), None);
+        assert_eq!(parse_ticks("d"), None);
+        assert_eq!(parse_ticks(""), None);
+    }
+}
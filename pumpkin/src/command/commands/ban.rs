@@ -1,24 +1,30 @@
 use crate::{
     command::{
-        args::{message::MsgArgConsumer, players::PlayersArgumentConsumer, Arg, ConsumedArgs},
+        args::{
+            duration::DurationArgumentConsumer, message::MsgArgConsumer,
+            players::PlayersArgumentConsumer, Arg, ConsumedArgs, FindArg,
+        },
         tree::CommandTree,
         tree_builder::argument,
         CommandError, CommandExecutor, CommandSender,
     },
     data::{
-        banlist_serializer::BannedPlayerEntry, banned_player_data::BANNED_PLAYER_LIST,
+        banlist_serializer::{expires_after, BannedPlayerEntry},
+        banned_player_data::BANNED_PLAYER_LIST,
         SaveJSONConfiguration,
     },
     entity::player::Player,
 };
 use async_trait::async_trait;
 use pumpkin_util::text::TextComponent;
-use CommandError::InvalidConsumption;
+use std::time::Duration;
+use CommandError::{GeneralCommandIssue, InvalidConsumption};
 
 const NAMES: [&str; 1] = ["ban"];
 const DESCRIPTION: &str = "bans a player";
 
 const ARG_TARGET: &str = "player";
+const ARG_DURATION: &str = "duration";
 const ARG_REASON: &str = "reason";
 
 struct BanNoReasonExecutor;
@@ -35,8 +41,8 @@ impl CommandExecutor for BanNoReasonExecutor {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
 
-        ban_player(sender, &targets[0], None).await;
-        Ok(())
+        let duration = DurationArgumentConsumer::find_arg(args, ARG_DURATION).ok();
+        ban_player(sender, &targets[0], duration, None).await
     }
 }
 
@@ -58,12 +64,24 @@ impl CommandExecutor for BanReasonExecutor {
             return Err(InvalidConsumption(Some(ARG_REASON.into())));
         };
 
-        ban_player(sender, &targets[0], Some(reason.to_string())).await;
-        Ok(())
+        let duration = DurationArgumentConsumer::find_arg(args, ARG_DURATION).ok();
+        ban_player(sender, &targets[0], duration, Some(reason.to_string())).await
     }
 }
 
-async fn ban_player(sender: &CommandSender<'_>, player: &Player, reason: Option<String>) {
+/// Bans the player, forever if there is no `duration`
+async fn ban_player(
+    sender: &CommandSender<'_>,
+    player: &Player,
+    duration: Option<Duration>,
+    reason: Option<String>,
+) -> Result<(), CommandError> {
+    let expires = duration
+        .map(|duration| {
+            expires_after(duration)
+                .ok_or_else(|| GeneralCommandIssue("The ban duration is too long".to_string()))
+        })
+        .transpose()?;
     let mut banned_players = BANNED_PLAYER_LIST.write().await;
 
     let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
@@ -73,13 +91,13 @@ async fn ban_player(sender: &CommandSender<'_>, player: &Player, reason: Option<
         sender
             .send_message(TextComponent::translate("commands.ban.failed", [].into()))
             .await;
-        return;
+        return Ok(());
     }
 
     banned_players.banned_players.push(BannedPlayerEntry::new(
         profile,
         sender.to_string(),
-        None,
+        expires,
         reason.clone(),
     ));
 
@@ -104,12 +122,19 @@ async fn ban_player(sender: &CommandSender<'_>, player: &Player, reason: Option<
             [].into(),
         ))
         .await;
+    Ok(())
 }
 
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_TARGET, PlayersArgumentConsumer)
             .execute(BanNoReasonExecutor)
+            // Tried before the reason, so `7d` is read as a duration instead of a reason
+            .then(
+                argument(ARG_DURATION, DurationArgumentConsumer)
+                    .execute(BanNoReasonExecutor)
+                    .then(argument(ARG_REASON, MsgArgConsumer).execute(BanReasonExecutor)),
+            )
             .then(argument(ARG_REASON, MsgArgConsumer).execute(BanReasonExecutor)),
     )
 }
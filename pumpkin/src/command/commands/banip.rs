@@ -1,25 +1,31 @@
-use std::{net::IpAddr, str::FromStr};
+use std::{net::IpAddr, str::FromStr, time::Duration};
 
 use crate::{
     command::{
-        args::{message::MsgArgConsumer, simple::SimpleArgConsumer, Arg, ConsumedArgs},
+        args::{
+            duration::DurationArgumentConsumer, message::MsgArgConsumer, simple::SimpleArgConsumer,
+            Arg, ConsumedArgs, FindArg,
+        },
         tree::CommandTree,
         tree_builder::argument,
         CommandError, CommandExecutor, CommandSender,
     },
     data::{
-        banlist_serializer::BannedIpEntry, banned_ip_data::BANNED_IP_LIST, SaveJSONConfiguration,
+        banlist_serializer::{expires_after, BannedIpEntry},
+        banned_ip_data::BANNED_IP_LIST,
+        SaveJSONConfiguration,
     },
     server::Server,
 };
 use async_trait::async_trait;
 use pumpkin_util::text::TextComponent;
-use CommandError::InvalidConsumption;
+use CommandError::{GeneralCommandIssue, InvalidConsumption};
 
 const NAMES: [&str; 1] = ["ban-ip"];
 const DESCRIPTION: &str = "bans a player-ip";
 
 const ARG_TARGET: &str = "ip";
+const ARG_DURATION: &str = "duration";
 const ARG_REASON: &str = "reason";
 
 async fn parse_ip(target: &str, server: &Server) -> Option<IpAddr> {
@@ -50,8 +56,8 @@ impl CommandExecutor for BanIpNoReasonExecutor {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
 
-        ban_ip(sender, server, target, None).await;
-        Ok(())
+        let duration = DurationArgumentConsumer::find_arg(args, ARG_DURATION).ok();
+        ban_ip(sender, server, target, duration, None).await
     }
 }
 
@@ -73,13 +79,26 @@ impl CommandExecutor for BanIpReasonExecutor {
             return Err(InvalidConsumption(Some(ARG_REASON.into())));
         };
 
-        ban_ip(sender, server, target, Some(reason.to_string())).await;
-        Ok(())
+        let duration = DurationArgumentConsumer::find_arg(args, ARG_DURATION).ok();
+        ban_ip(sender, server, target, duration, Some(reason.to_string())).await
     }
 }
 
-async fn ban_ip(sender: &CommandSender<'_>, server: &Server, target: &str, reason: Option<String>) {
+/// Bans the ip, forever if there is no `duration`
+async fn ban_ip(
+    sender: &CommandSender<'_>,
+    server: &Server,
+    target: &str,
+    duration: Option<Duration>,
+    reason: Option<String>,
+) -> Result<(), CommandError> {
     let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
+    let expires = duration
+        .map(|duration| {
+            expires_after(duration)
+                .ok_or_else(|| GeneralCommandIssue("The ban duration is too long".to_string()))
+        })
+        .transpose()?;
 
     let Some(target_ip) = parse_ip(target, server).await else {
         sender
@@ -88,7 +107,7 @@ async fn ban_ip(sender: &CommandSender<'_>, server: &Server, target: &str, reaso
                 [].into(),
             ))
             .await;
-        return;
+        return Ok(());
     };
 
     let mut banned_ips = BANNED_IP_LIST.write().await;
@@ -97,13 +116,13 @@ async fn ban_ip(sender: &CommandSender<'_>, server: &Server, target: &str, reaso
         sender
             .send_message(TextComponent::translate("commands.banip.failed", [].into()))
             .await;
-        return;
+        return Ok(());
     }
 
     banned_ips.banned_ips.push(BannedIpEntry::new(
         target_ip,
         sender.to_string(),
-        None,
+        expires,
         reason.clone(),
     ));
 
@@ -148,12 +167,19 @@ async fn ban_ip(sender: &CommandSender<'_>, server: &Server, target: &str, reaso
             ))
             .await;
     }
+    Ok(())
 }
 
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_TARGET, SimpleArgConsumer)
             .execute(BanIpNoReasonExecutor)
+            // Tried before the reason, so `7d` is read as a duration instead of a reason
+            .then(
+                argument(ARG_DURATION, DurationArgumentConsumer)
+                    .execute(BanIpNoReasonExecutor)
+                    .then(argument(ARG_REASON, MsgArgConsumer).execute(BanIpReasonExecutor)),
+            )
             .then(argument(ARG_REASON, MsgArgConsumer).execute(BanIpReasonExecutor)),
     )
 }
@@ -32,7 +32,8 @@ impl CommandExecutor for BanListExecutor {
 
         match *list_type {
             "ips" => {
-                let lock = &BANNED_IP_LIST.read().await;
+                let mut lock = BANNED_IP_LIST.write().await;
+                lock.remove_invalid_entries();
                 let entires = lock
                     .banned_ips
                     .iter()
@@ -44,11 +45,13 @@ impl CommandExecutor for BanListExecutor {
                         )
                     })
                     .collect();
+                drop(lock);
 
                 handle_banlist(entires, sender).await;
             }
             "players" => {
-                let lock = &BANNED_PLAYER_LIST.read().await;
+                let mut lock = BANNED_PLAYER_LIST.write().await;
+                lock.remove_invalid_entries();
                 let entires = lock
                     .banned_players
                     .iter()
@@ -60,6 +63,7 @@ impl CommandExecutor for BanListExecutor {
                         )
                     })
                     .collect();
+                drop(lock);
 
                 handle_banlist(entires, sender).await;
             }
@@ -85,7 +89,9 @@ impl CommandExecutor for BanListAllExecutor {
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let mut entries = Vec::new();
-        for entry in &BANNED_PLAYER_LIST.read().await.banned_players {
+        let mut banned_players = BANNED_PLAYER_LIST.write().await;
+        banned_players.remove_invalid_entries();
+        for entry in &banned_players.banned_players {
             entries.push((
                 entry.name.clone(),
                 entry.source.clone(),
@@ -93,7 +99,11 @@ impl CommandExecutor for BanListAllExecutor {
             ));
         }
 
-        for entry in &BANNED_IP_LIST.read().await.banned_ips {
+        drop(banned_players);
+
+        let mut banned_ips = BANNED_IP_LIST.write().await;
+        banned_ips.remove_invalid_entries();
+        for entry in &banned_ips.banned_ips {
             entries.push((
                 entry.ip.to_string(),
                 entry.source.clone(),
@@ -101,6 +111,8 @@ impl CommandExecutor for BanListAllExecutor {
             ));
         }
 
+        drop(banned_ips);
+
         handle_banlist(entries, sender).await;
         Ok(())
     }
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    args::{message::MsgArgConsumer, Arg, ConsumedArgs},
+    tree::CommandTree,
+    tree_builder::argument,
+    CommandError, CommandExecutor, CommandSender,
+};
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 3] = ["broadcast", "bc", "alert"];
+
+const DESCRIPTION: &str = "Announce a message to all Players.";
+
+const ARG_MESSAGE: &str = "message";
+
+struct BroadcastExecutor;
+
+#[async_trait]
+impl CommandExecutor for BroadcastExecutor {
+    async fn execute<'a>(
+        &self,
+        _sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(msg)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+
+        let announcement = TextComponent::from_legacy(&ADVANCED_CONFIG.broadcast.prefix)
+            .add_child(parse_message(msg));
+        server.broadcast_system_message(&announcement).await;
+        Ok(())
+    }
+}
+
+/// Messages can either be a JSON text component or use `&` formatting codes
+fn parse_message(msg: &str) -> TextComponent {
+    if msg.starts_with('{') {
+        if let Ok(component) = serde_json::from_str(msg) {
+            return component;
+        }
+    }
+    TextComponent::from_legacy(msg)
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_MESSAGE, MsgArgConsumer).execute(BroadcastExecutor))
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::text::TextComponent;
+
+    use super::parse_message;
+
+    #[test]
+    fn json_and_legacy_messages() {
+        assert_eq!(
+            parse_message(r#"{"text":"Restart soon"}"#),
+            TextComponent::text("Restart soon")
+        );
+        assert_eq!(
+            parse_message("&cRestart soon"),
+            TextComponent::from_legacy("&cRestart soon")
+        );
+        // Invalid JSON is sent as it was typed
+        assert_eq!(
+            parse_message("{not json"),
+            TextComponent::from_legacy("{not json")
+        );
+    }
+}
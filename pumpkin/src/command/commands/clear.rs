@@ -2,13 +2,12 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use pumpkin_inventory::Container;
-use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::color::NamedColor;
-use pumpkin_util::text::hover::HoverEvent;
 use pumpkin_util::text::TextComponent;
 
-use crate::command::args::entities::EntitiesArgumentConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
 use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::text::CommandTextExt;
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, require};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
@@ -47,17 +46,7 @@ fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> Text
             "commands.clear.success.single",
             vec![
                 TextComponent::text(item_count.to_string()),
-                TextComponent::text(target.gameprofile.name.clone())
-                    .click_event(ClickEvent::SuggestCommand(
-                        format!("/tell {} ", target.gameprofile.name.clone()).into(),
-                    ))
-                    .hover_event(HoverEvent::show_entity(
-                        target.living_entity.entity.entity_uuid.to_string(),
-                        Some(
-                            format!("{:?}", target.living_entity.entity.entity_type).to_lowercase(),
-                        ),
-                        Some(TextComponent::text(target.gameprofile.name.clone())),
-                    )),
+                TextComponent::entity_mention(target),
             ],
         ),
         targets if item_count == 0 => TextComponent::translate(
@@ -85,7 +74,7 @@ impl CommandExecutor for ClearExecutor {
         _server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Entities(targets)) = args.get(&ARG_TARGET) else {
+        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
 
@@ -128,6 +117,6 @@ impl CommandExecutor for ClearSelfExecutor {
 #[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
-        .then(argument(ARG_TARGET, EntitiesArgumentConsumer).execute(ClearExecutor))
+        .then(argument(ARG_TARGET, PlayersArgumentConsumer).execute(ClearExecutor))
         .then(require(|sender| sender.is_player()).execute(ClearSelfExecutor))
 }
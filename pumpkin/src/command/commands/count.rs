@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::block::block_registry::{get_block_by_state_id, get_state_by_state_id};
+
+use crate::command::args::block::BlockArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+use super::selection::selected_region;
+
+const NAMES: [&str; 1] = ["count"];
+
+const DESCRIPTION: &str = "Counts the blocks in your selection, or only the blocks of one kind.";
+
+const ARG_BLOCK: &str = "block";
+
+struct CountExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for CountExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let block = if self.0 {
+            Some(BlockArgumentConsumer::find_arg(args, ARG_BLOCK)?)
+        } else {
+            None
+        };
+        let (player, region) = selected_region(sender)?;
+        let world = player.world();
+
+        let mut count = 0;
+        world
+            .for_each_block_state(&region, |_, state_id| {
+                let counted = match block {
+                    Some(block) => {
+                        get_block_by_state_id(state_id).is_some_and(|found| found.id == block.id)
+                    }
+                    None => get_state_by_state_id(state_id).is_some_and(|state| !state.air),
+                };
+                if counted {
+                    count += 1;
+                }
+            })
+            .await;
+
+        let msg = block.map_or_else(
+            || {
+                format!(
+                    "Your selection has {} blocks, {count} of them are not air",
+                    region.volume()
+                )
+            },
+            |block| {
+                format!(
+                    "Your selection has {count} blocks of minecraft:{}",
+                    block.name
+                )
+            },
+        );
+        sender.send_message(TextComponent::text(msg)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(CountExecutor(false))
+        .then(argument(ARG_BLOCK, BlockArgumentConsumer).execute(CountExecutor(true)))
+}
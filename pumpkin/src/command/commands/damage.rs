@@ -0,0 +1,144 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::damage_type::DamageTypeArgumentConsumer;
+use crate::command::args::entities::EntitiesArgumentConsumer;
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::text::CommandTextExt;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::entity_query::WorldEntity;
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["damage"];
+
+const DESCRIPTION: &str = "Applies damage to the specified entities.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_SOURCE: &str = "entity";
+
+/// Used when no damage type is given
+const DEFAULT_DAMAGE_TYPE: &str = "generic";
+
+fn amount_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new().name("amount").min(0.0)
+}
+
+/// Whether a damage type and a source entity were given
+struct DamageExecutor {
+    with_type: bool,
+    with_source: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for DamageExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Entities(targets)) = args.get(&ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+        let Ok(amount) = amount_consumer().find_arg_default_name(args)? else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Amount must not be negative".to_string(),
+            ));
+        };
+        let (damage_type, _) = if self.with_type {
+            DamageTypeArgumentConsumer.find_arg_default_name(args)?
+        } else {
+            pumpkin_registry::get_damage_type(DEFAULT_DAMAGE_TYPE)
+                .expect("The generic damage type always exists")
+        };
+        let source = if self.with_source {
+            Some(EntityArgumentConsumer::find_arg(args, ARG_SOURCE)?)
+        } else {
+            None
+        };
+
+        if targets.is_empty() {
+            return Err(CommandError::GeneralCommandIssue(
+                "No entity was found".to_string(),
+            ));
+        }
+
+        let mut damaged = 0;
+        for target in targets {
+            match target {
+                WorldEntity::Player(player) if player.abilities.lock().await.invulnerable => {
+                    continue
+                }
+                // Already dying
+                WorldEntity::Mob(mob) if mob.living_entity.health.load() <= 0.0 => continue,
+                _ => (),
+            }
+            target
+                .living_entity()
+                .damage_with_source(
+                    amount,
+                    damage_type,
+                    source.as_ref().map(WorldEntity::entity),
+                )
+                .await;
+            // Dead players respawn, dead mobs are removed like when killed by a player
+            if let WorldEntity::Mob(mob) = target {
+                let living = &mob.living_entity;
+                if living.health.load() <= 0.0 {
+                    living
+                        .entity
+                        .world
+                        .clone()
+                        .remove_mob_entity(living.clone())
+                        .await;
+                }
+            }
+            damaged += 1;
+        }
+
+        let msg = match damaged {
+            0 => TextComponent::translate("commands.damage.invulnerable", [].into()),
+            1 if targets.len() == 1 => TextComponent::translate(
+                "commands.damage.success",
+                [
+                    TextComponent::text(amount.to_string()),
+                    TextComponent::world_entity_mention(&targets[0]),
+                ]
+                .into(),
+            ),
+            count => TextComponent::text(format!("Applied {amount} damage to {count} entities")),
+        };
+        sender.send_message(msg).await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, EntitiesArgumentConsumer).then(
+            argument_default_name(amount_consumer())
+                .execute(DamageExecutor {
+                    with_type: false,
+                    with_source: false,
+                })
+                .then(
+                    argument_default_name(DamageTypeArgumentConsumer)
+                        .execute(DamageExecutor {
+                            with_type: true,
+                            with_source: false,
+                        })
+                        .then(literal("by").then(
+                            argument(ARG_SOURCE, EntityArgumentConsumer).execute(DamageExecutor {
+                                with_type: true,
+                                with_source: true,
+                            }),
+                        )),
+                ),
+        ),
+    )
+}
@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+use pumpkin_world::block::block_registry::{
+    get_block_collision_shapes, get_block_entity_type_name,
+};
+
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::commands::info_line;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["debug"];
+
+const DESCRIPTION: &str = "Shows internal server state for debugging.";
+
+const ARG_POS: &str = "pos";
+
+/// How far away the looked at block of `/debug block` may be
+const BLOCK_RAYCAST_DISTANCE: f64 = 64.0;
+
+/// How many of the chunks with the most mobs `/debug entities` lists
+const FULLEST_CHUNKS: usize = 5;
+
+struct ActivationExecutor;
+
+#[async_trait]
+impl CommandExecutor for ActivationExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        if !ADVANCED_CONFIG.activation_range.enabled {
+            sender
+                .send_message(
+                    TextComponent::text("Activation range is disabled, all mobs tick every tick")
+                        .color_named(NamedColor::Yellow),
+                )
+                .await;
+        }
+        for world in server.worlds.read().await.iter() {
+            let stats = world.activation_stats();
+            sender
+                .send_message(TextComponent::text(format!(
+                    "{}: {} active, {} inactive mobs",
+                    world.dimension_type.name(),
+                    stats.active,
+                    stats.inactive
+                )))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Shows how many mobs there are per world and in the fullest chunks, compared to the entity limits
+struct EntitiesExecutor;
+
+#[async_trait]
+impl CommandExecutor for EntitiesExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let limits = &ADVANCED_CONFIG.entity_limits;
+        for world in server.worlds.read().await.iter() {
+            let mut chunks: Vec<_> = world.mobs_per_chunk().await.into_iter().collect();
+            chunks.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+            let players = world.current_players.lock().await.len();
+            let mut message = TextComponent::text(format!(
+                "{}: {}/{} mobs, {players} players",
+                world.dimension_type.name(),
+                chunks.iter().map(|(_, count)| count).sum::<usize>(),
+                limits.per_world,
+            ))
+            .color_named(NamedColor::Gold);
+            for (chunk, count) in chunks.iter().take(FULLEST_CHUNKS) {
+                message = message.add_child(TextComponent::text(format!(
+                    "\n  chunk {} {}: {count}/{} mobs",
+                    chunk.x, chunk.z, limits.per_chunk
+                )));
+            }
+            sender.send_message(message).await;
+        }
+        Ok(())
+    }
+}
+
+/// Shows the block at the given position, or the block the player is looking at
+struct BlockExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for BlockExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        // The console looks at the first world
+        let console_world;
+        let world = if let Some(world) = sender.world() {
+            world
+        } else {
+            console_world = server.worlds.read().await[0].clone();
+            &console_world
+        };
+        let pos = if self.0 {
+            BlockPosArgumentConsumer::find_arg(args, ARG_POS)?
+        } else {
+            let Some(player) = sender.as_player() else {
+                return Err(CommandError::GeneralCommandIssue(
+                    "A position is required when not run by a player".to_string(),
+                ));
+            };
+            let entity = &player.living_entity.entity;
+            world
+                .raycast_block(
+                    entity.eye_position(),
+                    entity.rotation_vector(),
+                    BLOCK_RAYCAST_DISTANCE,
+                )
+                .await
+                .ok_or_else(|| {
+                    CommandError::GeneralCommandIssue("You are not looking at a block".to_string())
+                })?
+        };
+
+        let Ok((block, state)) = world.get_block_and_block_state(&pos).await else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "There is no block at {pos}"
+            )));
+        };
+        let shapes = get_block_collision_shapes(state.id)
+            .unwrap_or_default()
+            .chunks_exact(6)
+            .map(|shape| {
+                format!(
+                    "[{}, {}, {} -> {}, {}, {}]",
+                    shape[0], shape[1], shape[2], shape[3], shape[4], shape[5]
+                )
+            })
+            .collect::<Vec<_>>();
+        // Block entities are not stored yet, so only their type can be shown
+        let block_entity = state
+            .block_entity_type
+            .and_then(get_block_entity_type_name)
+            .unwrap_or("none");
+
+        sender
+            .send_message(
+                TextComponent::text(format!("Block at {pos}"))
+                    .color_named(NamedColor::Gold)
+                    .bold()
+                    .add_child(info_line("Name", format!("minecraft:{}", block.name)))
+                    .add_child(info_line("State id", state.id.to_string()))
+                    .add_child(info_line("Hardness", block.hardness.to_string()))
+                    .add_child(info_line("Block entity", block_entity.to_string()))
+                    .add_child(info_line(
+                        "Collision shapes",
+                        if shapes.is_empty() {
+                            "none".to_string()
+                        } else {
+                            shapes.join(" ")
+                        },
+                    )),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("activation").execute(ActivationExecutor))
+        .then(literal("entities").execute(EntitiesExecutor))
+        .then(
+            literal("block")
+                .execute(BlockExecutor(false))
+                .then(argument(ARG_POS, BlockPosArgumentConsumer).execute(BlockExecutor(true))),
+        )
+}
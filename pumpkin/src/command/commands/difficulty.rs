@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::Difficulty;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::literal;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["difficulty"];
+
+const DESCRIPTION: &str = "Sets or queries the difficulty level.";
+
+/// Shows the difficulty, or sets it in every world if there is one
+struct DifficultyExecutor(Option<Difficulty>);
+
+#[async_trait]
+impl CommandExecutor for DifficultyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let worlds = server.worlds.read().await;
+        // The console sees the difficulty of the first world
+        let current = sender
+            .world()
+            .or_else(|| worlds.first().map(AsRef::as_ref))
+            .expect("There should always be at least one world")
+            .difficulty();
+
+        let Some(difficulty) = self.0 else {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.difficulty.query",
+                    [difficulty_name(current)].into(),
+                ))
+                .await;
+            return Ok(());
+        };
+        if difficulty == current {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "The difficulty did not change; it is already set to {difficulty:?}"
+            )));
+        }
+        // Like in vanilla the difficulty is the same for the whole server
+        for world in worlds.iter() {
+            world.set_difficulty(difficulty).await;
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.difficulty.success",
+                [difficulty_name(difficulty)].into(),
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+const fn difficulty_key(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Peaceful => "peaceful",
+        Difficulty::Easy => "easy",
+        Difficulty::Normal => "normal",
+        Difficulty::Hard => "hard",
+    }
+}
+
+fn difficulty_name(difficulty: Difficulty) -> TextComponent {
+    TextComponent::translate(
+        format!("options.difficulty.{}", difficulty_key(difficulty)),
+        [].into(),
+    )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut tree = CommandTree::new(NAMES, DESCRIPTION).execute(DifficultyExecutor(None));
+    for difficulty in [
+        Difficulty::Peaceful,
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+    ] {
+        tree = tree.then(
+            literal(difficulty_key(difficulty)).execute(DifficultyExecutor(Some(difficulty))),
+        );
+    }
+    tree
+}
@@ -0,0 +1,232 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::effect::EffectArgumentConsumer;
+use crate::command::args::entities::EntitiesArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::text::CommandTextExt;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::effect::{Effect, EffectInstance};
+use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
+
+const NAMES: [&str; 1] = ["effect"];
+
+const DESCRIPTION: &str = "Adds or removes status effects.";
+
+const ARG_EFFECT: &str = "effect";
+const ARG_HIDE_PARTICLES: &str = "hideParticles";
+
+/// Effects given without a duration last this many seconds
+const DEFAULT_SECONDS: i32 = 30;
+
+fn seconds_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("seconds")
+        .min(1)
+        .max(1_000_000)
+}
+
+fn amplifier_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("amplifier")
+        .min(0)
+        .max(255)
+}
+
+/// The targets of the command, the sender itself if none are given
+fn targets(
+    sender: &CommandSender<'_>,
+    args: &ConsumedArgs<'_>,
+) -> Result<Vec<WorldEntity>, CommandError> {
+    if let Ok(targets) = EntitiesArgumentConsumer.find_arg_default_name(args) {
+        return Ok(targets.to_vec());
+    }
+    sender
+        .as_player()
+        .map(|player| vec![WorldEntity::Player(player)])
+        .ok_or_else(|| {
+            CommandError::GeneralCommandIssue(
+                "A target is required when not run by a player".into(),
+            )
+        })
+}
+
+fn effect_name(effect: Effect) -> TextComponent {
+    TextComponent::translate(effect.translation_key(), [].into())
+}
+
+struct GiveExecutor;
+
+#[async_trait]
+impl CommandExecutor for GiveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = targets(sender, args)?;
+        let effect = EffectArgumentConsumer::find_arg(args, ARG_EFFECT)?;
+        let seconds = match seconds_consumer().find_arg_default_name(args) {
+            Err(_) => DEFAULT_SECONDS,
+            Ok(Ok(seconds)) => seconds,
+            Ok(Err(())) => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "The duration is too large or too small".to_string(),
+                ))
+            }
+        };
+        let amplifier = match amplifier_consumer().find_arg_default_name(args) {
+            Err(_) => 0,
+            Ok(Ok(amplifier)) => amplifier,
+            Ok(Err(())) => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "The amplifier is too large or too small".to_string(),
+                ))
+            }
+        };
+        let hide_particles = BoolArgConsumer::find_arg(args, ARG_HIDE_PARTICLES).unwrap_or(false);
+        // Instant effects only last a single tick
+        let duration = if effect.is_instant() { 1 } else { seconds * 20 };
+        let instance = EffectInstance::new(
+            u8::try_from(amplifier).unwrap_or(u8::MAX),
+            duration,
+            !hide_particles,
+        );
+
+        let mut affected = Vec::new();
+        for target in targets {
+            if target.living_entity().add_effect(effect, instance).await {
+                affected.push(target);
+            }
+        }
+        let msg = match affected.as_slice() {
+            [] => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "Unable to apply this effect (target is either immune to effects, or has something stronger)".to_string(),
+                ))
+            }
+            [target] => TextComponent::translate(
+                "commands.effect.give.success.single",
+                [effect_name(effect), TextComponent::world_entity_mention(target)].into(),
+            ),
+            targets => TextComponent::translate(
+                "commands.effect.give.success.multiple",
+                [
+                    effect_name(effect),
+                    TextComponent::text(targets.len().to_string()),
+                ]
+                .into(),
+            ),
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+/// Removes the given effect, or every effect if none is given
+struct ClearExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for ClearExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = targets(sender, args)?;
+        let effect = if self.0 {
+            Some(EffectArgumentConsumer::find_arg(args, ARG_EFFECT)?)
+        } else {
+            None
+        };
+
+        let mut affected = Vec::new();
+        for target in targets {
+            let removed = match effect {
+                Some(effect) => target.living_entity().remove_effect(effect).await,
+                None => target.living_entity().clear_effects().await > 0,
+            };
+            if removed {
+                affected.push(target);
+            }
+        }
+        let msg = match (effect, affected.as_slice()) {
+            (Some(_), []) => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "Target doesn't have the requested effect".to_string(),
+                ))
+            }
+            (None, []) => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "Target has no effects to remove".to_string(),
+                ))
+            }
+            (Some(effect), [target]) => TextComponent::translate(
+                "commands.effect.clear.specific.success.single",
+                [
+                    effect_name(effect),
+                    TextComponent::world_entity_mention(target),
+                ]
+                .into(),
+            ),
+            (Some(effect), targets) => TextComponent::translate(
+                "commands.effect.clear.specific.success.multiple",
+                [
+                    effect_name(effect),
+                    TextComponent::text(targets.len().to_string()),
+                ]
+                .into(),
+            ),
+            (None, [target]) => TextComponent::translate(
+                "commands.effect.clear.everything.success.single",
+                [TextComponent::world_entity_mention(target)].into(),
+            ),
+            (None, targets) => TextComponent::translate(
+                "commands.effect.clear.everything.success.multiple",
+                [TextComponent::text(targets.len().to_string())].into(),
+            ),
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("give").then(
+                argument_default_name(EntitiesArgumentConsumer).then(
+                    argument(ARG_EFFECT, EffectArgumentConsumer)
+                        .execute(GiveExecutor)
+                        .then(
+                            argument_default_name(seconds_consumer())
+                                .execute(GiveExecutor)
+                                .then(
+                                    argument_default_name(amplifier_consumer())
+                                        .execute(GiveExecutor)
+                                        .then(
+                                            argument(ARG_HIDE_PARTICLES, BoolArgConsumer)
+                                                .execute(GiveExecutor),
+                                        ),
+                                ),
+                        ),
+                ),
+            ),
+        )
+        .then(
+            literal("clear").execute(ClearExecutor(false)).then(
+                argument_default_name(EntitiesArgumentConsumer)
+                    .execute(ClearExecutor(false))
+                    .then(
+                        argument(ARG_EFFECT, EffectArgumentConsumer).execute(ClearExecutor(true)),
+                    ),
+            ),
+        )
+}
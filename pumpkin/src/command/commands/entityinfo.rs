@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::commands::info_line;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::NBTStorage;
+use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
+
+const NAMES: [&str; 1] = ["entityinfo"];
+
+const DESCRIPTION: &str = "Shows information about an entity.";
+
+const ARG_TARGET: &str = "target";
+
+/// How far away the looked at entity may be
+const ENTITY_RAYCAST_DISTANCE: f64 = 64.0;
+
+/// Shows the given entity, or the entity the player is looking at and otherwise the player itself
+struct EntityInfoExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for EntityInfoExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = if self.0 {
+            EntityArgumentConsumer::find_arg(args, ARG_TARGET)?
+        } else {
+            let Some(player) = sender.as_player() else {
+                return Err(CommandError::GeneralCommandIssue(
+                    "A target is required when not run by a player".to_string(),
+                ));
+            };
+            let entity = &player.living_entity.entity;
+            entity
+                .world
+                .raycast_entity(
+                    entity.eye_position(),
+                    entity.rotation_vector(),
+                    ENTITY_RAYCAST_DISTANCE,
+                    entity.entity_id,
+                )
+                .await
+                .unwrap_or(WorldEntity::Player(player))
+        };
+
+        let living = target.living_entity();
+        let entity = &living.entity;
+        let pos = entity.pos.load();
+        let velocity = entity.velocity.load();
+        let mut nbt = NbtCompound::new();
+        match &target {
+            WorldEntity::Player(player) => player.write_nbt(&mut nbt).await,
+            WorldEntity::Mob(mob) => mob.write_nbt(&mut nbt).await,
+        }
+        let nbt_keys = nbt
+            .child_tags
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        let nbt_summary = format!(
+            "{} tags, {} bytes ({})",
+            nbt_keys.len(),
+            nbt.serialize_content().len(),
+            nbt_keys.join(", ")
+        );
+
+        let effects = living
+            .active_effects
+            .lock()
+            .await
+            .iter()
+            .map(|(effect, instance)| {
+                format!(
+                    "{} {} ({}t)",
+                    effect.name(),
+                    u16::from(instance.amplifier) + 1,
+                    instance.duration
+                )
+            })
+            .collect::<Vec<_>>();
+        let effects = if effects.is_empty() {
+            "none".to_string()
+        } else {
+            effects.join(", ")
+        };
+
+        sender
+            .send_message(
+                TextComponent::text(format!("Entity {}", entity.entity_id))
+                    .color_named(NamedColor::Gold)
+                    .bold()
+                    .add_child(info_line(
+                        "Type",
+                        format!("minecraft:{}", entity.entity_type.to_name()),
+                    ))
+                    .add_child(info_line("UUID", entity.entity_uuid.to_string()))
+                    .add_child(info_line(
+                        "Position",
+                        format!("{:.2} {:.2} {:.2}", pos.x, pos.y, pos.z),
+                    ))
+                    .add_child(info_line(
+                        "Rotation",
+                        format!("{:.1} {:.1}", entity.yaw.load(), entity.pitch.load()),
+                    ))
+                    .add_child(info_line("Health", living.health.load().to_string()))
+                    .add_child(info_line(
+                        "Velocity",
+                        format!("{:.3} {:.3} {:.3}", velocity.x, velocity.y, velocity.z),
+                    ))
+                    .add_child(info_line("Effects", effects))
+                    .add_child(info_line("NBT", nbt_summary)),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(EntityInfoExecutor(false))
+        .then(argument(ARG_TARGET, EntityArgumentConsumer).execute(EntityInfoExecutor(true)))
+}
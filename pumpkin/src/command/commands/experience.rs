@@ -0,0 +1,197 @@
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::text::CommandTextExt;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal, NonLeafNodeBuilder};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::experience;
+use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
+
+const NAMES: [&str; 2] = ["experience", "xp"];
+
+const DESCRIPTION: &str = "Adds, sets or queries the experience of players.";
+
+const ARG_TARGET: &str = "target";
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Points,
+    Levels,
+}
+
+impl Unit {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Points => "points",
+            Self::Levels => "levels",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Add,
+    Set,
+}
+
+impl Mode {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Set => "set",
+        }
+    }
+
+    fn amount_consumer(self) -> BoundedNumArgumentConsumer<i32> {
+        let consumer = BoundedNumArgumentConsumer::new().name("amount");
+        match self {
+            Self::Add => consumer,
+            // Experience can only be set to positive amounts, but it can be taken away
+            Self::Set => consumer.min(0),
+        }
+    }
+}
+
+struct ChangeExecutor(Mode, Unit);
+
+#[async_trait]
+impl CommandExecutor for ChangeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Self(mode, unit) = *self;
+        let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
+        let Ok(amount) = mode.amount_consumer().find_arg_default_name(args)? else {
+            return Err(CommandError::GeneralCommandIssue(
+                "The amount is too large or too small".to_string(),
+            ));
+        };
+
+        let mut changed = 0;
+        for target in targets {
+            match (mode, unit) {
+                (Mode::Add, Unit::Points) => target.add_experience_points(amount).await,
+                (Mode::Add, Unit::Levels) => target.add_experience_levels(amount).await,
+                (Mode::Set, Unit::Points) => {
+                    // Points are set within the current level
+                    let level = target.experience_level.load(Ordering::Relaxed);
+                    if amount >= experience::points_to_next_level(level) {
+                        continue;
+                    }
+                    target
+                        .set_experience(level, experience::progress(level, amount))
+                        .await;
+                }
+                (Mode::Set, Unit::Levels) => {
+                    target
+                        .set_experience(amount, target.experience_progress.load())
+                        .await;
+                }
+            }
+            changed += 1;
+        }
+        if changed == 0 {
+            return Err(CommandError::GeneralCommandIssue(
+                "Cannot set experience points above the maximum points for the player's current level".to_string(),
+            ));
+        }
+
+        let key = format!(
+            "commands.experience.{}.{}.success",
+            mode.name(),
+            unit.name()
+        );
+        let msg = if let [target] = targets {
+            TextComponent::translate(
+                format!("{key}.single"),
+                [
+                    TextComponent::text(amount.to_string()),
+                    TextComponent::entity_mention(target),
+                ]
+                .into(),
+            )
+        } else {
+            TextComponent::translate(
+                format!("{key}.multiple"),
+                [
+                    TextComponent::text(amount.to_string()),
+                    TextComponent::text(changed.to_string()),
+                ]
+                .into(),
+            )
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct QueryExecutor(Unit);
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let WorldEntity::Player(target) = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?
+        else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Only players have experience".to_string(),
+            ));
+        };
+        let level = target.experience_level.load(Ordering::Relaxed);
+        let amount = match self.0 {
+            // Like vanilla, only the points into the current level
+            Unit::Points => experience::points_in_level(level, target.experience_progress.load()),
+            Unit::Levels => level,
+        };
+        sender
+            .send_message(TextComponent::translate(
+                format!("commands.experience.query.{}", self.0.name()),
+                [
+                    TextComponent::entity_mention(&target),
+                    TextComponent::text(amount.to_string()),
+                ]
+                .into(),
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+fn change(mode: Mode) -> NonLeafNodeBuilder {
+    literal(mode.name()).then(
+        argument_default_name(PlayersArgumentConsumer).then(
+            argument_default_name(mode.amount_consumer())
+                .execute(ChangeExecutor(mode, Unit::Points))
+                .then(literal("points").execute(ChangeExecutor(mode, Unit::Points)))
+                .then(literal("levels").execute(ChangeExecutor(mode, Unit::Levels))),
+        ),
+    )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(change(Mode::Add))
+        .then(change(Mode::Set))
+        .then(
+            literal("query").then(
+                argument(ARG_TARGET, EntityArgumentConsumer)
+                    .then(literal("points").execute(QueryExecutor(Unit::Points)))
+                    .then(literal("levels").execute(QueryExecutor(Unit::Levels))),
+            ),
+        )
+}
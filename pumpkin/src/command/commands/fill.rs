@@ -35,7 +35,6 @@ enum Mode {
 
 struct SetblockExecutor(Mode);
 
-#[expect(clippy::too_many_lines)]
 #[async_trait]
 impl CommandExecutor for SetblockExecutor {
     async fn execute<'a>(
@@ -59,89 +58,63 @@ impl CommandExecutor for SetblockExecutor {
         let end_z = from.0.z.max(to.0.z);
 
         let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
-        let mut placed_blocks = 0;
+        let mut changes = Vec::new();
 
-        match mode {
-            Mode::Destroy => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3 { x, y, z });
-                            world.break_block(&block_position, None).await;
-                            world.set_block_state(&block_position, block_state_id).await;
-                            placed_blocks += 1;
+        for x in start_x..=end_x {
+            for y in start_y..=end_y {
+                for z in start_z..=end_z {
+                    let block_position = BlockPos(Vector3::new(x, y, z));
+                    let is_edge = x == start_x
+                        || x == end_x
+                        || y == start_y
+                        || y == end_y
+                        || z == start_z
+                        || z == end_z;
+                    match mode {
+                        Mode::Destroy | Mode::Replace => {
+                            changes.push((block_position, block_state_id));
                         }
-                    }
-                }
-            }
-            Mode::Replace => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3 { x, y, z });
-                            world.set_block_state(&block_position, block_state_id).await;
-                            placed_blocks += 1;
-                        }
-                    }
-                }
-            }
-            Mode::Keep => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3 { x, y, z });
-                            match world.get_block_state(&block_position).await {
-                                Ok(old_state) if old_state.air => {
-                                    world.set_block_state(&block_position, block_state_id).await;
-                                    placed_blocks += 1;
-                                }
-                                _ => {}
+                        Mode::Keep => match world.get_block_state(&block_position).await {
+                            Ok(old_state) if old_state.air => {
+                                changes.push((block_position, block_state_id));
                             }
+                            _ => {}
+                        },
+                        Mode::Hollow => {
+                            changes
+                                .push((block_position, if is_edge { block_state_id } else { 0 }));
                         }
-                    }
-                }
-            }
-            Mode::Hollow => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3::new(x, y, z));
-                            let is_edge = x == start_x
-                                || x == end_x
-                                || y == start_y
-                                || y == end_y
-                                || z == start_z
-                                || z == end_z;
+                        Mode::Outline => {
                             if is_edge {
-                                world.set_block_state(&block_position, block_state_id).await;
-                            } else {
-                                world.set_block_state(&block_position, 0).await;
+                                changes.push((block_position, block_state_id));
                             }
-                            placed_blocks += 1;
                         }
                     }
                 }
             }
-            Mode::Outline => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = BlockPos(Vector3::new(x, y, z));
-                            let is_edge = x == start_x
-                                || x == end_x
-                                || y == start_y
-                                || y == end_y
-                                || z == start_z
-                                || z == end_z;
-                            if is_edge {
-                                world.set_block_state(&block_position, block_state_id).await;
-                                placed_blocks += 1;
-                            }
-                        }
-                    }
-                }
+        }
+
+        let placed_blocks = changes.len();
+        let replaced = world.set_block_states(&changes).await;
+        if matches!(mode, Mode::Destroy) {
+            let broken: Vec<_> = changes
+                .iter()
+                .zip(&replaced)
+                .map(|((position, _), replaced)| (*position, *replaced))
+                .collect();
+            world.broadcast_broken_blocks(&broken).await;
+        }
+        // Destroyed blocks were broken like by a player, restoring them could duplicate their drops
+        let undoable = !matches!(mode, Mode::Destroy);
+        if let Some(player) = sender.as_player().filter(|_| undoable) {
+            if !player.record_edit(&changes, &replaced).await {
+                sender
+                    .send_message(TextComponent::text(format!(
+                        "{placed_blocks} blocks are too many to be undone"
+                    )))
+                    .await;
             }
-        };
+        }
 
         sender
             .send_message(TextComponent::translate(
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    args::{function::FunctionArgumentConsumer, ConsumedArgs, FindArg},
+    dispatcher::CommandDispatcher,
+    function::FUNCTIONS,
+    tree::CommandTree,
+    tree_builder::argument,
+    CommandError, CommandExecutor, CommandSender,
+};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["function"];
+
+const DESCRIPTION: &str = "Runs the commands of a function.";
+
+const ARG_NAME: &str = "name";
+
+struct FunctionExecutor;
+
+#[async_trait]
+impl CommandExecutor for FunctionExecutor {
+    /// Only used when the executor is called without a dispatcher, which then has to be locked
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let dispatcher = server.command_dispatcher.read().await;
+        self.execute_with_dispatcher(sender, server, &dispatcher, args)
+            .await
+    }
+
+    async fn execute_with_dispatcher<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        dispatcher: &CommandDispatcher,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let id = FunctionArgumentConsumer::find_arg(args, ARG_NAME)?;
+        // Cloned so reloading does not have to wait for the function to finish
+        let Some(function) = FUNCTIONS.read().await.get(&id).cloned() else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Unknown function {id}"
+            )));
+        };
+
+        let ran = function
+            .run(dispatcher, sender, server)
+            .await
+            .map_err(|err| CommandError::GeneralCommandIssue(format!("Function {id} {err}")))?;
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Ran {ran} commands from function {id}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_NAME, FunctionArgumentConsumer).execute(FunctionExecutor))
+}
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::game_rules::{GameRuleError, GAME_RULE_NAMES};
+
+const NAMES: [&str; 1] = ["gamerule"];
+
+const DESCRIPTION: &str = "Sets or queries a game rule value.";
+
+const ARG_VALUE: &str = "value";
+
+/// Shows the value of the rule, or sets it if there is a value argument
+struct GameRuleExecutor {
+    rule: &'static str,
+    set: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for GameRuleExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let worlds = server.worlds.read().await;
+        // The console changes the rules of the first world
+        let world = sender
+            .world()
+            .or_else(|| worlds.first().map(AsRef::as_ref))
+            .expect("There should always be at least one world");
+
+        if !self.set {
+            let value = world.game_rules.lock().await.get(self.rule);
+            let value = value.map(|value| value.to_string()).unwrap_or_default();
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.gamerule.query",
+                    [TextComponent::text(self.rule), TextComponent::text(value)].into(),
+                ))
+                .await;
+            return Ok(());
+        }
+
+        let value = SimpleArgConsumer::find_arg(args, ARG_VALUE)?;
+        let result = world.game_rules.lock().await.set(self.rule, value);
+        let value = result.map_err(|err| {
+            CommandError::GeneralCommandIssue(match err {
+                GameRuleError::UnknownRule => format!("Unknown game rule {}", self.rule),
+                GameRuleError::InvalidValue => {
+                    format!("{value} is not a valid value for {}", self.rule)
+                }
+            })
+        })?;
+        // Clients only advance the time themselves while the daylight cycle is on
+        if self.rule == "doDaylightCycle" {
+            world.level_time.lock().await.send_time(world).await;
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.gamerule.set",
+                [
+                    TextComponent::text(self.rule),
+                    TextComponent::text(value.to_string()),
+                ]
+                .into(),
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut tree = CommandTree::new(NAMES, DESCRIPTION);
+    for rule in GAME_RULE_NAMES {
+        tree = tree.then(
+            literal(rule)
+                .execute(GameRuleExecutor { rule, set: false })
+                .then(
+                    argument(ARG_VALUE, SimpleArgConsumer)
+                        .execute(GameRuleExecutor { rule, set: true }),
+                ),
+        );
+    }
+    tree
+}
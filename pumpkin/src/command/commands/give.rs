@@ -1,5 +1,4 @@
 use async_trait::async_trait;
-use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::color::{Color, NamedColor};
 use pumpkin_util::text::hover::HoverEvent;
 use pumpkin_util::text::TextComponent;
@@ -8,6 +7,7 @@ use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
 use crate::command::args::item::ItemArgumentConsumer;
 use crate::command::args::players::PlayersArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::text::CommandTextExt;
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, argument_default_name};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
@@ -53,8 +53,26 @@ impl CommandExecutor for GiveExecutor {
             }
         };
 
+        if targets.is_empty() {
+            return Err(CommandError::GeneralCommandIssue(
+                "No player was found".to_string(),
+            ));
+        }
+
         for target in targets {
-            target.give_items(item, item_count as u32).await;
+            let leftover = target.give_items(item, item_count as u32).await;
+            if leftover > 0 {
+                sender
+                    .send_message(
+                        TextComponent::text(format!(
+                            "Only {} of {item_count} items fit into the inventory of {}, {leftover} were discarded",
+                            item_count as u32 - leftover,
+                            target.gameprofile.name
+                        ))
+                        .color_named(NamedColor::Yellow),
+                    )
+                    .await;
+            }
         }
 
         let msg = if targets.len() == 1 {
@@ -70,18 +88,7 @@ impl CommandExecutor for GiveExecutor {
                             count: Some(item_count),
                             tag: None,
                         }),
-                    TextComponent::text(targets[0].gameprofile.name.to_string())
-                        .hover_event(HoverEvent::show_entity(
-                            targets[0].living_entity.entity.entity_uuid.to_string(),
-                            Some(
-                                format!("{:?}", targets[0].living_entity.entity.entity_type)
-                                    .to_lowercase(),
-                            ),
-                            Some(TextComponent::text(targets[0].gameprofile.name.clone())),
-                        ))
-                        .click_event(ClickEvent::SuggestCommand(
-                            format!("/tell {} ", targets[0].gameprofile.name.clone()).into(),
-                        )),
+                    TextComponent::entity_mention(&targets[0]),
                 ]
                 .into(),
             )
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::entities::EntitiesArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs, FindArg};
+use crate::command::text::CommandTextExt;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["glow"];
+
+// Glow colors are taken from the entity's team, as there are no teams yet every outline is white
+const DESCRIPTION: &str = "Outlines entities so they can be seen through walls.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_GLOWING: &str = "glowing";
+
+/// Toggles the outline of every target unless a state is given
+struct GlowExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for GlowExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Entities(targets)) = args.get(&ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+        let state = if self.0 {
+            Some(BoolArgConsumer::find_arg(args, ARG_GLOWING)?)
+        } else {
+            None
+        };
+
+        let mut glowing = 0;
+        for target in targets {
+            let entity = target.entity();
+            let glow = state.unwrap_or_else(|| !entity.is_glowing());
+            entity.set_glowing(glow).await;
+            if glow {
+                glowing += 1;
+            }
+        }
+
+        let msg = match targets.len() {
+            0 => TextComponent::text("No entity was found"),
+            1 => TextComponent::text("")
+                .add_child(TextComponent::world_entity_mention(&targets[0]))
+                .add_child(TextComponent::text(if glowing == 1 {
+                    " is now glowing"
+                } else {
+                    " is no longer glowing"
+                })),
+            count => TextComponent::text(format!("{glowing} of {count} entities are now glowing")),
+        };
+        sender.send_message(msg).await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, EntitiesArgumentConsumer)
+            .execute(GlowExecutor(false))
+            .then(argument(ARG_GLOWING, BoolArgConsumer).execute(GlowExecutor(true))),
+    )
+}
@@ -32,13 +32,24 @@ impl CommandExecutor for CommandHelpExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let Some(Arg::CommandTree(tree)) = args.get(&ARG_COMMAND) else {
             return Err(InvalidConsumption(Some(ARG_COMMAND.into())));
         };
 
+        let permission = server
+            .command_dispatcher
+            .read()
+            .await
+            .get_permission_lvl(&tree.names[0]);
+        if !permission.is_none_or(|lvl| sender.has_permission_lvl(lvl)) {
+            return Err(CommandError::GeneralCommandIssue(
+                "Unknown command or insufficient permissions".to_string(),
+            ));
+        }
+
         let command_names = tree.names.join(", /");
         let usage = format!("{tree}");
         let description = &tree.description;
@@ -85,6 +96,14 @@ impl CommandExecutor for CommandHelpExecutor {
                         TextComponent::text(format!("{usage}\n")).color_named(NamedColor::White),
                     )
                     .click_event(ClickEvent::SuggestCommand(format!("{tree}").into())),
+            )
+            .add_child(
+                TextComponent::text("Permission level: ")
+                    .color_named(NamedColor::Aqua)
+                    .add_child(
+                        TextComponent::text(format!("{}\n", permission.unwrap_or_default() as i8))
+                            .color_named(NamedColor::White),
+                    ),
             );
 
         message =
@@ -128,6 +147,12 @@ impl CommandExecutor for BaseHelpExecutor {
                 Command::Tree(tree) => Some(tree),
                 Command::Alias(_) => None,
             })
+            // Only list the commands the sender can run
+            .filter(|tree| {
+                dispatcher
+                    .get_permission_lvl(&tree.names[0])
+                    .is_none_or(|lvl| sender.has_permission_lvl(lvl))
+            })
             .collect();
 
         commands.sort_by(|a, b| a.names[0].cmp(&b.names[0]));
@@ -1,14 +1,13 @@
 use async_trait::async_trait;
-use pumpkin_data::entity;
-use pumpkin_util::text::click::ClickEvent;
-use pumpkin_util::text::hover::HoverEvent;
 use pumpkin_util::text::TextComponent;
 
 use crate::command::args::entities::EntitiesArgumentConsumer;
 use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::text::CommandTextExt;
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, require};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::entity_query::WorldEntity;
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["kill"];
@@ -16,6 +15,9 @@ const DESCRIPTION: &str = "Kills all target entities.";
 
 const ARG_TARGET: &str = "target";
 
+/// The message id of the `generic_kill` damage type, so death messages say the player was killed
+const KILL_MESSAGE_ID: &str = "genericKill";
+
 struct KillExecutor;
 
 #[async_trait]
@@ -31,28 +33,30 @@ impl CommandExecutor for KillExecutor {
         };
 
         let target_count = targets.len();
-        let mut name = String::new();
         for target in targets {
-            target.living_entity.kill().await;
-            name.clone_from(&target.gameprofile.name);
+            match target {
+                WorldEntity::Player(player) => player.kill(KILL_MESSAGE_ID).await,
+                WorldEntity::Mob(mob) => {
+                    let living = &mob.living_entity;
+                    if living.health.load() <= 0.0 {
+                        continue;
+                    }
+                    living.kill().await;
+                    living
+                        .entity
+                        .world
+                        .clone()
+                        .remove_mob_entity(living.clone())
+                        .await;
+                }
+            }
         }
 
         let msg = if target_count == 1 {
-            let entity = &targets[0].living_entity.entity;
-            let mut entity_display =
-                TextComponent::text(name.clone()).hover_event(HoverEvent::show_entity(
-                    entity.entity_uuid.to_string(),
-                    Some(format!("{:?}", entity.entity_type).to_lowercase()),
-                    Some(TextComponent::text(name.clone())),
-                ));
-
-            if entity.entity_type == entity::EntityType::Player {
-                entity_display = entity_display.click_event(ClickEvent::SuggestCommand(
-                    format!("/tell {} ", name.clone()).into(),
-                ));
-            }
-
-            TextComponent::translate("commands.kill.success.single", [entity_display].into())
+            TextComponent::translate(
+                "commands.kill.success.single",
+                [TextComponent::world_entity_mention(&targets[0])].into(),
+            )
         } else {
             TextComponent::translate(
                 "commands.kill.success.multiple",
@@ -77,24 +81,13 @@ impl CommandExecutor for KillSelfExecutor {
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
-        let name = target.gameprofile.name.clone();
-        let entity = &target.living_entity.entity;
 
-        target.living_entity.kill().await;
+        target.kill(KILL_MESSAGE_ID).await;
 
         sender
             .send_message(TextComponent::translate(
                 "commands.kill.success.single",
-                [TextComponent::text(name.clone())
-                    .hover_event(HoverEvent::show_entity(
-                        entity.entity_uuid.to_string(),
-                        Some(format!("{:?}", entity.entity_type).to_lowercase()),
-                        Some(TextComponent::text(name.clone())),
-                    ))
-                    .click_event(ClickEvent::SuggestCommand(
-                        format!("/tell {} ", name.clone()).into(),
-                    ))]
-                .into(),
+                [TextComponent::entity_mention(&target)].into(),
             ))
             .await;
 
@@ -0,0 +1,254 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::permission::PermissionLvl;
+use pumpkin_util::text::color::NamedColor;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::item_registry::{self, ITEMS_REGISTRY_NAME_BY_ID};
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::dispatcher::claim_cooldown;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::data::kit_data::{Kit, KitItem, KIT_LIST};
+use crate::data::SaveJSONConfiguration;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["kit"];
+
+const DESCRIPTION: &str = "Claims a kit of items.";
+
+const ARG_NAME: &str = "name";
+
+/// The permission level needed to create and delete kits
+const EDIT_PERMISSION_LVL: PermissionLvl = PermissionLvl::Two;
+
+fn cooldown_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("cooldown").min(0)
+}
+
+/// Kits without a permission can be claimed by everyone, operators can claim every kit
+fn can_claim(sender: &CommandSender, kit: &Kit) -> bool {
+    kit.permission.as_ref().is_none_or(|permission| {
+        sender.has_permission(permission) || sender.has_permission_lvl(EDIT_PERMISSION_LVL)
+    })
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let kits = KIT_LIST
+            .read()
+            .await
+            .kits
+            .iter()
+            .filter(|kit| can_claim(sender, kit))
+            .map(|kit| kit.name.clone())
+            .collect::<Vec<_>>();
+        let msg = if kits.is_empty() {
+            "There are no kits you can claim".to_string()
+        } else {
+            format!("Kits: {}", kits.join(", "))
+        };
+        sender.send_message(TextComponent::text(msg)).await;
+        Ok(())
+    }
+}
+
+struct ClaimExecutor;
+
+#[async_trait]
+impl CommandExecutor for ClaimExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Only players can claim kits".to_string(),
+            ));
+        };
+        let Some(kit) = KIT_LIST.read().await.get(name).cloned() else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Kit {name} does not exist"
+            )));
+        };
+        if !can_claim(sender, &kit) {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "You are not allowed to claim the kit {name}"
+            )));
+        }
+
+        let has_cooldown = kit.cooldown > 0
+            && !sender.has_permission_lvl(ADVANCED_CONFIG.commands.cooldown_bypass_level);
+        let claim = if has_cooldown {
+            let cooldown_key = format!("kit {}", kit.name);
+            let cooldown = Duration::from_secs(kit.cooldown);
+            match claim_cooldown(player, &cooldown_key, cooldown).await {
+                Ok(claim) => Some(claim),
+                Err(remaining) => {
+                    return Err(CommandError::GeneralCommandIssue(format!(
+                        "You have to wait {} seconds before claiming the kit {name} again",
+                        remaining.as_secs() + 1
+                    )));
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut given = 0;
+        let mut discarded = 0;
+        for KitItem { item, count } in &kit.items {
+            // Unknown items were already reported when loading the kits
+            if let Some(item) = item_registry::get_item(item) {
+                let leftover = player.give_items(item, *count).await;
+                given += count - leftover;
+                discarded += leftover;
+            }
+        }
+        if given == 0 && discarded > 0 {
+            // Nothing was claimed, so the player can try again once there is room
+            if let Some(claim) = claim {
+                claim.release(player).await;
+            }
+            return Err(CommandError::GeneralCommandIssue(
+                "Your inventory is full".to_string(),
+            ));
+        }
+
+        sender
+            .send_message(TextComponent::text(format!("You claimed the kit {name}")))
+            .await;
+        if discarded > 0 {
+            sender
+                .send_message(
+                    TextComponent::text(format!(
+                        "Your inventory is full, {discarded} items were discarded"
+                    ))
+                    .color_named(NamedColor::Yellow),
+                )
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a kit from every item in the player's inventory
+struct CreateExecutor;
+
+#[async_trait]
+impl CommandExecutor for CreateExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let Ok(cooldown) = cooldown_consumer().find_arg_default_name(args)? else {
+            return Err(CommandError::GeneralCommandIssue(
+                "The cooldown is too large or too small".to_string(),
+            ));
+        };
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Only players can create kits".to_string(),
+            ));
+        };
+        let items = player
+            .inventory()
+            .lock()
+            .await
+            .slots()
+            .into_iter()
+            .flatten()
+            .filter_map(|stack| {
+                Some(KitItem {
+                    item: format!(
+                        "minecraft:{}",
+                        ITEMS_REGISTRY_NAME_BY_ID.get(&stack.item_id)?
+                    ),
+                    count: u32::from(stack.item_count),
+                })
+            })
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return Err(CommandError::GeneralCommandIssue(
+                "Your inventory is empty".to_string(),
+            ));
+        }
+
+        let mut kits = KIT_LIST.write().await;
+        kits.insert(Kit {
+            name: name.to_string(),
+            items,
+            permission: None,
+            cooldown: u64::from(cooldown.unsigned_abs()),
+        });
+        kits.save();
+        sender
+            .send_message(TextComponent::text(format!("Created the kit {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+struct DeleteExecutor;
+
+#[async_trait]
+impl CommandExecutor for DeleteExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+        let mut kits = KIT_LIST.write().await;
+        if !kits.remove(name) {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Kit {name} does not exist"
+            )));
+        }
+        kits.save();
+        sender
+            .send_message(TextComponent::text(format!("Deleted the kit {name}")))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(ListExecutor)
+        .then(
+            require(|sender| sender.has_permission_lvl(EDIT_PERMISSION_LVL))
+                .then(
+                    literal("create").then(
+                        argument(ARG_NAME, SimpleArgConsumer).then(
+                            argument_default_name(cooldown_consumer()).execute(CreateExecutor),
+                        ),
+                    ),
+                )
+                .then(
+                    literal("delete")
+                        .then(argument(ARG_NAME, SimpleArgConsumer).execute(DeleteExecutor)),
+                ),
+        )
+        .then(argument(ARG_NAME, SimpleArgConsumer).execute(ClaimExecutor))
+}
@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::data::language_data::LANGUAGES;
+use crate::server::Server;
+
+const NAMES: [&str; 2] = ["language", "lang"];
+
+const DESCRIPTION: &str = "Sets the language messages from the server are shown in.";
+
+const ARG_LOCALE: &str = "locale";
+
+struct ShowExecutor;
+
+#[async_trait]
+impl CommandExecutor for ShowExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let language = player.language.lock().await.clone();
+        let msg = match language {
+            Some(language) => format!("Messages are shown in {language}"),
+            None => format!(
+                "Messages are shown in the language of your client ({})",
+                player.config.lock().await.locale
+            ),
+        };
+        sender
+            .send_message(TextComponent::text(format!(
+                "{msg}, available languages: {}",
+                LANGUAGES.locales().join(", ")
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let locale = SimpleArgConsumer::find_arg(args, ARG_LOCALE)?.to_lowercase();
+        if LANGUAGES.get(&locale).is_none() {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "The language {locale} is not available, available languages: {}",
+                LANGUAGES.locales().join(", ")
+            )));
+        }
+        *player.language.lock().await = Some(locale.clone());
+        sender
+            .send_message(TextComponent::text(format!(
+                "Messages are now shown in {locale}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct ResetExecutor;
+
+#[async_trait]
+impl CommandExecutor for ResetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        *player.language.lock().await = None;
+        sender
+            .send_message(TextComponent::text(
+                "Messages are now shown in the language of your client",
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        require(|sender| sender.is_player())
+            .execute(ShowExecutor)
+            .then(literal("reset").execute(ResetExecutor))
+            .then(argument(ARG_LOCALE, SimpleArgConsumer).execute(SetExecutor)),
+    )
+}
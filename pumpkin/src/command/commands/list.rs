@@ -33,7 +33,7 @@ impl CommandExecutor for ListExecutor {
                 [
                     TextComponent::text(players.len().to_string()),
                     TextComponent::text(BASIC_CONFIG.max_players.to_string()),
-                    TextComponent::text(get_player_names(players)),
+                    get_player_names(players).await,
                 ]
                 .into(),
             ))
@@ -43,13 +43,13 @@ impl CommandExecutor for ListExecutor {
     }
 }
 
-fn get_player_names(players: Vec<Arc<Player>>) -> String {
-    let mut names = String::new();
-    for player in players {
-        if !names.is_empty() {
-            names.push_str(", ");
+async fn get_player_names(players: Vec<Arc<Player>>) -> TextComponent {
+    let mut names = TextComponent::text("");
+    for (i, player) in players.iter().enumerate() {
+        if i > 0 {
+            names = names.add_text(", ");
         }
-        names.push_str(&player.gameprofile.name);
+        names = names.add_child(player.display_name().await);
     }
     names
 }
@@ -1,18 +1,34 @@
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
 pub mod ban;
 pub mod banip;
 pub mod banlist;
 pub mod bossbar;
+pub mod broadcast;
 pub mod clear;
+pub mod count;
+pub mod damage;
+pub mod debug;
 pub mod deop;
+pub mod difficulty;
+pub mod effect;
+pub mod entityinfo;
+pub mod experience;
 pub mod fill;
+pub mod function;
 pub mod gamemode;
+pub mod gamerule;
 pub mod give;
+pub mod glow;
 pub mod help;
 pub mod kick;
 pub mod kill;
+pub mod kit;
+pub mod language;
 pub mod list;
 pub mod me;
 pub mod msg;
+pub mod nick;
 pub mod op;
 pub mod pardon;
 pub mod pardonip;
@@ -20,13 +36,32 @@ pub mod playsound;
 pub mod plugin;
 pub mod plugins;
 pub mod pumpkin;
+pub mod reload;
+pub mod replace;
+pub mod save_off;
+pub mod save_on;
 pub mod say;
 pub mod seed;
+pub mod seen;
+pub mod selection;
+pub mod set;
 pub mod setblock;
+pub mod shout;
+pub mod spectate;
 pub mod stop;
 pub mod summon;
 pub mod teleport;
 pub mod time;
 pub mod title;
 pub mod transfer;
+pub mod undo;
+pub mod walls;
+pub mod weather;
 pub mod worldborder;
+
+/// A `name: value` line of the info shown by commands like `/pumpkin status`
+pub(crate) fn info_line(name: &str, value: String) -> TextComponent {
+    TextComponent::text(format!("\n{name}: "))
+        .color_named(NamedColor::Gold)
+        .add_child(TextComponent::text(value).color_named(NamedColor::White))
+}
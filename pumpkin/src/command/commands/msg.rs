@@ -1,12 +1,13 @@
 use async_trait::async_trait;
 use pumpkin_data::world::{MSG_COMMAND_INCOMING, MSG_COMMAND_OUTGOING};
-use pumpkin_util::text::{click::ClickEvent, hover::HoverEvent, TextComponent};
+use pumpkin_util::text::TextComponent;
 
 use crate::command::{
     args::{
         message::MsgArgConsumer, players::PlayersArgumentConsumer, Arg, ConsumedArgs,
         FindArgDefaultName,
     },
+    text::CommandTextExt,
     tree::CommandTree,
     tree_builder::{argument, argument_default_name},
     CommandError, CommandExecutor, CommandSender,
@@ -33,28 +34,39 @@ impl CommandExecutor for MsgExecutor {
             return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
         };
         let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
-        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        let Some(player) = sender.as_player() else {
+            // The console and RCON have no chat, so they get the outgoing message as plain feedback
+            let sender_name = TextComponent::text(sender.to_string());
+            for target in targets {
+                let target_name = target.display_name().await;
+                target
+                    .send_message(
+                        &TextComponent::text(msg.clone()),
+                        MSG_COMMAND_INCOMING,
+                        &sender_name,
+                        Some(&target_name),
+                    )
+                    .await;
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.message.display.outgoing",
+                        [target_name, TextComponent::text(msg.clone())].into(),
+                    ))
+                    .await;
+            }
+            return Ok(());
+        };
 
         for target in targets {
             player
                 .send_message(
                     &TextComponent::text(msg.clone()),
                     MSG_COMMAND_OUTGOING,
-                    &TextComponent::text(player.gameprofile.name.clone()),
-                    Some(
-                        &TextComponent::text(target.gameprofile.name.clone())
-                            .hover_event(HoverEvent::show_entity(
-                                target.living_entity.entity.entity_uuid.to_string(),
-                                Some(
-                                    format!("{:?}", target.living_entity.entity.entity_type)
-                                        .to_lowercase(),
-                                ),
-                                Some(TextComponent::text(target.gameprofile.name.clone())),
-                            ))
-                            .click_event(ClickEvent::SuggestCommand(
-                                format!("/tell {} ", target.gameprofile.name.clone()).into(),
-                            )),
-                    ),
+                    &player.display_name().await,
+                    Some(&TextComponent::entity_mention_as(
+                        target,
+                        target.display_name().await,
+                    )),
                 )
                 .await;
         }
@@ -63,19 +75,8 @@ impl CommandExecutor for MsgExecutor {
                 .send_message(
                     &TextComponent::text(msg.clone()),
                     MSG_COMMAND_INCOMING,
-                    &TextComponent::text(player.gameprofile.name.clone())
-                        .hover_event(HoverEvent::show_entity(
-                            player.living_entity.entity.entity_uuid.to_string(),
-                            Some(
-                                format!("{:?}", player.living_entity.entity.entity_type)
-                                    .to_lowercase(),
-                            ),
-                            Some(TextComponent::text(player.gameprofile.name.clone())),
-                        ))
-                        .click_event(ClickEvent::SuggestCommand(
-                            format!("/tell {} ", player.gameprofile.name.clone()).into(),
-                        )),
-                    Some(&TextComponent::text(target.gameprofile.name.clone())),
+                    &TextComponent::entity_mention_as(&player, player.display_name().await),
+                    Some(&target.display_name().await),
                 )
                 .await;
         }
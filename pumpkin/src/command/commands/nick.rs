@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::{permission::PermissionLvl, text::TextComponent};
+
+use crate::command::args::message::MsgArgConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["nick"];
+
+const DESCRIPTION: &str = "Changes the name shown for a player. Supports & color codes.";
+
+const ARG_TARGET: &str = "target";
+const ARG_NAME: &str = "name";
+
+/// Removes everything except letters, digits, underscores and formatting codes
+fn sanitize(name: &str) -> String {
+    let mut sanitized = String::new();
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            if let Some(code) = chars.next_if(|code| {
+                code.is_ascii_hexdigit() || "klmnor".contains(code.to_ascii_lowercase())
+            }) {
+                sanitized.push(c);
+                sanitized.push(code);
+            }
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            sanitized.push(c);
+        }
+    }
+    sanitized
+}
+
+/// Whether another online player already uses this name as username or nickname
+async fn is_name_taken(server: &Server, target: &Player, name: &str) -> bool {
+    for player in server.get_all_players().await {
+        if player.gameprofile.id == target.gameprofile.id {
+            continue;
+        }
+        if player.gameprofile.name.eq_ignore_ascii_case(name)
+            || player
+                .display_name()
+                .await
+                .to_plain_text()
+                .eq_ignore_ascii_case(name)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+async fn set_nick(
+    sender: &CommandSender<'_>,
+    server: &Server,
+    target: &Arc<Player>,
+    name: &str,
+) -> Result<(), CommandError> {
+    let config = &ADVANCED_CONFIG.nick;
+    let nick = TextComponent::from_legacy(&sanitize(name));
+    let plain = nick.clone().to_plain_text();
+
+    if plain.is_empty() {
+        return Err(CommandError::GeneralCommandIssue(
+            "Nicknames can only contain letters, numbers and underscores".into(),
+        ));
+    }
+    if plain.chars().count() > config.max_length {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "Nicknames can be at most {} characters long",
+            config.max_length
+        )));
+    }
+    // Only allow players to take their own username, so nobody can pretend to be someone else
+    if config.unique
+        && !plain.eq_ignore_ascii_case(&target.gameprofile.name)
+        && is_name_taken(server, target, &plain).await
+    {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "The name {plain} is already used by another player"
+        )));
+    }
+
+    target.set_display_name(Some(nick.clone())).await;
+    sender
+        .send_message(
+            TextComponent::text(format!(
+                "Set the nickname of {} to ",
+                target.gameprofile.name
+            ))
+            .add_child(nick),
+        )
+        .await;
+    Ok(())
+}
+
+async fn reset_nick(sender: &CommandSender<'_>, target: &Player) {
+    target.set_display_name(None).await;
+    sender
+        .send_message(TextComponent::text(format!(
+            "Removed the nickname of {}",
+            target.gameprofile.name
+        )))
+        .await;
+}
+
+struct NickSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for NickSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(name)) = args.get(ARG_NAME) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_NAME.into())));
+        };
+        let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        set_nick(sender, server, &target, name).await
+    }
+}
+
+struct NickOffSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for NickOffSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+        reset_nick(sender, &target).await;
+        Ok(())
+    }
+}
+
+struct NickTargetExecutor;
+
+#[async_trait]
+impl CommandExecutor for NickTargetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(name)) = args.get(ARG_NAME) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_NAME.into())));
+        };
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        for target in targets {
+            set_nick(sender, server, target, name).await?;
+        }
+        Ok(())
+    }
+}
+
+struct NickOffTargetExecutor;
+
+#[async_trait]
+impl CommandExecutor for NickOffTargetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        for target in targets {
+            reset_nick(sender, target).await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            require(|sender| sender.has_permission_lvl(PermissionLvl::Two)).then(
+                argument(ARG_TARGET, PlayersArgumentConsumer)
+                    .then(literal("off").execute(NickOffTargetExecutor))
+                    .then(argument(ARG_NAME, MsgArgConsumer).execute(NickTargetExecutor)),
+            ),
+        )
+        .then(
+            require(|sender| sender.as_player().is_some())
+                .then(literal("off").execute(NickOffSelfExecutor))
+                .then(argument(ARG_NAME, MsgArgConsumer).execute(NickSelfExecutor)),
+        )
+}
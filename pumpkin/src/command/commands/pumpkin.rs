@@ -1,13 +1,16 @@
 use async_trait::async_trait;
+use pumpkin_config::BASIC_CONFIG;
 use pumpkin_protocol::CURRENT_MC_PROTOCOL;
 use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::hover::HoverEvent;
 use pumpkin_util::text::{color::NamedColor, TextComponent};
 use std::borrow::Cow;
+use std::time::Duration;
 
 use crate::{
     command::{
-        args::ConsumedArgs, tree::CommandTree, CommandError, CommandExecutor, CommandSender,
+        args::ConsumedArgs, commands::info_line, tree::CommandTree, tree_builder::literal,
+        CommandError, CommandExecutor, CommandSender,
     },
     server::CURRENT_MC_VERSION,
     GIT_VERSION,
@@ -97,6 +100,140 @@ impl CommandExecutor for PumpkinExecutor {
     }
 }
 
+struct VersionExecutor;
+
+#[async_trait]
+impl CommandExecutor for VersionExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        sender
+            .send_message(
+                TextComponent::text("Pumpkin ")
+                    .color_named(NamedColor::Gold)
+                    .add_child(
+                        TextComponent::text(format!("{CARGO_PKG_VERSION} ({GIT_VERSION})"))
+                            .color_named(NamedColor::White),
+                    )
+                    .add_child(
+                        TextComponent::text(format!(
+                            " - Minecraft {CURRENT_MC_VERSION}, Protocol {CURRENT_MC_PROTOCOL}"
+                        ))
+                        .color_named(NamedColor::Gray),
+                    ),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+struct UptimeExecutor;
+
+#[async_trait]
+impl CommandExecutor for UptimeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        sender
+            .send_message(
+                TextComponent::text("Uptime: ")
+                    .color_named(NamedColor::Gold)
+                    .add_child(
+                        TextComponent::text(format_duration(server.start_time.elapsed()))
+                            .color_named(NamedColor::White),
+                    ),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+struct StatusExecutor;
+
+#[async_trait]
+impl CommandExecutor for StatusExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let players = server.get_all_players().await.len();
+        let chunks: usize = server
+            .worlds
+            .read()
+            .await
+            .iter()
+            .map(|world| world.level.loaded_chunk_count())
+            .sum();
+        let (tps, mspt) = {
+            let stats = server.tick_stats.lock().await;
+            (stats.tps(BASIC_CONFIG.tps), stats.mspt())
+        };
+
+        sender
+            .send_message(
+                TextComponent::text("Pumpkin Status")
+                    .color_named(NamedColor::Gold)
+                    .bold()
+                    .add_child(info_line("Online players", players.to_string()))
+                    .add_child(info_line("Loaded chunks", chunks.to_string()))
+                    .add_child(info_line("TPS", format!("{tps:.1}")))
+                    .add_child(info_line("MSPT", format!("{mspt:.2}"))),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+/// Formats a duration like `1d 2h 3m 4s`, leaving out leading zero units
+pub(super) fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m {seconds}s")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m {seconds}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
 pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION).execute(PumpkinExecutor)
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("version").execute(VersionExecutor))
+        .then(literal("uptime").execute(UptimeExecutor))
+        .then(literal("status").execute(StatusExecutor))
+        .execute(PumpkinExecutor)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::format_duration;
+
+    #[test]
+    fn uptime_format() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(3 * 60 + 5)), "3m 5s");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1h 0m 0s");
+        assert_eq!(
+            format_duration(Duration::from_secs(2 * 86400 + 3600 + 61)),
+            "2d 1h 1m 1s"
+        );
+    }
 }
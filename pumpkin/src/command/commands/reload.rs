@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::command::{
+    args::ConsumedArgs, function::FunctionRegistry, tree::CommandTree, CommandError,
+    CommandExecutor, CommandSender,
+};
+use crate::data::starter_inventory_data::StarterInventory;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["reload"];
+
+const DESCRIPTION: &str = "Reloads functions and the starter inventory from disk.";
+
+struct ReloadExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReloadExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        StarterInventory::reload().await;
+        let (loaded, errors) = FunctionRegistry::reload().await;
+        // Broken functions are skipped, the others are still usable
+        for error in &errors {
+            sender
+                .send_message(
+                    TextComponent::text(format!("Skipped function {error}"))
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+        }
+        sender
+            .send_message(TextComponent::text(format!(
+                "Loaded {loaded} functions, skipped {}",
+                errors.len()
+            )))
+            .await;
+        if errors.is_empty() {
+            sender
+                .send_message(TextComponent::translate("commands.reload.success", vec![]))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(ReloadExecutor)
+}
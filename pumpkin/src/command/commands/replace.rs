@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use pumpkin_world::block::block_registry::get_block_by_state_id;
+
+use crate::command::args::block::BlockArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+use super::selection::{edit_selection, selected_region};
+
+const NAMES: [&str; 1] = ["replace"];
+
+const DESCRIPTION: &str = "Replaces every block of one kind in your selection with another.";
+
+const ARG_FROM: &str = "from";
+const ARG_TO: &str = "to";
+
+struct ReplaceExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReplaceExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let from = BlockArgumentConsumer::find_arg(args, ARG_FROM)?;
+        let to = BlockArgumentConsumer::find_arg(args, ARG_TO)?;
+        let (player, region) = selected_region(sender)?;
+        let world = player.world();
+        let mut changes = Vec::new();
+        world
+            .for_each_block_state(&region, |position, state_id| {
+                // Any state of the block is replaced
+                if get_block_by_state_id(state_id).is_some_and(|block| block.id == from.id) {
+                    changes.push((position, to.default_state_id));
+                }
+            })
+            .await;
+        edit_selection(sender, &player, &changes).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_FROM, BlockArgumentConsumer)
+            .then(argument(ARG_TO, BlockArgumentConsumer).execute(ReplaceExecutor)),
+    )
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::{
+    args::ConsumedArgs, tree::CommandTree, CommandError, CommandExecutor, CommandSender,
+};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["save-on"];
+
+const DESCRIPTION: &str = "Enables automatic saving of the worlds.";
+
+struct SaveOnExecutor;
+
+#[async_trait]
+impl CommandExecutor for SaveOnExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        if !server.set_autosave_enabled(true) {
+            return Err(CommandError::GeneralCommandIssue(
+                "Saving is already turned on".to_string(),
+            ));
+        }
+        sender
+            .send_message(TextComponent::translate("commands.save.enabled", vec![]))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(SaveOnExecutor)
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use chrono::Local;
+use pumpkin_util::text::{color::NamedColor, TextComponent};
+
+use crate::command::{
+    args::{simple::SimpleArgConsumer, Arg, ConsumedArgs},
+    tree::CommandTree,
+    tree_builder::argument,
+    CommandError, CommandExecutor, CommandSender,
+};
+use crate::data::last_seen_data::LAST_SEEN_LIST;
+use crate::server::Server;
+use CommandError::InvalidConsumption;
+
+use super::pumpkin::format_duration;
+
+const NAMES: [&str; 1] = ["seen"];
+
+const DESCRIPTION: &str = "Shows when a player was last online.";
+
+const ARG_PLAYER: &str = "player";
+
+struct SeenExecutor;
+
+#[async_trait]
+impl CommandExecutor for SeenExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Simple(name)) = args.get(ARG_PLAYER) else {
+            return Err(InvalidConsumption(Some(ARG_PLAYER.into())));
+        };
+
+        if let Some(player) = server.get_player_by_name(name).await {
+            sender
+                .send_message(TextComponent::text(format!(
+                    "{} is online now",
+                    player.gameprofile.name
+                )))
+                .await;
+            return Ok(());
+        }
+
+        let Some(entry) = LAST_SEEN_LIST.read().await.get_by_name(name).cloned() else {
+            sender
+                .send_message(
+                    TextComponent::text(format!("{name} has never joined the server"))
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+
+        // The logout is missing if the server stopped while the player was online
+        let last_seen = entry.last_logout.unwrap_or(entry.last_login);
+        let ago = (Local::now().fixed_offset() - last_seen)
+            .to_std()
+            .unwrap_or_default();
+        sender
+            .send_message(TextComponent::text(format!(
+                "{} was last seen {} ago, on {}",
+                entry.name,
+                format_duration(ago),
+                last_seen.format("%Y-%m-%d %H:%M:%S %z")
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_PLAYER, SimpleArgConsumer).execute(SeenExecutor))
+}
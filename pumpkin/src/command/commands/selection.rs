@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::selection::{Corner, Region};
+
+const ARG_POS: &str = "pos";
+
+/// Sets a corner of the player's selection to the given position, or where the player stands
+struct PosExecutor {
+    corner: Corner,
+    has_pos: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for PosExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Only players can select regions".to_string(),
+            ));
+        };
+        let position = if self.has_pos {
+            BlockPosArgumentConsumer::find_arg(args, ARG_POS)?
+        } else {
+            player.living_entity.entity.block_pos.load()
+        };
+        player.set_selection_corner(self.corner, position).await;
+        Ok(())
+    }
+}
+
+/// The player running the command and their selection, if it can be edited
+pub(super) fn selected_region(
+    sender: &CommandSender<'_>,
+) -> Result<(Arc<Player>, Region), CommandError> {
+    let Some(player) = sender.as_player() else {
+        return Err(CommandError::GeneralCommandIssue(
+            "Only players can edit selections".to_string(),
+        ));
+    };
+    let region = player
+        .selection
+        .load()
+        .editable_region(player.world().dimension_type)
+        .map_err(|err| CommandError::GeneralCommandIssue(err.to_string()))?;
+    Ok((player, region))
+}
+
+/// Applies the changes to the player's world and reports how many blocks changed
+pub(super) async fn edit_selection(
+    sender: &CommandSender<'_>,
+    player: &Player,
+    changes: &[(BlockPos, u16)],
+) {
+    if !player.edit_blocks(changes).await {
+        sender
+            .send_message(TextComponent::text(format!(
+                "{} blocks are too many to be undone",
+                changes.len()
+            )))
+            .await;
+    }
+    sender
+        .send_message(TextComponent::text(format!(
+            "{} blocks were changed",
+            changes.len()
+        )))
+        .await;
+}
+
+pub fn init_command_tree(corner: Corner) -> CommandTree {
+    let (names, description) = match corner {
+        Corner::First => (["pos1"], "Sets the first corner of your selection."),
+        Corner::Second => (["pos2"], "Sets the second corner of your selection."),
+    };
+    CommandTree::new(names, description)
+        .execute(PosExecutor {
+            corner,
+            has_pos: false,
+        })
+        .then(
+            argument(ARG_POS, BlockPosArgumentConsumer).execute(PosExecutor {
+                corner,
+                has_pos: true,
+            }),
+        )
+}
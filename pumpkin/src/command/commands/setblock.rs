@@ -44,25 +44,31 @@ impl CommandExecutor for SetblockExecutor {
         // TODO: allow console to use the command (seed sender.world)
         let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
 
-        let success = match mode {
+        let replaced = match mode {
             Mode::Destroy => {
+                let previous = world
+                    .get_block_state_id(&pos)
+                    .await
+                    .map_err(|e| CommandError::OtherPumpkin(e.into()))?;
                 world.break_block(&pos, None).await;
                 world.set_block_state(&pos, block_state_id).await;
-                true
-            }
-            Mode::Replace => {
-                world.set_block_state(&pos, block_state_id).await;
-                true
+                Some(previous)
             }
+            Mode::Replace => Some(world.set_block_state(&pos, block_state_id).await),
             Mode::Keep => match world.get_block_state(&pos).await {
                 Ok(old_state) if old_state.air => {
-                    world.set_block_state(&pos, block_state_id).await;
-                    true
+                    Some(world.set_block_state(&pos, block_state_id).await)
                 }
-                Ok(_) => false,
+                Ok(_) => None,
                 Err(e) => return Err(CommandError::OtherPumpkin(e.into())),
             },
         };
+        let success = replaced.is_some();
+        if let (Some(replaced), Some(player)) = (replaced, sender.as_player()) {
+            player
+                .record_edit(&[(pos, block_state_id)], &[replaced])
+                .await;
+        }
 
         sender
             .send_message(if success {
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+
+use crate::command::{
+    args::{message::MsgArgConsumer, Arg, ConsumedArgs},
+    tree::CommandTree,
+    tree_builder::argument,
+    CommandError, CommandExecutor, CommandSender,
+};
+use crate::net::chat::ChatChannel;
+use crate::server::Server;
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["shout"];
+
+const DESCRIPTION: &str =
+    "Sends a chat message to every player in your world, even when chat is local.";
+
+const ARG_MESSAGE: &str = "message";
+
+struct ShoutExecutor;
+
+#[async_trait]
+impl CommandExecutor for ShoutExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(msg)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Only players can shout".to_string(),
+            ));
+        };
+
+        // Shouting is chatting, so the same checks and plugin events apply
+        let Some(message) = player.filter_chat_message(msg).await else {
+            return Ok(());
+        };
+
+        log::info!("<chat>{}: {}", player.gameprofile.name, message);
+        let entity = &player.living_entity.entity;
+        let recipients = ChatChannel::Global
+            .recipients(&entity.world, entity.pos.load())
+            .await;
+        player.send_unsigned_chat(&message, &recipients).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_MESSAGE, MsgArgConsumer).execute(ShoutExecutor))
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::text::CommandTextExt;
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::entity_query::WorldEntity;
+
+const NAMES: [&str; 1] = ["spectate"];
+
+const DESCRIPTION: &str =
+    "Follows an entity with your camera as a spectator, or stops following without a target.";
+
+const ARG_TARGET: &str = "target";
+const ARG_ENABLED: &str = "enabled";
+
+fn spectator(sender: &CommandSender<'_>) -> Result<Arc<Player>, CommandError> {
+    let Some(player) = sender.as_player() else {
+        return Err(CommandError::GeneralCommandIssue(
+            "Only players can spectate".to_string(),
+        ));
+    };
+    if !player.is_spectator() {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "{} is not in spectator mode",
+            player.gameprofile.name
+        )));
+    }
+    Ok(player)
+}
+
+struct SpectateExecutor;
+
+#[async_trait]
+impl CommandExecutor for SpectateExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let player = spectator(sender)?;
+
+        if let WorldEntity::Player(target) = &target {
+            if Arc::ptr_eq(target, &player) {
+                return Err(CommandError::GeneralCommandIssue(
+                    "You can't spectate yourself".to_string(),
+                ));
+            }
+        }
+        if !Arc::ptr_eq(&target.entity().world, &player.living_entity.entity.world) {
+            return Err(CommandError::GeneralCommandIssue(
+                "The target is in another world".to_string(),
+            ));
+        }
+
+        let msg = TextComponent::text("Now spectating ")
+            .add_child(TextComponent::world_entity_mention(&target));
+        player.start_spectating(target).await;
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct StopSpectatingExecutor;
+
+#[async_trait]
+impl CommandExecutor for StopSpectatingExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Only players can spectate".to_string(),
+            ));
+        };
+        let Some(target) = player.stop_spectating().await else {
+            return Err(CommandError::GeneralCommandIssue(
+                "You are not spectating anything".to_string(),
+            ));
+        };
+        sender
+            .send_message(
+                TextComponent::text("Stopped spectating ")
+                    .add_child(TextComponent::world_entity_mention(&target)),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+/// Shows or hides the health, position and outlines while following an entity
+struct SpectatorHudExecutor;
+
+#[async_trait]
+impl CommandExecutor for SpectatorHudExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let enabled = BoolArgConsumer::find_arg(args, ARG_ENABLED)?;
+        let player = spectator(sender)?;
+
+        player.set_spectator_hud(enabled).await;
+        sender
+            .send_message(TextComponent::text(if enabled {
+                "The spectator HUD is now shown"
+            } else {
+                "The spectator HUD is now hidden"
+            }))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(StopSpectatingExecutor)
+        .then(
+            literal("hud")
+                .then(argument(ARG_ENABLED, BoolArgConsumer).execute(SpectatorHudExecutor)),
+        )
+        .then(argument(ARG_TARGET, EntityArgumentConsumer).execute(SpectateExecutor))
+}
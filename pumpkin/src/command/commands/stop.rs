@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::text::TextComponent;
@@ -10,6 +12,9 @@ const NAMES: [&str; 1] = ["stop"];
 
 const DESCRIPTION: &str = "Stop the server.";
 
+/// How long running commands get to finish before the server stops anyway
+const COMMAND_TASKS_TIMEOUT: Duration = Duration::from_secs(10);
+
 struct StopExecutor;
 
 #[async_trait]
@@ -27,13 +32,20 @@ impl CommandExecutor for StopExecutor {
             )
             .await;
 
-        // TODO: Gracefully stop
+        if !server.command_tasks.shutdown(COMMAND_TASKS_TIMEOUT).await {
+            log::warn!(
+                "Stopping with {} commands still running",
+                server.command_tasks.running()
+            );
+        }
 
         let kick_message = TextComponent::text("Server stopped");
         for player in server.get_all_players().await {
             player.kick(kick_message.clone()).await;
         }
         server.save().await;
+        // Players that were kicked save their data while disconnecting
+        server.player_data_storage.wait_for_saves().await;
         std::process::exit(0)
     }
 }
@@ -4,14 +4,14 @@ use pumpkin_util::text::TextComponent;
 use crate::{
     command::{
         args::{
-            position_3d::Position3DArgumentConsumer,
+            nbt::NbtArgConsumer, position_3d::Position3DArgumentConsumer,
             summonable_entities::SummonableEntitiesArgumentConsumer, ConsumedArgs, FindArg,
         },
         tree::CommandTree,
         tree_builder::argument,
         CommandError, CommandExecutor, CommandSender,
     },
-    entity::mob,
+    plugin::entity::SpawnReason,
 };
 const NAMES: [&str; 1] = ["summon"];
 
@@ -21,6 +21,8 @@ const ARG_ENTITY: &str = "entity";
 
 const ARG_POS: &str = "pos";
 
+const ARG_NBT: &str = "nbt";
+
 struct SummonExecutor;
 
 #[async_trait]
@@ -33,15 +35,26 @@ impl CommandExecutor for SummonExecutor {
     ) -> Result<(), CommandError> {
         let entity = SummonableEntitiesArgumentConsumer::find_arg(args, ARG_ENTITY)?;
         let pos = Position3DArgumentConsumer::find_arg(args, ARG_POS);
+        let nbt = NbtArgConsumer::find_arg(args, ARG_NBT);
 
         // TODO: Make this work in console
         if let Some(player) = sender.as_player() {
             let pos = pos.unwrap_or(player.living_entity.entity.pos.load());
-            let (mob, uuid) = mob::from_type(entity, server, pos, player.world()).await;
-            player
-                .world()
-                .broadcast_packet_all(&mob.living_entity.entity.create_spawn_packet(uuid))
-                .await;
+            let world = player.world();
+            if let Some(limit) = world.entity_limit_reached(pos).await {
+                return Err(CommandError::GeneralCommandIssue(limit.to_string()));
+            }
+            let Some(mob) = world
+                .spawn_entity(server, entity, pos, SpawnReason::Command)
+                .await
+            else {
+                return Err(CommandError::GeneralCommandIssue(
+                    "The entity could not be spawned".to_string(),
+                ));
+            };
+            if let Ok(nbt) = nbt {
+                mob.living_entity.entity.apply_custom_name(nbt).await;
+            }
             sender
                 .send_message(TextComponent::translate(
                     "commands.summon.success",
@@ -58,7 +71,49 @@ pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_ENTITY, SummonableEntitiesArgumentConsumer)
             .execute(SummonExecutor)
-            .then(argument(ARG_POS, Position3DArgumentConsumer).execute(SummonExecutor)),
-        // TODO: Add NBT
+            .then(
+                argument(ARG_POS, Position3DArgumentConsumer)
+                    .execute(SummonExecutor)
+                    .then(argument(ARG_NBT, NbtArgConsumer).execute(SummonExecutor)),
+            ),
     )
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use pumpkin_util::{text::TextComponent, PermissionLvl};
+
+    use crate::{
+        command::CommandSender, data::test_folder::TestFolder, entity::player::test_player,
+        server::Server,
+    };
+
+    #[tokio::test]
+    async fn summons_named_mobs() {
+        let folder = TestFolder::new("summon");
+        let server = Server::with_world_folder(folder.join("server"));
+        let player = test_player(folder.join("world"), uuid::Uuid::new_v4()).await;
+        player.permission_lvl.store(PermissionLvl::Two);
+        server
+            .command_dispatcher
+            .read()
+            .await
+            .handle_command(
+                &mut CommandSender::Player(player.clone()),
+                &server,
+                r#"summon zombie 0 100 0 {CustomName:'"Bob"', CustomNameVisible:1b}"#,
+            )
+            .await;
+
+        let mobs = player.world().current_living_mobs.lock().await;
+        assert_eq!(mobs.len(), 1);
+        let entity = &mobs.values().next().unwrap().living_entity.entity;
+        assert_eq!(
+            *entity.custom_name.lock().await,
+            Some(TextComponent::text("Bob"))
+        );
+        assert!(entity.custom_name_visible.load(Ordering::Relaxed));
+    }
+}
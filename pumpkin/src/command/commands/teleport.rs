@@ -62,12 +62,12 @@ impl CommandExecutor for TpEntitiesToEntityExecutor {
         let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
 
         let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
-        let pos = destination.living_entity.entity.pos.load();
+        let pos = destination.entity().pos.load();
 
         for target in targets {
-            let yaw = target.living_entity.entity.yaw.load();
-            let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            let yaw = target.entity().yaw.load();
+            let pitch = target.entity().pitch.load();
+            target.entity().teleport(pos, yaw, pitch).await;
         }
 
         Ok(())
@@ -92,7 +92,7 @@ impl CommandExecutor for TpEntitiesToPosFacingPosExecutor {
         let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_pos);
 
         for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target.entity().teleport(pos, yaw, pitch).await;
         }
 
         Ok(())
@@ -113,13 +113,11 @@ impl CommandExecutor for TpEntitiesToPosFacingEntityExecutor {
 
         let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
 
-        let facing_entity = &EntityArgumentConsumer::find_arg(args, ARG_FACING_ENTITY)?
-            .living_entity
-            .entity;
-        let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_entity.pos.load());
+        let facing_entity = EntityArgumentConsumer::find_arg(args, ARG_FACING_ENTITY)?;
+        let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_entity.entity().pos.load());
 
         for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target.entity().teleport(pos, yaw, pitch).await;
         }
 
         Ok(())
@@ -143,7 +141,7 @@ impl CommandExecutor for TpEntitiesToPosWithRotationExecutor {
         let (yaw, pitch) = RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?;
 
         for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            target.entity().teleport(pos, yaw, pitch).await;
         }
 
         Ok(())
@@ -165,9 +163,9 @@ impl CommandExecutor for TpEntitiesToPosExecutor {
         let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
 
         for target in targets {
-            let yaw = target.living_entity.entity.yaw.load();
-            let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+            let yaw = target.entity().yaw.load();
+            let pitch = target.entity().pitch.load();
+            target.entity().teleport(pos, yaw, pitch).await;
         }
 
         Ok(())
@@ -185,7 +183,7 @@ impl CommandExecutor for TpSelfToEntityExecutor {
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
-        let pos = destination.living_entity.entity.pos.load();
+        let pos = destination.entity().pos.load();
 
         match sender {
             CommandSender::Player(player) => {
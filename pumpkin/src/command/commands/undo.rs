@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument_default_name;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["undo"];
+
+const DESCRIPTION: &str =
+    "Reverts your most recent block edits made with /setblock and /fill, except destroying fills.";
+
+fn count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("count").min(1)
+}
+
+struct UndoExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for UndoExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let count = if self.0 {
+            let Ok(count) = count_consumer().find_arg_default_name(args)? else {
+                return Err(CommandError::GeneralCommandIssue(
+                    "The count is too large or too small".to_string(),
+                ));
+            };
+            count
+        } else {
+            1
+        };
+        let Some(player) = sender.as_player() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Only players can undo their edits".to_string(),
+            ));
+        };
+
+        let (mut edits, mut blocks) = (0, 0);
+        for _ in 0..count {
+            // The lock is not held while restoring, so the player's other commands are not blocked
+            let Some(edit) = player.edit_history.lock().await.pop() else {
+                break;
+            };
+            // The world the edit was made in no longer exists, there is nothing left to restore
+            if let Some(world) = edit.world.upgrade() {
+                world.set_block_states(&edit.previous).await;
+                blocks += edit.previous.len();
+            }
+            edits += 1;
+        }
+        if edits == 0 {
+            return Err(CommandError::GeneralCommandIssue(
+                "There is nothing to undo".to_string(),
+            ));
+        }
+
+        sender
+            .send_message(TextComponent::text(format!(
+                "Undid {edits} edits, restoring {blocks} blocks"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(UndoExecutor(false))
+        .then(argument_default_name(count_consumer()).execute(UndoExecutor(true)))
+}
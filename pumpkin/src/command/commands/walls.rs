@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+
+use crate::command::args::block::BlockArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+use super::selection::{edit_selection, selected_region};
+
+const NAMES: [&str; 1] = ["walls"];
+
+const DESCRIPTION: &str =
+    "Builds walls of a block around your selection, without floor and ceiling.";
+
+const ARG_BLOCK: &str = "block";
+
+struct WallsExecutor;
+
+#[async_trait]
+impl CommandExecutor for WallsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let block = BlockArgumentConsumer::find_arg(args, ARG_BLOCK)?;
+        let (player, region) = selected_region(sender)?;
+        let changes: Vec<_> = region
+            .positions()
+            .filter(|position| region.is_wall(*position))
+            .map(|position| (position, block.default_state_id))
+            .collect();
+        edit_selection(sender, &player, &changes).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_BLOCK, BlockArgumentConsumer).execute(WallsExecutor))
+}
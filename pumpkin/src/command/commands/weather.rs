@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::time::TimeArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::weather::WeatherType;
+
+const NAMES: [&str; 1] = ["weather"];
+
+const DESCRIPTION: &str = "Sets the weather.";
+
+const ARG_DURATION: &str = "duration";
+
+/// Sets the weather for the given duration, or a random one if there is no duration argument
+struct WeatherExecutor(WeatherType);
+
+#[async_trait]
+impl CommandExecutor for WeatherExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let duration = if args.contains_key(ARG_DURATION) {
+            Some(TimeArgumentConsumer::find_arg(args, ARG_DURATION)?)
+        } else {
+            None
+        };
+        let worlds = server.worlds.read().await;
+        // The console changes the weather of the first world
+        let world = sender
+            .world()
+            .or_else(|| worlds.first().map(AsRef::as_ref))
+            .expect("There should always be at least one world");
+        world.set_weather(self.0, duration).await;
+        sender.send_message(set_message(self.0)).await;
+        Ok(())
+    }
+}
+
+fn set_message(weather_type: WeatherType) -> TextComponent {
+    let key = match weather_type {
+        WeatherType::Clear => "commands.weather.set.clear",
+        WeatherType::Rain => "commands.weather.set.rain",
+        WeatherType::Thunder => "commands.weather.set.thunder",
+    };
+    TextComponent::translate(key, [].into())
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut tree = CommandTree::new(NAMES, DESCRIPTION);
+    for (name, weather_type) in [
+        ("clear", WeatherType::Clear),
+        ("rain", WeatherType::Rain),
+        ("thunder", WeatherType::Thunder),
+    ] {
+        tree = tree.then(
+            literal(name).execute(WeatherExecutor(weather_type)).then(
+                argument(ARG_DURATION, TimeArgumentConsumer { min: 1 })
+                    .execute(WeatherExecutor(weather_type)),
+            ),
+        );
+    }
+    tree
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::tree::NodeType;
+
+    use super::init_command_tree;
+
+    #[test]
+    fn tree_paths() {
+        let tree = init_command_tree();
+        let paths: Vec<String> = tree
+            .iter_paths()
+            .map(|path| {
+                path.iter()
+                    .filter_map(|&i| match &tree.nodes[i].node_type {
+                        NodeType::Literal { string } => Some(string.clone()),
+                        NodeType::Argument { name, .. } => Some(format!("<{name}>")),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        for weather in ["clear", "rain", "thunder"] {
+            assert!(paths.contains(&weather.to_string()));
+            assert!(paths.contains(&format!("{weather} <duration>")));
+        }
+        assert_eq!(paths.len(), 6);
+    }
+}
@@ -9,9 +9,12 @@ use crate::command::dispatcher::CommandError::{
 };
 use crate::command::tree::{Command, CommandTree, NodeType, RawArgs};
 use crate::command::CommandSender;
+use crate::entity::player::Player;
 use crate::error::PumpkinError;
 use crate::server::Server;
+use pumpkin_config::ADVANCED_CONFIG;
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug)]
 pub enum CommandError {
@@ -55,6 +58,8 @@ pub struct CommandDispatcher {
     pub(crate) commands: HashMap<String, Command>,
     pub(crate) permissions: HashMap<String, PermissionLvl>,
     pub(crate) plugin_names: HashMap<String, String>,
+    /// How long players have to wait between two uses of a command, keyed by the command's primary name
+    pub(crate) cooldowns: HashMap<String, Duration>,
 }
 
 impl CommandDispatcher {
@@ -63,6 +68,7 @@ impl CommandDispatcher {
             commands: HashMap::new(),
             permissions: HashMap::new(),
             plugin_names: HashMap::new(),
+            cooldowns: HashMap::new(),
         }
     }
 
@@ -70,12 +76,143 @@ impl CommandDispatcher {
         self.register_with_plugin(tree, permission, "pumpkin");
     }
 
-    pub(crate) fn register_with_plugin(&mut self, tree: CommandTree, permission: PermissionLvl, plugin_name: &str) {
+    pub(crate) fn register_with_plugin(
+        &mut self,
+        tree: CommandTree,
+        permission: PermissionLvl,
+        plugin_name: &str,
+    ) {
+        let Some(primary_name) = tree.names.first().cloned() else {
+            log::warn!("Tried to register a command without a name");
+            return;
+        };
         for name in &tree.names {
-            self.commands.insert(name.clone(), Command::Tree(tree.clone()));
             self.permissions.insert(name.clone(), permission);
-            self.plugin_names.insert(name.clone(), plugin_name.to_string());
+            self.plugin_names
+                .insert(name.clone(), plugin_name.to_string());
+            // The tree is stored once, every other name points to it
+            if *name != primary_name {
+                self.commands
+                    .insert(name.clone(), Command::Alias(primary_name.clone()));
+            }
+        }
+        self.commands.insert(primary_name, Command::Tree(tree));
+    }
+
+    /// Makes `alias` run the command `target`, with the same permission level and plugin as the target.
+    /// Fails if there is no command called `target`, `alias` already is a command or the alias would point to itself
+    pub fn register_alias(&mut self, alias: &str, target: &str) -> Result<(), CommandError> {
+        if matches!(self.commands.get(alias), Some(Command::Tree(_))) {
+            return Err(GeneralCommandIssue(format!(
+                "Command {alias} already exists"
+            )));
+        }
+        let mut next = target;
+        loop {
+            if next == alias {
+                return Err(GeneralCommandIssue(format!(
+                    "Alias {alias} would point to itself through {target}"
+                )));
+            }
+            match self.commands.get(next) {
+                Some(Command::Tree(_)) => break,
+                Some(Command::Alias(name)) => next = name,
+                None => {
+                    return Err(GeneralCommandIssue(format!(
+                        "Command {target} does not exist"
+                    )))
+                }
+            }
+        }
+
+        if let Some(permission) = self.permissions.get(target).copied() {
+            self.permissions.insert(alias.to_string(), permission);
+        }
+        if let Some(plugin_name) = self.plugin_names.get(target).cloned() {
+            self.plugin_names.insert(alias.to_string(), plugin_name);
         }
+        self.commands
+            .insert(alias.to_string(), Command::Alias(target.to_string()));
+        Ok(())
+    }
+
+    /// Removes a command with all of its names, returns false if there is no command called `name`
+    pub(crate) fn unregister(&mut self, name: &str) -> bool {
+        let Ok(tree) = self.get_tree(name) else {
+            return false;
+        };
+        let primary_name = tree.names[0].clone();
+        let names: Vec<String> = self
+            .commands
+            .keys()
+            .filter(|key| {
+                self.get_tree(key)
+                    .is_ok_and(|tree| tree.names[0] == primary_name)
+            })
+            .cloned()
+            .collect();
+        for name in &names {
+            self.commands.remove(name);
+            self.permissions.remove(name);
+            self.plugin_names.remove(name);
+        }
+        self.cooldowns.remove(&primary_name);
+        true
+    }
+
+    /// Removes every command the plugin registered, returns how many were removed
+    pub(crate) fn unregister_all_for_plugin(&mut self, plugin_name: &str) -> usize {
+        let names: Vec<String> = self
+            .plugin_names
+            .iter()
+            .filter(|(_, plugin)| *plugin == plugin_name)
+            .map(|(name, _)| name.clone())
+            .collect();
+        // Removing a command also removes its other names, which are skipped then
+        names.iter().filter(|name| self.unregister(name)).count()
+    }
+
+    /// Sets how long players have to wait between two uses of a command and all of its names.
+    /// A cooldown of zero removes it
+    pub fn set_cooldown(&mut self, name: &str, cooldown: Duration) {
+        let Ok(tree) = self.get_tree(name) else {
+            log::warn!("Tried to set a cooldown for the unknown command \"{name}\"");
+            return;
+        };
+        let primary_name = tree.names[0].clone();
+        if cooldown.is_zero() {
+            self.cooldowns.remove(&primary_name);
+        } else {
+            self.cooldowns.insert(primary_name, cooldown);
+        }
+    }
+
+    /// Starts the cooldown of the command for the player, fails if they used it too recently.
+    /// Players with the bypass level are exempt
+    async fn claim_command_cooldown(
+        &self,
+        src: &CommandSender<'_>,
+        tree: &CommandTree,
+    ) -> Result<Option<CooldownClaim>, CommandError> {
+        let Some(cooldown) = self.cooldowns.get(&tree.names[0]) else {
+            return Ok(None);
+        };
+        let Some(player) = src.as_player() else {
+            return Ok(None);
+        };
+        if src.has_permission_lvl(ADVANCED_CONFIG.commands.cooldown_bypass_level) {
+            return Ok(None);
+        }
+        claim_cooldown(&player, &tree.names[0], *cooldown)
+            .await
+            .map(Some)
+            .map_err(|remaining| {
+                GeneralCommandIssue(format!(
+                    "You have to wait {} seconds before using /{} again",
+                    remaining.as_secs() + 1,
+                    tree.names[0]
+                ))
+            })
     }
 
     pub async fn handle_command<'a>(
@@ -88,7 +225,7 @@ impl CommandDispatcher {
             match e.into_string_or_pumpkin_error(cmd) {
                 Ok(err) => {
                     sender
-                        .send_message(
+                        .send_error(
                             TextComponent::text(err)
                                 .color_named(pumpkin_util::text::color::NamedColor::Red),
                         )
@@ -96,13 +233,14 @@ impl CommandDispatcher {
                 }
                 Err(pumpkin_error) => {
                     pumpkin_error.log();
-                    sender.send_message(TextComponent::text("Unknown internal error occurred while running command. Please see server log").color_named(pumpkin_util::text::color::NamedColor::Red)).await;
+                    sender.send_error(TextComponent::text("Unknown internal error occurred while running command. Please see server log").color_named(pumpkin_util::text::color::NamedColor::Red)).await;
                 }
             }
         }
     }
 
     async fn try_is_fitting_path<'a>(
+        &self,
         src: &mut CommandSender<'a>,
         server: &'a Server,
         path: &[usize],
@@ -123,7 +261,9 @@ impl CommandDispatcher {
             match &node.node_type {
                 NodeType::ExecuteLeaf { executor } => {
                     return if raw_args.is_empty() {
-                        executor.execute(src, server, &parsed_args).await?;
+                        executor
+                            .execute_with_dispatcher(src, server, self, &parsed_args)
+                            .await?;
                         Ok(true)
                     } else {
                         Ok(false)
@@ -135,7 +275,7 @@ impl CommandDispatcher {
                     }
                 }
                 NodeType::Argument { consumer, name, .. } => {
-                    match consumer.consume(src, server, raw_args).await {
+                    match consumer.try_consume(src, server, raw_args).await? {
                         Some(consumed) => {
                             parsed_args.insert(name, consumed);
                         }
@@ -218,7 +358,7 @@ impl CommandDispatcher {
         &'a self,
         src: &CommandSender<'a>,
         cmd: &str,
-        key: &str
+        key: &str,
     ) -> Result<(), CommandError> {
         let Some(required_level) = self.permissions.get(key) else {
             return Ok(());
@@ -292,18 +432,68 @@ impl CommandDispatcher {
         };
 
         let tree = self.get_tree(key)?;
+        let mut claim = None;
+        let result = self
+            .dispatch_paths(
+                src,
+                server,
+                cmd,
+                key,
+                tree,
+                &raw_args,
+                plugin_name,
+                &mut claim,
+            )
+            .await;
+        // The command did not run, so it does not count towards the cooldown
+        if result.is_err() {
+            if let (Some(claim), Some(player)) = (claim, src.as_player()) {
+                claim.release(&player).await;
+            }
+        }
+        result
+    }
 
+    /// Runs the first path of the tree fitting the arguments. The cooldown is claimed once the
+    /// permission checks passed
+    #[expect(clippy::too_many_arguments)]
+    async fn dispatch_paths<'a>(
+        &'a self,
+        src: &mut CommandSender<'a>,
+        server: &'a Server,
+        cmd: &'a str,
+        key: &str,
+        tree: &'a CommandTree,
+        raw_args: &RawArgs<'a>,
+        plugin_name: &str,
+        claim: &mut Option<CooldownClaim>,
+    ) -> Result<(), CommandError> {
         // Check permissions based on command type
         match plugin_name {
             "minecraft" | "pumpkin" => {
                 self.check_core_command_permissions(src, cmd, key).await?;
+                *claim = self.claim_command_cooldown(src, tree).await?;
             }
             plugin_name => {
                 // Plugin commands check permissions per path
                 for path in tree.iter_paths() {
-                    self.check_plugin_command_permissions(src, cmd, plugin_name, tree, &path).await?;
-                    
-                    if Self::try_is_fitting_path(src, server, &path, tree, &mut raw_args.clone(), plugin_name).await? {
+                    self.check_plugin_command_permissions(src, cmd, plugin_name, tree, &path)
+                        .await?;
+                    if claim.is_none() {
+                        *claim = self.claim_command_cooldown(src, tree).await?;
+                    }
+
+                    if self
+                        .try_is_fitting_path(
+                            src,
+                            server,
+                            &path,
+                            tree,
+                            &mut raw_args.clone(),
+                            plugin_name,
+                        )
+                        .await?
+                    {
                         return Ok(());
                     }
                 }
@@ -313,7 +503,17 @@ impl CommandDispatcher {
         // If it's a core command or we haven't returned yet, try paths
         if plugin_name == "minecraft" || plugin_name == "pumpkin" {
             for path in tree.iter_paths() {
-                if Self::try_is_fitting_path(src, server, &path, tree, &mut raw_args.clone(), plugin_name).await? {
+                if self
+                    .try_is_fitting_path(
+                        src,
+                        server,
+                        &path,
+                        tree,
+                        &mut raw_args.clone(),
+                        plugin_name,
+                    )
+                    .await?
+                {
                     return Ok(());
                 }
             }
@@ -325,21 +525,29 @@ impl CommandDispatcher {
     }
 
     pub(crate) fn get_tree(&self, key: &str) -> Result<&CommandTree, CommandError> {
-        let command = self
+        let mut command = self
             .commands
             .get(key)
             .ok_or(GeneralCommandIssue("Command not found".to_string()))?;
 
-        match command {
-            Command::Tree(tree) => Ok(tree),
-            Command::Alias(target) => {
-                let Some(Command::Tree(tree)) = self.commands.get(target) else {
-                    log::error!("Error while parsing command alias \"{key}\": pointing to \"{target}\" which is not a valid tree");
-                    return Err(GeneralCommandIssue(
-                        "Internal Error (See logs for details)".into(),
-                    ));
-                };
-                Ok(tree)
+        // Aliases may point to other aliases, each name is only followed once in case of a cycle
+        let mut followed = HashSet::new();
+        loop {
+            match command {
+                Command::Tree(tree) => return Ok(tree),
+                Command::Alias(target) => {
+                    let next = followed
+                        .insert(target)
+                        .then(|| self.commands.get(target))
+                        .flatten();
+                    let Some(next) = next else {
+                        log::error!("Error while parsing command alias \"{key}\": pointing to \"{target}\" which is not a valid tree");
+                        return Err(GeneralCommandIssue(
+                            "Internal Error (See logs for details)".into(),
+                        ));
+                    };
+                    command = next;
+                }
             }
         }
     }
@@ -395,14 +603,263 @@ impl CommandDispatcher {
     }
 }
 
+/// How much longer a player has to wait before using a command again, if they ran it at `last_run`
+pub(crate) fn remaining_cooldown(
+    last_run: Option<SystemTime>,
+    cooldown: Duration,
+    now: SystemTime,
+) -> Option<Duration> {
+    // A clock set back counts as no time having passed
+    let elapsed = now.duration_since(last_run?).unwrap_or_default();
+    cooldown
+        .checked_sub(elapsed)
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// A cooldown started by [`claim_cooldown`], it can be given back if the command failed
+pub(crate) struct CooldownClaim {
+    key: String,
+    started: SystemTime,
+    previous: Option<SystemTime>,
+}
+
+/// Starts the cooldown `key` of the player unless it is still running, then the remaining time is
+/// returned. Checking and starting happen under one lock, so two commands running at the same time
+/// can't both pass the check
+pub(crate) async fn claim_cooldown(
+    player: &Player,
+    key: &str,
+    cooldown: Duration,
+) -> Result<CooldownClaim, Duration> {
+    let now = SystemTime::now();
+    let mut cooldowns = player.command_cooldowns.lock().await;
+    let previous = cooldowns.get(key).copied();
+    if let Some(remaining) = remaining_cooldown(previous, cooldown, now) {
+        return Err(remaining);
+    }
+    cooldowns.insert(key.to_string(), now);
+    Ok(CooldownClaim {
+        key: key.to_string(),
+        started: now,
+        previous,
+    })
+}
+
+impl CooldownClaim {
+    /// Restores the cooldown from before the claim, unless it was claimed again in the meantime
+    pub(crate) async fn release(self, player: &Player) {
+        let mut cooldowns = player.command_cooldowns.lock().await;
+        if cooldowns.get(&self.key) != Some(&self.started) {
+            return;
+        }
+        match self.previous {
+            Some(previous) => cooldowns.insert(self.key, previous),
+            None => cooldowns.remove(&self.key),
+        };
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::command::{default_dispatcher, tree::CommandTree};
+    use std::time::{Duration, SystemTime};
+
+    use crate::command::{default_dispatcher, tree::CommandTree, CommandSender};
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::test_player;
+    use crate::server::Server;
     use pumpkin_util::permission::PermissionLvl;
+
+    use super::{
+        claim_cooldown, remaining_cooldown,
+        CommandError::{GeneralCommandIssue, PermissionDenied},
+    };
+
+    #[test]
+    fn cooldown_gate() {
+        let cooldown = Duration::from_secs(10);
+        let last_run = SystemTime::now();
+        // never ran the command
+        assert_eq!(remaining_cooldown(None, cooldown, last_run), None);
+        let now = last_run + Duration::from_secs(4);
+        assert_eq!(
+            remaining_cooldown(Some(last_run), cooldown, now),
+            Some(Duration::from_secs(6))
+        );
+        // the cooldown is over
+        let now = last_run + Duration::from_secs(10);
+        assert_eq!(remaining_cooldown(Some(last_run), cooldown, now), None);
+    }
+
+    #[tokio::test]
+    async fn cooldown_claims() {
+        let folder = TestFolder::new("cooldown-claims");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let cooldown = Duration::from_secs(10);
+
+        let claim = claim_cooldown(&player, "kit tools", cooldown)
+            .await
+            .unwrap();
+        // A second claim while the first one runs fails
+        assert!(claim_cooldown(&player, "kit tools", cooldown)
+            .await
+            .is_err());
+        assert!(claim_cooldown(&player, "kit food", cooldown).await.is_ok());
+
+        claim.release(&player).await;
+        assert!(!player
+            .command_cooldowns
+            .lock()
+            .await
+            .contains_key("kit tools"));
+        assert!(claim_cooldown(&player, "kit tools", cooldown).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn cooldowns_only_start_when_the_command_runs() {
+        let folder = TestFolder::new("command-cooldowns");
+        let server = Server::with_world_folder(folder.join("server"));
+        let player = test_player(folder.join("world"), uuid::Uuid::new_v4()).await;
+        let mut dispatcher = default_dispatcher();
+        for name in ["op", "me", "list"] {
+            dispatcher.set_cooldown(name, Duration::from_secs(5));
+        }
+        let mut sender = CommandSender::Player(player.clone());
+
+        // Not allowed to use the command
+        assert!(matches!(
+            dispatcher.dispatch(&mut sender, &server, "op Alex").await,
+            Err(PermissionDenied)
+        ));
+        // No path fits the arguments
+        assert!(dispatcher
+            .dispatch(&mut sender, &server, "me")
+            .await
+            .is_err());
+        assert!(player.command_cooldowns.lock().await.is_empty());
+
+        assert!(dispatcher
+            .dispatch(&mut sender, &server, "list")
+            .await
+            .is_ok());
+        assert!(dispatcher
+            .dispatch(&mut sender, &server, "list")
+            .await
+            .is_err());
+        assert!(player.command_cooldowns.lock().await.contains_key("list"));
+    }
+
+    #[test]
+    fn cooldowns_are_shared_by_aliases() {
+        let mut dispatcher = default_dispatcher();
+        dispatcher.register(
+            CommandTree::new(["home", "h"], "test_desc"),
+            PermissionLvl::Zero,
+        );
+        dispatcher.set_cooldown("h", Duration::from_secs(5));
+        assert_eq!(
+            dispatcher.cooldowns.get("home"),
+            Some(&Duration::from_secs(5))
+        );
+        dispatcher.set_cooldown("home", Duration::ZERO);
+        assert!(dispatcher.cooldowns.is_empty());
+    }
+
+    #[test]
+    fn unregister_removes_all_names() {
+        let mut dispatcher = default_dispatcher();
+        let tree = CommandTree::new(["home", "h"], "test_desc");
+        dispatcher.register(tree, PermissionLvl::Zero);
+        dispatcher.set_cooldown("home", Duration::from_secs(5));
+        assert!(dispatcher.get_tree("home").is_ok());
+
+        assert!(dispatcher.unregister("h"));
+        assert!(dispatcher.get_tree("home").is_err());
+        assert!(dispatcher.get_tree("h").is_err());
+        assert_eq!(dispatcher.get_permission_lvl("h"), None);
+        assert!(dispatcher.cooldowns.is_empty());
+        assert!(!dispatcher.unregister("home"));
+    }
+
+    #[test]
+    fn unregister_plugin_commands() {
+        let mut dispatcher = default_dispatcher();
+        let tree = CommandTree::new(["home", "h"], "test_desc");
+        dispatcher.register_with_plugin(tree, PermissionLvl::Zero, "homes");
+        let tree = CommandTree::new(["sethome"], "test_desc");
+        dispatcher.register_with_plugin(tree, PermissionLvl::Zero, "homes");
+        let tree = CommandTree::new(["warp"], "test_desc");
+        dispatcher.register_with_plugin(tree, PermissionLvl::Zero, "warps");
+
+        assert_eq!(dispatcher.unregister_all_for_plugin("homes"), 2);
+        assert!(dispatcher.get_tree("home").is_err());
+        assert!(dispatcher.get_tree("sethome").is_err());
+        assert!(dispatcher.get_tree("warp").is_ok());
+        assert!(dispatcher.get_tree("say").is_ok());
+    }
+
+    #[test]
+    fn alias_runs_target_tree() {
+        let mut dispatcher = default_dispatcher();
+        let tree = CommandTree::new(["home"], "test_desc");
+        dispatcher.register_with_plugin(tree, PermissionLvl::Two, "homes");
+        dispatcher.register_alias("h", "home").unwrap();
+        // Aliases of aliases run the same tree
+        dispatcher.register_alias("hh", "h").unwrap();
+
+        let tree = dispatcher.get_tree("home").unwrap();
+        assert!(std::ptr::eq(tree, dispatcher.get_tree("h").unwrap()));
+        assert!(std::ptr::eq(tree, dispatcher.get_tree("hh").unwrap()));
+        assert_eq!(
+            dispatcher.get_permission_lvl("hh"),
+            Some(PermissionLvl::Two)
+        );
+        assert_eq!(
+            dispatcher.plugin_names.get("h").map(String::as_str),
+            Some("homes")
+        );
+
+        assert!(dispatcher.register_alias("h", "hh").is_err());
+        assert!(dispatcher.register_alias("home", "say").is_err());
+        assert!(dispatcher.register_alias("w", "warp").is_err());
+
+        // Unregistering the command also removes the aliases
+        assert!(dispatcher.unregister("home"));
+        assert!(dispatcher.get_tree("hh").is_err());
+    }
+
+    #[test]
+    fn dangling_alias_is_internal_error() {
+        let mut dispatcher = default_dispatcher();
+        let tree = CommandTree::new(["home"], "test_desc");
+        dispatcher.register(tree, PermissionLvl::Zero);
+        dispatcher.register_alias("h", "home").unwrap();
+        dispatcher.commands.remove("home");
+
+        let Err(GeneralCommandIssue(msg)) = dispatcher.get_tree("h") else {
+            panic!("dangling alias resolved");
+        };
+        assert_eq!(msg, "Internal Error (See logs for details)");
+    }
+
     #[test]
     fn test_dynamic_command() {
         let mut dispatcher = default_dispatcher();
         let tree = CommandTree::new(["test"], "test_desc");
         dispatcher.register(tree, PermissionLvl::Zero);
     }
+
+    #[test]
+    fn names_share_one_tree() {
+        let mut dispatcher = default_dispatcher();
+        let tree = CommandTree::new(["test", "t"], "test_desc");
+        dispatcher.register(tree, PermissionLvl::Zero);
+
+        let tree = dispatcher.get_tree("test").unwrap();
+        let alias = dispatcher.get_tree("t").unwrap();
+        assert!(std::ptr::eq(tree, alias));
+        assert_eq!(
+            dispatcher.get_permission_lvl("t"),
+            Some(PermissionLvl::Zero)
+        );
+    }
 }
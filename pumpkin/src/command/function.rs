@@ -0,0 +1,642 @@
+//! Functions are lists of commands stored in `.mcfunction` files, like in datapacks.
+//! A function with the id `namespace:path` is loaded from `functions/<namespace>/<path>.mcfunction`.
+//! All functions are loaded into [`FUNCTIONS`] when the server starts and again on `/reload`.
+//! The functions listed in the `load_functions` config run on startup and after every `/reload`,
+//! the ones in the `tick_functions` config every tick
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt, fs,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        LazyLock,
+    },
+};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::{math::vector3::Vector3, permission::PermissionLvl};
+
+use super::{dispatcher::CommandDispatcher, CommandError, CommandSender, CommandSource};
+use crate::{server::Server, world::game_rules::GameRules};
+
+pub static FUNCTIONS: LazyLock<tokio::sync::RwLock<FunctionRegistry>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(FunctionRegistry::load().0));
+
+/// How often the functions were reloaded, see [`FunctionHooks`]
+static RELOADS: AtomicU64 = AtomicU64::new(0);
+
+const FUNCTIONS_FOLDER: &str = "functions/";
+
+const FUNCTION_EXTENSION: &str = "mcfunction";
+
+/// How many ticks have to pass before the error of a failing function is logged again,
+/// so a tick function that fails every tick does not flood the log
+const ERROR_LOG_INTERVAL: u64 = 200;
+
+/// How deeply functions may call each other. Every call nests the commands of the function,
+/// so without a limit a function calling itself would overflow the stack
+const MAX_FUNCTION_DEPTH: usize = 64;
+
+/// The namespace of functions whose id has none
+const DEFAULT_NAMESPACE: &str = "minecraft";
+
+/// The id of a function, e.g. `mymap:start/reset`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionId {
+    pub namespace: String,
+    pub path: String,
+}
+
+impl FunctionId {
+    /// Parses an id like `namespace:path`, the namespace defaults to `minecraft`.
+    /// Returns `None` if the id contains characters that are not allowed in resource locations
+    pub fn parse(id: &str) -> Option<Self> {
+        let (namespace, path) = id.split_once(':').unwrap_or((DEFAULT_NAMESPACE, id));
+        let valid_namespace = !namespace.is_empty()
+            && namespace
+                .chars()
+                .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.'));
+        // Every segment has to be a name, so ids can not leave the functions folder
+        let valid_path = path.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.'))
+        });
+        (valid_namespace && valid_path).then(|| Self {
+            namespace: namespace.to_string(),
+            path: path.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for FunctionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.path)
+    }
+}
+
+/// The commands of a function, in the order they run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub commands: Vec<String>,
+}
+
+impl Function {
+    /// Empty lines and comments starting with `#` are skipped. Commands may start with a `/`.
+    /// Fails on macro lines starting with `$`, which are not supported
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut commands = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('$') {
+                return Err(format!("Line {}: macros are not supported", number + 1));
+            }
+            commands.push(line.strip_prefix('/').unwrap_or(line).to_string());
+        }
+        Ok(Self { commands })
+    }
+
+    /// Runs the commands one after another as `sender` with `dispatcher` and returns how many ran.
+    /// Stops at the first command that fails and returns why. Functions called by the commands share
+    /// the limits of [`FunctionChain`], so functions calling themselves can not run forever
+    pub async fn run(
+        &self,
+        dispatcher: &CommandDispatcher,
+        sender: &CommandSender<'_>,
+        server: &Server,
+    ) -> Result<usize, String> {
+        let run = self.run_with(move |command| {
+            let mut sender = function_sender(sender);
+            async move { dispatcher.dispatch(&mut sender, server, command).await }
+        });
+        // Functions run by the commands of this one join its chain
+        if CHAIN.try_with(|_| ()).is_ok() {
+            run.await
+        } else {
+            // Like in vanilla, the limit is a game rule
+            let max_commands = match sender.world() {
+                Some(world) => world.game_rules.lock().await.max_command_chain_length,
+                None => GameRules::default().max_command_chain_length,
+            };
+            let chain = FunctionChain::new(usize::try_from(max_commands).unwrap_or(0));
+            CHAIN.scope(RefCell::new(chain), run).await
+        }
+    }
+
+    /// Runs every command with `dispatch`, counting them towards the current [`FunctionChain`]
+    async fn run_with<'a, F, Fut>(&'a self, mut dispatch: F) -> Result<usize, String>
+    where
+        F: FnMut(&'a str) -> Fut,
+        Fut: Future<Output = Result<(), CommandError>>,
+    {
+        CHAIN.with(|chain| chain.borrow_mut().enter())?;
+        let mut result = Ok(self.commands.len());
+        for (ran, command) in self.commands.iter().enumerate() {
+            if let Err(err) = CHAIN.with(|chain| chain.borrow_mut().count_command()) {
+                result = Err(err);
+                break;
+            }
+            if let Err(err) = dispatch(command).await {
+                // The limit error is passed up as is instead of being wrapped by every function of the chain
+                if let Some(err) = CHAIN.with(|chain| chain.borrow().exceeded.clone()) {
+                    result = Err(err);
+                    break;
+                }
+                let msg = match err.into_string_or_pumpkin_error(command) {
+                    Ok(msg) => msg,
+                    Err(err) => {
+                        err.log();
+                        "Unknown internal error occurred while running command".to_string()
+                    }
+                };
+                result = Err(format!(
+                    "stopped after {ran} commands, /{command} failed: {msg}"
+                ));
+                break;
+            }
+        }
+        CHAIN.with(|chain| chain.borrow_mut().leave());
+        result
+    }
+}
+
+tokio::task_local! {
+    /// The chain of the function that is currently running, shared with the functions it calls
+    static CHAIN: RefCell<FunctionChain>;
+}
+
+/// Limits how deeply functions may call each other and how many commands one function and all the
+/// functions it calls may run together, see the `maxCommandChainLength` game rule
+struct FunctionChain {
+    depth: usize,
+    commands: usize,
+    max_commands: usize,
+    /// Why the chain was stopped, once a limit was hit
+    exceeded: Option<String>,
+}
+
+impl FunctionChain {
+    const fn new(max_commands: usize) -> Self {
+        Self {
+            depth: 0,
+            commands: 0,
+            max_commands,
+            exceeded: None,
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        if self.depth >= MAX_FUNCTION_DEPTH {
+            return Err(self.exceed(format!(
+                "stopped: functions called each other more than {MAX_FUNCTION_DEPTH} times deep"
+            )));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn count_command(&mut self) -> Result<(), String> {
+        let max = self.max_commands;
+        if self.commands >= max {
+            return Err(self.exceed(format!("stopped: ran more than {max} commands")));
+        }
+        self.commands += 1;
+        Ok(())
+    }
+
+    fn exceed(&mut self, reason: String) -> String {
+        self.exceeded.get_or_insert(reason).clone()
+    }
+}
+
+/// The sender the commands of a function run as, which is the sender that ran the function
+fn function_sender<'a>(sender: &CommandSender<'a>) -> CommandSender<'a> {
+    match sender {
+        CommandSender::Rcon(output) => CommandSender::Rcon(*output),
+        CommandSender::Console => CommandSender::Console,
+        CommandSender::Player(player) => CommandSender::Player(player.clone()),
+        CommandSender::Command(source) => CommandSender::Command(source.clone()),
+    }
+}
+
+/// A function that could not be loaded, it is skipped until the next reload
+#[derive(Debug)]
+pub struct FunctionLoadError {
+    /// The file of the function
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl fmt::Display for FunctionLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.reason)
+    }
+}
+
+/// All loaded functions by their id
+#[derive(Default)]
+pub struct FunctionRegistry {
+    functions: HashMap<FunctionId, Function>,
+}
+
+impl FunctionRegistry {
+    /// Loads every function in the functions folder, see [`Self::load_from`]
+    pub fn load() -> (Self, Vec<FunctionLoadError>) {
+        Self::load_from(Path::new(FUNCTIONS_FOLDER))
+    }
+
+    /// Reads the functions from disk again, replacing the loaded ones
+    pub async fn reload() -> (usize, Vec<FunctionLoadError>) {
+        Self::reload_from(Path::new(FUNCTIONS_FOLDER)).await
+    }
+
+    async fn reload_from(folder: &Path) -> (usize, Vec<FunctionLoadError>) {
+        let (registry, errors) = Self::load_from(folder);
+        let loaded = registry.len();
+        *FUNCTIONS.write().await = registry;
+        // The load functions run again on the next tick
+        RELOADS.fetch_add(1, Ordering::Relaxed);
+        (loaded, errors)
+    }
+
+    /// Loads every function in `folder`. Functions that fail to load are skipped and returned
+    /// as errors, so one broken file does not prevent the others from loading
+    fn load_from(folder: &Path) -> (Self, Vec<FunctionLoadError>) {
+        let mut registry = Self::default();
+        let mut errors = Vec::new();
+        let Ok(namespaces) = fs::read_dir(folder) else {
+            // No functions folder means no functions
+            return (registry, errors);
+        };
+        for namespace in namespaces.flatten() {
+            let path = namespace.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let namespace = namespace.file_name().to_string_lossy().into_owned();
+            registry.load_dir(&namespace, &path, &path, &mut errors);
+        }
+        for error in &errors {
+            log::warn!("Skipping function {error}");
+        }
+        (registry, errors)
+    }
+
+    fn load_dir(
+        &mut self,
+        namespace: &str,
+        root: &Path,
+        dir: &Path,
+        errors: &mut Vec<FunctionLoadError>,
+    ) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                errors.push(FunctionLoadError {
+                    path: dir.to_path_buf(),
+                    reason: err.to_string(),
+                });
+                return;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            // A linked folder may contain itself, it would be loaded over and over again
+            if entry.file_type().is_ok_and(|kind| kind.is_symlink()) && path.is_dir() {
+                continue;
+            }
+            if path.is_dir() {
+                self.load_dir(namespace, root, &path, errors);
+                continue;
+            }
+            if path.extension().is_none_or(|ext| ext != FUNCTION_EXTENSION) {
+                continue;
+            }
+            let relative = path.strip_prefix(root).unwrap_or(&path).with_extension("");
+            let id = relative
+                .to_str()
+                .map(|relative| relative.replace(std::path::MAIN_SEPARATOR, "/"))
+                .and_then(|relative| FunctionId::parse(&format!("{namespace}:{relative}")));
+            let Some(id) = id else {
+                errors.push(FunctionLoadError {
+                    path,
+                    reason: "Invalid function id".to_string(),
+                });
+                continue;
+            };
+            let function = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|content| Function::parse(&content));
+            match function {
+                Ok(function) => {
+                    self.functions.insert(id, function);
+                }
+                Err(reason) => errors.push(FunctionLoadError { path, reason }),
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &FunctionId) -> Option<&Function> {
+        self.functions.get(id)
+    }
+
+    /// The ids of all loaded functions, sorted
+    #[must_use]
+    pub fn ids(&self) -> Vec<String> {
+        let mut ids: Vec<_> = self.functions.keys().map(ToString::to_string).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+/// Runs the load functions on the first tick and after every reload, and the tick functions on every tick
+#[derive(Default)]
+pub struct FunctionHooks {
+    /// The reload the load functions last ran after, `None` until they ran for the first time
+    loaded: Option<u64>,
+    ticks: u64,
+    /// The tick the last error of each function was logged in
+    logged_errors: HashMap<String, u64>,
+}
+
+impl FunctionHooks {
+    pub async fn tick(&mut self, server: &Server) {
+        let config = &ADVANCED_CONFIG.commands;
+        let due = self.due_functions(
+            &config.load_functions,
+            &config.tick_functions,
+            RELOADS.load(Ordering::Relaxed),
+        );
+        self.ticks += 1;
+        if due.is_empty() {
+            return;
+        }
+
+        // Functions run like command blocks at the world spawn
+        let world = server.worlds.read().await.first().cloned();
+        let position = world.as_ref().map(|world| {
+            let info = &world.level.level_info;
+            Vector3::new(
+                f64::from(info.spawn_x) + 0.5,
+                f64::from(info.spawn_y),
+                f64::from(info.spawn_z) + 0.5,
+            )
+        });
+        let sender = CommandSender::Command(CommandSource {
+            name: "Server".to_string(),
+            world,
+            position,
+            rotation: None,
+            permission_lvl: PermissionLvl::Two,
+            silent: true,
+        });
+
+        for name in due {
+            let Some(id) = FunctionId::parse(name) else {
+                self.log_error(name, || format!("Invalid function id {name}"));
+                continue;
+            };
+            // Cloned so reloading does not have to wait for the function to finish
+            let Some(function) = FUNCTIONS.read().await.get(&id).cloned() else {
+                // The default `minecraft:load` and `minecraft:tick` functions are optional
+                continue;
+            };
+            let dispatcher = server.command_dispatcher.read().await;
+            if let Err(err) = function.run(&dispatcher, &sender, server).await {
+                self.log_error(name, || format!("Function {id} {err}"));
+            }
+        }
+    }
+
+    /// The functions that run this tick, the load functions only run on the first one and after `reloads` changed
+    fn due_functions<'a>(
+        &mut self,
+        load: &'a [String],
+        tick: &'a [String],
+        reloads: u64,
+    ) -> Vec<&'a str> {
+        let mut due = Vec::new();
+        if self.loaded != Some(reloads) {
+            self.loaded = Some(reloads);
+            due.extend(load.iter().map(String::as_str));
+        }
+        due.extend(tick.iter().map(String::as_str));
+        due
+    }
+
+    /// Logs the error of a function, unless one was logged recently
+    fn log_error(&mut self, name: &str, error: impl FnOnce() -> String) {
+        if self.should_log_error(name) {
+            log::warn!("{}", error());
+        }
+    }
+
+    fn should_log_error(&mut self, name: &str) -> bool {
+        let ticks = self.ticks;
+        if let Some(&logged) = self.logged_errors.get(name) {
+            if ticks < logged + ERROR_LOG_INTERVAL {
+                return false;
+            }
+        }
+        self.logged_errors.insert(name.to_string(), ticks);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, env, fs, future::Future, pin::Pin, sync::atomic::Ordering};
+
+    use super::{
+        Function, FunctionChain, FunctionHooks, FunctionId, FunctionRegistry, CHAIN,
+        ERROR_LOG_INTERVAL, FUNCTIONS, MAX_FUNCTION_DEPTH, RELOADS,
+    };
+    use crate::command::CommandError;
+    use crate::data::test_folder::TestFolder;
+
+    /// Runs the function as if every command called it again
+    fn run_recursive<'a>(
+        function: &'a Function,
+    ) -> Pin<Box<dyn Future<Output = Result<usize, String>> + 'a>> {
+        Box::pin(function.run_with(move |_| async move {
+            run_recursive(function)
+                .await
+                .map(|_| ())
+                .map_err(CommandError::GeneralCommandIssue)
+        }))
+    }
+
+    #[test]
+    fn comments_and_empty_lines_are_skipped() {
+        let function = Function::parse("# setup\nsay hi\n\n  /time set day  \n#say no\n").unwrap();
+        assert_eq!(function.commands, ["say hi", "time set day"]);
+        assert!(Function::parse("say hi\n$say $(name)").is_err());
+    }
+
+    #[test]
+    fn parse_id() {
+        let id = FunctionId::parse("mymap:start/reset").unwrap();
+        assert_eq!(id.namespace, "mymap");
+        assert_eq!(id.path, "start/reset");
+        assert_eq!(id.to_string(), "mymap:start/reset");
+        assert_eq!(FunctionId::parse("reset").unwrap().namespace, "minecraft");
+
+        assert_eq!(FunctionId::parse("mymap:../secret"), None);
+        assert_eq!(FunctionId::parse("mymap:start//reset"), None);
+        assert_eq!(FunctionId::parse("MyMap:reset"), None);
+        assert_eq!(FunctionId::parse(":reset"), None);
+    }
+
+    #[test]
+    fn load_from_folder() {
+        let folder = TestFolder::new("functions");
+        fs::create_dir_all(folder.join("mymap/start")).unwrap();
+        fs::write(
+            folder.join("mymap/start/reset.mcfunction"),
+            "time set day\nweather clear\n",
+        )
+        .unwrap();
+
+        let (registry, errors) = FunctionRegistry::load_from(&folder);
+        assert!(errors.is_empty());
+        let id = FunctionId::parse("mymap:start/reset").unwrap();
+        let function = registry.get(&id).unwrap();
+        assert_eq!(function.commands, ["time set day", "weather clear"]);
+        assert!(registry
+            .get(&FunctionId::parse("mymap:missing").unwrap())
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn reload_picks_up_new_functions() {
+        let folder = TestFolder::new("reload");
+        fs::create_dir_all(folder.join("mymap")).unwrap();
+        fs::write(folder.join("mymap/start.mcfunction"), "time set day\n").unwrap();
+        fs::write(folder.join("mymap/broken.mcfunction"), "$say $(name)\n").unwrap();
+
+        let reloads = RELOADS.load(Ordering::Relaxed);
+        let (loaded, errors) = FunctionRegistry::reload_from(&folder).await;
+        let added = FunctionId::parse("mymap:extra/added").unwrap();
+        assert_eq!(loaded, 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(FUNCTIONS.read().await.ids(), ["mymap:start"]);
+        assert!(FUNCTIONS.read().await.get(&added).is_none());
+
+        fs::create_dir_all(folder.join("mymap/extra")).unwrap();
+        fs::write(folder.join("mymap/extra/added.mcfunction"), "say added\n").unwrap();
+        let (loaded, _) = FunctionRegistry::reload_from(&folder).await;
+        assert_eq!(loaded, 2);
+        assert_eq!(
+            FUNCTIONS.read().await.get(&added).unwrap().commands,
+            ["say added"]
+        );
+        // The load functions run again after every reload
+        assert_eq!(RELOADS.load(Ordering::Relaxed), reloads + 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn linked_folders_are_not_followed() {
+        let folder = TestFolder::new("linked");
+        fs::create_dir_all(folder.join("mymap")).unwrap();
+        fs::write(folder.join("mymap/start.mcfunction"), "time set day\n").unwrap();
+        std::os::unix::fs::symlink(folder.join("mymap"), folder.join("mymap/again")).unwrap();
+
+        let (registry, errors) = FunctionRegistry::load_from(&folder);
+        assert!(errors.is_empty());
+        assert_eq!(registry.ids(), ["mymap:start"]);
+    }
+
+    #[test]
+    fn load_functions_run_once_per_reload() {
+        let load = ["mymap:load".to_string()];
+        let tick = ["mymap:tick".to_string()];
+        let mut hooks = FunctionHooks::default();
+        assert_eq!(
+            hooks.due_functions(&load, &tick, 0),
+            ["mymap:load", "mymap:tick"]
+        );
+        for _ in 0..3 {
+            assert_eq!(hooks.due_functions(&load, &tick, 0), ["mymap:tick"]);
+        }
+        // A reload runs them once more
+        assert_eq!(
+            hooks.due_functions(&load, &tick, 1),
+            ["mymap:load", "mymap:tick"]
+        );
+        assert_eq!(hooks.due_functions(&load, &tick, 1), ["mymap:tick"]);
+    }
+
+    #[test]
+    fn errors_are_rate_limited() {
+        let mut hooks = FunctionHooks::default();
+        assert!(hooks.should_log_error("mymap:tick"));
+        hooks.ticks += 1;
+        assert!(!hooks.should_log_error("mymap:tick"));
+        // Other functions have their own limit
+        assert!(hooks.should_log_error("mymap:other"));
+        hooks.ticks += ERROR_LOG_INTERVAL;
+        assert!(hooks.should_log_error("mymap:tick"));
+    }
+
+    #[tokio::test]
+    async fn self_recursion_is_limited() {
+        let function = Function::parse("function mymap:loop").unwrap();
+        let result = CHAIN
+            .scope(
+                RefCell::new(FunctionChain::new(usize::MAX)),
+                run_recursive(&function),
+            )
+            .await;
+        assert_eq!(
+            result.unwrap_err(),
+            format!(
+                "stopped: functions called each other more than {MAX_FUNCTION_DEPTH} times deep"
+            )
+        );
+        // The chain is left once the functions returned
+        CHAIN
+            .scope(RefCell::new(FunctionChain::new(usize::MAX)), async {
+                let _ = run_recursive(&function).await;
+                CHAIN.with(|chain| assert_eq!(chain.borrow().depth, 0));
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn chain_length_is_limited() {
+        let function = Function::parse(&"say hi\n".repeat(1500)).unwrap();
+        let result = CHAIN
+            .scope(RefCell::new(FunctionChain::new(1000)), async {
+                let result = function.run_with(|_| async { Ok(()) }).await;
+                CHAIN.with(|chain| assert_eq!(chain.borrow().commands, 1000));
+                result
+            })
+            .await;
+        assert_eq!(result.unwrap_err(), "stopped: ran more than 1000 commands");
+    }
+}
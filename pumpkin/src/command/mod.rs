@@ -1,28 +1,38 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::command::commands::seed;
 use crate::command::commands::{bossbar, transfer};
 use crate::command::dispatcher::CommandDispatcher;
 use crate::entity::player::Player;
+use crate::plugin::api::permissions::{get_permission_checker, PermissionChecker};
 use crate::server::Server;
+use crate::world::selection::{Corner, SELECTION_PERMISSION_LVL};
 use crate::world::World;
 use args::ConsumedArgs;
 use async_trait::async_trait;
 use commands::{
-    ban, banip, banlist, clear, deop, fill, gamemode, give, help, kick, kill, list, me, msg, op,
-    pardon, pardonip, playsound, plugin, plugins, pumpkin, say, setblock, stop, summon, teleport,
-    time, title, worldborder,
+    ban, banip, banlist, broadcast, clear, count, damage, debug, deop, difficulty, effect,
+    entityinfo, experience, fill, function, gamemode, gamerule, give, glow, help, kick, kill, kit,
+    language, list, me, msg, nick, op, pardon, pardonip, playsound, plugin, plugins, pumpkin,
+    reload, replace, save_off, save_on, say, seen, selection, set, setblock, shout, spectate, stop,
+    summon, teleport, time, title, undo, walls, weather, worldborder,
 };
 use dispatcher::CommandError;
+use pumpkin_config::ADVANCED_CONFIG;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::text::TextComponent;
+use uuid::Uuid;
 
 pub mod args;
 pub mod client_cmd_suggestions;
 mod commands;
 pub mod dispatcher;
+pub mod function;
+pub mod text;
 pub mod tree;
 pub mod tree_builder;
 mod tree_format;
@@ -31,6 +41,22 @@ pub enum CommandSender<'a> {
     Rcon(&'a tokio::sync::Mutex<Vec<String>>),
     Console,
     Player(Arc<Player>),
+    /// A non player source, e.g. a command block or a sender created by `/execute`
+    Command(CommandSource),
+}
+
+/// The context of a command that was not run by a player, console or rcon
+#[derive(Clone)]
+pub struct CommandSource {
+    /// Shown as the sender's name, e.g. `@` for command blocks
+    pub name: String,
+    pub world: Option<Arc<World>>,
+    pub position: Option<Vector3<f64>>,
+    /// Yaw and pitch
+    pub rotation: Option<(f32, f32)>,
+    pub permission_lvl: PermissionLvl,
+    /// Discards the feedback of commands, like for functions that run every tick
+    pub silent: bool,
 }
 
 impl fmt::Display for CommandSender<'_> {
@@ -42,17 +68,54 @@ impl fmt::Display for CommandSender<'_> {
                 CommandSender::Console => "Server",
                 CommandSender::Rcon(_) => "Rcon",
                 CommandSender::Player(p) => &p.gameprofile.name,
+                CommandSender::Command(source) => &source.name,
             }
         )
     }
 }
 
+/// Shows the feedback of a console command. Multi-line output like `/help` keeps its line breaks,
+/// when logged every line gets its own log prefix so it stays aligned
+fn print_console_feedback(text: TextComponent) {
+    let text = if ADVANCED_CONFIG.logging.color {
+        text.to_pretty_console()
+    } else {
+        text.to_plain_text()
+    };
+    if ADVANCED_CONFIG.commands.console_feedback_stdout {
+        println!("{text}");
+    } else {
+        for line in text.lines() {
+            log::info!("{line}");
+        }
+    }
+}
+
 impl CommandSender<'_> {
     pub async fn send_message(&self, text: TextComponent) {
         match self {
-            CommandSender::Console => log::info!("{}", text.to_pretty_console()),
-            CommandSender::Player(c) => c.send_system_message(&text).await,
+            CommandSender::Console => print_console_feedback(text),
+            CommandSender::Player(c) => {
+                // Players only see the messages of their commands if their world allows it
+                let feedback = c.world().game_rules.lock().await.send_command_feedback;
+                if feedback {
+                    c.send_system_message(&text).await;
+                }
+            }
             CommandSender::Rcon(s) => s.lock().await.push(text.to_pretty_console()),
+            CommandSender::Command(source) => {
+                if !source.silent {
+                    log::info!("[{}] {}", source.name, text.to_pretty_console());
+                }
+            }
+        }
+    }
+
+    /// Like [`CommandSender::send_message`], but always shown to players even if command feedback is off
+    pub async fn send_error(&self, text: TextComponent) {
+        match self {
+            CommandSender::Player(c) => c.send_system_message(&text).await,
+            _ => self.send_message(text).await,
         }
     }
 
@@ -75,6 +138,7 @@ impl CommandSender<'_> {
         match self {
             CommandSender::Console | CommandSender::Rcon(_) => PermissionLvl::Four,
             CommandSender::Player(p) => p.permission_lvl.load(),
+            CommandSender::Command(source) => source.permission_lvl,
         }
     }
 
@@ -82,6 +146,7 @@ impl CommandSender<'_> {
         match self {
             CommandSender::Console | CommandSender::Rcon(_) => true,
             CommandSender::Player(p) => p.permission_lvl.load().ge(&lvl),
+            CommandSender::Command(source) => source.permission_lvl.ge(&lvl),
         }
     }
 
@@ -90,33 +155,23 @@ impl CommandSender<'_> {
             CommandSender::Console => true, // Console always has permission
             CommandSender::Rcon(_) => true, // RCON always has permission
             CommandSender::Player(player) => {
+                // No permission checker means all permission nodes are denied by default
+                let has_node = get_permission_checker().is_some_and(|checker| {
+                    has_permission_node(checker.as_ref(), &player.gameprofile.id, permission)
+                });
+
                 // For core minecraft/pumpkin commands, require both permission level and permission node
                 if permission.starts_with("minecraft.command.") {
-                    // First check if they have the permission node via the permission plugin
-                    let has_node = if let Some(checker) = crate::plugin::api::permissions::get_permission_checker() {
-                        checker.check_permission(&player.gameprofile.id, permission)
-                    } else {
-                        false
-                    };
-
-                    // Then check if they have the required permission level
-                    let has_level = match permission.strip_prefix("minecraft.command.") {
-                        Some("op") | Some("stop") => self.has_permission_lvl(PermissionLvl::Three),
-                        Some("gamemode") | Some("tp") | Some("give") => self.has_permission_lvl(PermissionLvl::Two),
-                        Some("help") | Some("list") | Some("msg") => self.has_permission_lvl(PermissionLvl::Zero),
-                        _ => self.has_permission_lvl(PermissionLvl::Two), // Default to level 2 for unknown commands
-                    };
-
-                    // Need both the node and the level
-                    return has_node && has_level;
-                }
-                
-                // For plugin commands, use permission checker
-                if let Some(checker) = crate::plugin::api::permissions::get_permission_checker() {
-                    checker.check_permission(&player.gameprofile.id, permission)
-                } else {
-                    false // No permission checker means all permissions are denied by default
+                    return has_node
+                        && self.has_permission_lvl(required_permission_lvl(permission));
                 }
+
+                has_node
+            }
+            // Command sources have no permission nodes, so only core commands can be used depending on their level
+            CommandSender::Command(_) => {
+                permission.starts_with("minecraft.command.")
+                    && self.has_permission_lvl(required_permission_lvl(permission))
             }
         }
     }
@@ -125,6 +180,20 @@ impl CommandSender<'_> {
         match self {
             CommandSender::Console | CommandSender::Rcon(..) => None,
             CommandSender::Player(p) => Some(p.living_entity.entity.pos.load()),
+            CommandSender::Command(source) => source.position,
+        }
+    }
+
+    /// Returns the yaw and pitch of the sender
+    #[must_use]
+    pub fn rotation(&self) -> Option<(f32, f32)> {
+        match self {
+            CommandSender::Console | CommandSender::Rcon(..) => None,
+            CommandSender::Player(p) => {
+                let entity = &p.living_entity.entity;
+                Some((entity.yaw.load(), entity.pitch.load()))
+            }
+            CommandSender::Command(source) => source.rotation,
         }
     }
 
@@ -133,10 +202,21 @@ impl CommandSender<'_> {
             // TODO: maybe return first world when console
             CommandSender::Console | CommandSender::Rcon(..) => None,
             CommandSender::Player(p) => Some(&p.living_entity.entity.world),
+            CommandSender::Command(source) => source.world.as_deref(),
         }
     }
 }
 
+/// The permission level needed to use a core minecraft/pumpkin command
+fn required_permission_lvl(permission: &str) -> PermissionLvl {
+    match permission.strip_prefix("minecraft.command.") {
+        Some("debug" | "op" | "save-off" | "save-on" | "stop") => PermissionLvl::Three,
+        Some("help" | "kit" | "language" | "list" | "msg") => PermissionLvl::Zero,
+        // Default to level 2 for unknown commands
+        _ => PermissionLvl::Two,
+    }
+}
+
 #[must_use]
 pub fn default_dispatcher() -> CommandDispatcher {
     let mut dispatcher = CommandDispatcher::new();
@@ -145,10 +225,12 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(pumpkin::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(bossbar::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(say::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(broadcast::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(gamemode::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(stop::init_command_tree(), PermissionLvl::Four);
     dispatcher.register(help::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(kill::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(damage::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(kick::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(plugin::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(plugins::init_command_tree(), PermissionLvl::Three);
@@ -156,28 +238,111 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(teleport::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(time::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(give::init_command_tree(), PermissionLvl::Two);
-    dispatcher.register(list::init_command_tree(), PermissionLvl::Zero);      
-    dispatcher.register(clear::init_command_tree(), PermissionLvl::Two);      
-    dispatcher.register(setblock::init_command_tree(), PermissionLvl::Two);   
-    dispatcher.register(seed::init_command_tree(), PermissionLvl::Two);       
-    dispatcher.register(transfer::init_command_tree(), PermissionLvl::Zero);  
-    dispatcher.register(fill::init_command_tree(), PermissionLvl::Two);       
-    dispatcher.register(op::init_command_tree(), PermissionLvl::Three);       
-    dispatcher.register(deop::init_command_tree(), PermissionLvl::Three);     
-    dispatcher.register(me::init_command_tree(), PermissionLvl::Zero);        
-    dispatcher.register(playsound::init_command_tree(), PermissionLvl::Two);  
-    dispatcher.register(title::init_command_tree(), PermissionLvl::Two);      
-    dispatcher.register(summon::init_command_tree(), PermissionLvl::Two);     
-    dispatcher.register(msg::init_command_tree(), PermissionLvl::Zero);       
-    dispatcher.register(ban::init_command_tree(), PermissionLvl::Three);      
-    dispatcher.register(banip::init_command_tree(), PermissionLvl::Three);    
-    dispatcher.register(banlist::init_command_tree(), PermissionLvl::Three);  
-    dispatcher.register(pardon::init_command_tree(), PermissionLvl::Three);   
+    dispatcher.register(list::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(clear::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(setblock::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(seed::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(transfer::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(fill::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(op::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(deop::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(me::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(playsound::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(title::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(summon::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(msg::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(ban::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(banip::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(banlist::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(pardon::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(pardonip::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(nick::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(glow::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(debug::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(entityinfo::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(kit::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(weather::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(difficulty::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(gamerule::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(undo::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(effect::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(experience::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(shout::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(seen::init_command_tree(), PermissionLvl::Zero);
+    for corner in [Corner::First, Corner::Second] {
+        dispatcher.register(
+            selection::init_command_tree(corner),
+            SELECTION_PERMISSION_LVL,
+        );
+    }
+    dispatcher.register(set::init_command_tree(), SELECTION_PERMISSION_LVL);
+    dispatcher.register(count::init_command_tree(), SELECTION_PERMISSION_LVL);
+    dispatcher.register(replace::init_command_tree(), SELECTION_PERMISSION_LVL);
+    dispatcher.register(walls::init_command_tree(), SELECTION_PERMISSION_LVL);
+    dispatcher.register(spectate::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(function::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(reload::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(language::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(save_off::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(save_on::init_command_tree(), PermissionLvl::Four);
+
+    for (name, seconds) in &ADVANCED_CONFIG.commands.cooldowns {
+        dispatcher.set_cooldown(name, Duration::from_secs(*seconds));
+    }
 
     dispatcher
 }
 
+/// Whether the checker grants `permission` or a wildcard covering it, so `minecraft.command.*`,
+/// `minecraft.*` and `*` all grant `minecraft.command.gamemode`. Players with a `group.<name>` node
+/// also have the nodes of that group from the config
+pub(crate) fn has_permission_node(
+    checker: &dyn PermissionChecker,
+    uuid: &Uuid,
+    permission: &str,
+) -> bool {
+    resolve_permission_node(
+        checker,
+        uuid,
+        permission,
+        &ADVANCED_CONFIG.commands.permission_groups,
+    )
+}
+
+/// Like [`has_permission_node`], with the groups `group.<name>` nodes are resolved from.
+/// Groups do not include other groups
+fn resolve_permission_node(
+    checker: &dyn PermissionChecker,
+    uuid: &Uuid,
+    permission: &str,
+    groups: &HashMap<String, Vec<String>>,
+) -> bool {
+    if covers(permission, |node| checker.check_permission(uuid, node)) {
+        return true;
+    }
+    groups.iter().any(|(name, nodes)| {
+        checker.check_permission(uuid, &format!("group.{name}"))
+            && covers(permission, |node| {
+                nodes.iter().any(|granted| granted == node)
+            })
+    })
+}
+
+/// Whether `permission` itself or a wildcard covering it is granted
+fn covers(permission: &str, granted: impl Fn(&str) -> bool) -> bool {
+    if granted(permission) {
+        return true;
+    }
+    let mut node = permission;
+    while let Some((parent, _)) = node.rsplit_once('.') {
+        if granted(&format!("{parent}.*")) {
+            return true;
+        }
+        node = parent;
+    }
+    granted("*")
+}
+
 #[async_trait]
 pub trait CommandExecutor: Sync {
     async fn execute<'a>(
@@ -186,4 +351,77 @@ pub trait CommandExecutor: Sync {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError>;
+
+    /// Like [`Self::execute`], but with the dispatcher that is running the command.
+    /// Commands that run other commands, like `/function`, use it instead of locking the dispatcher again
+    async fn execute_with_dispatcher<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _dispatcher: &CommandDispatcher,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        self.execute(sender, server, args).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use uuid::Uuid;
+
+    use super::{has_permission_node, resolve_permission_node};
+    use crate::plugin::api::permissions::PermissionChecker;
+
+    /// Grants the same nodes to everyone
+    struct Granted(&'static [&'static str]);
+
+    impl PermissionChecker for Granted {
+        fn check_permission(&self, _uuid: &Uuid, permission: &str) -> bool {
+            self.0.contains(&permission)
+        }
+    }
+
+    #[test]
+    fn exact_node() {
+        let uuid = Uuid::new_v4();
+        let checker = Granted(&["minecraft.command.gamemode"]);
+        let has = |node| has_permission_node(&checker, &uuid, node);
+        assert!(has("minecraft.command.gamemode"));
+        assert!(!has("minecraft.command.give"));
+    }
+
+    #[test]
+    fn wildcard_nodes() {
+        let uuid = Uuid::new_v4();
+        let checker = Granted(&["minecraft.command.*", "myplugin.*"]);
+        let has = |node| has_permission_node(&checker, &uuid, node);
+        assert!(has("minecraft.command.gamemode"));
+        assert!(has("myplugin.home.set"));
+        assert!(!has("minecraft.admin"));
+        assert!(!has("otherplugin.home"));
+        // A wildcard only covers nodes below it
+        assert!(!has("myplugin"));
+        assert!(has_permission_node(&Granted(&["*"]), &uuid, "myplugin"));
+    }
+
+    #[test]
+    fn group_nodes() {
+        let uuid = Uuid::new_v4();
+        let groups = HashMap::from([(
+            "builder".to_string(),
+            vec![
+                "pumpkin.build.*".to_string(),
+                "minecraft.command.fill".to_string(),
+            ],
+        )]);
+        let has = |checker: &Granted, node| resolve_permission_node(checker, &uuid, node, &groups);
+        let builder = Granted(&["group.builder"]);
+        assert!(has(&builder, "pumpkin.build.bypass_limit"));
+        assert!(has(&builder, "minecraft.command.fill"));
+        assert!(!has(&builder, "minecraft.command.give"));
+        // Without the group node its nodes are not granted
+        assert!(!has(&Granted(&["group.admin"]), "minecraft.command.fill"));
+    }
 }
@@ -0,0 +1,71 @@
+use std::borrow::Cow;
+
+use pumpkin_util::text::{click::ClickEvent, hover::HoverEvent, TextComponent};
+
+use crate::{entity::player::Player, world::entity_query::WorldEntity};
+
+/// Shortcuts for the clickable text used in command feedback
+pub trait CommandTextExt {
+    /// The player's username, showing the player when hovered and suggesting `/tell` when clicked
+    fn entity_mention(player: &Player) -> TextComponent;
+
+    /// Like [`CommandTextExt::entity_mention`], but showing `name` instead of the username, e.g. the display name
+    fn entity_mention_as(player: &Player, name: TextComponent) -> TextComponent;
+
+    /// Like [`CommandTextExt::entity_mention`], but also for mobs, which are shown by their type
+    fn world_entity_mention(entity: &WorldEntity) -> TextComponent;
+
+    /// A `[label]` button that runs `command` when clicked and shows the command when hovered
+    fn run_command_button<L, C>(label: L, command: C) -> TextComponent
+    where
+        L: Into<Cow<'static, str>>,
+        C: Into<Cow<'static, str>>;
+}
+
+impl CommandTextExt for TextComponent {
+    fn entity_mention(player: &Player) -> Self {
+        Self::entity_mention_as(player, Self::text(player.gameprofile.name.clone()))
+    }
+
+    fn entity_mention_as(player: &Player, name: TextComponent) -> Self {
+        let entity = &player.living_entity.entity;
+        name.hover_event(HoverEvent::show_entity(
+            entity.entity_uuid.to_string(),
+            Some(format!("{:?}", entity.entity_type).to_lowercase()),
+            Some(Self::text(player.gameprofile.name.clone())),
+        ))
+        .click_event(ClickEvent::SuggestCommand(
+            format!("/tell {} ", player.gameprofile.name).into(),
+        ))
+    }
+
+    fn world_entity_mention(entity: &WorldEntity) -> Self {
+        match entity {
+            WorldEntity::Player(player) => Self::entity_mention(player),
+            WorldEntity::Mob(mob) => {
+                let entity = &mob.living_entity.entity;
+                let name = entity.entity_type.to_name();
+                Self::translate(format!("entity.minecraft.{name}"), [].into()).hover_event(
+                    HoverEvent::show_entity(
+                        entity.entity_uuid.to_string(),
+                        Some(format!("minecraft:{name}")),
+                        None,
+                    ),
+                )
+            }
+        }
+    }
+
+    fn run_command_button<L, C>(label: L, command: C) -> Self
+    where
+        L: Into<Cow<'static, str>>,
+        C: Into<Cow<'static, str>>,
+    {
+        let command = command.into();
+        Self::text("[")
+            .add_child(Self::text(label))
+            .add_child(Self::text("]"))
+            .hover_event(HoverEvent::show_text(Self::text(command.clone())))
+            .click_event(ClickEvent::RunCommand(command))
+    }
+}
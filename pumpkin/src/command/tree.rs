@@ -81,7 +81,7 @@ impl CommandTree {
         match &node.node_type {
             NodeType::Literal { string } => Some(string.to_lowercase()),
             NodeType::Argument { name, .. } => Some(name.to_lowercase()),
-            _ => None
+            _ => None,
         }
     }
 
@@ -89,19 +89,19 @@ impl CommandTree {
     pub fn get_permission(&self, node_index: usize, plugin_name: &str) -> Option<String> {
         let mut current_index = node_index;
         let mut permission_parts = vec![plugin_name.to_string()];
-        
+
         // collect permissions from the current node up to the root
         while let Some(_) = self.nodes.get(current_index) {
             if let Some(perm) = self.get_node_permission_name(current_index) {
                 permission_parts.push(perm);
             }
-            
+
             current_index = self.find_parent(current_index)?;
         }
-        
+
         // reverse to get root-to-leaf order (except plugin_name which stays first)
         permission_parts[1..].reverse();
-        
+
         if permission_parts.len() <= 1 {
             None
         } else {
@@ -122,7 +122,7 @@ impl CommandTree {
     /// Returns the required permission for this command path, if any
     pub fn get_required_permission(&self, path: &[usize], plugin_name: &str) -> Option<String> {
         let last_node = &self.nodes[*path.last().unwrap()];
-        
+
         if !last_node.requires_permission {
             return None;
         }
@@ -164,4 +164,4 @@ impl Iterator for TraverseAllPathsIter<'_> {
             }
         }
     }
-}
\ No newline at end of file
+}
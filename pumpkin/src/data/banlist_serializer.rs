@@ -1,6 +1,6 @@
-use std::net::IpAddr;
+use std::{net::IpAddr, time::Duration};
 
-use chrono::{DateTime, FixedOffset, Local};
+use chrono::{DateTime, Datelike, FixedOffset, Local, TimeDelta};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -66,6 +66,16 @@ impl BannedIpEntry {
     }
 }
 
+/// When a ban lasting `duration` from now expires, `None` if that is too far in the future to be saved
+#[must_use]
+pub fn expires_after(duration: Duration) -> Option<DateTime<FixedOffset>> {
+    let expires = Local::now()
+        .fixed_offset()
+        .checked_add_signed(TimeDelta::from_std(duration).ok()?)?;
+    // The date format only has room for four digit years
+    (expires.year() <= 9999).then_some(expires)
+}
+
 mod format {
     const FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
 
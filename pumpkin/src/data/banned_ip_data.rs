@@ -1,6 +1,6 @@
 use std::{net::IpAddr, path::Path, sync::LazyLock};
 
-use chrono::Local;
+use chrono::{DateTime, FixedOffset, Local};
 use serde::{Deserialize, Serialize};
 
 use super::{banlist_serializer::BannedIpEntry, LoadJSONConfiguration, SaveJSONConfiguration};
@@ -21,16 +21,20 @@ impl BannedIpList {
         self.banned_ips.iter().find(|entry| entry.ip == *ip)
     }
 
-    fn remove_invalid_entries(&mut self) {
-        let original_len = self.banned_ips.len();
-
-        self.banned_ips
-            .retain(|entry| entry.expires.is_none_or(|expires| expires >= Local::now()));
-
-        if original_len != self.banned_ips.len() {
+    /// Removes the bans that expired and saves the list if there were any
+    pub fn remove_invalid_entries(&mut self) {
+        if self.remove_expired(Local::now().fixed_offset()) {
             self.save();
         }
     }
+
+    /// Removes the bans that expired before `now`, returns whether there were any
+    fn remove_expired(&mut self, now: DateTime<FixedOffset>) -> bool {
+        let original_len = self.banned_ips.len();
+        self.banned_ips
+            .retain(|entry| entry.expires.is_none_or(|expires| expires >= now));
+        original_len != self.banned_ips.len()
+    }
 }
 
 impl LoadJSONConfiguration for BannedIpList {
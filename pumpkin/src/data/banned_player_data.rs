@@ -1,6 +1,6 @@
 use std::{path::Path, sync::LazyLock};
 
-use chrono::Local;
+use chrono::{DateTime, FixedOffset, Local};
 use serde::{Deserialize, Serialize};
 
 use crate::net::GameProfile;
@@ -25,16 +25,20 @@ impl BannedPlayerList {
             .find(|entry| entry.name == profile.name && entry.uuid == profile.id)
     }
 
-    fn remove_invalid_entries(&mut self) {
-        let original_len = self.banned_players.len();
-
-        self.banned_players
-            .retain(|entry| entry.expires.is_none_or(|expires| expires >= Local::now()));
-
-        if original_len != self.banned_players.len() {
+    /// Removes the bans that expired and saves the list if there were any
+    pub fn remove_invalid_entries(&mut self) {
+        if self.remove_expired(Local::now().fixed_offset()) {
             self.save();
         }
     }
+
+    /// Removes the bans that expired before `now`, returns whether there were any
+    fn remove_expired(&mut self, now: DateTime<FixedOffset>) -> bool {
+        let original_len = self.banned_players.len();
+        self.banned_players
+            .retain(|entry| entry.expires.is_none_or(|expires| expires >= now));
+        original_len != self.banned_players.len()
+    }
 }
 
 impl LoadJSONConfiguration for BannedPlayerList {
@@ -47,3 +51,44 @@ impl LoadJSONConfiguration for BannedPlayerList {
 }
 
 impl SaveJSONConfiguration for BannedPlayerList {}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, FixedOffset, Local, TimeDelta};
+    use uuid::Uuid;
+
+    use super::BannedPlayerList;
+    use crate::data::banlist_serializer::BannedPlayerEntry;
+
+    fn entry(name: &str, expires: Option<DateTime<FixedOffset>>) -> BannedPlayerEntry {
+        BannedPlayerEntry {
+            uuid: Uuid::new_v4(),
+            name: name.to_string(),
+            created: Local::now().fixed_offset(),
+            source: "Server".to_string(),
+            expires,
+            reason: "Banned by an operator.".to_string(),
+        }
+    }
+
+    #[test]
+    fn expired_bans_are_removed() {
+        let now = Local::now().fixed_offset();
+        let mut list = BannedPlayerList {
+            banned_players: vec![
+                entry("expired", Some(now - TimeDelta::hours(1))),
+                entry("temporary", Some(now + TimeDelta::hours(1))),
+                entry("permanent", None),
+            ],
+        };
+        assert!(list.remove_expired(now));
+        let names: Vec<_> = list
+            .banned_players
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert_eq!(names, ["temporary", "permanent"]);
+        // Nothing changed, so the list does not have to be saved again
+        assert!(!list.remove_expired(now));
+    }
+}
@@ -0,0 +1,77 @@
+use std::{path::Path, sync::LazyLock};
+
+use pumpkin_world::item::item_registry;
+use serde::{Deserialize, Serialize};
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static KIT_LIST: LazyLock<tokio::sync::RwLock<KitList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(KitList::load()));
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct KitList {
+    pub kits: Vec<Kit>,
+}
+
+/// A named set of items players can claim with `/kit`
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Kit {
+    pub name: String,
+    pub items: Vec<KitItem>,
+    /// Permission node needed to claim the kit, everyone can claim it when there is none
+    #[serde(default)]
+    pub permission: Option<String>,
+    /// Seconds a player has to wait before claiming the kit again. When the kit was claimed is saved
+    /// with the player data like command cooldowns, so rejoining does not reset it
+    #[serde(default)]
+    pub cooldown: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct KitItem {
+    /// The item id, e.g. `minecraft:stone`
+    pub item: String,
+    pub count: u32,
+}
+
+impl KitList {
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Kit> {
+        self.kits.iter().find(|kit| kit.name == name)
+    }
+
+    /// Adds the kit, replacing any kit with the same name
+    pub fn insert(&mut self, kit: Kit) {
+        self.kits.retain(|existing| existing.name != kit.name);
+        self.kits.push(kit);
+    }
+
+    /// Returns false if there is no kit with this name
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.kits.len();
+        self.kits.retain(|kit| kit.name != name);
+        len != self.kits.len()
+    }
+}
+
+impl LoadJSONConfiguration for KitList {
+    fn get_path() -> &'static Path {
+        Path::new("kits.json")
+    }
+    fn validate(&self) {
+        for kit in &self.kits {
+            for item in &kit.items {
+                if item_registry::get_item(&item.item).is_none() {
+                    log::warn!(
+                        "Kit {} contains the unknown item {}, it will not be given",
+                        kit.name,
+                        item.item
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl SaveJSONConfiguration for KitList {}
@@ -0,0 +1,101 @@
+//! Languages the server can translate messages into, see `/language`.
+//! Besides the built-in `en_us`, languages are loaded from `lang/<locale>.json` files in the vanilla format
+
+use std::{collections::HashMap, fs, path::Path, sync::LazyLock};
+
+use pumpkin_util::translation::EN_US;
+
+const LANGUAGE_FOLDER: &str = "lang/";
+
+/// The language every server has, the client also falls back to it
+pub const DEFAULT_LANGUAGE: &str = "en_us";
+
+pub static LANGUAGES: LazyLock<Languages> = LazyLock::new(Languages::load);
+
+/// The translations of every available language by its locale
+pub struct Languages {
+    languages: HashMap<String, HashMap<String, String>>,
+}
+
+impl Languages {
+    fn load() -> Self {
+        Self::load_from(Path::new(LANGUAGE_FOLDER))
+    }
+
+    /// Loads every language file in `folder`, files that fail to parse are skipped
+    fn load_from(folder: &Path) -> Self {
+        let mut languages = HashMap::new();
+        let Ok(entries) = fs::read_dir(folder) else {
+            return Self { languages };
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let locale = locale.to_lowercase();
+            let translations = fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|content| serde_json::from_str(&content).map_err(|err| err.to_string()));
+            match translations {
+                Ok(translations) => {
+                    languages.insert(locale, translations);
+                }
+                Err(err) => log::warn!("Skipping language file {}: {err}", path.display()),
+            }
+        }
+        Self { languages }
+    }
+
+    /// The translations of a language, `None` if it is not available
+    #[must_use]
+    pub fn get(&self, locale: &str) -> Option<&HashMap<String, String>> {
+        if locale == DEFAULT_LANGUAGE {
+            return Some(&*EN_US);
+        }
+        self.languages.get(locale)
+    }
+
+    /// The locales of all available languages, sorted
+    #[must_use]
+    pub fn locales(&self) -> Vec<&str> {
+        let mut locales: Vec<_> = self.languages.keys().map(String::as_str).collect();
+        if !self.languages.contains_key(DEFAULT_LANGUAGE) {
+            locales.push(DEFAULT_LANGUAGE);
+        }
+        locales.sort_unstable();
+        locales
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::Languages;
+    use crate::data::test_folder::TestFolder;
+
+    #[test]
+    fn load_language_files() {
+        let folder = TestFolder::new("lang");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(
+            folder.join("de_DE.json"),
+            r#"{"multiplayer.player.joined": "%s hat das Spiel betreten"}"#,
+        )
+        .unwrap();
+        fs::write(folder.join("broken.json"), "{").unwrap();
+
+        let languages = Languages::load_from(&folder);
+        assert_eq!(languages.locales(), ["de_de", "en_us"]);
+        assert_eq!(
+            languages.get("de_de").unwrap()["multiplayer.player.joined"],
+            "%s hat das Spiel betreten"
+        );
+        assert!(languages.get("en_us").is_some());
+        assert!(languages.get("broken").is_none());
+    }
+}
@@ -0,0 +1,149 @@
+use std::{path::Path, sync::LazyLock};
+
+use chrono::{DateTime, FixedOffset, Local};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static LAST_SEEN_LIST: LazyLock<tokio::sync::RwLock<LastSeenList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(LastSeenList::load()));
+
+/// When players joined and left the server, also used to find offline players by their name
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct LastSeenList {
+    pub players: Vec<LastSeenEntry>,
+    /// Whether players joined or left since the list was last saved
+    #[serde(skip)]
+    changed: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct LastSeenEntry {
+    pub uuid: Uuid,
+    /// The name the player had when they last joined
+    pub name: String,
+    pub last_login: DateTime<FixedOffset>,
+    /// `None` while the player is online
+    pub last_logout: Option<DateTime<FixedOffset>>,
+}
+
+impl LastSeenList {
+    /// Finds a player by their name, ignoring case like vanilla does
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<&LastSeenEntry> {
+        self.players
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Records that the player joined, updating their name if it changed
+    pub fn login(&mut self, profile: &GameProfile) {
+        let now = Local::now().fixed_offset();
+        if let Some(entry) = self
+            .players
+            .iter_mut()
+            .find(|entry| entry.uuid == profile.id)
+        {
+            entry.name.clone_from(&profile.name);
+            entry.last_login = now;
+            entry.last_logout = None;
+        } else {
+            self.players.push(LastSeenEntry {
+                uuid: profile.id,
+                name: profile.name.clone(),
+                last_login: now,
+                last_logout: None,
+            });
+        }
+        self.changed = true;
+    }
+
+    /// Records that the player left
+    pub fn logout(&mut self, uuid: &Uuid) {
+        if let Some(entry) = self.players.iter_mut().find(|entry| entry.uuid == *uuid) {
+            entry.last_logout = Some(Local::now().fixed_offset());
+            self.changed = true;
+        }
+    }
+
+    /// A copy of the list to save if players joined or left since the last call
+    fn take_changes(&mut self) -> Option<Self> {
+        if !std::mem::take(&mut self.changed) {
+            return None;
+        }
+        Some(Self {
+            players: self.players.clone(),
+            changed: false,
+        })
+    }
+}
+
+/// Writes the list to disk if players joined or left since it was last saved.
+/// Runs on autosaves and when the server stops, the file is written off the async runtime
+pub async fn save_last_seen() {
+    let Some(changes) = LAST_SEEN_LIST.write().await.take_changes() else {
+        return;
+    };
+    if let Err(err) = tokio::task::spawn_blocking(move || changes.save()).await {
+        log::error!("Failed to save the last seen players: {err}");
+    }
+}
+
+impl LoadJSONConfiguration for LastSeenList {
+    fn get_path() -> &'static Path {
+        Path::new("last-seen.json")
+    }
+    fn validate(&self) {}
+}
+
+impl SaveJSONConfiguration for LastSeenList {}
+
+#[cfg(test)]
+mod test {
+    use crate::net::GameProfile;
+
+    use super::LastSeenList;
+
+    fn profile(name: &str) -> GameProfile {
+        GameProfile {
+            id: uuid::Uuid::from_u128(1),
+            name: name.to_string(),
+            properties: vec![],
+            profile_actions: None,
+        }
+    }
+
+    #[test]
+    fn login_clears_last_logout() {
+        let mut list = LastSeenList::default();
+        list.login(&profile("Alex"));
+        list.logout(&uuid::Uuid::from_u128(1));
+        assert!(list.players[0].last_logout.is_some());
+
+        // Renamed players are found by their new name
+        list.login(&profile("Steve"));
+        let entry = list.get_by_name("steve").unwrap();
+        assert!(entry.last_logout.is_none());
+        assert!(list.get_by_name("Alex").is_none());
+        assert_eq!(list.players.len(), 1);
+    }
+
+    #[test]
+    fn only_changes_are_saved() {
+        let mut list = LastSeenList::default();
+        assert!(list.take_changes().is_none());
+        list.login(&profile("Alex"));
+        assert_eq!(list.take_changes().unwrap().players.len(), 1);
+        assert!(list.take_changes().is_none());
+
+        // Unknown players leaving change nothing
+        list.logout(&uuid::Uuid::from_u128(2));
+        assert!(list.take_changes().is_none());
+        list.logout(&uuid::Uuid::from_u128(1));
+        assert!(list.take_changes().is_some());
+    }
+}
@@ -10,6 +10,16 @@ pub mod banlist_serializer;
 pub mod banned_ip_data;
 pub mod banned_player_data;
 
+pub mod kit_data;
+pub mod language_data;
+pub mod last_seen_data;
+
+pub mod player_data;
+pub mod starter_inventory_data;
+
+#[cfg(test)]
+pub(crate) mod test_folder;
+
 pub trait LoadJSONConfiguration {
     #[must_use]
     fn load() -> Self
@@ -0,0 +1,251 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use pumpkin_nbt::{compound::NbtCompound, Nbt};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::entity::{player::Player, NBTStorage};
+
+/// Stores the NBT data of players as gzip compressed `<uuid>.dat` files, like vanilla does
+/// inside the `playerdata` folder of a world
+pub struct PlayerDataStorage {
+    data_path: PathBuf,
+    /// Held while the data of a player is saved, so saves of the same player run one after another
+    save_locks: std::sync::Mutex<HashMap<Uuid, Arc<Mutex<()>>>>,
+}
+
+impl PlayerDataStorage {
+    #[must_use]
+    pub fn new(data_path: PathBuf) -> Self {
+        Self {
+            data_path,
+            save_locks: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether any data was saved for the player before, which means they joined before
+    #[must_use]
+    pub fn has_data(&self, uuid: &Uuid) -> bool {
+        self.data_path.join(format!("{uuid}.dat")).exists()
+    }
+
+    /// Writes the current state of the given player to disk
+    pub async fn save(&self, player: &Player) -> io::Result<()> {
+        let uuid = player.gameprofile.id;
+        let lock = self.save_lock(uuid);
+        let guard = lock.lock().await;
+        // The state is taken under the lock, so an older state never overwrites a newer one
+        let mut nbt = NbtCompound::new();
+        player.write_nbt(&mut nbt).await;
+        let data_path = self.data_path.clone();
+        let result = tokio::task::spawn_blocking(move || write(&data_path, &uuid, nbt)).await;
+        drop(guard);
+
+        let mut save_locks = self
+            .save_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Nobody else is saving the player, only the map and this save hold the lock
+        if Arc::strong_count(&lock) == 2 {
+            save_locks.remove(&uuid);
+        }
+        result?
+    }
+
+    /// Waits until the saves that are running right now are written, e.g. of players that just left
+    pub async fn wait_for_saves(&self) {
+        let locks: Vec<_> = self
+            .save_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect();
+        for lock in locks {
+            drop(lock.lock().await);
+        }
+    }
+
+    fn save_lock(&self, uuid: Uuid) -> Arc<Mutex<()>> {
+        self.save_locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(uuid)
+            .or_default()
+            .clone()
+    }
+
+    /// Reads the data saved for the player in a previous session, `None` if they never joined.
+    /// Fails if the file can not be read or is not the data of a player
+    pub async fn load(&self, uuid: &Uuid) -> io::Result<Option<NbtCompound>> {
+        let path = self.data_path.join(format!("{uuid}.dat"));
+        tokio::task::spawn_blocking(move || read(&path)).await?
+    }
+}
+
+fn write(data_path: &Path, uuid: &Uuid, nbt: NbtCompound) -> io::Result<()> {
+    fs::create_dir_all(data_path)?;
+    // Write to a temporary file first, so a crash while saving does not corrupt existing data
+    let temp_path = data_path.join(format!("{uuid}.dat_tmp"));
+    let mut encoder = GzEncoder::new(File::create(&temp_path)?, Compression::default());
+    Nbt::new(String::new(), nbt).write_to_writer(&mut encoder)?;
+    encoder.finish()?;
+    fs::rename(temp_path, data_path.join(format!("{uuid}.dat")))
+}
+
+fn read(path: &Path) -> io::Result<Option<NbtCompound>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let mut bytes = Vec::new();
+    GzDecoder::new(file).read_to_end(&mut bytes)?;
+    if bytes.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "empty player data",
+        ));
+    }
+    let nbt = Nbt::read(&mut bytes.as_slice())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+    if !has_valid_position(&nbt.root_tag) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing or invalid position",
+        ));
+    }
+    Ok(Some(nbt.root_tag))
+}
+
+/// Player data always has a position, without it the file is most likely not player data at all
+fn has_valid_position(nbt: &NbtCompound) -> bool {
+    nbt.get_list("Pos").is_some_and(|pos| {
+        pos.len() == 3
+            && pos
+                .iter()
+                .all(|tag| tag.extract_double().is_some_and(f64::is_finite))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs,
+        sync::{atomic::Ordering, Arc},
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+    use pumpkin_util::{math::vector3::Vector3, GameMode};
+    use uuid::Uuid;
+
+    use super::{write, PlayerDataStorage};
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::{player::test_player, NBTStorage};
+
+    #[tokio::test]
+    async fn round_trip() {
+        let folder = TestFolder::new("playerdata");
+        let storage = PlayerDataStorage::new(folder.join("playerdata"));
+        let uuid = Uuid::new_v4();
+        assert!(storage.load(&uuid).await.unwrap().is_none());
+
+        let player = test_player(folder.join("world"), uuid).await;
+        player
+            .living_entity
+            .entity
+            .set_pos(Vector3::new(12.5, 64.0, -3.25));
+        player.living_entity.health.store(17.0);
+        player.food.store(14, Ordering::Relaxed);
+        player.gamemode.store(GameMode::Creative);
+        let last_run = UNIX_EPOCH + Duration::from_millis(1_700_000_000_000);
+        player.command_cooldowns.lock().await.extend([
+            ("home".to_string(), last_run),
+            ("kit starter".to_string(), last_run),
+        ]);
+        storage.save(&player).await.unwrap();
+        assert!(storage.has_data(&uuid));
+
+        let mut nbt = storage.load(&uuid).await.unwrap().unwrap();
+        let mut loaded = Arc::into_inner(test_player(folder.join("world"), uuid).await).unwrap();
+        loaded.read_nbt(&mut nbt).await;
+        assert_eq!(
+            loaded.living_entity.entity.pos.load(),
+            Vector3::new(12.5, 64.0, -3.25)
+        );
+        assert!((loaded.living_entity.health.load() - 17.0).abs() < f32::EPSILON);
+        assert_eq!(loaded.food.load(Ordering::Relaxed), 14);
+        assert_eq!(loaded.gamemode.load(), GameMode::Creative);
+        let cooldowns = loaded.command_cooldowns.get_mut();
+        assert_eq!(cooldowns.get("home"), Some(&last_run));
+        // kit claims survive rejoining too
+        assert_eq!(cooldowns.get("kit starter"), Some(&last_run));
+
+        fs::write(folder.join(format!("playerdata/{uuid}.dat")), b"not gzip").unwrap();
+        assert!(storage.load(&uuid).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn concurrent_saves() {
+        let folder = TestFolder::new("playerdata");
+        let storage = PlayerDataStorage::new(folder.join("playerdata"));
+        let uuid = Uuid::new_v4();
+        let player = test_player(folder.join("world"), uuid).await;
+
+        // Like an autosave running while the player disconnects
+        let (first, second, third) = tokio::join!(
+            storage.save(&player),
+            storage.save(&player),
+            storage.save(&player)
+        );
+        first.unwrap();
+        second.unwrap();
+        third.unwrap();
+        assert!(storage.load(&uuid).await.unwrap().is_some());
+        assert!(storage.save_locks.lock().unwrap().is_empty());
+
+        // A save that is still running is waited for
+        let save = storage.save(&player);
+        let (saved, ()) = tokio::join!(save, storage.wait_for_saves());
+        saved.unwrap();
+    }
+
+    #[tokio::test]
+    async fn partial_data_keeps_defaults() {
+        let folder = TestFolder::new("playerdata");
+        let storage = PlayerDataStorage::new(folder.join("playerdata"));
+        let uuid = Uuid::new_v4();
+
+        // Data that is not from a player is rejected
+        let mut nbt = NbtCompound::new();
+        nbt.put_int("foodLevel", 14);
+        write(&folder.join("playerdata"), &uuid, nbt).unwrap();
+        assert!(storage.load(&uuid).await.is_err());
+
+        // Missing tags keep their defaults, so the player is not spawned dead
+        let mut nbt = NbtCompound::new();
+        nbt.put(
+            "Pos",
+            NbtTag::List(vec![12.5.into(), 64.0.into(), (-3.25).into()]),
+        );
+        write(&folder.join("playerdata"), &uuid, nbt).unwrap();
+        let mut nbt = storage.load(&uuid).await.unwrap().unwrap();
+        let mut player = Arc::into_inner(test_player(folder.join("world"), uuid).await).unwrap();
+        let health = player.living_entity.health.load();
+        player.read_nbt(&mut nbt).await;
+        assert!(health > 0.0);
+        assert!((player.living_entity.health.load() - health).abs() < f32::EPSILON);
+        assert_eq!(
+            player.living_entity.entity.pos.load(),
+            Vector3::new(12.5, 64.0, -3.25)
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use std::{path::Path, sync::LazyLock};
+
+use pumpkin_world::item::item_registry;
+use serde::{Deserialize, Serialize};
+
+use super::{kit_data::KitItem, LoadJSONConfiguration};
+
+pub static STARTER_INVENTORY: LazyLock<tokio::sync::RwLock<StarterInventory>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(StarterInventory::load()));
+
+/// Items every player gets once, when joining for the first time
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct StarterInventory {
+    pub items: Vec<KitItem>,
+}
+
+impl StarterInventory {
+    /// Reads the item list from disk again
+    pub async fn reload() {
+        *STARTER_INVENTORY.write().await = Self::load();
+    }
+}
+
+impl LoadJSONConfiguration for StarterInventory {
+    fn get_path() -> &'static Path {
+        Path::new("starter-inventory.json")
+    }
+    fn validate(&self) {
+        for item in &self.items {
+            if item_registry::get_item(&item.item).is_none() {
+                log::warn!(
+                    "The starter inventory contains the unknown item {}, it will not be given",
+                    item.item
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::{
+    env, fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+/// A folder in the temp dir for tests that write files. It is removed when dropped, so also when
+/// an assert of the test fails
+pub(crate) struct TestFolder(PathBuf);
+
+impl TestFolder {
+    /// The `name` only makes the folder easier to find, every folder gets a unique path
+    pub(crate) fn new(name: &str) -> Self {
+        Self(env::temp_dir().join(format!("pumpkin-{name}-{}", uuid::Uuid::new_v4())))
+    }
+}
+
+impl Deref for TestFolder {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TestFolder {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestFolder {
+    fn drop(&mut self) {
+        // Tests that never wrote anything have no folder
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -1,7 +1,10 @@
 use pumpkin_protocol::client::play::CUpdateEntityPos;
 use pumpkin_util::math::vector3::Vector3;
 
-use crate::entity::living::LivingEntity;
+use crate::entity::{
+    effect::{movement_speed_multiplier, Effect},
+    living::LivingEntity,
+};
 
 #[derive(Default)]
 pub struct Navigator {
@@ -62,7 +65,12 @@ impl Navigator {
                 return;
             }
             // Update current progress based on the best move
-            goal.current_progress += best_move.normalize() * goal.speed;
+            let speed = goal.speed
+                * movement_speed_multiplier(
+                    entity.effect_amplifier(Effect::Speed).await,
+                    entity.effect_amplifier(Effect::Slowness).await,
+                );
+            goal.current_progress += best_move.normalize() * speed;
 
             // now lets move
             entity.set_pos(goal.current_progress);
@@ -0,0 +1,349 @@
+use std::{collections::HashMap, hash::BuildHasher};
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+/// A status effect, in the order of the `mob_effect` registry, which gives their network ids
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Effect {
+    Speed,
+    Slowness,
+    Haste,
+    MiningFatigue,
+    Strength,
+    InstantHealth,
+    InstantDamage,
+    JumpBoost,
+    Nausea,
+    Regeneration,
+    Resistance,
+    FireResistance,
+    WaterBreathing,
+    Invisibility,
+    Blindness,
+    NightVision,
+    Hunger,
+    Weakness,
+    Poison,
+    Wither,
+    HealthBoost,
+    Absorption,
+    Saturation,
+    Glowing,
+    Levitation,
+    Luck,
+    Unluck,
+    SlowFalling,
+    ConduitPower,
+    DolphinsGrace,
+    BadOmen,
+    HeroOfTheVillage,
+    Darkness,
+    TrialOmen,
+    RaidOmen,
+    WindCharged,
+    Weaving,
+    Oozing,
+    Infested,
+}
+
+impl Effect {
+    pub const ALL: [Self; 39] = [
+        Self::Speed,
+        Self::Slowness,
+        Self::Haste,
+        Self::MiningFatigue,
+        Self::Strength,
+        Self::InstantHealth,
+        Self::InstantDamage,
+        Self::JumpBoost,
+        Self::Nausea,
+        Self::Regeneration,
+        Self::Resistance,
+        Self::FireResistance,
+        Self::WaterBreathing,
+        Self::Invisibility,
+        Self::Blindness,
+        Self::NightVision,
+        Self::Hunger,
+        Self::Weakness,
+        Self::Poison,
+        Self::Wither,
+        Self::HealthBoost,
+        Self::Absorption,
+        Self::Saturation,
+        Self::Glowing,
+        Self::Levitation,
+        Self::Luck,
+        Self::Unluck,
+        Self::SlowFalling,
+        Self::ConduitPower,
+        Self::DolphinsGrace,
+        Self::BadOmen,
+        Self::HeroOfTheVillage,
+        Self::Darkness,
+        Self::TrialOmen,
+        Self::RaidOmen,
+        Self::WindCharged,
+        Self::Weaving,
+        Self::Oozing,
+        Self::Infested,
+    ];
+
+    #[must_use]
+    pub const fn id(self) -> i32 {
+        self as i32
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Speed => "speed",
+            Self::Slowness => "slowness",
+            Self::Haste => "haste",
+            Self::MiningFatigue => "mining_fatigue",
+            Self::Strength => "strength",
+            Self::InstantHealth => "instant_health",
+            Self::InstantDamage => "instant_damage",
+            Self::JumpBoost => "jump_boost",
+            Self::Nausea => "nausea",
+            Self::Regeneration => "regeneration",
+            Self::Resistance => "resistance",
+            Self::FireResistance => "fire_resistance",
+            Self::WaterBreathing => "water_breathing",
+            Self::Invisibility => "invisibility",
+            Self::Blindness => "blindness",
+            Self::NightVision => "night_vision",
+            Self::Hunger => "hunger",
+            Self::Weakness => "weakness",
+            Self::Poison => "poison",
+            Self::Wither => "wither",
+            Self::HealthBoost => "health_boost",
+            Self::Absorption => "absorption",
+            Self::Saturation => "saturation",
+            Self::Glowing => "glowing",
+            Self::Levitation => "levitation",
+            Self::Luck => "luck",
+            Self::Unluck => "unluck",
+            Self::SlowFalling => "slow_falling",
+            Self::ConduitPower => "conduit_power",
+            Self::DolphinsGrace => "dolphins_grace",
+            Self::BadOmen => "bad_omen",
+            Self::HeroOfTheVillage => "hero_of_the_village",
+            Self::Darkness => "darkness",
+            Self::TrialOmen => "trial_omen",
+            Self::RaidOmen => "raid_omen",
+            Self::WindCharged => "wind_charged",
+            Self::Weaving => "weaving",
+            Self::Oozing => "oozing",
+            Self::Infested => "infested",
+        }
+    }
+
+    /// Accepts names with and without the `minecraft:` namespace
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL.into_iter().find(|effect| effect.name() == name)
+    }
+
+    /// The key of the effect's name in the language files
+    #[must_use]
+    pub fn translation_key(self) -> String {
+        format!("effect.minecraft.{}", self.name())
+    }
+
+    /// Instant effects are applied once and never become active
+    #[must_use]
+    pub const fn is_instant(self) -> bool {
+        matches!(self, Self::InstantHealth | Self::InstantDamage)
+    }
+}
+
+/// An effect active on a living entity
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EffectInstance {
+    /// The level of the effect minus one
+    pub amplifier: u8,
+    /// Ticks left until the effect ends, -1 for effects that last forever
+    pub duration: i32,
+    /// Ambient effects come from beacons and have less visible particles
+    pub ambient: bool,
+    pub show_particles: bool,
+    pub show_icon: bool,
+}
+
+impl EffectInstance {
+    #[must_use]
+    pub const fn new(amplifier: u8, duration: i32, show_particles: bool) -> Self {
+        Self {
+            amplifier,
+            duration,
+            ambient: false,
+            show_particles,
+            show_icon: true,
+        }
+    }
+
+    /// Counts the duration down, returns false once the effect ended
+    pub fn tick(&mut self) -> bool {
+        if self.duration == -1 {
+            return true;
+        }
+        self.duration = (self.duration - 1).max(0);
+        self.duration > 0
+    }
+
+    /// The flags of the effect as sent to the client
+    #[must_use]
+    pub fn flags(&self) -> i8 {
+        i8::from(self.ambient) | i8::from(self.show_particles) << 1 | i8::from(self.show_icon) << 2
+    }
+
+    /// Stores the effect like vanilla does in the `active_effects` list of an entity
+    #[must_use]
+    pub fn to_nbt(&self, effect: Effect) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put("id", NbtTag::String(format!("minecraft:{}", effect.name())));
+        nbt.put_byte("amplifier", self.amplifier as i8);
+        nbt.put_int("duration", self.duration);
+        nbt.put_bool("ambient", self.ambient);
+        nbt.put_bool("show_particles", self.show_particles);
+        nbt.put_bool("show_icon", self.show_icon);
+        nbt
+    }
+
+    /// Reads an effect stored by [`EffectInstance::to_nbt`], `None` for unknown effects
+    #[must_use]
+    pub fn from_nbt(nbt: &NbtCompound) -> Option<(Effect, Self)> {
+        let effect = Effect::from_name(nbt.get_string("id")?)?;
+        let instance = Self {
+            amplifier: nbt.get_byte("amplifier").unwrap_or(0) as u8,
+            duration: nbt.get_int("duration")?,
+            ambient: nbt.get_bool("ambient").unwrap_or(false),
+            show_particles: nbt.get_bool("show_particles").unwrap_or(true),
+            show_icon: nbt.get_bool("show_icon").unwrap_or(true),
+        };
+        Some((effect, instance))
+    }
+
+    /// An active effect is only replaced by a stronger one, or an equally strong one that lasts longer
+    #[must_use]
+    pub fn replaced_by(&self, other: &Self) -> bool {
+        let lasts_longer = |a: i32, b: i32| b != -1 && (a == -1 || a > b);
+        other.amplifier > self.amplifier
+            || other.amplifier == self.amplifier && lasts_longer(other.duration, self.duration)
+    }
+}
+
+/// Counts down all effects, removes the ones that ended and returns them
+pub fn tick_effects<S: BuildHasher>(
+    effects: &mut HashMap<Effect, EffectInstance, S>,
+) -> Vec<Effect> {
+    let mut expired = Vec::new();
+    effects.retain(|effect, instance| {
+        let active = instance.tick();
+        if !active {
+            expired.push(*effect);
+        }
+        active
+    });
+    expired
+}
+
+/// The melee damage strength and weakness add to an attack
+#[must_use]
+pub fn attack_damage_bonus(strength: Option<u8>, weakness: Option<u8>) -> f64 {
+    let levels =
+        |amplifier: Option<u8>| amplifier.map_or(0.0, |amplifier| f64::from(amplifier) + 1.0);
+    3.0 * levels(strength) - 4.0 * levels(weakness)
+}
+
+/// What speed and slowness multiply the movement speed by
+#[must_use]
+pub fn movement_speed_multiplier(speed: Option<u8>, slowness: Option<u8>) -> f64 {
+    let levels =
+        |amplifier: Option<u8>| amplifier.map_or(0.0, |amplifier| f64::from(amplifier) + 1.0);
+    (1.0 + 0.2 * levels(speed) - 0.15 * levels(slowness)).max(0.0)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+    use super::{
+        attack_damage_bonus, movement_speed_multiplier, tick_effects, Effect, EffectInstance,
+    };
+
+    #[test]
+    fn effect_names() {
+        assert_eq!(Effect::from_name("minecraft:speed"), Some(Effect::Speed));
+        assert_eq!(Effect::from_name("strength"), Some(Effect::Strength));
+        assert_eq!(Effect::from_name("flying"), None);
+        assert_eq!(Effect::Infested.id(), 38);
+        assert_eq!(
+            Effect::Glowing.translation_key(),
+            "effect.minecraft.glowing"
+        );
+    }
+
+    #[test]
+    fn effect_ticks_to_expiry() {
+        let mut effect = EffectInstance::new(0, 3, true);
+        assert!(effect.tick());
+        assert!(effect.tick());
+        assert!(!effect.tick());
+        assert_eq!(effect.duration, 0);
+
+        let mut infinite = EffectInstance::new(0, -1, true);
+        assert!(infinite.tick());
+        assert_eq!(infinite.duration, -1);
+    }
+
+    #[test]
+    fn expired_effects_are_removed() {
+        let mut effects = HashMap::new();
+        effects.insert(Effect::Speed, EffectInstance::new(0, 2, true));
+        effects.insert(Effect::Strength, EffectInstance::new(1, -1, true));
+        assert!(tick_effects(&mut effects).is_empty());
+        assert_eq!(tick_effects(&mut effects), vec![Effect::Speed]);
+        assert!(!effects.contains_key(&Effect::Speed));
+        assert!(effects.contains_key(&Effect::Strength));
+    }
+
+    #[test]
+    fn stronger_effects_replace() {
+        let active = EffectInstance::new(1, 200, true);
+        assert!(active.replaced_by(&EffectInstance::new(2, 10, true)));
+        assert!(active.replaced_by(&EffectInstance::new(1, 400, true)));
+        assert!(active.replaced_by(&EffectInstance::new(1, -1, true)));
+        assert!(!active.replaced_by(&EffectInstance::new(0, 400, true)));
+        assert!(!active.replaced_by(&EffectInstance::new(1, 100, true)));
+    }
+
+    #[test]
+    fn effect_modifiers() {
+        assert!((attack_damage_bonus(Some(1), None) - 6.0).abs() < f64::EPSILON);
+        assert!((attack_damage_bonus(None, Some(0)) + 4.0).abs() < f64::EPSILON);
+        assert!((movement_speed_multiplier(Some(1), None) - 1.4).abs() < 1e-9);
+        assert!(movement_speed_multiplier(None, Some(9)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn effects_round_trip_through_nbt() {
+        let mut effect = EffectInstance::new(3, 1200, false);
+        effect.ambient = true;
+        let nbt = effect.to_nbt(Effect::Haste);
+        assert_eq!(nbt.get_string("id").unwrap(), "minecraft:haste");
+        assert_eq!(
+            EffectInstance::from_nbt(&nbt),
+            Some((Effect::Haste, effect))
+        );
+
+        let mut unknown = NbtCompound::new();
+        unknown.put("id", NbtTag::String("minecraft:flying".to_string()));
+        unknown.put_int("duration", 20);
+        assert_eq!(EffectInstance::from_nbt(&unknown), None);
+    }
+}
@@ -0,0 +1,120 @@
+//! Converts between experience points and levels, like vanilla
+
+/// The highest level whose total amount of points still fits into an `i32`, levels are clamped to it
+pub const MAX_LEVEL: i32 = 21863;
+
+/// How many points it takes to get from `level` to the next level
+#[must_use]
+pub const fn points_to_next_level(level: i32) -> i32 {
+    match level {
+        ..0 => 7,
+        0..15 => 2 * level + 7,
+        15..30 => 5 * level - 38,
+        // Clamped so the result cannot overflow
+        30..MAX_LEVEL => 9 * level - 158,
+        _ => 9 * MAX_LEVEL - 158,
+    }
+}
+
+/// How many points it takes to reach `level` from level 0
+#[must_use]
+pub fn points_for_level(level: i32) -> i32 {
+    let level = i64::from(level.clamp(0, MAX_LEVEL));
+    let points = match level {
+        ..17 => level * level + 6 * level,
+        17..32 => (5 * level * level - 81 * level + 720) / 2,
+        _ => (9 * level * level - 325 * level + 4440) / 2,
+    };
+    i32::try_from(points).unwrap_or(i32::MAX)
+}
+
+/// The level and the progress towards the next one for the total amount of points
+#[must_use]
+pub fn level_from_points(points: i32) -> (i32, f32) {
+    let points = points.max(0);
+    let mut level = 0;
+    while level < MAX_LEVEL && points_for_level(level + 1) <= points {
+        level += 1;
+    }
+    let progress = progress(level, points - points_for_level(level)).min(1.0);
+    (level, progress)
+}
+
+/// The total amount of points at `level` with `progress` towards the next one
+#[must_use]
+pub fn points_from_level(level: i32, progress: f32) -> i32 {
+    let level = level.clamp(0, MAX_LEVEL);
+    points_for_level(level).saturating_add(points_in_level(level, progress))
+}
+
+/// How many points into `level` the progress towards the next one is
+#[must_use]
+pub fn points_in_level(level: i32, progress: f32) -> i32 {
+    (f64::from(progress) * f64::from(points_to_next_level(level))).round() as i32
+}
+
+/// The progress towards the next level with `points` points into `level`
+#[must_use]
+pub fn progress(level: i32, points: i32) -> f32 {
+    (f64::from(points) / f64::from(points_to_next_level(level))) as f32
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        level_from_points, points_for_level, points_from_level, points_to_next_level, MAX_LEVEL,
+    };
+
+    #[test]
+    fn level_thresholds() {
+        assert_eq!(points_for_level(0), 0);
+        assert_eq!(points_for_level(1), 7);
+        assert_eq!(points_for_level(16), 352);
+        assert_eq!(points_for_level(17), 394);
+        assert_eq!(points_for_level(30), 1395);
+        assert_eq!(points_for_level(31), 1507);
+        assert_eq!(points_for_level(32), 1628);
+        assert_eq!(points_for_level(40), 2920);
+        // Each threshold is the previous one plus the points to get there
+        for level in 0..100 {
+            assert_eq!(
+                points_for_level(level + 1),
+                points_for_level(level) + points_to_next_level(level)
+            );
+        }
+    }
+
+    #[test]
+    fn points_to_levels() {
+        assert_eq!(level_from_points(0), (0, 0.0));
+        assert_eq!(level_from_points(7), (1, 0.0));
+        let (level, progress) = level_from_points(398);
+        assert_eq!(level, 17);
+        assert!((progress - 4.0 / 47.0).abs() < f32::EPSILON);
+        // Negative totals are clamped to nothing
+        assert_eq!(level_from_points(-20), (0, 0.0));
+        for points in [0, 5, 100, 394, 1500, 5000] {
+            let (level, progress) = level_from_points(points);
+            assert_eq!(points_from_level(level, progress), points);
+        }
+    }
+
+    #[test]
+    fn extremes_do_not_overflow() {
+        assert_eq!(
+            points_to_next_level(i32::MAX),
+            points_to_next_level(MAX_LEVEL)
+        );
+        assert_eq!(points_for_level(i32::MAX), points_for_level(MAX_LEVEL));
+        assert!(points_for_level(MAX_LEVEL) > points_for_level(MAX_LEVEL - 1));
+        assert_eq!(points_from_level(i32::MAX, 1.0), i32::MAX);
+        assert_eq!(points_from_level(i32::MIN, 0.0), 0);
+
+        let (level, progress) = level_from_points(i32::MAX);
+        assert_eq!(level, MAX_LEVEL);
+        assert!(progress <= 1.0);
+        // The highest total stays the same when converted back and forth
+        let total = points_from_level(level, progress);
+        assert_eq!(level_from_points(total).0, MAX_LEVEL);
+    }
+}
@@ -0,0 +1,215 @@
+//! Eating food items. Like in vanilla, players eat for a while before the food is restored
+
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_protocol::client::play::{CSetEntityMetadata, Metadata};
+use pumpkin_util::GameMode;
+use pumpkin_world::item::{
+    item_registry::{get_item_by_id, FoodComponent},
+    ItemStack,
+};
+use std::sync::atomic::Ordering;
+
+use super::player::Player;
+
+/// The food an item restores at most
+const MAX_FOOD_LEVEL: i32 = 20;
+/// How often the eating sound is played in ticks
+const EAT_SOUND_INTERVAL: u32 = 4;
+
+/// The food a player is eating, see [`Player::start_eating`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eating {
+    off_hand: bool,
+    item_id: u16,
+    ticks_left: u32,
+}
+
+impl Player {
+    /// Starts eating the food in the main hand or off hand. Returns false if the item is no food
+    /// or the player is not hungry
+    pub async fn start_eating(&self, off_hand: bool) -> bool {
+        let Some(stack) = self.stack_in_hand(off_hand).await else {
+            return false;
+        };
+        let Some(item) = get_item_by_id(stack.item_id) else {
+            return false;
+        };
+        let Some(food) = &item.components.food else {
+            return false;
+        };
+        if !can_eat(
+            self.food.load(Ordering::Relaxed),
+            food,
+            self.gamemode.load(),
+        ) {
+            return false;
+        }
+        let seconds = item
+            .components
+            .consumable
+            .as_ref()
+            .map_or(1.6, |consumable| consumable.consume_seconds);
+        self.eating.store(Some(Eating {
+            off_hand,
+            item_id: stack.item_id,
+            ticks_left: (seconds * 20.0).round() as u32,
+        }));
+        self.send_hand_active(Some(off_hand)).await;
+        true
+    }
+
+    /// Stops eating without consuming the food, like when the player lets go of the use key
+    pub async fn stop_eating(&self) {
+        if self.eating.swap(None).is_some() {
+            self.send_hand_active(None).await;
+        }
+    }
+
+    pub(crate) async fn tick_eating(&self) {
+        let Some(mut eating) = self.eating.load() else {
+            return;
+        };
+        // The player may have switched or dropped the item meanwhile
+        let stack = self.stack_in_hand(eating.off_hand).await;
+        if stack.is_none_or(|stack| stack.item_id != eating.item_id) {
+            self.stop_eating().await;
+            return;
+        }
+        eating.ticks_left = eating.ticks_left.saturating_sub(1);
+        if eating.ticks_left == 0 {
+            self.eating.store(None);
+            self.finish_eating(eating).await;
+            return;
+        }
+        self.eating.store(Some(eating));
+        if eating.ticks_left % EAT_SOUND_INTERVAL == 0 {
+            self.world()
+                .play_sound(
+                    Sound::EntityGenericEat,
+                    SoundCategory::Players,
+                    &self.living_entity.entity.pos.load(),
+                )
+                .await;
+        }
+    }
+
+    async fn finish_eating(&self, eating: Eating) {
+        self.send_hand_active(None).await;
+        let Some(food) =
+            get_item_by_id(eating.item_id).and_then(|item| item.components.food.as_ref())
+        else {
+            return;
+        };
+        let (food_level, saturation) = eat(
+            self.food.load(Ordering::Relaxed),
+            self.food_saturation.load(),
+            food,
+        );
+        self.set_health(self.living_entity.health.load(), food_level, saturation)
+            .await;
+
+        let consumed = {
+            let mut inventory = self.inventory.lock().await;
+            let slot = if eating.off_hand {
+                inventory.offhand_mut()
+            } else {
+                inventory.held_item_mut()
+            };
+            consume_one(slot, self.gamemode.load())
+        };
+        if consumed {
+            self.set_container_content(None).await;
+        }
+        self.world()
+            .play_sound(
+                Sound::EntityPlayerBurp,
+                SoundCategory::Players,
+                &self.living_entity.entity.pos.load(),
+            )
+            .await;
+    }
+
+    async fn stack_in_hand(&self, off_hand: bool) -> Option<ItemStack> {
+        let mut inventory = self.inventory.lock().await;
+        if off_hand {
+            *inventory.offhand_mut()
+        } else {
+            inventory.held_item().copied()
+        }
+    }
+
+    /// Shows other players the eating animation, `None` when no hand is used anymore
+    async fn send_hand_active(&self, off_hand: Option<bool>) {
+        let flags: i8 = match off_hand {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 1 | 2,
+        };
+        let packet =
+            CSetEntityMetadata::new(self.entity_id().into(), Metadata::new(8, 0.into(), flags));
+        self.world().broadcast_packet_all(&packet).await;
+    }
+}
+
+/// Players in creative and hungry players can eat anything, some food can be eaten anytime
+fn can_eat(food_level: i32, food: &FoodComponent, gamemode: GameMode) -> bool {
+    gamemode == GameMode::Creative || food.can_always_eat || food_level < MAX_FOOD_LEVEL
+}
+
+/// The food level and saturation after eating, the saturation can't exceed the food level
+fn eat(food_level: i32, saturation: f32, food: &FoodComponent) -> (i32, f32) {
+    let food_level = (food_level + food.nutrition).clamp(0, MAX_FOOD_LEVEL);
+    #[allow(clippy::cast_precision_loss)]
+    let saturation = (saturation + food.saturation).clamp(0.0, food_level as f32);
+    (food_level, saturation)
+}
+
+/// Takes the eaten item from the stack, players in creative keep it. Returns whether the stack changed
+fn consume_one(slot: &mut Option<ItemStack>, gamemode: GameMode) -> bool {
+    if gamemode == GameMode::Creative {
+        return false;
+    }
+    let Some(stack) = slot else {
+        return false;
+    };
+    stack.item_count -= 1;
+    if stack.item_count == 0 {
+        *slot = None;
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::GameMode;
+    use pumpkin_world::item::{item_registry::get_item, ItemStack};
+
+    use super::{can_eat, consume_one, eat};
+
+    #[test]
+    fn eating_bread_restores_food_and_uses_it_up() {
+        let bread = get_item("bread").unwrap();
+        let food = bread.components.food.as_ref().unwrap();
+        assert!(can_eat(6, food, GameMode::Survival));
+        assert!(!can_eat(20, food, GameMode::Survival));
+        assert!(can_eat(20, food, GameMode::Creative));
+
+        assert_eq!(eat(6, 0.0, food), (11, 6.0));
+        assert_eq!(eat(18, 19.0, food), (20, 20.0));
+
+        let mut slot = Some(ItemStack::new(2, bread.id));
+        assert!(consume_one(&mut slot, GameMode::Survival));
+        assert_eq!(slot.map(|stack| stack.item_count), Some(1));
+        assert!(!consume_one(&mut slot, GameMode::Creative));
+        assert_eq!(slot.map(|stack| stack.item_count), Some(1));
+        assert!(consume_one(&mut slot, GameMode::Survival));
+        assert!(slot.is_none());
+    }
+
+    #[test]
+    fn golden_apples_can_always_be_eaten() {
+        let apple = get_item("golden_apple").unwrap();
+        let food = apple.components.food.as_ref().unwrap();
+        assert!(can_eat(20, food, GameMode::Survival));
+    }
+}
@@ -0,0 +1,325 @@
+//! Hunger and natural regeneration, following the vanilla rules. Players get exhausted by moving
+//! and fighting, which first uses up their saturation and then their food level
+
+use pumpkin_util::{math::vector3::Vector3, Difficulty, GameMode};
+use std::sync::atomic::Ordering;
+
+use super::player::Player;
+
+const MAX_FOOD_LEVEL: i32 = 20;
+const MAX_HEALTH: f32 = 20.0;
+/// Every time the exhaustion reaches this, a point of saturation or food is used up
+const EXHAUSTION_PER_FOOD: f32 = 4.0;
+const MAX_EXHAUSTION: f32 = 40.0;
+/// Above this food level health slowly regenerates
+const REGENERATION_FOOD_LEVEL: i32 = 18;
+
+pub const SPRINT_EXHAUSTION_PER_BLOCK: f32 = 0.1;
+pub const JUMP_EXHAUSTION: f32 = 0.05;
+pub const SPRINT_JUMP_EXHAUSTION: f32 = 0.2;
+pub const ATTACK_EXHAUSTION: f32 = 0.1;
+
+/// The hunger of a player between two ticks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Hunger {
+    pub food: i32,
+    pub saturation: f32,
+    pub exhaustion: f32,
+    /// Ticks since the last regeneration or starvation damage
+    pub timer: u32,
+}
+
+/// What a hunger tick does to the health of the player
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum HealthChange {
+    None,
+    Heal(f32),
+    Starve,
+}
+
+impl Hunger {
+    /// Advances the hunger by one tick. `natural_regeneration` is the game rule of the same name
+    pub fn tick(
+        &mut self,
+        health: f32,
+        difficulty: Difficulty,
+        natural_regeneration: bool,
+    ) -> HealthChange {
+        if self.exhaustion > EXHAUSTION_PER_FOOD {
+            self.exhaustion -= EXHAUSTION_PER_FOOD;
+            if self.saturation > 0.0 {
+                self.saturation = (self.saturation - 1.0).max(0.0);
+            } else if difficulty != Difficulty::Peaceful {
+                self.food = (self.food - 1).max(0);
+            }
+        }
+
+        let can_heal = health > 0.0 && health < MAX_HEALTH;
+        if natural_regeneration && can_heal && self.saturation > 0.0 && self.food >= MAX_FOOD_LEVEL
+        {
+            // Well fed players heal quickly, paying for it with their saturation
+            self.timer += 1;
+            if self.timer >= 10 {
+                self.timer = 0;
+                let used = self.saturation.min(6.0);
+                self.add_exhaustion(used);
+                return HealthChange::Heal(used / 6.0);
+            }
+        } else if natural_regeneration && can_heal && self.food >= REGENERATION_FOOD_LEVEL {
+            self.timer += 1;
+            if self.timer >= 80 {
+                self.timer = 0;
+                self.add_exhaustion(6.0);
+                return HealthChange::Heal(1.0);
+            }
+        } else if self.food <= 0 {
+            self.timer += 1;
+            if self.timer >= 80 {
+                self.timer = 0;
+                // Starving only kills on hard, on normal it leaves half a heart and on easy five hearts
+                let starve = match difficulty {
+                    Difficulty::Peaceful => false,
+                    Difficulty::Easy => health > 10.0,
+                    Difficulty::Normal => health > 1.0,
+                    Difficulty::Hard => true,
+                };
+                if starve {
+                    return HealthChange::Starve;
+                }
+            }
+        } else {
+            self.timer = 0;
+        }
+        HealthChange::None
+    }
+
+    pub fn add_exhaustion(&mut self, exhaustion: f32) {
+        self.exhaustion = (self.exhaustion + exhaustion).min(MAX_EXHAUSTION);
+    }
+}
+
+impl Player {
+    fn hunger(&self) -> Hunger {
+        Hunger {
+            food: self.food.load(Ordering::Relaxed),
+            saturation: self.food_saturation.load(),
+            exhaustion: self.food_exhaustion.load(),
+            timer: self.food_tick_timer.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Exhausts the player, like when they sprint or attack. Creative and spectator players never get hungry
+    pub fn add_exhaustion(&self, exhaustion: f32) {
+        if matches!(
+            self.gamemode.load(),
+            GameMode::Creative | GameMode::Spectator
+        ) {
+            return;
+        }
+        let mut hunger = self.hunger();
+        hunger.add_exhaustion(exhaustion);
+        self.food_exhaustion.store(hunger.exhaustion);
+    }
+
+    /// Exhausts the player for sprinting and jumping between two positions they reported
+    pub(crate) fn exhaust_from_movement(
+        &self,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        on_ground: bool,
+    ) {
+        let entity = &self.living_entity.entity;
+        let exhaustion = movement_exhaustion(
+            from,
+            to,
+            entity.on_ground.load(Ordering::Relaxed),
+            on_ground,
+            entity.sprinting.load(Ordering::Relaxed),
+        );
+        if exhaustion > 0.0 {
+            self.add_exhaustion(exhaustion);
+        }
+    }
+
+    pub(crate) async fn tick_hunger(&self) {
+        let health = self.living_entity.health.load();
+        if health <= 0.0 {
+            return;
+        }
+        let difficulty = self.world().difficulty();
+        let natural_regeneration = self.world().game_rules.lock().await.natural_regeneration;
+
+        let before = self.hunger();
+        let mut hunger = before;
+        let mut change = hunger.tick(health, difficulty, natural_regeneration);
+
+        // On peaceful players don't get hungry and regenerate regardless of their food level
+        if difficulty == Difficulty::Peaceful {
+            let ticks = self.peaceful_regen_timer.fetch_add(1, Ordering::Relaxed) + 1;
+            if natural_regeneration && ticks % 20 == 0 && change == HealthChange::None {
+                change = HealthChange::Heal(1.0);
+            }
+            if ticks % 10 == 0 && hunger.food < MAX_FOOD_LEVEL {
+                hunger.food += 1;
+            }
+        }
+
+        self.food_exhaustion.store(hunger.exhaustion);
+        self.food_tick_timer.store(hunger.timer, Ordering::Relaxed);
+        match change {
+            HealthChange::Starve => {
+                self.food.store(hunger.food, Ordering::Relaxed);
+                self.food_saturation.store(hunger.saturation);
+                self.living_entity.damage(1.0, 39).await; // Starve
+                let health = self.living_entity.health.load();
+                if health > 0.0 {
+                    self.set_health(health, hunger.food, hunger.saturation)
+                        .await;
+                }
+            }
+            HealthChange::Heal(amount) if health < MAX_HEALTH => {
+                self.set_health(
+                    (health + amount).min(MAX_HEALTH),
+                    hunger.food,
+                    hunger.saturation,
+                )
+                .await;
+            }
+            _ => {
+                if hunger.food != before.food || hunger.saturation != before.saturation {
+                    self.set_health(health, hunger.food, hunger.saturation)
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+/// Sprinting exhausts per block moved horizontally, jumping exhausts once when leaving the ground upwards
+fn movement_exhaustion(
+    from: Vector3<f64>,
+    to: Vector3<f64>,
+    was_on_ground: bool,
+    on_ground: bool,
+    sprinting: bool,
+) -> f32 {
+    let mut exhaustion = 0.0;
+    if sprinting {
+        let (dx, dz) = (to.x - from.x, to.z - from.z);
+        exhaustion += SPRINT_EXHAUSTION_PER_BLOCK * dx.hypot(dz) as f32;
+    }
+    if was_on_ground && !on_ground && to.y > from.y {
+        exhaustion += if sprinting {
+            SPRINT_JUMP_EXHAUSTION
+        } else {
+            JUMP_EXHAUSTION
+        };
+    }
+    exhaustion
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::{math::vector3::Vector3, Difficulty};
+
+    use super::{movement_exhaustion, HealthChange, Hunger};
+
+    fn hunger(food: i32, saturation: f32) -> Hunger {
+        Hunger {
+            food,
+            saturation,
+            exhaustion: 0.0,
+            timer: 0,
+        }
+    }
+
+    #[test]
+    fn exhaustion_uses_saturation_then_food() {
+        let mut state = hunger(20, 1.0);
+        state.add_exhaustion(4.5);
+        state.tick(20.0, Difficulty::Normal, true);
+        assert_eq!((state.food, state.saturation), (20, 0.0));
+
+        state.add_exhaustion(4.5);
+        state.tick(20.0, Difficulty::Normal, true);
+        assert_eq!(state.food, 19);
+
+        // Food is never used up on peaceful
+        state.add_exhaustion(4.5);
+        state.tick(20.0, Difficulty::Peaceful, true);
+        assert_eq!(state.food, 19);
+    }
+
+    #[test]
+    fn sprinting_for_a_while_makes_hungry() {
+        let mut state = hunger(20, 0.0);
+        let mut position = Vector3::new(0.0, 64.0, 0.0);
+        for _ in 0..400 {
+            let next = Vector3::new(position.x + 0.28, 64.0, 0.0);
+            state.add_exhaustion(movement_exhaustion(position, next, true, true, true));
+            state.tick(20.0, Difficulty::Normal, true);
+            position = next;
+        }
+        // 112 blocks of sprinting are 11.2 exhaustion
+        assert_eq!(state.food, 18);
+    }
+
+    #[test]
+    fn fed_players_regenerate() {
+        let mut state = hunger(20, 20.0);
+        let mut health = 10.0;
+        for _ in 0..40 {
+            if let HealthChange::Heal(amount) = state.tick(health, Difficulty::Normal, true) {
+                health += amount;
+            }
+        }
+        assert!((health - 14.0).abs() < f32::EPSILON);
+        assert!(state.saturation < 20.0);
+
+        // Regeneration is slow below full food and stops when hungry
+        let mut state = hunger(18, 0.0);
+        let changes: Vec<_> = (0..80)
+            .map(|_| state.tick(10.0, Difficulty::Normal, true))
+            .collect();
+        assert_eq!(changes.last(), Some(&HealthChange::Heal(1.0)));
+        assert_eq!(
+            changes
+                .iter()
+                .filter(|change| **change != HealthChange::None)
+                .count(),
+            1
+        );
+
+        let mut state = hunger(17, 0.0);
+        assert!((0..200).all(|_| state.tick(10.0, Difficulty::Normal, true) == HealthChange::None));
+    }
+
+    #[test]
+    fn natural_regeneration_can_be_disabled() {
+        let mut state = hunger(20, 20.0);
+        assert!((0..200).all(|_| state.tick(10.0, Difficulty::Normal, false) == HealthChange::None));
+    }
+
+    #[test]
+    fn starving_depends_on_difficulty() {
+        let starves = |health: f32, difficulty: Difficulty| {
+            let mut state = hunger(0, 0.0);
+            (0..80).any(|_| state.tick(health, difficulty, true) == HealthChange::Starve)
+        };
+        assert!(starves(1.0, Difficulty::Hard));
+        assert!(starves(2.0, Difficulty::Normal));
+        assert!(!starves(1.0, Difficulty::Normal));
+        assert!(starves(11.0, Difficulty::Easy));
+        assert!(!starves(10.0, Difficulty::Easy));
+        assert!(!starves(20.0, Difficulty::Peaceful));
+    }
+
+    #[test]
+    fn jumping_exhausts_once() {
+        let ground = Vector3::new(0.0, 64.0, 0.0);
+        let air = Vector3::new(0.0, 64.4, 0.0);
+        assert!((movement_exhaustion(ground, air, true, false, false) - 0.05).abs() < f32::EPSILON);
+        assert!((movement_exhaustion(ground, air, true, false, true) - 0.2).abs() < f32::EPSILON);
+        assert!(movement_exhaustion(air, ground, false, true, false) == 0.0);
+    }
+}
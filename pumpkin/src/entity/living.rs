@@ -1,13 +1,25 @@
-use std::sync::atomic::AtomicI32;
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicI32, Arc},
+};
 
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
-use pumpkin_data::sound::Sound;
+use pumpkin_data::{entity::EntityType, sound::Sound};
 use pumpkin_nbt::tag::NbtTag;
-use pumpkin_protocol::client::play::{CDamageEvent, CEntityStatus, CSetEntityMetadata, Metadata};
+use pumpkin_protocol::client::play::{
+    CDamageEvent, CEntityStatus, CRemoveMobEffect, CSetEntityMetadata, CUpdateMobEffect, Metadata,
+};
+use pumpkin_registry::get_damage_type_by_id;
 use pumpkin_util::math::vector3::Vector3;
+use tokio::sync::Mutex;
 
-use super::{Entity, EntityId, NBTStorage};
+use super::{
+    effect::{tick_effects, Effect, EffectInstance},
+    player::Player,
+    Entity, EntityId, NBTStorage,
+};
+use crate::net::Client;
 
 /// Represents a living entity within the game world.
 ///
@@ -23,22 +35,28 @@ pub struct LivingEntity {
     pub last_damage_taken: AtomicCell<f32>,
     /// The current health level of the entity.
     pub health: AtomicCell<f32>,
+    /// Extra health that is lost before the actual health, shown as golden hearts
+    absorption: AtomicCell<f32>,
     /// The distance the entity has been falling
     pub fall_distance: AtomicCell<f32>,
+    /// The status effects on the entity, see [`LivingEntity::add_effect`]
+    pub active_effects: Mutex<HashMap<Effect, EffectInstance>>,
 }
 impl LivingEntity {
-    pub const fn new(entity: Entity) -> Self {
+    pub fn new(entity: Entity) -> Self {
         Self {
             entity,
             last_pos: AtomicCell::new(Vector3::new(0.0, 0.0, 0.0)),
             time_until_regen: AtomicI32::new(0),
             last_damage_taken: AtomicCell::new(0.0),
             health: AtomicCell::new(20.0),
+            absorption: AtomicCell::new(0.0),
             fall_distance: AtomicCell::new(0.0),
+            active_effects: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn tick(&self) {
+    pub async fn tick(&self) {
         if self
             .time_until_regen
             .load(std::sync::atomic::Ordering::Relaxed)
@@ -47,6 +65,118 @@ impl LivingEntity {
             self.time_until_regen
                 .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
         }
+
+        let expired = tick_effects(&mut *self.active_effects.lock().await);
+        for effect in expired {
+            self.send_effect_removed(effect).await;
+        }
+    }
+
+    /// Applies an effect, instant effects take place right away instead of becoming active.
+    /// Returns false if the entity already has a stronger effect of the same kind
+    pub async fn add_effect(&self, effect: Effect, instance: EffectInstance) -> bool {
+        if effect.is_instant() {
+            self.apply_instant_effect(effect, instance.amplifier).await;
+            return true;
+        }
+        let mut effects = self.active_effects.lock().await;
+        if effects
+            .get(&effect)
+            .is_some_and(|active| !active.replaced_by(&instance))
+        {
+            return false;
+        }
+        effects.insert(effect, instance);
+        drop(effects);
+        self.entity
+            .world
+            .broadcast_packet_all(&self.effect_packet(effect, &instance))
+            .await;
+        true
+    }
+
+    /// Shows the active effects to a client that just started seeing the entity
+    pub async fn send_effects(&self, client: &Client) {
+        for packet in self.effect_packets().await {
+            client.send_packet(&packet).await;
+        }
+    }
+
+    /// Shows the active effects to every client, e.g. after they were loaded
+    pub async fn broadcast_effects(&self) {
+        for packet in self.effect_packets().await {
+            self.entity.world.broadcast_packet_all(&packet).await;
+        }
+    }
+
+    async fn effect_packets(&self) -> Vec<CUpdateMobEffect> {
+        self.active_effects
+            .lock()
+            .await
+            .iter()
+            .map(|(effect, instance)| self.effect_packet(*effect, instance))
+            .collect()
+    }
+
+    fn effect_packet(&self, effect: Effect, instance: &EffectInstance) -> CUpdateMobEffect {
+        CUpdateMobEffect::new(
+            self.entity.entity_id.into(),
+            effect.id().into(),
+            i32::from(instance.amplifier).into(),
+            instance.duration.into(),
+            instance.flags(),
+        )
+    }
+
+    /// Returns false if the entity did not have the effect
+    pub async fn remove_effect(&self, effect: Effect) -> bool {
+        if self.active_effects.lock().await.remove(&effect).is_none() {
+            return false;
+        }
+        self.send_effect_removed(effect).await;
+        true
+    }
+
+    /// Removes every effect, returns how many there were
+    pub async fn clear_effects(&self) -> usize {
+        let effects: Vec<_> = self.active_effects.lock().await.drain().collect();
+        for (effect, _) in &effects {
+            self.send_effect_removed(*effect).await;
+        }
+        effects.len()
+    }
+
+    /// The amplifier of the effect if it is active
+    pub async fn effect_amplifier(&self, effect: Effect) -> Option<u8> {
+        self.active_effects
+            .lock()
+            .await
+            .get(&effect)
+            .map(|instance| instance.amplifier)
+    }
+
+    async fn send_effect_removed(&self, effect: Effect) {
+        self.entity
+            .world
+            .broadcast_packet_all(&CRemoveMobEffect::new(
+                self.entity.entity_id.into(),
+                effect.id().into(),
+            ))
+            .await;
+    }
+
+    async fn apply_instant_effect(&self, effect: Effect, amplifier: u8) {
+        // Each level doubles the amount
+        let amount = f32::from(1u16 << amplifier.min(8));
+        match effect {
+            // There is no max health attribute yet, so it is always 20
+            Effect::InstantHealth => {
+                let health = (self.health.load() + 4.0 * amount).min(20.0);
+                self.set_health(health).await;
+            }
+            Effect::InstantDamage => self.damage(6.0 * amount, 26).await, // Magic
+            _ => {}
+        }
     }
 
     pub fn set_pos(&self, position: Vector3<f64>) {
@@ -66,32 +196,95 @@ impl LivingEntity {
             .await;
     }
 
+    pub fn get_absorption(&self) -> f32 {
+        self.absorption.load()
+    }
+
+    pub async fn set_absorption(&self, absorption: f32) {
+        let absorption = absorption.max(0.0);
+        self.absorption.store(absorption);
+        // Only players sync absorption through metadata, other entities show it through the effect
+        if self.entity.entity_type == EntityType::Player {
+            self.entity
+                .world
+                .broadcast_packet_all(&CSetEntityMetadata::new(
+                    self.entity.entity_id.into(),
+                    Metadata::new(15, 3.into(), absorption),
+                ))
+                .await;
+        }
+    }
+
     pub const fn entity_id(&self) -> EntityId {
         self.entity.entity_id
     }
 
     // TODO add damage_type enum
     pub async fn damage(&self, amount: f32, damage_type: u8) {
+        self.damage_with_source(amount, damage_type, None).await;
+    }
+
+    /// Like [`Self::damage`], but attributes the damage to the entity that caused it
+    pub async fn damage_with_source(&self, amount: f32, damage_type: u8, source: Option<&Entity>) {
         self.entity
             .world
             .broadcast_packet_all(&CDamageEvent::new(
                 self.entity.entity_id.into(),
                 damage_type.into(),
-                None,
-                None,
+                source.map(|source| source.entity_id.into()),
+                source.map(|source| source.entity_id.into()),
                 None,
             ))
             .await;
 
+        let absorption = self.absorption.load();
+        let (amount, new_absorption) = absorb_damage(amount, absorption);
+        if absorption > 0.0 {
+            self.set_absorption(new_absorption).await;
+        }
+        if amount <= 0.0 {
+            return;
+        }
+
         let new_health = (self.health.load() - amount).max(0.0);
 
         if new_health == 0.0 {
-            self.kill().await;
+            if self.try_use_totem(damage_type).await {
+                return;
+            }
+            // Players get a death screen with the death message of the damage type
+            match self.player().await {
+                Some(player) => {
+                    let message_id = get_damage_type_by_id(damage_type)
+                        .map_or("generic", |damage_type| damage_type.message_id());
+                    player.kill(message_id).await;
+                }
+                None => self.kill().await,
+            }
         } else {
             self.set_health(new_health).await;
         }
     }
 
+    /// The player this living entity belongs to, if it is one
+    async fn player(&self) -> Option<Arc<Player>> {
+        if self.entity.entity_type != EntityType::Player {
+            return None;
+        }
+        self.entity
+            .world
+            .get_player_by_entityid(self.entity_id())
+            .await
+    }
+
+    /// Saves the entity from dying if it holds a totem of undying, only players can hold items for now
+    async fn try_use_totem(&self, damage_type: u8) -> bool {
+        match self.player().await {
+            Some(player) => player.try_use_totem(damage_type).await,
+            None => false,
+        }
+    }
+
     /// Returns if the entity was damaged or not
     pub fn check_damage(&self, amount: f32) -> bool {
         let regen = self
@@ -157,6 +350,8 @@ impl LivingEntity {
     /// This is similar to `kill` but Spawn Particles, Animation and plays death sound
     pub async fn kill(&self) {
         self.set_health(0.0).await;
+        // Effects end with death, respawned players start without any
+        self.clear_effects().await;
 
         // Spawns death smoke particles
         self.entity
@@ -175,12 +370,89 @@ impl NBTStorage for LivingEntity {
     async fn write_nbt(&self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
         self.entity.write_nbt(nbt).await;
         nbt.put("Health", NbtTag::Float(self.health.load()));
+        nbt.put("AbsorptionAmount", NbtTag::Float(self.absorption.load()));
+        let effects: Vec<NbtTag> = self
+            .active_effects
+            .lock()
+            .await
+            .iter()
+            .map(|(effect, instance)| NbtTag::Compound(instance.to_nbt(*effect)))
+            .collect();
+        if !effects.is_empty() {
+            nbt.put("active_effects", NbtTag::List(effects));
+        }
         // todo more...
     }
 
     async fn read_nbt(&mut self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
         self.entity.read_nbt(nbt).await;
-        self.health.store(nbt.get_float("Health").unwrap_or(0.0));
+        // Missing values keep the defaults, so a living entity is never loaded dead
+        if let Some(health) = nbt.get_float("Health") {
+            self.health.store(health);
+        }
+        if let Some(absorption) = nbt.get_float("AbsorptionAmount") {
+            self.absorption.store(absorption);
+        }
+        if let Some(effects) = nbt.get_list("active_effects") {
+            self.active_effects.get_mut().extend(
+                effects
+                    .iter()
+                    .filter_map(NbtTag::extract_compound)
+                    .filter_map(EffectInstance::from_nbt),
+            );
+        }
         // todo more...
     }
 }
+
+/// Takes as much of the damage as possible from the absorption.
+/// Returns the damage left for the health and the new absorption
+fn absorb_damage(amount: f32, absorption: f32) -> (f32, f32) {
+    let absorbed = amount.min(absorption);
+    (amount - absorbed, absorption - absorbed)
+}
+
+#[cfg(test)]
+mod test {
+
+    use pumpkin_nbt::compound::NbtCompound;
+
+    use super::absorb_damage;
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::{
+        effect::{Effect, EffectInstance},
+        player::test_player,
+        NBTStorage,
+    };
+
+    #[test]
+    fn damage_depletes_absorption_first() {
+        assert_eq!(absorb_damage(3.0, 4.0), (0.0, 1.0));
+        assert_eq!(absorb_damage(6.0, 4.0), (2.0, 0.0));
+        assert_eq!(absorb_damage(5.0, 0.0), (5.0, 0.0));
+    }
+
+    #[tokio::test]
+    async fn effects_are_saved_and_end_with_death() {
+        let folder = TestFolder::new("living-effects");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let living = &player.living_entity;
+        living
+            .add_effect(Effect::Speed, EffectInstance::new(1, 600, true))
+            .await;
+
+        let mut nbt = NbtCompound::new();
+        living.write_nbt(&mut nbt).await;
+        let saved = nbt.get_list("active_effects").unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(
+            saved[0]
+                .extract_compound()
+                .and_then(EffectInstance::from_nbt),
+            Some((Effect::Speed, EffectInstance::new(1, 600, true)))
+        );
+
+        living.kill().await;
+        assert!(living.active_effects.lock().await.is_empty());
+    }
+}
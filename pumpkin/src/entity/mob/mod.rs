@@ -1,7 +1,12 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
 
+use async_trait::async_trait;
 use pumpkin_data::entity::EntityType;
-use pumpkin_util::math::vector3::Vector3;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::{math::vector3::Vector3, random::RandomImpl};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 use zombie::Zombie;
@@ -11,18 +16,33 @@ use crate::{server::Server, world::World};
 use super::{
     ai::{goal::Goal, path::Navigator},
     living::LivingEntity,
+    NBTStorage,
 };
 
 pub mod zombie;
 
+/// Mobs further than this from every player are removed right away
+const DESPAWN_DISTANCE: f64 = 128.0;
+/// Mobs further than this from every player may randomly despawn
+const SOFT_DESPAWN_DISTANCE: f64 = 32.0;
+/// Ticks a mob has to stay out of the soft despawn distance before it may despawn
+const SOFT_DESPAWN_DELAY: u32 = 600;
+/// Once the delay passed, a mob despawns with a chance of one in this many per tick
+const SOFT_DESPAWN_CHANCE: i32 = 800;
+
 pub struct MobEntity {
     pub living_entity: Arc<LivingEntity>,
     pub goals: Mutex<Vec<(Arc<dyn Goal>, bool)>>,
     pub navigator: Mutex<Navigator>,
+    /// Mobs that are required to persist never despawn, like vanilla's `PersistenceRequired`
+    pub persistence_required: AtomicBool,
+    /// How many ticks in a row the mob was out of the soft despawn distance of every player
+    pub far_ticks: AtomicU32,
 }
 
 impl MobEntity {
     pub async fn tick(&self) {
+        self.living_entity.tick().await;
         let mut goals = self.goals.lock().await;
         for (goal, running) in goals.iter_mut() {
             if *running {
@@ -57,7 +77,195 @@ pub async fn from_type(
 }
 
 impl MobEntity {
+    /// Whether this mob is a monster, which is not allowed to exist on Peaceful difficulty
+    pub fn is_hostile(&self) -> bool {
+        matches!(
+            self.living_entity.entity.entity_type,
+            EntityType::Blaze
+                | EntityType::Bogged
+                | EntityType::Breeze
+                | EntityType::CaveSpider
+                | EntityType::Creaking
+                | EntityType::Creeper
+                | EntityType::Drowned
+                | EntityType::ElderGuardian
+                | EntityType::Endermite
+                | EntityType::Enderman
+                | EntityType::Evoker
+                | EntityType::Ghast
+                | EntityType::Giant
+                | EntityType::Guardian
+                | EntityType::Hoglin
+                | EntityType::Husk
+                | EntityType::Illusioner
+                | EntityType::MagmaCube
+                | EntityType::Phantom
+                | EntityType::Piglin
+                | EntityType::PiglinBrute
+                | EntityType::Pillager
+                | EntityType::Ravager
+                | EntityType::Shulker
+                | EntityType::Silverfish
+                | EntityType::Skeleton
+                | EntityType::Slime
+                | EntityType::Spider
+                | EntityType::Stray
+                | EntityType::Vex
+                | EntityType::Vindicator
+                | EntityType::Warden
+                | EntityType::Witch
+                | EntityType::Wither
+                | EntityType::WitherSkeleton
+                | EntityType::Zoglin
+                | EntityType::Zombie
+                | EntityType::ZombieVillager
+                | EntityType::ZombifiedPiglin
+        )
+    }
+
     pub async fn goal<T: Goal + 'static>(&self, goal: T) {
         self.goals.lock().await.push((Arc::new(goal), false));
     }
+
+    /// Whether this mob is removed when far away from players.
+    /// Like in vanilla only monsters do, unless they are persistent or named
+    pub async fn can_despawn(&self) -> bool {
+        self.is_hostile()
+            && !self.persistence_required.load(Ordering::Relaxed)
+            && self.living_entity.entity.custom_name.lock().await.is_none()
+    }
+
+    /// Whether this mob should be removed because no player is close to it, only called while there are players in the world
+    pub async fn should_despawn(&self) -> bool {
+        let entity = &self.living_entity.entity;
+        let pos = entity.pos.load();
+        let closest = entity
+            .world
+            .get_nearby_players(pos, DESPAWN_DISTANCE)
+            .await
+            .values()
+            .map(|player| {
+                player
+                    .living_entity
+                    .entity
+                    .pos
+                    .load()
+                    .squared_distance_to_vec(pos)
+            })
+            .min_by(f64::total_cmp);
+        let far_ticks = if closest.is_some_and(|distance| distance <= SOFT_DESPAWN_DISTANCE.powi(2))
+        {
+            self.far_ticks.store(0, Ordering::Relaxed);
+            0
+        } else {
+            self.far_ticks.fetch_add(1, Ordering::Relaxed) + 1
+        };
+        let roll = entity.world.rng().next_bounded_i32(SOFT_DESPAWN_CHANCE);
+        should_despawn(self.can_despawn().await, closest, far_ticks, roll)
+    }
+}
+
+#[async_trait]
+impl NBTStorage for MobEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.living_entity.write_nbt(nbt).await;
+        nbt.put_bool(
+            "PersistenceRequired",
+            self.persistence_required.load(Ordering::Relaxed),
+        );
+    }
+
+    async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
+        if let Some(living_entity) = Arc::get_mut(&mut self.living_entity) {
+            living_entity.read_nbt(nbt).await;
+        }
+        self.persistence_required.store(
+            nbt.get_bool("PersistenceRequired").unwrap_or(false),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// `closest` is the squared distance to the closest player within the despawn distance.
+/// `roll` is a random number below [`SOFT_DESPAWN_CHANCE`], the mob despawns if it is 0
+fn should_despawn(can_despawn: bool, closest: Option<f64>, far_ticks: u32, roll: i32) -> bool {
+    if !can_despawn {
+        return false;
+    }
+    match closest {
+        None => true,
+        Some(distance) if distance > SOFT_DESPAWN_DISTANCE.powi(2) => {
+            far_ticks > SOFT_DESPAWN_DELAY && roll == 0
+        }
+        Some(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{atomic::Ordering, Arc};
+
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::vector3::Vector3;
+    use pumpkin_world::dimension::Dimension;
+
+    use super::{should_despawn, SOFT_DESPAWN_DELAY};
+    use crate::data::test_folder::TestFolder;
+    use crate::{server::Server, world::World};
+
+    #[test]
+    fn far_away_mobs_despawn() {
+        assert!(should_despawn(true, None, 0, 1));
+        assert!(should_despawn(
+            true,
+            Some(64.0 * 64.0),
+            SOFT_DESPAWN_DELAY + 1,
+            0
+        ));
+        // not long enough out of range or unlucky
+        assert!(!should_despawn(
+            true,
+            Some(64.0 * 64.0),
+            SOFT_DESPAWN_DELAY,
+            0
+        ));
+        assert!(!should_despawn(
+            true,
+            Some(64.0 * 64.0),
+            SOFT_DESPAWN_DELAY + 1,
+            1
+        ));
+        // close to a player
+        assert!(!should_despawn(
+            true,
+            Some(16.0 * 16.0),
+            SOFT_DESPAWN_DELAY + 1,
+            0
+        ));
+    }
+
+    #[tokio::test]
+    async fn persistent_mobs_never_despawn() {
+        let folder = TestFolder::new("persistent-mobs");
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        let server = Server::with_world_folder(folder.join("server"));
+        let position = Vector3::new(8.5, 100.0, 8.5);
+        let (mob, _) = server
+            .add_mob_entity(EntityType::Zombie, position, &world)
+            .await;
+        let (persistent, _) = server
+            .add_mob_entity(EntityType::Zombie, position, &world)
+            .await;
+        persistent
+            .persistence_required
+            .store(true, Ordering::Relaxed);
+
+        // No player is anywhere near them
+        assert!(mob.should_despawn().await);
+        assert!(!persistent.should_despawn().await);
+    }
 }
@@ -1,5 +1,8 @@
 use core::f32;
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::{
+    atomic::{AtomicBool, AtomicI8},
+    Arc,
+};
 
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
@@ -14,26 +17,40 @@ use pumpkin_protocol::{
     },
     codec::var_int::VarInt,
 };
-use pumpkin_util::math::{
-    boundingbox::{BoundingBox, BoundingBoxSize},
-    get_section_cord,
-    position::BlockPos,
-    vector2::Vector2,
-    vector3::Vector3,
-    wrap_degrees,
+use pumpkin_util::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        get_section_cord,
+        position::BlockPos,
+        vector2::Vector2,
+        vector3::Vector3,
+        wrap_degrees,
+    },
+    random::RandomImpl,
+    text::TextComponent,
 };
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::world::World;
+use player::Player;
 
 pub mod ai;
+pub mod effect;
+pub mod experience;
+pub mod food;
+pub mod hunger;
 pub mod mob;
 
 pub mod living;
 pub mod player;
+pub mod spectate;
 
 pub type EntityId = i32;
 
+/// The custom name metadata, index 2 holds the optional name and index 3 whether it is always shown
+pub type CustomNameMetadata = CSetEntityMetadata<(Metadata<Option<TextComponent>>, Metadata<bool>)>;
+
 /// Represents a not living Entity (e.g. Item, Egg, Snowball...)
 pub struct Entity {
     /// A unique identifier for the entity
@@ -60,6 +77,8 @@ pub struct Entity {
     pub velocity: AtomicCell<Vector3<f64>>,
     /// Indicates whether the entity is on the ground (may not always be accurate).
     pub on_ground: AtomicBool,
+    /// Indicates whether the entity ignores gravity
+    pub no_gravity: AtomicBool,
     /// The entity's yaw rotation (horizontal rotation) ← →
     pub yaw: AtomicCell<f32>,
     /// The entity's head yaw rotation (horizontal rotation of the head)
@@ -74,6 +93,12 @@ pub struct Entity {
     pub bounding_box: AtomicCell<BoundingBox>,
     ///The size (width and height) of the bounding box
     pub bounding_box_size: AtomicCell<BoundingBoxSize>,
+    /// The shared flags byte (see [`Flag`]) as it was last sent to clients
+    flags: AtomicI8,
+    /// The name rendered above the entity, e.g. given by a name tag
+    pub custom_name: Mutex<Option<TextComponent>>,
+    /// Whether the custom name is shown even when not looking at the entity
+    pub custom_name_visible: AtomicBool,
 }
 
 impl Entity {
@@ -97,9 +122,13 @@ impl Entity {
             entity_uuid,
             entity_type,
             on_ground: AtomicBool::new(false),
+            no_gravity: AtomicBool::new(false),
             pos: AtomicCell::new(position),
             block_pos: AtomicCell::new(BlockPos(Vector3::new(floor_x, floor_y, floor_z))),
-            chunk_pos: AtomicCell::new(Vector2::new(floor_x, floor_z)),
+            chunk_pos: AtomicCell::new(Vector2::new(
+                get_section_cord(floor_x),
+                get_section_cord(floor_z),
+            )),
             sneaking: AtomicBool::new(false),
             world,
             // TODO: Load this from previous instance
@@ -113,6 +142,9 @@ impl Entity {
             pose: AtomicCell::new(EntityPose::Standing),
             bounding_box,
             bounding_box_size,
+            flags: AtomicI8::new(0),
+            custom_name: Mutex::new(None),
+            custom_name_visible: AtomicBool::new(false),
         }
     }
 
@@ -207,6 +239,27 @@ impl Entity {
         self.pitch.store(pitch);
     }
 
+    /// Returns the position of this entity's eyes
+    pub fn eye_position(&self) -> Vector3<f64> {
+        let position = self.pos.load();
+        Vector3::new(
+            position.x,
+            position.y + f64::from(self.standing_eye_height),
+            position.z,
+        )
+    }
+
+    /// Returns the normalized direction this entity is looking at
+    pub fn rotation_vector(&self) -> Vector3<f64> {
+        let yaw = f64::from(self.yaw.load()).to_radians();
+        let pitch = f64::from(self.pitch.load()).to_radians();
+        Vector3::new(
+            -yaw.sin() * pitch.cos(),
+            -pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        )
+    }
+
     /// Removes the Entity from their current World
     pub async fn remove(&self) {
         self.world.remove_entity(self).await;
@@ -232,6 +285,56 @@ impl Entity {
         )
     }
 
+    /// The metadata clients need to render the custom name, should be sent right after the spawn packet.
+    /// Returns `None` when the entity has no custom name, as that is what clients assume by default
+    pub async fn create_custom_name_packet(&self) -> Option<CustomNameMetadata> {
+        let name = self.custom_name.lock().await.clone()?;
+        Some(custom_name_metadata(
+            self.entity_id,
+            Some(name),
+            self.custom_name_visible
+                .load(std::sync::atomic::Ordering::Relaxed),
+        ))
+    }
+
+    pub async fn set_custom_name(&self, name: Option<TextComponent>) {
+        *self.custom_name.lock().await = name.clone();
+        let visible = self
+            .custom_name_visible
+            .load(std::sync::atomic::Ordering::Relaxed);
+        self.world
+            .broadcast_packet_all(&custom_name_metadata(self.entity_id, name, visible))
+            .await;
+    }
+
+    /// Applies the `CustomName` and `CustomNameVisible` tags, like the ones given to `/summon`, and
+    /// shows the changes to the players
+    pub async fn apply_custom_name(&self, nbt: &NbtCompound) {
+        let visible = nbt.get_bool("CustomNameVisible");
+        match read_custom_name(nbt) {
+            Some(name) => {
+                if let Some(visible) = visible {
+                    self.custom_name_visible
+                        .store(visible, std::sync::atomic::Ordering::Relaxed);
+                }
+                self.set_custom_name(Some(name)).await;
+            }
+            None => {
+                if let Some(visible) = visible {
+                    self.set_custom_name_visible(visible).await;
+                }
+            }
+        }
+    }
+
+    pub async fn set_custom_name_visible(&self, visible: bool) {
+        self.custom_name_visible
+            .store(visible, std::sync::atomic::Ordering::Relaxed);
+        let packet =
+            CSetEntityMetadata::new(self.entity_id.into(), Metadata::new(3, 8.into(), visible));
+        self.world.broadcast_packet_all(&packet).await;
+    }
+
     /// Applies knockback to the entity, following vanilla Minecraft's mechanics.
     ///
     /// This function calculates the entity's new velocity based on the specified knockback strength and direction.
@@ -240,8 +343,9 @@ impl Entity {
         let mut x = x;
         let mut z = z;
         while x.mul_add(x, z * z) < 1.0E-5 {
-            x = (rand::random::<f64>() - rand::random::<f64>()) * 0.01;
-            z = (rand::random::<f64>() - rand::random::<f64>()) * 0.01;
+            let mut rng = self.world.rng();
+            x = (rng.next_f64() - rng.next_f64()) * 0.01;
+            z = (rng.next_f64() - rng.next_f64()) * 0.01;
         }
 
         let var8 = Vector3::new(x, 0.0, z).normalize() * strength;
@@ -287,14 +391,59 @@ impl Entity {
         self.set_flag(Flag::FallFlying, fall_flying).await;
     }
 
+    /// Whether physics should pull the entity down
+    pub fn has_gravity(&self) -> bool {
+        !self.no_gravity.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Makes the entity ignore (or respect again) gravity, e.g. for floating display entities
+    pub async fn set_no_gravity(&self, no_gravity: bool) {
+        self.no_gravity
+            .store(no_gravity, std::sync::atomic::Ordering::Relaxed);
+        let packet = CSetEntityMetadata::new(
+            self.entity_id.into(),
+            Metadata::new(5, 8.into(), no_gravity),
+        );
+        self.world.broadcast_packet_all(&packet).await;
+    }
+
+    pub fn is_glowing(&self) -> bool {
+        self.has_flag(Flag::Glowing)
+    }
+
+    /// Outlines the entity so it can be seen through walls
+    pub async fn set_glowing(&self, glowing: bool) {
+        self.set_flag(Flag::Glowing, glowing).await;
+    }
+
+    /// Outlines the entity for `player` only, or shows its own outline state to them again.
+    /// Other players still see the entity as it is
+    pub async fn send_glowing_to(&self, player: &Player, glowing: bool) {
+        let mut flags = self.flags.load(std::sync::atomic::Ordering::Relaxed);
+        if glowing {
+            flags |= 1 << Flag::Glowing as u8;
+        }
+        let packet =
+            CSetEntityMetadata::new(self.entity_id.into(), Metadata::new(0, 0.into(), flags));
+        player.client.send_packet(&packet).await;
+    }
+
+    fn has_flag(&self, flag: Flag) -> bool {
+        self.flags.load(std::sync::atomic::Ordering::Relaxed) & (1 << flag as u8) != 0
+    }
+
     async fn set_flag(&self, flag: Flag, value: bool) {
-        let index = flag as u8;
-        let mut b = 0i8;
-        if value {
-            b |= 1 << index;
+        let mask = 1i8 << flag as u8;
+        // The client replaces all flags at once, so keep the other ones intact
+        let b = if value {
+            self.flags
+                .fetch_or(mask, std::sync::atomic::Ordering::Relaxed)
+                | mask
         } else {
-            b &= !(1 << index);
-        }
+            self.flags
+                .fetch_and(!mask, std::sync::atomic::Ordering::Relaxed)
+                & !mask
+        };
         let packet = CSetEntityMetadata::new(self.entity_id.into(), Metadata::new(0, 0.into(), b));
         self.world.broadcast_packet_all(&packet).await;
     }
@@ -306,10 +455,19 @@ impl Entity {
             .await;
     }
 
+    /// Changes the pose, players also change their size with it, like shrinking while sneaking
     pub async fn set_pose(&self, pose: EntityPose) {
         self.pose.store(pose);
+        if self.entity_type == EntityType::Player {
+            let size = player_pose_size(pose);
+            let position = self.pos.load();
+            self.bounding_box_size.store(size);
+            self.bounding_box.store(BoundingBox::new_from_pos(
+                position.x, position.y, position.z, &size,
+            ));
+        }
         let pose = pose as i32;
-        let packet = CSetEntityMetadata::<VarInt>::new(
+        let packet = CSetEntityMetadata::<Metadata<VarInt>>::new(
             self.entity_id.into(),
             Metadata::new(6, 21.into(), pose.into()),
         );
@@ -342,31 +500,97 @@ impl NBTStorage for Entity {
             "Rotation",
             NbtTag::List(vec![self.yaw.load().into(), self.pitch.load().into()]),
         );
+        nbt.put_bool("NoGravity", !self.has_gravity());
+        if let Some(name) = self.custom_name.lock().await.as_ref() {
+            write_custom_name(nbt, name);
+        }
+        if self
+            .custom_name_visible
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            nbt.put_bool("CustomNameVisible", true);
+        }
 
         // todo more...
     }
 
     async fn read_nbt(&mut self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
-        let position = nbt.get_list("Pos").unwrap();
-        let x = position[0].extract_double().unwrap_or(0.0);
-        let y = position[1].extract_double().unwrap_or(0.0);
-        let z = position[2].extract_double().unwrap_or(0.0);
-        self.pos.store(Vector3::new(x, y, z));
-        let velocity = nbt.get_list("Motion").unwrap();
-        let x = velocity[0].extract_double().unwrap_or(0.0);
-        let y = velocity[1].extract_double().unwrap_or(0.0);
-        let z = velocity[2].extract_double().unwrap_or(0.0);
-        self.velocity.store(Vector3::new(x, y, z));
-        let rotation = nbt.get_list("Rotation").unwrap();
-        let yaw = rotation[0].extract_float().unwrap_or(0.0);
-        let pitch = rotation[1].extract_float().unwrap_or(0.0);
-        self.yaw.store(yaw);
-        self.pitch.store(pitch);
+        // Missing or malformed values keep the current ones
+        if let Some([x, y, z]) = read_numbers(nbt, "Pos", NbtTag::extract_double) {
+            // Also moves the block and chunk position along
+            self.set_pos(Vector3::new(x, y, z));
+        }
+        if let Some([x, y, z]) = read_numbers(nbt, "Motion", NbtTag::extract_double) {
+            self.velocity.store(Vector3::new(x, y, z));
+        }
+        if let Some([yaw, pitch]) = read_numbers(nbt, "Rotation", NbtTag::extract_float) {
+            self.yaw.store(yaw);
+            self.pitch.store(pitch);
+        }
+        self.no_gravity.store(
+            nbt.get_bool("NoGravity").unwrap_or(false),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        *self.custom_name.get_mut() = read_custom_name(nbt);
+        self.custom_name_visible.store(
+            nbt.get_bool("CustomNameVisible").unwrap_or(false),
+            std::sync::atomic::Ordering::Relaxed,
+        );
 
         // todo more...
     }
 }
 
+/// Reads a list of `N` numbers like `Pos`, `None` if the tag is missing or not such a list
+fn read_numbers<T: Copy + Default, const N: usize>(
+    nbt: &NbtCompound,
+    name: &str,
+    extract: fn(&NbtTag) -> Option<T>,
+) -> Option<[T; N]> {
+    let list = nbt.get_list(name)?;
+    if list.len() != N {
+        return None;
+    }
+    let mut numbers = [T::default(); N];
+    for (number, tag) in numbers.iter_mut().zip(list) {
+        *number = extract(tag)?;
+    }
+    Some(numbers)
+}
+
+fn custom_name_metadata(
+    entity_id: EntityId,
+    name: Option<TextComponent>,
+    visible: bool,
+) -> CustomNameMetadata {
+    CSetEntityMetadata::with_entries(
+        entity_id.into(),
+        (
+            Metadata::new(2, 6.into(), name),
+            Metadata::new(3, 8.into(), visible),
+        ),
+    )
+}
+
+/// Stores the name as JSON text, like vanilla does for the `CustomName` tag
+pub fn write_custom_name(nbt: &mut NbtCompound, name: &TextComponent) {
+    match serde_json::to_string(&name.0) {
+        Ok(json) => nbt.put("CustomName", NbtTag::String(json)),
+        Err(err) => log::warn!("Failed to serialize the custom name: {err}"),
+    }
+}
+
+#[must_use]
+pub fn read_custom_name(nbt: &NbtCompound) -> Option<TextComponent> {
+    let json = nbt.get_string("CustomName")?;
+    // Plain strings like `"Bob"` are text too
+    serde_json::from_str(json).ok().or_else(|| {
+        serde_json::from_str::<String>(json)
+            .ok()
+            .map(TextComponent::text)
+    })
+}
+
 #[async_trait]
 pub trait NBTStorage: Send + Sync {
     async fn write_nbt(&self, nbt: &mut NbtCompound);
@@ -399,3 +623,63 @@ pub enum Flag {
     /// Indicates if the entity is flying due to a fall.
     FallFlying = 7,
 }
+
+/// The size of a player in a pose, in vanilla other entities only change their size when they grow up
+fn player_pose_size(pose: EntityPose) -> BoundingBoxSize {
+    let (width, height) = match pose {
+        EntityPose::Crouching => (0.6, 1.5),
+        EntityPose::Swimming | EntityPose::FallFlying | EntityPose::SpinAttack => (0.6, 0.6),
+        EntityPose::Sleeping | EntityPose::Dying => (0.2, 0.2),
+        _ => (0.6, 1.8),
+    };
+    BoundingBoxSize { width, height }
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::BytesMut;
+    use pumpkin_data::entity::EntityPose;
+    use pumpkin_protocol::ClientPacket;
+    use pumpkin_util::{math::vector3::Vector3, text::TextComponent};
+
+    use super::custom_name_metadata;
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::test_player;
+
+    #[test]
+    fn custom_name_is_sent_with_metadata() {
+        let name = TextComponent::text("Bob");
+        let mut bytes = BytesMut::new();
+        custom_name_metadata(5, Some(name.clone()), true).write(&mut bytes);
+
+        // entity id, then index 2 with an present optional text component
+        assert_eq!(bytes[..4], [5, 2, 6, 1]);
+        let encoded = name.encode();
+        assert_eq!(bytes[4..4 + encoded.len()], encoded[..]);
+        // index 3 always shows the name, then the end marker
+        assert_eq!(bytes[4 + encoded.len()..], [3, 8, 1, 255]);
+    }
+
+    #[tokio::test]
+    async fn sneaking_shrinks_players() {
+        let folder = TestFolder::new("sneaking");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let entity = &player.living_entity.entity;
+        entity.set_pos(Vector3::new(0.5, 64.0, 0.5));
+        entity.set_pose(EntityPose::Standing).await;
+        let height = || {
+            let bounding_box = entity.bounding_box.load();
+            assert!((bounding_box.min_y - 64.0).abs() < 1e-9);
+            bounding_box.max_y - bounding_box.min_y
+        };
+        assert!((height() - 1.8).abs() < 1e-9);
+
+        entity.set_sneaking(true).await;
+        assert!((height() - 1.5).abs() < 1e-9);
+        assert_eq!(entity.pose.load() as u8, EntityPose::Crouching as u8);
+
+        // Standing up again restores the full height
+        entity.set_sneaking(false).await;
+        assert!((height() - 1.8).abs() < 1e-9);
+    }
+}
@@ -1,10 +1,11 @@
 use std::{
+    collections::HashMap,
     num::NonZeroU8,
     sync::{
         atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32, AtomicU8, Ordering},
         Arc,
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
@@ -12,6 +13,7 @@ use crossbeam::atomic::AtomicCell;
 use pumpkin_config::{ADVANCED_CONFIG, BASIC_CONFIG};
 use pumpkin_data::{
     entity::EntityType,
+    scoreboard::ScoreboardDisplaySlot,
     sound::{Sound, SoundCategory},
 };
 use pumpkin_inventory::player::PlayerInventory;
@@ -19,18 +21,20 @@ use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_protocol::{
     bytebuf::packet_id::Packet,
     client::play::{
-        CActionBar, CCombatDeath, CDisguisedChatMessage, CEntityStatus, CGameEvent, CHurtAnimation,
-        CKeepAlive, CPlayDisconnect, CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition,
-        CSetHealth, CSubtitle, CSystemChatMessage, CTitleText, GameEvent, PlayerAction,
+        CActionBar, CCombatDeath, CDisguisedChatMessage, CDisplayObjective, CEntityStatus,
+        CGameEvent, CHurtAnimation, CKeepAlive, CPlayDisconnect, CPlayerAbilities,
+        CPlayerInfoUpdate, CPlayerPosition, CResetScore, CSetExperience, CSetHealth, CSubtitle,
+        CSystemChatMessage, CTitleText, CUpdateObjectives, CUpdateScore, GameEvent, Mode,
+        PlayerAction, RenderType,
     },
     server::play::{
-        SChatCommand, SChatMessage, SClientCommand, SClientInformationPlay, SClientTickEnd,
-        SCommandSuggestion, SConfirmTeleport, SInteract, SPickItemFromBlock, SPlayerAbilities,
-        SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerPosition, SPlayerPositionRotation,
-        SPlayerRotation, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUseItem,
-        SUseItemOn,
+        SChatAck, SChatCommand, SChatMessage, SChatSessionUpdate, SClientCommand,
+        SClientInformationPlay, SClientTickEnd, SCommandSuggestion, SConfirmTeleport, SInteract,
+        SPickItemFromBlock, SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput,
+        SPlayerPosition, SPlayerPositionRotation, SPlayerRotation, SResourcePackResponse,
+        SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUseItem, SUseItemOn,
     },
-    RawPacket, ServerPacket,
+    NumberFormat, RawPacket, ServerPacket,
 };
 use pumpkin_protocol::{
     client::play::CSoundEffect,
@@ -43,6 +47,7 @@ use pumpkin_protocol::{
     client::play::{CSetEntityMetadata, Metadata},
     server::play::{SClickContainer, SKeepAlive},
 };
+use pumpkin_registry::damage_type_has_tag;
 use pumpkin_util::{
     math::{
         boundingbox::{BoundingBox, BoundingBoxSize},
@@ -51,32 +56,59 @@ use pumpkin_util::{
         vector3::Vector3,
     },
     permission::PermissionLvl,
-    text::TextComponent,
+    text::{color::NamedColor, TextComponent},
     GameMode,
 };
 use pumpkin_world::{
     cylindrical_chunk_iterator::Cylindrical,
-    item::{
-        item_registry::{get_item_by_id, Operation},
-        ItemStack,
-    },
+    item::{item_registry::get_item, ItemStack},
 };
-use tokio::sync::{Mutex, Notify, RwLock};
+use tokio::sync::{Mutex, Notify, RwLock, Semaphore};
+use uuid::Uuid;
 
-use super::{Entity, EntityId, NBTStorage};
+use super::{write_custom_name, Entity, EntityId, NBTStorage};
 use crate::{
     command::{client_cmd_suggestions, dispatcher::CommandDispatcher},
-    data::op_data::OPERATOR_CONFIG,
+    data::{
+        banlist_serializer::BannedPlayerEntry, banned_player_data::BANNED_PLAYER_LIST,
+        kit_data::KitItem, language_data::LANGUAGES, op_data::OPERATOR_CONFIG,
+        starter_inventory_data::STARTER_INVENTORY, SaveJSONConfiguration,
+    },
     net::{
-        combat::{self, player_attack_sound, AttackType},
+        chat::{ChatSession, LastSeenMessages},
+        combat::{self, player_attack_sound, AttackModifiers, AttackType, HeldItemAttributes},
         Client, PlayerConfig,
     },
+    plugin::packet as packet_events,
     server::Server,
-    world::World,
+    world::{
+        bossbar::Bossbar,
+        edit_history::{BlockEdit, EditHistory},
+        selection::{Corner, Selection},
+        World,
+    },
 };
 use crate::{error::PumpkinError, net::GameProfile};
 
+use super::effect::{attack_damage_bonus, Effect, EffectInstance};
+use super::experience;
+use super::food::Eating;
+use super::hunger;
 use super::living::LivingEntity;
+use super::spectate::Spectating;
+
+/// How many blocks up or down [`Player::safe_teleport`] looks for a position to stand at
+const SAFE_TELEPORT_RANGE: i32 = 8;
+/// The objective of the sidebar set with [`Player::set_sidebar`], only this player knows about it
+const SIDEBAR_OBJECTIVE: &str = "pumpkin_sidebar";
+/// The client does not show more lines in the sidebar
+pub const SIDEBAR_MAX_LINES: usize = 15;
+/// The effects a totem of undying gives, with their amplifier and duration in ticks
+const TOTEM_EFFECTS: [(Effect, u8, i32); 3] = [
+    (Effect::Regeneration, 1, 900),
+    (Effect::Absorption, 1, 100),
+    (Effect::FireResistance, 0, 800),
+];
 
 /// Represents a Minecraft player entity.
 ///
@@ -86,6 +118,8 @@ pub struct Player {
     pub living_entity: LivingEntity,
     /// The player's game profile information, including their username and UUID.
     pub gameprofile: GameProfile,
+    /// The name shown instead of the username in chat and the tab list, if set
+    display_name: Mutex<Option<TextComponent>>,
     /// The client connection associated with the player.
     pub client: Arc<Client>,
     /// Players Inventory
@@ -98,6 +132,18 @@ pub struct Player {
     pub food: AtomicI32,
     /// The player's food saturation level.
     pub food_saturation: AtomicCell<f32>,
+    /// Ticks since the last hunger or natural regeneration update
+    pub food_tick_timer: AtomicU32,
+    /// Sprinting, jumping and fighting exhaust the player, using up saturation and food, see [`Player::add_exhaustion`]
+    pub food_exhaustion: AtomicCell<f32>,
+    /// Ticks towards the next regeneration on peaceful, which happens regardless of the food level
+    peaceful_regen_timer: AtomicU32,
+    /// The player's experience level
+    pub experience_level: AtomicI32,
+    /// The progress towards the next experience level, from 0 to 1
+    pub experience_progress: AtomicCell<f32>,
+    /// All experience points the player has, see [`experience`]
+    pub total_experience: AtomicI32,
     /// The ID of the currently open container (if any).
     pub open_container: AtomicCell<Option<u64>>,
     /// The item currently being held by the player.
@@ -111,12 +157,22 @@ pub struct Player {
     /// **Note:** When the `abilities` field is updated, the server should send a `send_abilities_update` packet to the client to notify them of the changes.
     pub abilities: Mutex<Abilities>,
 
+    /// The movement keys the player held during their last completed client tick, used to steer the vehicle they are riding
+    pub current_input: AtomicCell<PlayerInput>,
+    /// The movement keys sent during the client tick that is still in progress
+    pub pending_input: AtomicCell<PlayerInput>,
+    /// The number of ticks the client reported as completed
+    pub client_ticks: AtomicU32,
     /// The current stage of the block the player is breaking.
     pub current_block_destroy_stage: AtomicU8,
+    /// The highest block change sequence the client sent us, used to ignore duplicated packets
+    pub last_block_sequence: AtomicI32,
     /// A counter for teleport IDs used to track pending teleports.
     pub teleport_id_count: AtomicI32,
     /// The pending teleport information, including the teleport ID and target location.
     pub awaiting_teleport: Mutex<Option<(VarInt, Vector3<f64>)>>,
+    /// The bed or respawn anchor the player respawns at instead of the world spawn, if set
+    pub respawn_point: AtomicCell<Option<BlockPos>>,
     /// The coordinates of the chunk section the player is currently watching.
     pub watched_section: AtomicCell<Cylindrical>,
     /// Did we send a keep alive Packet and wait for the response?
@@ -129,12 +185,48 @@ pub struct Player {
     pub last_attacked_ticks: AtomicU32,
     /// The players op permission level
     pub permission_lvl: AtomicCell<PermissionLvl>,
+    /// The key the player signs their chat messages with, if they sent one
+    pub chat_session: Mutex<Option<ChatSession>>,
+    /// The language translated messages are shown in instead of the client's, set with `/language`
+    pub language: Mutex<Option<String>>,
+    /// The index of the next signed chat message of this player
+    pub chat_index: AtomicI32,
+    /// The signed chat messages this player received
+    pub last_seen_messages: Mutex<LastSeenMessages>,
     /// Tell tasks to stop if we are closing
     cancel_tasks: Notify,
     /// whether the client has reported it has loaded
     pub client_loaded: AtomicBool,
     /// timeout (in ticks) client has to report it has finished loading.
     pub client_loaded_timeout: AtomicU32,
+    /// Whether the player already got the starter inventory on their first join
+    pub received_starter_inventory: AtomicBool,
+    /// Whether the player's state was restored from the data saved in their previous session
+    pub restored_from_save: bool,
+    /// When the player last ran each command that has a cooldown, keyed by the command's primary name.
+    /// Commands with more specific cooldowns use their own keys, like `kit <name>`.
+    /// They are saved with the player data, so rejoining or restarting does not reset them
+    pub command_cooldowns: Mutex<HashMap<String, SystemTime>>,
+    /// Ticks left in which other players can not hurt this player, see [`Player::start_spawn_grace`]
+    pub spawn_grace_ticks: AtomicU32,
+    /// The attack modifiers of the held item, read once per item instead of on every hit
+    pub held_item_attributes: HeldItemAttributes,
+    /// Limits how many commands of the player run at the same time, see [`pumpkin_config::CommandsConfig`]
+    pub running_commands: Arc<Semaphore>,
+    /// Block edits made with commands like `/fill`, which `/undo` reverts
+    pub edit_history: Mutex<EditHistory>,
+    /// The region the player selected for commands like `/set`
+    pub selection: AtomicCell<Selection>,
+    /// How many lines the sidebar set with [`Player::set_sidebar`] has, `None` if it is not shown
+    sidebar_lines: Mutex<Option<usize>>,
+    /// The boss bars shown to the player, keyed by their uuid. They are gone with the player when they quit
+    pub(crate) bossbars: Mutex<HashMap<Uuid, Bossbar>>,
+    /// The entity the player follows with their camera as a spectator, see [`Player::start_spectating`]
+    pub spectating: Mutex<Option<Spectating>>,
+    /// Whether following an entity shows its health and position and outlines the entities around it
+    pub spectator_hud: AtomicBool,
+    /// The food the player is eating right now, see [`Player::start_eating`]
+    pub eating: AtomicCell<Option<Eating>>,
 }
 
 impl Player {
@@ -178,16 +270,28 @@ impl Player {
             )),
             config: Mutex::new(config),
             gameprofile,
+            display_name: Mutex::new(None),
+            language: Mutex::new(None),
             client,
             awaiting_teleport: Mutex::new(None),
-            // TODO: Load this from previous instance
+            respawn_point: AtomicCell::new(None),
             food: AtomicI32::new(20),
             food_saturation: AtomicCell::new(20.0),
+            food_tick_timer: AtomicU32::new(0),
+            food_exhaustion: AtomicCell::new(0.0),
+            peaceful_regen_timer: AtomicU32::new(0),
+            experience_level: AtomicI32::new(0),
+            experience_progress: AtomicCell::new(0.0),
+            total_experience: AtomicI32::new(0),
             current_block_destroy_stage: AtomicU8::new(0),
+            last_block_sequence: AtomicI32::new(-1),
             open_container: AtomicCell::new(None),
             carried_item: AtomicCell::new(None),
             teleport_id_count: AtomicI32::new(0),
             abilities: Mutex::new(Abilities::default()),
+            current_input: AtomicCell::new(PlayerInput::default()),
+            pending_input: AtomicCell::new(PlayerInput::default()),
+            client_ticks: AtomicU32::new(0),
             gamemode: AtomicCell::new(gamemode),
             // We want this to be an impossible watched section so that `player_chunker::update_position`
             // will mark chunks as watched for a new join rather than a respawn
@@ -203,6 +307,24 @@ impl Player {
             cancel_tasks: Notify::new(),
             client_loaded: AtomicBool::new(false),
             client_loaded_timeout: AtomicU32::new(60),
+            received_starter_inventory: AtomicBool::new(false),
+            restored_from_save: false,
+            command_cooldowns: Mutex::new(HashMap::new()),
+            spawn_grace_ticks: AtomicU32::new(0),
+            held_item_attributes: HeldItemAttributes::default(),
+            running_commands: Arc::new(Semaphore::new(
+                ADVANCED_CONFIG.commands.max_running_commands,
+            )),
+            edit_history: Mutex::new(EditHistory::default()),
+            selection: AtomicCell::new(Selection::default()),
+            sidebar_lines: Mutex::new(None),
+            bossbars: Mutex::new(HashMap::new()),
+            spectating: Mutex::new(None),
+            spectator_hud: AtomicBool::new(true),
+            eating: AtomicCell::new(None),
+            chat_session: Mutex::new(None),
+            chat_index: AtomicI32::new(0),
+            last_seen_messages: Mutex::new(LastSeenMessages::default()),
             // Minecraft has no why to change the default permission level of new players.
             // Minecrafts default permission level is 0
             permission_lvl: OPERATOR_CONFIG
@@ -223,11 +345,56 @@ impl Player {
         &self.inventory
     }
 
+    /// Remembers the states `changes` in the player's world replaced, so `/undo` can restore them.
+    /// Returns false if the edit is too large to be undone
+    pub async fn record_edit(&self, changes: &[(BlockPos, u16)], replaced: &[u16]) -> bool {
+        let config = &ADVANCED_CONFIG.commands;
+        self.edit_history.lock().await.push(
+            BlockEdit::new(
+                Arc::downgrade(&self.living_entity.entity.world),
+                changes,
+                replaced,
+            ),
+            config.max_undo_edits,
+            config.max_undo_blocks,
+        )
+    }
+
+    /// Sets blocks in the player's world and records the edit for `/undo`.
+    /// Returns false if the edit is too large to be undone
+    pub async fn edit_blocks(&self, changes: &[(BlockPos, u16)]) -> bool {
+        let replaced = self.world().set_block_states(changes).await;
+        self.record_edit(changes, &replaced).await
+    }
+
+    /// Sets a corner of the player's selection and tells them about it
+    pub async fn set_selection_corner(&self, corner: Corner, position: BlockPos) {
+        let selection =
+            self.selection
+                .load()
+                .with_corner(self.world().dimension_type, corner, position);
+        self.selection.store(selection);
+        let name = match corner {
+            Corner::First => "First",
+            Corner::Second => "Second",
+        };
+        let size = selection
+            .region()
+            .map(|region| format!(" ({} blocks)", region.volume()))
+            .unwrap_or_default();
+        self.send_system_message(&TextComponent::text(format!(
+            "{name} position set to {}, {}, {}{size}",
+            position.0.x, position.0.y, position.0.z
+        )))
+        .await;
+    }
+
     /// Removes the Player out of the current World
     #[allow(unused_variables)]
     pub async fn remove(self: Arc<Self>) {
         let world = self.world();
         self.cancel_tasks.notify_waiters();
+        self.edit_history.lock().await.clear();
 
         world.remove_player(self.clone()).await;
 
@@ -270,35 +437,30 @@ impl Player {
         let attacker_entity = &self.living_entity.entity;
         let config = &ADVANCED_CONFIG.pvp;
 
-        let inventory = self.inventory().lock().await;
-        let item_slot = inventory.held_item();
+        let held_item_id = self
+            .inventory()
+            .lock()
+            .await
+            .held_item()
+            .map(|stack| stack.item_id);
+        let modifiers = self
+            .held_item_attributes
+            .get(held_item_id, AttackModifiers::parse);
 
         let base_damage = 1.0;
         let base_attack_speed = 4.0;
 
         let mut damage_multiplier = 1.0;
-        let mut add_damage = 0.0;
-        let mut add_speed = 0.0;
-
-        // get attack damage
-        if let Some(item_stack) = item_slot {
-            if let Some(item) = get_item_by_id(item_stack.item_id) {
-                // TODO: this should be cached in memory
-                if let Some(modifiers) = &item.components.attribute_modifiers {
-                    for item_mod in &modifiers.modifiers {
-                        if item_mod.operation == Operation::AddValue {
-                            if item_mod.id == "minecraft:base_attack_damage" {
-                                add_damage = item_mod.amount;
-                            }
-                            if item_mod.id == "minecraft:base_attack_speed" {
-                                add_speed = item_mod.amount;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        drop(inventory);
+        let add_damage = modifiers.damage
+            + attack_damage_bonus(
+                self.living_entity.effect_amplifier(Effect::Strength).await,
+                self.living_entity.effect_amplifier(Effect::Weakness).await,
+            );
+        let add_speed = modifiers.speed;
+
+        // Attacking gives up the own grace, but players still in theirs can not be hurt
+        self.cancel_spawn_grace().await;
+        let in_grace = victim.spawn_grace_ticks.load(Ordering::Relaxed) > 0;
 
         let attack_speed = base_attack_speed + add_speed;
 
@@ -317,6 +479,8 @@ impl Player {
         let pos = victim_entity.pos.load();
 
         if (config.protect_creative && victim.gamemode.load() == GameMode::Creative)
+            || in_grace
+            || world.is_protected(&victim_entity.block_pos.load(), self)
             || !victim.living_entity.check_damage(damage as f32)
         {
             world
@@ -345,6 +509,7 @@ impl Player {
             .living_entity
             .damage(damage as f32, 34) // PlayerAttack
             .await;
+        self.add_exhaustion(hunger::ATTACK_EXHAUSTION);
 
         let mut knockback_strength = 1.0;
         match attack_type {
@@ -418,8 +583,23 @@ impl Player {
         self.last_attacked_ticks
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
-        self.living_entity.tick();
+        self.living_entity.tick().await;
         self.tick_client_load_timeout();
+        if tick_spawn_grace(&self.spawn_grace_ticks) {
+            self.show_spawn_grace_indicator("Your spawn protection has ended")
+                .await;
+        }
+        self.tick_hunger().await;
+        self.tick_eating().await;
+
+        // Players below the world slowly die, like in vanilla
+        let void_y = f64::from(self.world().min_y - 64);
+        if self.living_entity.entity.pos.load().y < void_y
+            && self.living_entity.health.load() > 0.0
+            && self.living_entity.check_damage(4.0)
+        {
+            self.living_entity.damage(4.0, 32).await; // OutOfWorld
+        }
 
         if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
             // We never got a response from our last keep alive we send
@@ -441,6 +621,42 @@ impl Player {
         }
     }
 
+    /// Protects the player from other players for a while after joining or respawning, to prevent spawn killing
+    pub async fn start_spawn_grace(&self) {
+        let ticks = ADVANCED_CONFIG.pvp.spawn_grace_ticks;
+        self.spawn_grace_ticks.store(ticks, Ordering::Relaxed);
+        if ticks > 0 {
+            self.show_spawn_grace_indicator(&format!(
+                "You are protected from other players for {} seconds",
+                ticks.div_ceil(20)
+            ))
+            .await;
+        }
+    }
+
+    /// Ends the spawn grace early, like when the player attacks someone
+    pub async fn cancel_spawn_grace(&self) {
+        if self.spawn_grace_ticks.swap(0, Ordering::Relaxed) > 0 {
+            self.show_spawn_grace_indicator("Your spawn protection has ended")
+                .await;
+        }
+    }
+
+    async fn show_spawn_grace_indicator(&self, message: &str) {
+        self.show_title(
+            &TextComponent::text(message.to_string())
+                .color_named(NamedColor::Gray)
+                .italic(),
+            &TitleMode::ActionBar,
+        )
+        .await;
+    }
+
+    /// Spectators can fly through blocks and may not interact with the world
+    pub fn is_spectator(&self) -> bool {
+        self.gamemode.load() == GameMode::Spectator
+    }
+
     pub fn has_client_loaded(&self) -> bool {
         self.client_loaded.load(Ordering::Relaxed)
             || self.client_loaded_timeout.load(Ordering::Relaxed) == 0
@@ -522,16 +738,25 @@ impl Player {
 
     /// Sends the world time to just the player.
     pub async fn send_time(&self, world: &World) {
+        let do_daylight_cycle = world.game_rules.lock().await.do_daylight_cycle;
         let l_world = world.level_time.lock().await;
         self.client
             .send_packet(&CUpdateTime::new(
                 l_world.world_age,
                 l_world.time_of_day,
-                true,
+                do_daylight_cycle,
             ))
             .await;
     }
 
+    /// Sends the weather of the world to just the player.
+    pub async fn send_weather(&self, world: &World) {
+        let weather_type = world.weather.lock().await.weather_type;
+        for packet in weather_type.game_events() {
+            self.client.send_packet(&packet).await;
+        }
+    }
+
     /// Sends the mobs to just the player.
     // TODO: This should be optimized for larger servers based on current player chunk
     pub async fn send_mobs(&self, world: &World) {
@@ -540,6 +765,10 @@ impl Player {
             self.client
                 .send_packet(&mob.living_entity.entity.create_spawn_packet(uuid))
                 .await;
+            if let Some(packet) = mob.living_entity.entity.create_custom_name_packet().await {
+                self.client.send_packet(&packet).await;
+            }
+            mob.living_entity.send_effects(&self.client).await;
         }
     }
 
@@ -571,6 +800,31 @@ impl Player {
             .await;
     }
 
+    /// Like [`Self::request_teleport`], but moves the position up out of blocks or down to the ground first.
+    /// Returns false and tells the player if there is no safe position nearby
+    pub async fn safe_teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) -> bool {
+        let block = BlockPos(Vector3::new(
+            position.x.floor() as i32,
+            position.y.floor() as i32,
+            position.z.floor() as i32,
+        ));
+        let Some(safe) = self
+            .world()
+            .find_safe_position(block, SAFE_TELEPORT_RANGE)
+            .await
+        else {
+            self.send_system_message(
+                &TextComponent::text("There is no safe place to teleport to")
+                    .color_named(NamedColor::Red),
+            )
+            .await;
+            return false;
+        };
+        let position = Vector3::new(position.x, f64::from(safe.0.y), position.z);
+        self.request_teleport(position, yaw, pitch).await;
+        true
+    }
+
     pub fn block_interaction_range(&self) -> f64 {
         if self.gamemode.load() == GameMode::Creative {
             5.0
@@ -591,6 +845,19 @@ impl Player {
         }) < d * d
     }
 
+    /// Returns the first non air block the player is looking at within their block interaction range
+    pub async fn target_block(&self) -> Option<BlockPos> {
+        let entity = &self.living_entity.entity;
+        entity
+            .world
+            .raycast_block(
+                entity.eye_position(),
+                entity.rotation_vector(),
+                self.block_interaction_range(),
+            )
+            .await
+    }
+
     /// Kicks the Client with a reason depending on the connection state
     pub async fn kick(&self, reason: TextComponent) {
         if self
@@ -627,6 +894,50 @@ impl Player {
             .await;
     }
 
+    /// Sets the level and progress of the player, the total points follow from them.
+    /// Levels are clamped to [`experience::MAX_LEVEL`]
+    pub async fn set_experience(&self, level: i32, progress: f32) {
+        let level = level.clamp(0, experience::MAX_LEVEL);
+        let progress = progress.clamp(0.0, 1.0);
+        self.experience_level.store(level, Ordering::Relaxed);
+        self.experience_progress.store(progress);
+        self.total_experience.store(
+            experience::points_from_level(level, progress),
+            Ordering::Relaxed,
+        );
+        self.send_experience().await;
+    }
+
+    /// Adds or removes points, which can make the player reach another level
+    pub async fn add_experience_points(&self, points: i32) {
+        let total = self
+            .total_experience
+            .load(Ordering::Relaxed)
+            .saturating_add(points);
+        let (level, progress) = experience::level_from_points(total);
+        self.set_experience(level, progress).await;
+    }
+
+    /// Adds or removes levels, keeping the progress towards the next one
+    pub async fn add_experience_levels(&self, levels: i32) {
+        let level = self
+            .experience_level
+            .load(Ordering::Relaxed)
+            .saturating_add(levels);
+        self.set_experience(level, self.experience_progress.load())
+            .await;
+    }
+
+    pub async fn send_experience(&self) {
+        self.client
+            .send_packet(&CSetExperience::new(
+                self.experience_progress.load(),
+                self.experience_level.load(Ordering::Relaxed).into(),
+                self.total_experience.load(Ordering::Relaxed).into(),
+            ))
+            .await;
+    }
+
     pub fn tick_client_load_timeout(&self) {
         if !self.client_loaded.load(Ordering::Relaxed) {
             let timeout = self.client_loaded_timeout.load(Ordering::Relaxed);
@@ -635,15 +946,249 @@ impl Player {
         }
     }
 
-    pub async fn kill(&self) {
+    /// Consumes a totem of undying from the main hand or offhand to survive lethal damage of the
+    /// given damage type. Returns whether a totem was used
+    pub async fn try_use_totem(&self, damage_type: u8) -> bool {
+        // Like `/kill` and falling out of the world
+        if damage_type_has_tag(damage_type, "bypasses_invulnerability") {
+            return false;
+        }
+        let Some(totem) = get_item("totem_of_undying") else {
+            return false;
+        };
+        {
+            let mut inventory = self.inventory.lock().await;
+            let is_totem = |slot: &Option<ItemStack>| {
+                slot.as_ref().is_some_and(|stack| stack.item_id == totem.id)
+            };
+            let slot = if is_totem(inventory.held_item_mut()) {
+                inventory.held_item_mut()
+            } else if is_totem(inventory.offhand_mut()) {
+                inventory.offhand_mut()
+            } else {
+                return false;
+            };
+            if let Some(stack) = slot {
+                stack.item_count -= 1;
+                if stack.item_count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+        self.set_container_content(None).await;
+
+        self.set_health(
+            1.0,
+            self.food.load(Ordering::Relaxed),
+            self.food_saturation.load(),
+        )
+        .await;
+        // The totem replaces all effects
+        self.living_entity.clear_effects().await;
+        for (effect, amplifier, duration) in TOTEM_EFFECTS {
+            self.living_entity
+                .add_effect(effect, EffectInstance::new(amplifier, duration, true))
+                .await;
+        }
+        // Plays the totem animation, particles and sound
+        self.world()
+            .broadcast_packet_all(&CEntityStatus::new(self.entity_id(), 35))
+            .await;
+        true
+    }
+
+    /// Kills the player, showing the death message of the damage type with the given message id, e.g. `generic`
+    pub async fn kill(&self, message_id: &str) {
         self.living_entity.kill().await;
         self.set_client_loaded(false);
+        let death_message = TextComponent::translate(
+            format!("death.attack.{message_id}"),
+            [self.display_name().await].into(),
+        );
         self.client
-            .send_packet(&CCombatDeath::new(
-                self.entity_id().into(),
-                &TextComponent::text("noob"),
+            .send_packet(&CCombatDeath::new(self.entity_id().into(), &death_message))
+            .await;
+        if self.world().hardcore {
+            self.handle_hardcore_death(&death_message).await;
+        }
+    }
+
+    /// Gives the starter inventory to players joining for the first time.
+    /// Players never get it twice, even if their inventory is empty
+    pub async fn give_starter_inventory(&self, server: &Server) {
+        if self.received_starter_inventory.load(Ordering::Relaxed)
+            || server.player_data_storage.has_data(&self.gameprofile.id)
+        {
+            return;
+        }
+        self.received_starter_inventory
+            .store(true, Ordering::Relaxed);
+        for KitItem { item, count } in &STARTER_INVENTORY.read().await.items {
+            // Unknown items were already reported when loading the list
+            if let Some(item) = get_item(item) {
+                self.give_items(item, *count).await;
+            }
+        }
+    }
+
+    /// Players who died in hardcore mode can only spectate, or are banned if configured
+    async fn handle_hardcore_death(&self, death_message: &TextComponent) {
+        self.world().broadcast_system_message(death_message).await;
+
+        if BASIC_CONFIG.hardcore_ban_on_death {
+            let mut banned_players = BANNED_PLAYER_LIST.write().await;
+            if banned_players.get_entry(&self.gameprofile).is_none() {
+                banned_players.banned_players.push(BannedPlayerEntry::new(
+                    &self.gameprofile,
+                    "Server".to_string(),
+                    None,
+                    "Death in Hardcore".to_string(),
+                ));
+                banned_players.save();
+            }
+            drop(banned_players);
+            self.kick(TextComponent::translate(
+                "deathScreen.title.hardcore",
+                [].into(),
+            ))
+            .await;
+            return;
+        }
+
+        // Respawning keeps the gamemode, so the player stays a spectator
+        if self.gamemode.load() != GameMode::Spectator {
+            self.set_gamemode(GameMode::Spectator).await;
+        }
+    }
+
+    /// The last status the client reported for the server's resource pack, `None` if no pack was sent
+    pub fn resource_pack_status(&self) -> Option<ResourcePackStatus> {
+        self.client.resource_pack_status.load()
+    }
+
+    /// The name shown in chat, the tab list and messages. Falls back to the username
+    pub async fn display_name(&self) -> TextComponent {
+        self.display_name
+            .lock()
+            .await
+            .clone()
+            .unwrap_or_else(|| TextComponent::text(self.gameprofile.name.clone()))
+    }
+
+    /// Whether a display name was set, see [`Player::display_name`]
+    pub async fn custom_display_name(&self) -> Option<TextComponent> {
+        self.display_name.lock().await.clone()
+    }
+
+    /// Changes the name shown instead of the username, `None` shows the username again.
+    /// The username is still used for targeting players in commands
+    pub async fn set_display_name(&self, display_name: Option<TextComponent>) {
+        self.world()
+            .broadcast_packet_all(&CPlayerInfoUpdate::new(
+                0x20,
+                &[pumpkin_protocol::client::play::Player {
+                    uuid: self.gameprofile.id,
+                    actions: vec![PlayerAction::UpdateDisplayName(display_name.as_ref())],
+                }],
             ))
             .await;
+        *self.display_name.lock().await = display_name;
+    }
+
+    /// Shows a sidebar only this player sees, replacing the previous one.
+    /// Lines are shown from top to bottom, only the first [`SIDEBAR_MAX_LINES`] are kept
+    pub async fn set_sidebar(&self, title: TextComponent, mut lines: Vec<TextComponent>) {
+        lines.truncate(SIDEBAR_MAX_LINES);
+        let mut sidebar_lines = self.sidebar_lines.lock().await;
+        let mode = if sidebar_lines.is_some() {
+            Mode::Update
+        } else {
+            Mode::Add
+        };
+        // The scores are only used for ordering, so they are hidden
+        self.client
+            .send_packet(&CUpdateObjectives::new(
+                SIDEBAR_OBJECTIVE,
+                mode,
+                title,
+                RenderType::Integer,
+                Some(NumberFormat::Blank),
+            ))
+            .await;
+        if sidebar_lines.is_none() {
+            self.client
+                .send_packet(&CDisplayObjective::new(
+                    ScoreboardDisplaySlot::Sidebar,
+                    SIDEBAR_OBJECTIVE,
+                ))
+                .await;
+        }
+
+        let count = lines.len();
+        for (index, line) in lines.into_iter().enumerate() {
+            self.client
+                .send_packet(&CUpdateScore::new(
+                    &sidebar_entry(index),
+                    SIDEBAR_OBJECTIVE,
+                    VarInt(sidebar_score(index, count)),
+                    Some(line),
+                    None,
+                ))
+                .await;
+        }
+        for index in count..sidebar_lines.unwrap_or(0) {
+            self.client
+                .send_packet(&CResetScore::new(
+                    sidebar_entry(index),
+                    Some(SIDEBAR_OBJECTIVE.to_string()),
+                ))
+                .await;
+        }
+        *sidebar_lines = Some(count);
+    }
+
+    /// Hides the sidebar set with [`Player::set_sidebar`]
+    pub async fn clear_sidebar(&self) {
+        if self.sidebar_lines.lock().await.take().is_none() {
+            return;
+        }
+        self.client
+            .send_packet(&CUpdateObjectives::new(
+                SIDEBAR_OBJECTIVE,
+                Mode::Remove,
+                TextComponent::text(""),
+                RenderType::Integer,
+                None,
+            ))
+            .await;
+    }
+
+    /// Lets the player float in place, independent of their flying ability
+    pub async fn set_no_gravity(&self, no_gravity: bool) {
+        if no_gravity {
+            self.living_entity.fall_distance.store(0.0);
+        }
+        self.living_entity.entity.set_no_gravity(no_gravity).await;
+    }
+
+    /// Grants or revokes flight in survival and adventure mode, the grant is kept across gamemode changes
+    pub async fn set_flight_granted(&self, granted: bool) {
+        {
+            let mut abilities = self.abilities.lock().await;
+            abilities.flight_granted = granted;
+            if !matches!(
+                self.gamemode.load(),
+                GameMode::Creative | GameMode::Spectator
+            ) {
+                abilities.allow_flying = granted;
+                if !granted && abilities.flying {
+                    abilities.flying = false;
+                    // Let the player fall from where flight was revoked
+                    self.living_entity.fall_distance.store(0.0);
+                }
+            }
+        }
+        self.send_abilities_update().await;
     }
 
     pub async fn set_gamemode(&self, gamemode: GameMode) {
@@ -720,9 +1265,27 @@ impl Player {
     }
 
     pub async fn send_system_message_raw(&self, text: &TextComponent, overlay: bool) {
-        self.client
-            .send_packet(&CSystemChatMessage::new(text, overlay))
-            .await;
+        if !self
+            .config
+            .lock()
+            .await
+            .chat_mode
+            .accepts_system_messages(overlay)
+        {
+            return;
+        }
+        // Without a language of its own the client translates the message
+        let language = self.language.lock().await.clone();
+        if let Some(language) = language.and_then(|locale| LANGUAGES.get(&locale)) {
+            let text = text.clone().localize(language);
+            self.client
+                .send_packet(&CSystemChatMessage::new(&text, overlay))
+                .await;
+        } else {
+            self.client
+                .send_packet(&CSystemChatMessage::new(text, overlay))
+                .await;
+        }
     }
 }
 #[async_trait]
@@ -734,12 +1297,92 @@ impl NBTStorage for Player {
             self.inventory.lock().await.selected as i32,
         );
         self.abilities.lock().await.write_nbt(nbt).await;
+        if let Some(display_name) = self.display_name.lock().await.as_ref() {
+            write_custom_name(nbt, display_name);
+        }
+        if let Some(respawn_point) = self.respawn_point.load() {
+            nbt.put_int("SpawnX", respawn_point.0.x);
+            nbt.put_int("SpawnY", respawn_point.0.y);
+            nbt.put_int("SpawnZ", respawn_point.0.z);
+        }
+        nbt.put_bool(
+            "ReceivedStarterInventory",
+            self.received_starter_inventory.load(Ordering::Relaxed),
+        );
+        nbt.put_int("XpLevel", self.experience_level.load(Ordering::Relaxed));
+        nbt.put_float("XpP", self.experience_progress.load());
+        nbt.put_int("XpTotal", self.total_experience.load(Ordering::Relaxed));
+        nbt.put_int("foodLevel", self.food.load(Ordering::Relaxed));
+        nbt.put_float("foodSaturationLevel", self.food_saturation.load());
+        nbt.put_int(
+            "foodTickTimer",
+            self.food_tick_timer.load(Ordering::Relaxed) as i32,
+        );
+        nbt.put_float("foodExhaustionLevel", self.food_exhaustion.load());
+        nbt.put_int("playerGameType", self.gamemode.load() as i32);
+        if let Some(language) = self.language.lock().await.as_ref() {
+            nbt.put("Language", language.as_str());
+        }
+        let mut cooldowns = NbtCompound::new();
+        for (key, last_run) in self.command_cooldowns.lock().await.iter() {
+            let since_epoch = last_run.duration_since(UNIX_EPOCH).unwrap_or_default();
+            cooldowns.put_long(key, since_epoch.as_millis() as i64);
+        }
+        nbt.put_component("CommandCooldowns", cooldowns);
     }
 
     async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
         self.living_entity.read_nbt(nbt).await;
         self.inventory.lock().await.selected = nbt.get_int("SelectedItemSlot").unwrap_or(0) as u32;
         self.abilities.lock().await.read_nbt(nbt).await;
+        // Players show their custom name in chat and the tab list instead of above their head
+        *self.display_name.get_mut() = self.living_entity.entity.custom_name.get_mut().take();
+        if let (Some(x), Some(y), Some(z)) = (
+            nbt.get_int("SpawnX"),
+            nbt.get_int("SpawnY"),
+            nbt.get_int("SpawnZ"),
+        ) {
+            self.respawn_point
+                .store(Some(BlockPos(Vector3::new(x, y, z))));
+        }
+        self.received_starter_inventory.store(
+            nbt.get_bool("ReceivedStarterInventory").unwrap_or(false),
+            Ordering::Relaxed,
+        );
+        self.experience_level
+            .store(nbt.get_int("XpLevel").unwrap_or(0), Ordering::Relaxed);
+        self.experience_progress
+            .store(nbt.get_float("XpP").unwrap_or(0.0));
+        self.total_experience
+            .store(nbt.get_int("XpTotal").unwrap_or(0), Ordering::Relaxed);
+        self.food
+            .store(nbt.get_int("foodLevel").unwrap_or(20), Ordering::Relaxed);
+        self.food_saturation
+            .store(nbt.get_float("foodSaturationLevel").unwrap_or(20.0));
+        self.food_tick_timer.store(
+            nbt.get_int("foodTickTimer").unwrap_or(0) as u32,
+            Ordering::Relaxed,
+        );
+        self.food_exhaustion
+            .store(nbt.get_float("foodExhaustionLevel").unwrap_or(0.0));
+        *self.language.get_mut() = nbt.get_string("Language").cloned();
+        if let Some(gamemode) = nbt.get_int("playerGameType") {
+            match GameMode::from(gamemode as i8) {
+                GameMode::Undefined => {}
+                gamemode => self.gamemode.store(gamemode),
+            }
+        }
+        if let Some(cooldowns) = nbt.get_compound("CommandCooldowns") {
+            *self.command_cooldowns.get_mut() = cooldowns
+                .child_tags
+                .iter()
+                .filter_map(|(key, tag)| {
+                    let since_epoch =
+                        Duration::from_millis(u64::try_from(tag.extract_long()?).ok()?);
+                    Some((key.clone(), UNIX_EPOCH + since_epoch))
+                })
+                .collect();
+        }
     }
 }
 
@@ -780,6 +1423,9 @@ impl Player {
         server: &Arc<Server>,
         packet: &mut RawPacket,
     ) -> Result<(), Box<dyn PumpkinError>> {
+        if !packet_events::fire_receive(self, packet).await {
+            return Ok(());
+        }
         let bytebuf = &mut packet.bytebuf;
         match packet.id.0 {
             SConfirmTeleport::PACKET_ID => {
@@ -787,11 +1433,19 @@ impl Player {
                     .await;
             }
             SChatCommand::PACKET_ID => {
-                self.handle_chat_command(server, &(SChatCommand::read(bytebuf)?));
+                self.handle_chat_command(server, &(SChatCommand::read(bytebuf)?))
+                    .await;
             }
             SChatMessage::PACKET_ID => {
                 self.handle_chat_message(SChatMessage::read(bytebuf)?).await;
             }
+            SChatSessionUpdate::PACKET_ID => {
+                self.handle_chat_session_update(SChatSessionUpdate::read(bytebuf)?)
+                    .await;
+            }
+            SChatAck::PACKET_ID => {
+                self.handle_chat_ack(SChatAck::read(bytebuf)?).await;
+            }
             SClientInformationPlay::PACKET_ID => {
                 self.handle_client_information(SClientInformationPlay::read(bytebuf)?)
                     .await;
@@ -801,7 +1455,7 @@ impl Player {
                     .await;
             }
             SPlayerInput::PACKET_ID => {
-                // TODO
+                self.handle_player_input(&SPlayerInput::read(bytebuf)?);
             }
             SInteract::PACKET_ID => {
                 self.handle_interact(SInteract::read(bytebuf)?).await;
@@ -809,14 +1463,13 @@ impl Player {
             SKeepAlive::PACKET_ID => {
                 self.handle_keep_alive(SKeepAlive::read(bytebuf)?).await;
             }
-            SClientTickEnd::PACKET_ID => {
-                // TODO
-            }
+            SClientTickEnd::PACKET_ID => self.handle_client_tick_end(),
             SPlayerPosition::PACKET_ID => {
-                self.handle_position(SPlayerPosition::read(bytebuf)?).await;
+                self.handle_position(SPlayerPosition::read(bytebuf)?, server)
+                    .await;
             }
             SPlayerPositionRotation::PACKET_ID => {
-                self.handle_position_rotation(SPlayerPositionRotation::read(bytebuf)?)
+                self.handle_position_rotation(SPlayerPositionRotation::read(bytebuf)?, server)
                     .await;
             }
             SPlayerRotation::PACKET_ID => {
@@ -859,6 +1512,10 @@ impl Player {
                 self.handle_set_creative_slot(SSetCreativeSlot::read(bytebuf)?)
                     .await?;
             }
+            SResourcePackResponse::PACKET_ID => {
+                self.handle_resource_pack_response(SResourcePackResponse::read(bytebuf)?)
+                    .await;
+            }
             SSwingArm::PACKET_ID => {
                 self.handle_swing_arm(SSwingArm::read(bytebuf)?).await;
             }
@@ -866,7 +1523,7 @@ impl Player {
                 self.handle_use_item_on(SUseItemOn::read(bytebuf)?, server)
                     .await?;
             }
-            SUseItem::PACKET_ID => self.handle_use_item(&SUseItem::read(bytebuf)?),
+            SUseItem::PACKET_ID => self.handle_use_item(&SUseItem::read(bytebuf)?).await,
             SCommandSuggestion::PACKET_ID => {
                 self.handle_command_suggestion(SCommandSuggestion::read(bytebuf)?, server)
                     .await;
@@ -905,6 +1562,8 @@ pub struct Abilities {
     pub flying: bool,
     /// Indicates whether the player is allowed to fly (if enabled).
     pub allow_flying: bool,
+    /// Indicates whether the player was granted flight outside of creative and spectator mode.
+    pub flight_granted: bool,
     /// Indicates whether the player is in creative mode.
     pub creative: bool,
     /// Indicates whether the player is allowed to modify the world.
@@ -922,6 +1581,7 @@ impl NBTStorage for Abilities {
         component.put_bool("invulnerable", self.invulnerable);
         component.put_bool("flying", self.flying);
         component.put_bool("mayfly", self.allow_flying);
+        component.put_bool("flightGranted", self.flight_granted);
         component.put_bool("instabuild", self.creative);
         component.put_bool("mayBuild", self.allow_modify_world);
         component.put_float("flySpeed", self.fly_speed);
@@ -934,10 +1594,11 @@ impl NBTStorage for Abilities {
             self.invulnerable = component.get_bool("invulnerable").unwrap_or(false);
             self.flying = component.get_bool("flying").unwrap_or(false);
             self.allow_flying = component.get_bool("mayfly").unwrap_or(false);
+            self.flight_granted = component.get_bool("flightGranted").unwrap_or(false);
             self.creative = component.get_bool("instabuild").unwrap_or(false);
             self.allow_modify_world = component.get_bool("mayBuild").unwrap_or(false);
-            self.fly_speed = component.get_float("flySpeed").unwrap_or(0.0);
-            self.walk_speed = component.get_float("walk_speed").unwrap_or(0.0);
+            self.fly_speed = component.get_float("flySpeed").unwrap_or(0.05);
+            self.walk_speed = component.get_float("walkSpeed").unwrap_or(0.1);
         }
     }
 }
@@ -948,6 +1609,7 @@ impl Default for Abilities {
             invulnerable: false,
             flying: false,
             allow_flying: false,
+            flight_granted: false,
             creative: false,
             allow_modify_world: true,
             fly_speed: 0.05,
@@ -973,7 +1635,7 @@ impl Abilities {
             }
             GameMode::Survival | GameMode::Adventure | GameMode::Undefined => {
                 self.flying = false;
-                self.allow_flying = false;
+                self.allow_flying = self.flight_granted;
                 self.creative = false;
                 self.invulnerable = false;
             }
@@ -981,6 +1643,70 @@ impl Abilities {
     }
 }
 
+/// Counts the spawn grace down by one tick, returns true when it just ran out
+fn tick_spawn_grace(grace_ticks: &AtomicU32) -> bool {
+    grace_ticks.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |ticks| {
+        ticks.checked_sub(1)
+    }) == Ok(1)
+}
+
+/// The hidden score holder of a sidebar line, the line's text is shown instead
+fn sidebar_entry(index: usize) -> String {
+    format!("line{index}")
+}
+
+/// The sidebar sorts by descending score, so the first line gets the highest
+const fn sidebar_score(index: usize, count: usize) -> i32 {
+    (count - index) as i32
+}
+
+/// The movement keys a player is holding, as sent by the client every time they change
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlayerInput {
+    pub forward: bool,
+    pub backward: bool,
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+    pub sneak: bool,
+    pub sprint: bool,
+}
+
+impl PlayerInput {
+    #[must_use]
+    pub const fn from_flags(flags: i8) -> Self {
+        Self {
+            forward: flags & SPlayerInput::FORWARD != 0,
+            backward: flags & SPlayerInput::BACKWARD != 0,
+            left: flags & SPlayerInput::LEFT != 0,
+            right: flags & SPlayerInput::RIGHT != 0,
+            jump: flags & SPlayerInput::JUMP != 0,
+            sneak: flags & SPlayerInput::SNEAK != 0,
+            sprint: flags & SPlayerInput::SPRINT != 0,
+        }
+    }
+
+    /// 1 when moving forward, -1 when moving backward and 0 when both or neither are held
+    #[must_use]
+    pub fn forward_impulse(&self) -> f32 {
+        impulse(self.forward, self.backward)
+    }
+
+    /// 1 when moving left, -1 when moving right and 0 when both or neither are held
+    #[must_use]
+    pub fn sideways_impulse(&self) -> f32 {
+        impulse(self.left, self.right)
+    }
+}
+
+fn impulse(positive: bool, negative: bool) -> f32 {
+    match (positive, negative) {
+        (true, false) => 1.0,
+        (false, true) => -1.0,
+        _ => 0.0,
+    }
+}
+
 /// Represents the player's dominant hand.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -1016,6 +1742,27 @@ pub enum ChatMode {
     Hidden,
 }
 
+impl ChatMode {
+    /// Whether the player sees chat messages, `from_command` if they were sent by a command like `/say`
+    #[must_use]
+    pub const fn shows_chat(&self, from_command: bool) -> bool {
+        match self {
+            Self::Enabled => true,
+            Self::CommandsOnly => from_command,
+            Self::Hidden => false,
+        }
+    }
+
+    /// Whether the player sees system messages, players that hid their chat still see `overlay` messages
+    #[must_use]
+    pub const fn accepts_system_messages(&self, overlay: bool) -> bool {
+        match self {
+            Self::Hidden => overlay,
+            Self::Enabled | Self::CommandsOnly => true,
+        }
+    }
+}
+
 pub struct InvalidChatMode;
 
 impl TryFrom<i32> for ChatMode {
@@ -1030,3 +1777,169 @@ impl TryFrom<i32> for ChatMode {
         }
     }
 }
+
+/// The progress of loading the server's resource pack, as reported by the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourcePackStatus {
+    SuccessfullyLoaded,
+    Declined,
+    FailedDownload,
+    Accepted,
+    Downloaded,
+    InvalidUrl,
+    FailedToReload,
+    Discarded,
+}
+
+pub struct InvalidResourcePackStatus;
+
+impl TryFrom<i32> for ResourcePackStatus {
+    type Error = InvalidResourcePackStatus;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::SuccessfullyLoaded),
+            1 => Ok(Self::Declined),
+            2 => Ok(Self::FailedDownload),
+            3 => Ok(Self::Accepted),
+            4 => Ok(Self::Downloaded),
+            5 => Ok(Self::InvalidUrl),
+            6 => Ok(Self::FailedToReload),
+            7 => Ok(Self::Discarded),
+            _ => Err(InvalidResourcePackStatus),
+        }
+    }
+}
+
+/// Creates a player in a new world stored in `folder`, for tests that need a real player
+#[cfg(test)]
+pub(crate) async fn test_player(folder: std::path::PathBuf, uuid: uuid::Uuid) -> Arc<Player> {
+    use pumpkin_registry::DimensionType;
+    use pumpkin_world::dimension::Dimension;
+
+    let world = Arc::new(World::load(
+        Dimension::OverWorld.into_level(folder.to_path_buf()),
+        DimensionType::Overworld,
+    ));
+    test_player_in(world, uuid).await
+}
+
+/// Creates a player in an existing world, it is not added to the players of the world.
+/// Every test player gets its own entity id, so players in the same world can find each other
+#[cfg(test)]
+pub(crate) async fn test_player_in(world: Arc<World>, uuid: uuid::Uuid) -> Arc<Player> {
+    static NEXT_ENTITY_ID: AtomicI32 = AtomicI32::new(1);
+
+    let client = Client::new(
+        tokio::sync::mpsc::channel(1).0,
+        "127.0.0.1:25565".parse().unwrap(),
+        0,
+    );
+    *client.gameprofile.lock().await = Some(GameProfile {
+        id: uuid,
+        name: "Alex".to_string(),
+        properties: vec![],
+        profile_actions: None,
+    });
+    let entity_id = NEXT_ENTITY_ID.fetch_add(1, Ordering::Relaxed);
+    Arc::new(Player::new(Arc::new(client), world, entity_id, GameMode::Survival).await)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_registry::{get_damage_type, DimensionType};
+    use pumpkin_util::GameMode;
+    use pumpkin_world::{dimension::Dimension, item::item_registry::get_item, item::ItemStack};
+
+    use std::sync::{atomic::Ordering, Arc};
+
+    use super::{sidebar_score, test_player, test_player_in};
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::effect::Effect;
+    use crate::world::World;
+
+    #[tokio::test]
+    async fn hardcore_death_leaves_player_in_spectator() {
+        let folder = TestFolder::new("hardcore-death");
+        let mut world = World::load(
+            Dimension::OverWorld.into_level(folder.to_path_buf()),
+            DimensionType::Overworld,
+        );
+        world.hardcore = true;
+        let world = Arc::new(world);
+        let player = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        world
+            .current_players
+            .lock()
+            .await
+            .insert(player.gameprofile.id, player.clone());
+
+        let (fall, _) = get_damage_type("fall").unwrap();
+        player.living_entity.damage(100.0, fall).await;
+        assert_eq!(player.living_entity.health.load(), 0.0);
+        assert_eq!(player.gamemode.load(), GameMode::Spectator);
+    }
+
+    #[tokio::test]
+    async fn totems_do_not_save_from_kill_damage() {
+        let folder = TestFolder::new("totem");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let world = player.world().clone();
+        world
+            .current_players
+            .lock()
+            .await
+            .insert(player.gameprofile.id, player.clone());
+        let totem = get_item("totem_of_undying").unwrap();
+        *player.inventory.lock().await.held_item_mut() = Some(ItemStack::new(1, totem.id));
+
+        let (fall, _) = get_damage_type("fall").unwrap();
+        player.living_entity.damage(100.0, fall).await;
+        assert_eq!(player.living_entity.health.load(), 1.0);
+        assert!(player.inventory.lock().await.held_item_mut().is_none());
+        assert!(player
+            .living_entity
+            .active_effects
+            .lock()
+            .await
+            .contains_key(&Effect::Regeneration));
+
+        *player.inventory.lock().await.held_item_mut() = Some(ItemStack::new(1, totem.id));
+        let (generic_kill, _) = get_damage_type("generic_kill").unwrap();
+        player.living_entity.damage(100.0, generic_kill).await;
+        assert_eq!(player.living_entity.health.load(), 0.0);
+        assert!(player.inventory.lock().await.held_item_mut().is_some());
+    }
+
+    #[test]
+    fn sidebar_lines_top_to_bottom() {
+        let scores: Vec<_> = (0..3).map(|index| sidebar_score(index, 3)).collect();
+        assert!(scores.is_sorted_by(|a, b| a > b));
+        assert!(scores.iter().all(|&score| score > 0));
+    }
+
+    #[tokio::test]
+    async fn attacks_blocked_during_spawn_grace() {
+        let folder = TestFolder::new("spawn-grace");
+        let attacker = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let victim = test_player_in(attacker.world().clone(), uuid::Uuid::new_v4()).await;
+        let health = victim.living_entity.health.load();
+
+        // blocked on join
+        victim.start_spawn_grace().await;
+        attacker.attack(&victim).await;
+        assert_eq!(victim.living_entity.health.load(), health);
+
+        // applied again once it ran out
+        victim.spawn_grace_ticks.store(1, Ordering::Relaxed);
+        victim.tick().await;
+        assert_eq!(victim.spawn_grace_ticks.load(Ordering::Relaxed), 0);
+        attacker.attack(&victim).await;
+        assert!(victim.living_entity.health.load() < health);
+
+        // attacking gives up the own grace
+        attacker.start_spawn_grace().await;
+        attacker.attack(&victim).await;
+        assert_eq!(attacker.spawn_grace_ticks.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,215 @@
+//! Lets spectators follow an entity with their camera, like vanilla's `/spectate`.
+//! For observing matches, the followed entity's health and position are shown in the action bar
+//! and the entities around it are outlined for the spectator only, unless they turned the HUD off
+
+use std::sync::{atomic::Ordering, Arc};
+
+use pumpkin_protocol::client::play::CSetCamera;
+use pumpkin_util::{
+    math::{boundingbox::BoundingBox, vector3::Vector3},
+    text::{color::NamedColor, TextComponent},
+};
+
+use crate::command::text::CommandTextExt;
+use crate::world::{entity_query::WorldEntity, player_chunker, World};
+
+use super::player::{Player, TitleMode};
+
+/// Entities closer than this to the followed entity are outlined
+const OUTLINE_RADIUS: f64 = 24.0;
+/// How often the HUD is refreshed in ticks, the action bar fades after a few seconds without updates
+const HUD_INTERVAL: u32 = 10;
+
+/// The entity a spectator follows, see [`Player::start_spectating`]
+pub struct Spectating {
+    target: WorldEntity,
+    /// The entities currently outlined for the spectator
+    outlined: Vec<WorldEntity>,
+    ticks: u32,
+}
+
+impl Spectating {
+    #[must_use]
+    pub const fn target(&self) -> &WorldEntity {
+        &self.target
+    }
+}
+
+impl Player {
+    /// Follows `target` with the camera until the player stops, sneaks or leaves spectator mode.
+    /// The target has to be in the player's world
+    pub async fn start_spectating(&self, target: WorldEntity) {
+        self.stop_spectating().await;
+        let entity = target.entity();
+        // Bring the client close to the target first, so it knows the entity to attach the camera to
+        self.request_teleport(entity.pos.load(), entity.yaw.load(), entity.pitch.load())
+            .await;
+        self.client
+            .send_packet(&CSetCamera::new(entity.entity_id.into()))
+            .await;
+        *self.spectating.lock().await = Some(Spectating {
+            target,
+            outlined: Vec::new(),
+            ticks: 0,
+        });
+    }
+
+    /// Moves the camera back to the player, returns the entity they followed
+    pub async fn stop_spectating(&self) -> Option<WorldEntity> {
+        let spectating = self.spectating.lock().await.take()?;
+        for outlined in &spectating.outlined {
+            outlined.entity().send_glowing_to(self, false).await;
+        }
+        let entity = &self.living_entity.entity;
+        self.client
+            .send_packet(&CSetCamera::new(entity.entity_id.into()))
+            .await;
+        // The client is still where it started following, leave the player where the camera was instead
+        self.request_teleport(entity.pos.load(), entity.yaw.load(), entity.pitch.load())
+            .await;
+        Some(spectating.target)
+    }
+
+    pub async fn set_spectator_hud(&self, enabled: bool) {
+        self.spectator_hud.store(enabled, Ordering::Relaxed);
+        if enabled {
+            return;
+        }
+        if let Some(spectating) = self.spectating.lock().await.as_mut() {
+            for outlined in spectating.outlined.drain(..) {
+                outlined.entity().send_glowing_to(self, false).await;
+            }
+        }
+    }
+
+    /// Moves the player along with the entity they follow, like vanilla the client does not move while
+    /// following. Stops when the entity died or left the player's world
+    pub async fn tick_spectating(self: &Arc<Self>) {
+        let mut spectating = self.spectating.lock().await;
+        let Some(state) = spectating.as_mut() else {
+            return;
+        };
+        let entity = &self.living_entity.entity;
+        if !self.is_spectator() || entity.sneaking.load(Ordering::Relaxed) {
+            drop(spectating);
+            self.stop_spectating().await;
+            return;
+        }
+
+        let reason = if state.target.living_entity().health.load() <= 0.0 {
+            Some(" died")
+        } else if !is_in_world(&state.target, &entity.world).await {
+            Some(" left")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            let target = state.target.clone();
+            drop(spectating);
+            self.stop_spectating().await;
+            self.send_system_message(
+                &TextComponent::text("Stopped spectating, ")
+                    .add_child(TextComponent::world_entity_mention(&target))
+                    .add_child(TextComponent::text(reason))
+                    .color_named(NamedColor::Gray),
+            )
+            .await;
+            return;
+        }
+
+        let target = state.target.entity();
+        self.living_entity.set_pos(target.pos.load());
+        player_chunker::update_position(self).await;
+
+        if self.spectator_hud.load(Ordering::Relaxed) && state.ticks % HUD_INTERVAL == 0 {
+            let health = state.target.living_entity().health.load();
+            self.show_title(
+                &TextComponent::world_entity_mention(&state.target)
+                    .add_child(TextComponent::text(hud_line(health, target.pos.load()))),
+                &TitleMode::ActionBar,
+            )
+            .await;
+            self.update_outlines(state).await;
+        }
+        state.ticks = state.ticks.wrapping_add(1);
+    }
+
+    async fn update_outlines(&self, state: &mut Spectating) {
+        let center = state.target.entity().pos.load();
+        let radius = Vector3::new(OUTLINE_RADIUS, OUTLINE_RADIUS, OUTLINE_RADIUS);
+        let area = BoundingBox::new(center.sub(&radius), center.add(&radius));
+        let own_id = self.living_entity.entity.entity_id;
+        let nearby: Vec<_> = state
+            .target
+            .entity()
+            .world
+            .get_entities_in_box(&area)
+            .await
+            .into_iter()
+            .filter(|nearby| {
+                let entity = nearby.entity();
+                entity.entity_id != own_id
+                    && entity.pos.load().squared_distance_to_vec(center)
+                        <= OUTLINE_RADIUS * OUTLINE_RADIUS
+            })
+            .collect();
+
+        for outlined in &state.outlined {
+            let id = outlined.entity().entity_id;
+            if !nearby.iter().any(|nearby| nearby.entity().entity_id == id) {
+                outlined.entity().send_glowing_to(self, false).await;
+            }
+        }
+        // Sent again every time, as changes of the entity's own flags replace the outline
+        for nearby in &nearby {
+            nearby.entity().send_glowing_to(self, true).await;
+        }
+        state.outlined = nearby;
+    }
+}
+
+/// Players are removed from their world when they quit, mobs when they died or despawned
+async fn is_in_world(entity: &WorldEntity, world: &World) -> bool {
+    match entity {
+        WorldEntity::Player(player) => world
+            .current_players
+            .lock()
+            .await
+            .get(&player.gameprofile.id)
+            .is_some_and(|current| Arc::ptr_eq(current, player)),
+        WorldEntity::Mob(mob) => world
+            .current_living_mobs
+            .lock()
+            .await
+            .contains_key(&mob.living_entity.entity.entity_uuid),
+    }
+}
+
+/// The health and block position of the followed entity shown after its name
+fn hud_line(health: f32, pos: Vector3<f64>) -> String {
+    format!(
+        " | ❤ {health:.1} | {} {} {}",
+        pos.x.floor() as i32,
+        pos.y.floor() as i32,
+        pos.z.floor() as i32
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::hud_line;
+
+    #[test]
+    fn hud_shows_health_and_block_position() {
+        assert_eq!(
+            hud_line(17.5, Vector3::new(12.7, 64.0, -4.2)),
+            " | ❤ 17.5 | 12 64 -5"
+        );
+        assert_eq!(
+            hud_line(20.0, Vector3::new(-0.5, -10.3, 0.0)),
+            " | ❤ 20.0 | -1 -11 0"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::command::function::FUNCTIONS;
+use crate::data::last_seen_data::LAST_SEEN_LIST;
 use crate::net::{lan_broadcast, query, rcon::RCONServer, Client};
 use crate::server::{ticker::Ticker, Server};
 use plugin::PluginManager;
@@ -7,6 +9,7 @@ use rustyline::DefaultEditor;
 use std::{
     net::SocketAddr,
     sync::{Arc, LazyLock},
+    time::Duration,
 };
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
@@ -40,6 +43,8 @@ pub struct PumpkinServer {
 impl PumpkinServer {
     pub async fn new() -> Self {
         let server = Arc::new(Server::new());
+        // Loaded before the first tick, which runs the load functions
+        LazyLock::force(&FUNCTIONS);
 
         // Setup the TCP server socket.
         let listener = tokio::net::TcpListener::bind(BASIC_CONFIG.server_address)
@@ -145,15 +150,30 @@ impl PumpkinServer {
 
             let server = self.server.clone();
             tokio::spawn(async move {
-                while !client.closed.load(std::sync::atomic::Ordering::Relaxed)
-                    && !client
-                        .make_player
-                        .load(std::sync::atomic::Ordering::Relaxed)
+                let pre_play = async {
+                    while !client.closed.load(std::sync::atomic::Ordering::Relaxed)
+                        && !client
+                            .make_player
+                            .load(std::sync::atomic::Ordering::Relaxed)
+                    {
+                        let open = poll(&client, connection_reader.clone()).await;
+                        if open {
+                            client.process_packets(&server).await;
+                        };
+                    }
+                };
+                // Connections which never reach the play state should not be kept open forever
+                let login_timeout = ADVANCED_CONFIG.networking.login_timeout;
+                if login_timeout == 0 {
+                    pre_play.await;
+                } else if tokio::time::timeout(Duration::from_secs(login_timeout), pre_play)
+                    .await
+                    .is_err()
                 {
-                    let open = poll(&client, connection_reader.clone()).await;
-                    if open {
-                        client.process_packets(&server).await;
-                    };
+                    log::info!(
+                        "Closing stale connection (id {id}), did not finish login within {login_timeout} seconds"
+                    );
+                    client.close();
                 }
                 if client
                     .make_player
@@ -163,6 +183,8 @@ impl PumpkinServer {
                     world
                         .spawn_player(&BASIC_CONFIG, player.clone(), &server)
                         .await;
+                    player.give_starter_inventory(&server).await;
+                    LAST_SEEN_LIST.write().await.login(&player.gameprofile);
 
                     // poll Player
                     while !player
@@ -176,6 +198,13 @@ impl PumpkinServer {
                         };
                     }
                     log::debug!("Cleaning up player for id {}", id);
+                    if let Err(err) = server.player_data_storage.save(&player).await {
+                        log::error!(
+                            "Failed to save player data of {}: {err}",
+                            player.gameprofile.name
+                        );
+                    }
+                    LAST_SEEN_LIST.write().await.logout(&player.gameprofile.id);
                     player.remove().await;
                     server.remove_player().await;
                 }
@@ -259,4 +288,4 @@ fn scrub_address(ip: &str) -> String {
     ip.chars()
         .map(|ch| if ch == '.' || ch == ':' { ch } else { 'x' })
         .collect()
-}
\ No newline at end of file
+}
@@ -0,0 +1,305 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use pumpkin_config::ChatConfig;
+use pumpkin_protocol::{client::play::ChatSession as ProtocolChatSession, FixedBitSet};
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::{entity::player::Player, world::World};
+
+/// The amount of messages a client acknowledges at most when sending a message
+const LAST_SEEN_COUNT: usize = 20;
+
+/// The public key a player signs their chat messages with.
+///
+/// Clients only display a player's chat message as secure if they know this session, so it is
+/// shared with every other player. Without it, "Only Show Secure Chat" hides their messages.
+#[derive(Clone)]
+pub struct ChatSession {
+    pub session_id: uuid::Uuid,
+    /// Expiry time of the public key, in epoch milliseconds
+    pub expires_at: i64,
+    pub public_key: Bytes,
+    pub key_signature: Bytes,
+}
+
+impl ChatSession {
+    pub fn to_protocol(&self) -> ProtocolChatSession<'_> {
+        ProtocolChatSession {
+            session_id: self.session_id,
+            expires_at: self.expires_at,
+            public_key: &self.public_key,
+            key_signature: &self.key_signature,
+        }
+    }
+}
+
+/// Tracks the signed messages a player received, so the ones they acknowledge when sending a
+/// message can be forwarded as that message's previous messages.
+///
+/// Mirrors the tracker of the client: every signed message sent to the player is appended, and
+/// the client tells us how many of the oldest messages it dropped from its window (the offset).
+pub struct LastSeenMessages {
+    tracked: Vec<Option<Bytes>>,
+}
+
+impl Default for LastSeenMessages {
+    fn default() -> Self {
+        Self {
+            tracked: vec![None; LAST_SEEN_COUNT],
+        }
+    }
+}
+
+impl LastSeenMessages {
+    /// Remembers a signed message that was sent to the player
+    pub fn add_pending(&mut self, signature: Bytes) {
+        self.tracked.push(Some(signature));
+    }
+
+    /// Drops the `offset` oldest messages, returns false if the client acknowledged more messages than it received
+    pub fn apply_offset(&mut self, offset: usize) -> bool {
+        if offset > self.tracked.len() - LAST_SEEN_COUNT {
+            return false;
+        }
+        self.tracked.drain(..offset);
+        true
+    }
+
+    /// Applies the acknowledgement of a chat message and returns the signatures of the
+    /// acknowledged messages, oldest first. Returns `None` if the acknowledgement is invalid
+    pub fn apply_update(
+        &mut self,
+        offset: usize,
+        acknowledged: &FixedBitSet,
+    ) -> Option<Vec<Bytes>> {
+        if !self.apply_offset(offset) {
+            return None;
+        }
+
+        let mut signatures = Vec::new();
+        for (i, entry) in self.tracked.iter_mut().take(LAST_SEEN_COUNT).enumerate() {
+            let is_acknowledged = acknowledged
+                .get(i / 8)
+                .is_some_and(|byte| byte & (1 << (i % 8)) != 0);
+            if is_acknowledged {
+                signatures.push(entry.clone()?);
+            } else {
+                *entry = None;
+            }
+        }
+        Some(signatures)
+    }
+}
+
+/// Which players a chat message is delivered to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChatChannel {
+    /// Every player in the sender's world
+    Global,
+    /// Only players within `radius` blocks of the sender
+    Local { radius: f64 },
+}
+
+impl ChatChannel {
+    /// Picks the channel of a message sent in `world`, returns it along with the message
+    /// without the global prefix
+    #[must_use]
+    pub fn of<'a>(config: &ChatConfig, world: &str, message: &'a str) -> (Self, &'a str) {
+        if !config.is_local(world) {
+            return (Self::Global, message);
+        }
+        match message.strip_prefix(config.global_prefix.as_str()) {
+            Some(global) if !config.global_prefix.is_empty() => (Self::Global, global),
+            _ => (
+                Self::Local {
+                    radius: config.local_radius,
+                },
+                message,
+            ),
+        }
+    }
+
+    /// The players of `world` that receive a message sent from `sender` and have not hidden their chat
+    pub async fn recipients(self, world: &World, sender: Vector3<f64>) -> Vec<Arc<Player>> {
+        let players: Vec<_> = match self {
+            Self::Global => world
+                .current_players
+                .lock()
+                .await
+                .values()
+                .cloned()
+                .collect(),
+            Self::Local { radius } => world
+                .get_nearby_players(sender, radius)
+                .await
+                .into_values()
+                .collect(),
+        };
+        let mut recipients = Vec::with_capacity(players.len());
+        for player in players {
+            if player.config.lock().await.chat_mode.shows_chat(false) {
+                recipients.push(player);
+            }
+        }
+        recipients
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use pumpkin_config::ChatConfig;
+    use pumpkin_util::{math::vector3::Vector3, text::TextComponent};
+
+    use super::{ChatChannel, LastSeenMessages};
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::{test_player, test_player_in, ChatMode, Player};
+
+    #[test]
+    fn resolves_acknowledged_messages() {
+        let mut last_seen = LastSeenMessages::default();
+        for i in 0..3 {
+            last_seen.add_pending(Bytes::from(vec![i; 256]));
+        }
+
+        // The client window now holds the 3 messages as its newest entries
+        let acknowledged = Bytes::from(vec![0, 0, 0b0000_1010]);
+        let signatures = last_seen.apply_update(3, &acknowledged).unwrap();
+        assert_eq!(
+            signatures,
+            vec![Bytes::from(vec![0; 256]), Bytes::from(vec![2; 256])]
+        );
+
+        // Can't drop more messages than were received
+        assert!(last_seen.apply_update(1, &acknowledged).is_none());
+    }
+
+    #[test]
+    fn rejects_acknowledging_unknown_messages() {
+        let mut last_seen = LastSeenMessages::default();
+        let acknowledged = Bytes::from(vec![1, 0, 0]);
+        assert!(last_seen.apply_update(0, &acknowledged).is_none());
+    }
+
+    #[tokio::test]
+    async fn local_chat_reaches_only_nearby_players() {
+        let config = ChatConfig {
+            local: true,
+            local_radius: 10.0,
+            ..Default::default()
+        };
+        let folder = TestFolder::new("local-chat");
+        let sender = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let world = sender.world().clone();
+        let near = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        let far = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        for (player, pos) in [
+            (&sender, Vector3::new(0.0, 64.0, 0.0)),
+            (&near, Vector3::new(6.0, 64.0, 8.0)),
+            (&far, Vector3::new(6.0, 64.0, 8.5)),
+        ] {
+            player.living_entity.entity.set_pos(pos);
+            world
+                .current_players
+                .lock()
+                .await
+                .insert(player.gameprofile.id, player.clone());
+        }
+        let origin = sender.living_entity.entity.pos.load();
+        let ids = |recipients: Vec<Arc<Player>>| {
+            let mut ids: Vec<_> = recipients.iter().map(|p| p.gameprofile.id).collect();
+            ids.sort();
+            ids
+        };
+        let mut nearby = vec![sender.gameprofile.id, near.gameprofile.id];
+        nearby.sort();
+        let mut everyone = vec![
+            sender.gameprofile.id,
+            near.gameprofile.id,
+            far.gameprofile.id,
+        ];
+        everyone.sort();
+
+        let (channel, message) = ChatChannel::of(&config, "overworld", "hello");
+        assert_eq!(channel, ChatChannel::Local { radius: 10.0 });
+        assert_eq!(message, "hello");
+        assert_eq!(ids(channel.recipients(&world, origin).await), nearby);
+
+        // The global prefix is stripped and the message reaches everyone
+        let (channel, message) = ChatChannel::of(&config, "overworld", "!hello");
+        assert_eq!(channel, ChatChannel::Global);
+        assert_eq!(message, "hello");
+        assert_eq!(ids(channel.recipients(&world, origin).await), everyone);
+    }
+
+    #[test]
+    fn local_chat_per_world() {
+        let config = ChatConfig {
+            local_worlds: vec!["the_nether".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            ChatChannel::of(&config, "overworld", "!hello"),
+            (ChatChannel::Global, "!hello")
+        );
+        assert!(matches!(
+            ChatChannel::of(&config, "the_nether", "hello").0,
+            ChatChannel::Local { .. }
+        ));
+    }
+
+    /// Whether packets were sent to the player since the last call
+    async fn received_packets(player: &Player) -> bool {
+        !player.client.enc.lock().await.take().is_empty()
+    }
+
+    #[tokio::test]
+    async fn chat_respects_chat_mode() {
+        let folder = TestFolder::new("chat-mode");
+        let enabled = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let world = enabled.world().clone();
+        let commands_only = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        let hidden = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        commands_only.config.lock().await.chat_mode = ChatMode::CommandsOnly;
+        hidden.config.lock().await.chat_mode = ChatMode::Hidden;
+        for player in [&enabled, &commands_only, &hidden] {
+            world
+                .current_players
+                .lock()
+                .await
+                .insert(player.gameprofile.id, player.clone());
+            received_packets(player).await;
+        }
+
+        let recipients = ChatChannel::Global
+            .recipients(&world, Vector3::new(0.0, 0.0, 0.0))
+            .await;
+        assert_eq!(recipients.len(), 1);
+        assert_eq!(recipients[0].gameprofile.id, enabled.gameprofile.id);
+        enabled.send_unsigned_chat("hello", &recipients).await;
+        assert!(received_packets(&enabled).await);
+        assert!(!received_packets(&commands_only).await);
+        assert!(!received_packets(&hidden).await);
+
+        // Messages of commands like `/say` are also shown to players that only see commands
+        let sender = TextComponent::text("Server");
+        world
+            .broadcast_message(&TextComponent::text("hello"), &sender, 0, None)
+            .await;
+        assert!(received_packets(&enabled).await);
+        assert!(received_packets(&commands_only).await);
+        assert!(!received_packets(&hidden).await);
+
+        // System messages only reach players that hid their chat as an overlay
+        let message = TextComponent::text("saved the game");
+        hidden.send_system_message(&message).await;
+        assert!(!received_packets(&hidden).await);
+        hidden.send_system_message_raw(&message, true).await;
+        assert!(received_packets(&hidden).await);
+        commands_only.send_system_message(&message).await;
+        assert!(received_packets(&commands_only).await);
+    }
+}
@@ -1,5 +1,6 @@
 use std::f32::consts::PI;
 
+use crossbeam::atomic::AtomicCell;
 use pumpkin_data::{
     particle::Particle,
     sound::{Sound, SoundCategory},
@@ -9,7 +10,10 @@ use pumpkin_protocol::{
     codec::var_int::VarInt,
 };
 use pumpkin_util::math::vector3::Vector3;
-use pumpkin_world::item::ItemStack;
+use pumpkin_world::item::{
+    item_registry::{get_item_by_id, Operation},
+    ItemStack,
+};
 
 use crate::{
     entity::{player::Player, Entity},
@@ -63,6 +67,70 @@ impl AttackType {
     }
 }
 
+/// The attack modifiers an item adds to the player's base attack damage and speed
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AttackModifiers {
+    pub damage: f64,
+    pub speed: f64,
+}
+
+impl AttackModifiers {
+    /// Reads the modifiers from the item's components, items without any add nothing
+    #[must_use]
+    pub fn parse(item_id: u16) -> Self {
+        let mut modifiers = Self::default();
+        let Some(attribute_modifiers) =
+            get_item_by_id(item_id).and_then(|item| item.components.attribute_modifiers.as_ref())
+        else {
+            return modifiers;
+        };
+        for item_mod in &attribute_modifiers.modifiers {
+            if item_mod.operation == Operation::AddValue {
+                if item_mod.id == "minecraft:base_attack_damage" {
+                    modifiers.damage = item_mod.amount;
+                }
+                if item_mod.id == "minecraft:base_attack_speed" {
+                    modifiers.speed = item_mod.amount;
+                }
+            }
+        }
+        modifiers
+    }
+}
+
+/// Remembers the attack modifiers of the held item, so they are not read from the item components on every hit.
+/// The cache is keyed by the item id, holding a different item reads them again
+#[derive(Default)]
+pub struct HeldItemAttributes {
+    cached: AtomicCell<Option<(u16, AttackModifiers)>>,
+}
+
+impl HeldItemAttributes {
+    /// Returns the modifiers of the held item, `parse` is only called when the held item changed
+    pub fn get(
+        &self,
+        item_id: Option<u16>,
+        parse: impl FnOnce(u16) -> AttackModifiers,
+    ) -> AttackModifiers {
+        let Some(item_id) = item_id else {
+            return AttackModifiers::default();
+        };
+        if let Some((cached_id, modifiers)) = self.cached.load() {
+            if cached_id == item_id {
+                return modifiers;
+            }
+        }
+        let modifiers = parse(item_id);
+        self.cached.store(Some((item_id, modifiers)));
+        modifiers
+    }
+
+    /// Forgets the cached modifiers, like when the player selects another slot
+    pub fn invalidate(&self) {
+        self.cached.store(None);
+    }
+}
+
 pub async fn handle_knockback(
     attacker_entity: &Entity,
     victim: &Player,
@@ -151,3 +219,39 @@ pub async fn player_attack_sound(pos: &Vector3<f64>, world: &World, attack_type:
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::{AttackModifiers, HeldItemAttributes};
+
+    #[test]
+    fn repeated_attacks_do_not_reparse() {
+        let cache = HeldItemAttributes::default();
+        let parses = Cell::new(0);
+        let parse = |_| {
+            parses.set(parses.get() + 1);
+            AttackModifiers {
+                damage: 6.0,
+                speed: -2.4,
+            }
+        };
+        for _ in 0..1000 {
+            let modifiers = cache.get(Some(800), parse);
+            assert!((modifiers.damage - 6.0).abs() < f64::EPSILON);
+        }
+        assert_eq!(parses.get(), 1);
+
+        // switching items or slots reads the modifiers again
+        cache.get(Some(801), parse);
+        assert_eq!(parses.get(), 2);
+        cache.invalidate();
+        cache.get(Some(801), parse);
+        assert_eq!(parses.get(), 3);
+
+        // an empty hand has no modifiers
+        assert_eq!(cache.get(None, parse), AttackModifiers::default());
+        assert_eq!(parses.get(), 3);
+    }
+}
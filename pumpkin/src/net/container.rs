@@ -553,73 +553,34 @@ impl Player {
         }
     }
 
-    async fn pickup_items(&self, item: &Item, mut amount: u32) {
-        let max_stack = item.components.max_stack_size;
-        let mut inventory = self.inventory().lock().await;
-        let slots = inventory.slots_with_hotbar_first();
-
-        let matching_slots = slots.filter_map(|slot| {
-            if let Some(item_slot) = slot.as_mut() {
-                (item_slot.item_id == item.id && item_slot.item_count < max_stack).then(|| {
-                    let item_count = item_slot.item_count;
-                    (item_slot, item_count)
-                })
-            } else {
-                None
-            }
-        });
-
-        for (slot, item_count) in matching_slots {
-            if amount == 0 {
-                return;
-            }
-            let amount_to_add = max_stack - item_count;
-            if let Some(amount_left) = amount.checked_sub(u32::from(amount_to_add)) {
-                amount = amount_left;
-                *slot = ItemStack {
-                    item_id: item.id,
-                    item_count: item.components.max_stack_size,
-                };
-            } else {
-                *slot = ItemStack {
-                    item_id: item.id,
-                    item_count: max_stack - (amount_to_add - amount as u8),
-                };
-                return;
-            }
-        }
+    /// Add items to inventory if there's space.
+    /// Returns how many items did not fit, dropping them to the ground is not implemented yet
+    ///
+    /// This method automatically syncs changes with the client.
+    pub async fn give_items(&self, item: &Item, amount: u32) -> u32 {
+        let leftover = self.inventory().lock().await.add_item_stack(item, amount);
+        self.set_container_content(None).await;
+        leftover
+    }
 
-        let empty_slots = inventory
-            .slots_with_hotbar_first()
-            .filter(|slot| slot.is_none());
-        for slot in empty_slots {
-            if amount == 0 {
-                return;
-            }
-            if let Some(remaining_amount) = amount.checked_sub(u32::from(max_stack)) {
-                amount = remaining_amount;
-                *slot = Some(ItemStack {
-                    item_id: item.id,
-                    item_count: max_stack,
-                });
+    /// Swaps one of the held items for `item`, like a bucket being filled or emptied.
+    /// The new item goes into the held slot if nothing is left of the held stack
+    pub async fn exchange_held_item(&self, item: &Item) {
+        let replaced = {
+            let mut inventory = self.inventory().lock().await;
+            let held = inventory.held_item_mut();
+            if let Some(stack) = held.as_mut().filter(|stack| stack.item_count > 1) {
+                stack.item_count -= 1;
+                false
             } else {
-                *slot = Some(ItemStack {
-                    item_id: item.id,
-                    item_count: amount as u8,
-                });
-                return;
+                *held = Some(ItemStack::new(1, item.id));
+                true
             }
+        };
+        if replaced {
+            self.set_container_content(None).await;
+        } else {
+            self.give_items(item, 1).await;
         }
-        log::warn!(
-            "{amount} items were discarded because dropping them to the ground is not implemented"
-        );
-    }
-
-    /// Add items to inventory if there's space, else drop them to the ground.
-    ///
-    /// This method automatically syncs changes with the client.
-    pub async fn give_items(&self, item: &Item, amount: u32) {
-        self.pickup_items(item, amount).await;
-        self.set_container_content(None).await;
     }
 }
@@ -10,12 +10,16 @@ use std::{
 
 use crate::{
     data::{banned_ip_data::BANNED_IP_LIST, banned_player_data::BANNED_PLAYER_LIST},
-    entity::player::{ChatMode, Hand},
+    entity::player::{ChatMode, Hand, ResourcePackStatus},
+    plugin::packet as packet_events,
     server::Server,
 };
 
 use crossbeam::atomic::AtomicCell;
-use pumpkin_config::networking::compression::CompressionInfo;
+use pumpkin_config::{
+    networking::{compression::CompressionInfo, QueueOverflowPolicy},
+    ADVANCED_CONFIG,
+};
 use pumpkin_protocol::{
     bytebuf::{packet_id::Packet, ReadingError},
     client::{config::CConfigDisconnect, login::CLoginDisconnect, play::CPlayDisconnect},
@@ -23,8 +27,8 @@ use pumpkin_protocol::{
     packet_encoder::{PacketEncodeError, PacketEncoder},
     server::{
         config::{
-            SAcknowledgeFinishConfig, SClientInformationConfig, SConfigCookieResponse, SKnownPacks,
-            SPluginMessage,
+            SAcknowledgeFinishConfig, SClientInformationConfig, SConfigCookieResponse,
+            SConfigResourcePackResponse, SKnownPacks, SPluginMessage,
         },
         handshake::SHandShake,
         login::{
@@ -46,6 +50,7 @@ use tokio::sync::Mutex;
 use thiserror::Error;
 use uuid::Uuid;
 mod authentication;
+pub mod chat;
 pub mod combat;
 mod container;
 pub mod lan_broadcast;
@@ -141,6 +146,8 @@ pub struct Client {
     pub client_packets_queue: Arc<Mutex<VecDeque<RawPacket>>>,
     /// Indicates whether the client should be converted into a player.
     pub make_player: AtomicBool,
+    /// The last status the client reported for the server's resource pack
+    pub resource_pack_status: AtomicCell<Option<ResourcePackStatus>>,
 }
 
 impl Client {
@@ -161,13 +168,42 @@ impl Client {
             server_packets_channel,
             client_packets_queue: Arc::new(Mutex::new(VecDeque::new())),
             make_player: AtomicBool::new(false),
+            resource_pack_status: AtomicCell::new(None),
         }
     }
 
-    /// Adds a Incoming packet to the queue
+    /// Adds a Incoming packet to the queue.
+    /// Clients that send packets faster than they are processed are handled by the configured overflow policy
     pub async fn add_packet(&self, packet: RawPacket) {
-        let mut client_packets_queue = self.client_packets_queue.lock().await;
-        client_packets_queue.push_back(packet);
+        let config = &ADVANCED_CONFIG.networking;
+        let result = {
+            let mut client_packets_queue = self.client_packets_queue.lock().await;
+            enqueue_packet(
+                &mut client_packets_queue,
+                packet,
+                config.max_packet_queue,
+                config.packet_queue_overflow,
+            )
+        };
+        match result {
+            Ok(()) => {}
+            Err(QueueOverflowPolicy::Drop) => {
+                log::warn!(
+                    "Packet queue of client {} is full ({} packets), dropping packet",
+                    self.id,
+                    config.max_packet_queue
+                );
+            }
+            Err(QueueOverflowPolicy::Kick) => {
+                log::warn!(
+                    "Packet queue of client {} is full ({} packets), kicking",
+                    self.id,
+                    config.max_packet_queue
+                );
+                self.kick(&TextComponent::text("Sent too many packets"))
+                    .await;
+            }
+        }
     }
 
     /// Enables or disables packet encryption for the connection.
@@ -240,7 +276,9 @@ impl Client {
     pub async fn send_packet<P: ClientPacket>(&self, packet: &P) {
         //log::debug!("Sending packet with id {} to {}", P::PACKET_ID, self.id);
         // assert!(!self.closed);
-        if self.closed.load(std::sync::atomic::Ordering::Relaxed) {
+        if self.closed.load(std::sync::atomic::Ordering::Relaxed)
+            || !packet_events::fire_send(self, packet).await
+        {
             return;
         }
 
@@ -499,6 +537,11 @@ impl Client {
             SConfigCookieResponse::PACKET_ID => {
                 self.handle_config_cookie_response(SConfigCookieResponse::read(bytebuf)?);
             }
+            SConfigResourcePackResponse::PACKET_ID => {
+                self.handle_resource_pack_response(
+                    SConfigResourcePackResponse::read(bytebuf)?.result.0,
+                );
+            }
             _ => {
                 log::error!(
                     "Failed to handle client packet id {} in Config State",
@@ -608,6 +651,20 @@ impl Client {
     }
 }
 
+/// Queues the packet if there is room, otherwise returns the policy to apply
+fn enqueue_packet(
+    queue: &mut VecDeque<RawPacket>,
+    packet: RawPacket,
+    max_queue: usize,
+    overflow: QueueOverflowPolicy,
+) -> Result<(), QueueOverflowPolicy> {
+    if queue.len() >= max_queue {
+        return Err(overflow);
+    }
+    queue.push_back(packet);
+    Ok(())
+}
+
 #[derive(Error, Debug)]
 pub enum EncryptionError {
     #[error("failed to decrypt shared secret")]
@@ -615,3 +672,38 @@ pub enum EncryptionError {
     #[error("shared secret has the wrong length")]
     SharedWrongLength,
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use bytes::Bytes;
+    use pumpkin_config::networking::QueueOverflowPolicy;
+    use pumpkin_protocol::{codec::var_int::VarInt, RawPacket};
+
+    use super::enqueue_packet;
+
+    fn packet() -> RawPacket {
+        RawPacket {
+            id: VarInt(0),
+            bytebuf: Bytes::new(),
+        }
+    }
+
+    #[test]
+    fn packet_queue_overflow() {
+        for policy in [QueueOverflowPolicy::Drop, QueueOverflowPolicy::Kick] {
+            let mut queue = VecDeque::new();
+            for _ in 0..3 {
+                assert_eq!(enqueue_packet(&mut queue, packet(), 3, policy), Ok(()));
+            }
+            // a flooding client hits the configured policy, the queue does not grow
+            assert_eq!(enqueue_packet(&mut queue, packet(), 3, policy), Err(policy));
+            assert_eq!(queue.len(), 3);
+
+            // once the queue was drained packets are accepted again
+            queue.pop_front();
+            assert_eq!(enqueue_packet(&mut queue, packet(), 3, policy), Ok(()));
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::num::NonZeroU8;
 
 use crate::{
-    entity::player::{ChatMode, Hand},
+    entity::player::{ChatMode, Hand, ResourcePackStatus},
     net::{Client, PlayerConfig},
     server::Server,
 };
@@ -77,6 +77,21 @@ impl Client {
         );
     }
 
+    /// Stores the resource pack status the client reported, sent in both the config and the play state
+    pub(crate) fn handle_resource_pack_response(&self, result: i32) -> Option<ResourcePackStatus> {
+        let Ok(status) = ResourcePackStatus::try_from(result) else {
+            log::debug!(
+                "Client {} sent an invalid resource pack status {}",
+                self.id,
+                result
+            );
+            return None;
+        };
+        log::debug!("Client {} resource pack status: {status:?}", self.id);
+        self.resource_pack_status.store(Some(status));
+        Some(status)
+    }
+
     pub async fn handle_known_packs(&self, server: &Server, _config_acknowledged: SKnownPacks) {
         log::debug!("Handling known packs");
         for registry in &server.cached_registry {
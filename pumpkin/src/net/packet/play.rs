@@ -2,14 +2,26 @@ use std::num::NonZeroU8;
 use std::sync::Arc;
 
 use crate::block::block_manager::BlockActionResult;
-use crate::entity::mob;
+use crate::net::chat::{ChatChannel, ChatSession};
 use crate::net::PlayerConfig;
+use crate::plugin::api::events::block::place::BlockPlaceEventImpl;
+use crate::plugin::api::events::block::BlockPlaceEvent;
+use crate::plugin::api::events::entity::SpawnReason;
+use crate::plugin::api::events::player::chat::PlayerChatEventImpl;
+use crate::plugin::api::events::player::resource_pack_status::ResourcePackStatusEventImpl;
+use crate::plugin::api::events::player::PlayerChatEvent;
+use crate::plugin::api::events::CancellableEvent;
 use crate::{
     command::CommandSender,
-    entity::player::{ChatMode, Hand, Player},
+    entity::player::{ChatMode, Hand, Player, PlayerInput},
     error::PumpkinError,
     server::Server,
-    world::player_chunker,
+    world::{
+        fluid::{BucketAction, Fluid, EMPTY_BUCKET},
+        player_chunker,
+        selection::{Corner, SELECTION_PERMISSION_LVL, SELECTION_WAND},
+    },
+    PLUGIN_MANAGER,
 };
 use pumpkin_config::ADVANCED_CONFIG;
 use pumpkin_data::entity::EntityType;
@@ -22,34 +34,36 @@ use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::server::play::SCookieResponse as SPCookieResponse;
 use pumpkin_protocol::{
     client::play::{
-        Animation, CAcknowledgeBlockChange, CCommandSuggestions, CEntityAnimation, CHeadRot,
-        CPingResponse, CPlayerChatMessage, CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot,
-        FilterType,
+        Animation, CAcknowledgeBlockChange, CBlockUpdate, CCommandSuggestions,
+        CDisguisedChatMessage, CEntityAnimation, CHeadRot, CPingResponse, CPlayerChatMessage,
+        CPlayerInfoUpdate, CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot, FilterType,
+        PlayerAction, PreviousMessage,
     },
     server::play::{
-        Action, ActionType, SChatCommand, SChatMessage, SClientCommand, SClientInformationPlay,
-        SCloseContainer, SCommandSuggestion, SConfirmTeleport, SInteract, SKeepAlive,
-        SPickItemFromBlock, SPlayPingRequest, SPlayerAbilities, SPlayerAction, SPlayerCommand,
-        SPlayerPosition, SPlayerPositionRotation, SPlayerRotation, SSetCreativeSlot, SSetHeldItem,
-        SSetPlayerGround, SSwingArm, SUseItem, SUseItemOn, Status,
+        Action, ActionType, SChatAck, SChatCommand, SChatMessage, SChatSessionUpdate,
+        SClientCommand, SClientInformationPlay, SCloseContainer, SCommandSuggestion,
+        SConfirmTeleport, SInteract, SKeepAlive, SPickItemFromBlock, SPlayPingRequest,
+        SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerPosition,
+        SPlayerPositionRotation, SPlayerRotation, SResourcePackResponse, SSetCreativeSlot,
+        SSetHeldItem, SSetPlayerGround, SSwingArm, SUseItem, SUseItemOn, Status,
     },
 };
-use pumpkin_util::math::position::BlockPos;
+use pumpkin_registry::preformatted_chat_type;
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos};
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::{
     math::{vector3::Vector3, wrap_degrees},
     text::TextComponent,
     GameMode,
 };
-use pumpkin_world::block::block_registry::{get_block_collision_shapes, Block};
-use pumpkin_world::item::item_registry::get_item_by_id;
+use pumpkin_world::block::block_registry::{get_block, get_block_collision_shapes, Block};
+use pumpkin_world::item::item_registry::{get_item, get_item_by_id, ITEMS_REGISTRY_NAME_BY_ID};
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::{
     block::{block_registry::get_block_by_item, BlockFace},
     entity::entity_registry::get_entity_id,
     item::item_registry::get_spawn_egg,
 };
-use pumpkin_world::{WORLD_LOWEST_Y, WORLD_MAX_Y};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -94,6 +108,12 @@ impl PumpkinError for BlockPlacingError {
     }
 }
 
+/// Squared distance a player may move with one packet, moving further is undone like in vanilla
+const MAX_MOVEMENT_SQUARED: f64 = 100.0;
+const MAX_FALL_FLYING_MOVEMENT_SQUARED: f64 = 300.0;
+/// Squared distance a player's position may be off from where collisions with blocks would stop them
+const MOVEMENT_TOLERANCE_SQUARED: f64 = 0.0625;
+
 /// Handles all Play Packets send by a real Player
 /// NEVER TRUST THE CLIENT. HANDLE EVERY ERROR, UNWRAP/EXPECT ARE FORBIDDEN
 impl Player {
@@ -128,8 +148,59 @@ impl Player {
         self.set_client_loaded(true);
     }
 
-    pub async fn handle_position(self: &Arc<Self>, packet: SPlayerPosition) {
-        if !self.has_client_loaded() {
+    /// Whether the player may move to the position they reported. Players who moved too far
+    /// or through blocks are teleported back. Movement is ignored until a teleport is confirmed
+    async fn check_movement(&self, from: Vector3<f64>, to: Vector3<f64>) -> bool {
+        if self.awaiting_teleport.lock().await.is_some() {
+            return false;
+        }
+        // Spectators fly through blocks
+        if self.is_spectator() {
+            return true;
+        }
+        let entity = &self.living_entity.entity;
+        let delta = to.sub(&from);
+        let max_movement = if entity
+            .fall_flying
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            MAX_FALL_FLYING_MOVEMENT_SQUARED
+        } else {
+            MAX_MOVEMENT_SQUARED
+        };
+        let legal = if delta.length_squared() > max_movement {
+            log::warn!("{} moved too quickly!", self.gameprofile.name);
+            false
+        } else {
+            let bounding_box =
+                BoundingBox::new_from_pos(from.x, from.y, from.z, &entity.bounding_box_size.load());
+            let allowed = self.world().sweep_collision(&bounding_box, delta).await;
+            let legal = allowed.sub(&delta).length_squared() <= MOVEMENT_TOLERANCE_SQUARED;
+            if !legal {
+                log::warn!("{} moved wrongly!", self.gameprofile.name);
+            }
+            legal
+        };
+        if !legal {
+            self.request_teleport(from, entity.yaw.load(), entity.pitch.load())
+                .await;
+        }
+        legal
+    }
+
+    /// Lets the block the player just walked into react to them, like pressure plates.
+    /// Spectators pass through blocks without touching them
+    async fn enter_block(&self, server: &Server) {
+        if self.is_spectator() {
+            return;
+        }
+        let location = self.living_entity.entity.block_pos.load();
+        self.world().enter_block(location, server).await;
+    }
+
+    pub async fn handle_position(self: &Arc<Self>, packet: SPlayerPosition, server: &Server) {
+        // Following an entity, the player moves along with it and the client's position is outdated
+        if !self.has_client_loaded() || self.spectating.lock().await.is_some() {
             return;
         }
         // y = feet Y
@@ -149,8 +220,16 @@ impl Player {
         );
         let entity = &self.living_entity.entity;
         let last_pos = entity.pos.load();
+        if !self.check_movement(last_pos, position).await {
+            return;
+        }
+        let last_block_pos = entity.block_pos.load();
         self.living_entity.set_pos(position);
+        if entity.block_pos.load() != last_block_pos {
+            self.enter_block(server).await;
+        }
 
+        self.exhaust_from_movement(last_pos, position, packet.ground);
         entity
             .on_ground
             .store(packet.ground, std::sync::atomic::Ordering::Relaxed);
@@ -159,18 +238,6 @@ impl Player {
         let Vector3 { x, y, z } = position;
         let world = &entity.world;
 
-        // let delta = Vector3::new(x - lastx, y - lasty, z - lastz);
-        // let velocity = self.velocity;
-
-        // // Player is falling down fast, we should account for that
-        // let max_speed = if self.fall_flying { 300.0 } else { 100.0 };
-
-        // teleport when more than 8 blocks (i guess 8 blocks)
-        // TODO: REPLACE * 2.0 by movement packets. see vanilla for details
-        // if delta.length_squared() - velocity.length_squared() > max_speed * 2.0 {
-        //     self.teleport(x, y, z, self.entity.yaw, self.entity.pitch);
-        //     return;
-        // }
         // send new position to all other players
         world
             .broadcast_packet_except(
@@ -186,7 +253,10 @@ impl Player {
                 ),
             )
             .await;
-        if !self.abilities.lock().await.flying {
+        if self.abilities.lock().await.flying {
+            // Flying players never accumulate fall distance, so landing does not hurt them
+            self.living_entity.fall_distance.store(0.0);
+        } else if entity.has_gravity() {
             let height_difference = position.y - last_pos.y;
             self.living_entity
                 .update_fall_distance(
@@ -199,8 +269,13 @@ impl Player {
         player_chunker::update_position(self).await;
     }
 
-    pub async fn handle_position_rotation(self: &Arc<Self>, packet: SPlayerPositionRotation) {
-        if !self.has_client_loaded() {
+    pub async fn handle_position_rotation(
+        self: &Arc<Self>,
+        packet: SPlayerPositionRotation,
+        server: &Server,
+    ) {
+        // Following an entity, the player moves along with it and the client's position is outdated
+        if !self.has_client_loaded() || self.spectating.lock().await.is_some() {
             return;
         }
         // y = feet Y
@@ -226,8 +301,16 @@ impl Player {
         );
         let entity = &self.living_entity.entity;
         let last_pos = entity.pos.load();
+        if !self.check_movement(last_pos, position).await {
+            return;
+        }
+        let last_block_pos = entity.block_pos.load();
         self.living_entity.set_pos(position);
+        if entity.block_pos.load() != last_block_pos {
+            self.enter_block(server).await;
+        }
 
+        self.exhaust_from_movement(last_pos, position, packet.ground);
         entity
             .on_ground
             .store(packet.ground, std::sync::atomic::Ordering::Relaxed);
@@ -245,18 +328,6 @@ impl Player {
         // let head_yaw = (entity.head_yaw * 256.0 / 360.0).floor();
         let world = &entity.world;
 
-        // let delta = Vector3::new(x - lastx, y - lasty, z - lastz);
-        // let velocity = self.velocity;
-
-        // // Player is falling down fast, we should account for that
-        // let max_speed = if self.fall_flying { 300.0 } else { 100.0 };
-
-        // // teleport when more than 8 blocks (i guess 8 blocks)
-        // // TODO: REPLACE * 2.0 by movement packets. see vanilla for details
-        // if delta.length_squared() - velocity.length_squared() > max_speed * 2.0 {
-        //     self.teleport(x, y, z, yaw, pitch);
-        //     return;
-        // }
         // send new position to all other players
 
         world
@@ -281,7 +352,10 @@ impl Player {
                 &CHeadRot::new(entity_id.into(), yaw as u8),
             )
             .await;
-        if !self.abilities.lock().await.flying {
+        if self.abilities.lock().await.flying {
+            // Flying players never accumulate fall distance, so landing does not hurt them
+            self.living_entity.fall_distance.store(0.0);
+        } else if entity.has_gravity() {
             let height_difference = position.y - last_pos.y;
             self.living_entity
                 .update_fall_distance(
@@ -332,13 +406,38 @@ impl Player {
             .await;
     }
 
-    pub fn handle_chat_command(self: &Arc<Self>, server: &Arc<Server>, command: &SChatCommand) {
+    pub async fn handle_chat_command(
+        self: &Arc<Self>,
+        server: &Arc<Server>,
+        command: &SChatCommand,
+    ) {
+        // Limits how many tasks a single player can spawn by sending commands
+        let Ok(permit) = self.running_commands.clone().try_acquire_owned() else {
+            log::warn!(
+                "Player ({}) is running too many commands at once, rejected /{}",
+                self.gameprofile.name,
+                command.command
+            );
+            self.send_system_message(
+                &TextComponent::text("You are running too many commands at once")
+                    .color_named(NamedColor::Red),
+            )
+            .await;
+            return;
+        };
+        let Some(task) = server.command_tasks.start() else {
+            self.send_system_message(
+                &TextComponent::text("The server is shutting down").color_named(NamedColor::Red),
+            )
+            .await;
+            return;
+        };
         let player_clone = self.clone();
         let server_clone = server.clone();
         let command_clone = command.command.clone();
         // Some commands can take a long time to execute. If they do, they block packet processing for the player
         // Thats why we will spawn a task instead
-        tokio::spawn(async move {
+        tokio::spawn(task.run(async move {
             let dispatcher = server_clone.command_dispatcher.read().await;
             dispatcher
                 .handle_command(
@@ -347,7 +446,8 @@ impl Player {
                     &command_clone,
                 )
                 .await;
-        });
+            drop(permit);
+        }));
 
         if ADVANCED_CONFIG.commands.log_console {
             log::info!(
@@ -384,7 +484,7 @@ impl Player {
     }
 
     pub async fn handle_pick_item_from_block(&self, pick_item: SPickItemFromBlock) {
-        if !self.can_interact_with_block_at(&pick_item.pos, 1.0) {
+        if self.is_spectator() || !self.can_interact_with_block_at(&pick_item.pos, 1.0) {
             return;
         }
 
@@ -543,11 +643,12 @@ impl Player {
             .await;
     }
 
-    pub async fn handle_chat_message(&self, chat_message: SChatMessage) {
-        let message = chat_message.message;
+    /// Validates a chat message of the player and lets plugins change or cancel it.
+    /// Kicks the player for invalid messages, returns the message to send if any
+    pub async fn filter_chat_message(self: &Arc<Self>, message: &str) -> Option<String> {
         if message.len() > 256 {
             self.kick(TextComponent::text("Oversized message")).await;
-            return;
+            return None;
         }
 
         if message.chars().any(|c| c == '§' || c < ' ' || c == '\x7F') {
@@ -556,40 +657,161 @@ impl Player {
                 [].into(),
             ))
             .await;
-            return;
+            return None;
         }
 
+        let event = PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire_cancellable(PlayerChatEventImpl::new(self.clone(), message.to_string()))
+            .await;
+        if event.is_cancelled() {
+            return None;
+        }
+        Some(event.get_message().to_string())
+    }
+
+    pub async fn handle_chat_message(self: &Arc<Self>, chat_message: SChatMessage) {
+        let Some(filtered) = self.filter_chat_message(&chat_message.message).await else {
+            return;
+        };
+        // A message changed by a plugin no longer matches its signature
+        let signed = filtered == chat_message.message;
+        let message = filtered;
+
         let gameprofile = &self.gameprofile;
         log::info!("<chat>{}: {}", gameprofile.name, message);
 
         let entity = &self.living_entity.entity;
         let world = &entity.world;
-        world
-            .broadcast_packet_all(&CPlayerChatMessage::new(
-                gameprofile.id,
-                1.into(),
-                chat_message.signature.as_deref(),
-                &message,
-                chat_message.timestamp,
-                chat_message.salt,
-                &[],
-                Some(TextComponent::text(message.clone())),
-                FilterType::PassThrough,
-                (CHAT + 1).into(),
-                TextComponent::text(gameprofile.name.clone()),
-                None,
+        let world_name = world.dimension_type.name();
+        let (channel, text) = ChatChannel::of(&ADVANCED_CONFIG.chat, &world_name.path, &message);
+        let recipients = channel.recipients(world, entity.pos.load()).await;
+
+        // Clients with "Only Show Secure Chat" enabled hide player chat they can't verify, so
+        // messages are only sent as player chat if they are signed and the sender's chat session
+        // is known. Everything else is sent as disguised chat, which is always displayed.
+        // A stripped global prefix also invalidates the signature
+        let has_session = self.chat_session.lock().await.is_some();
+        let Some(signature) = chat_message.signature.filter(|_| {
+            signed && has_session && text == message && ADVANCED_CONFIG.chat.is_vanilla_format()
+        }) else {
+            self.send_unsigned_chat(text, &recipients).await;
+            return;
+        };
+
+        // The signature also covers the messages the sender has seen, the receivers need them to verify it
+        let Some(last_seen) = self.last_seen_messages.lock().await.apply_update(
+            chat_message.message_count.0 as usize,
+            &chat_message.acknowledged,
+        ) else {
+            self.kick(TextComponent::translate(
+                "multiplayer.disconnect.chat_validation_failed",
+                [].into(),
+            ))
+            .await;
+            return;
+        };
+        let previous_messages: Vec<PreviousMessage> = last_seen
+            .iter()
+            .map(|signature| PreviousMessage::new(signature))
+            .collect();
+
+        let packet = CPlayerChatMessage::new(
+            gameprofile.id,
+            self.chat_index
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+                .into(),
+            Some(&signature),
+            &message,
+            chat_message.timestamp,
+            chat_message.salt,
+            &previous_messages,
+            None,
+            FilterType::PassThrough,
+            (CHAT + 1).into(),
+            self.display_name().await,
+            None,
+        );
+        for player in recipients {
+            player
+                .last_seen_messages
+                .lock()
+                .await
+                .add_pending(signature.clone());
+            player.client.send_packet(&packet).await;
+        }
+    }
+
+    /// Sends a chat message of the player that can't be sent as signed player chat, using the configured chat format
+    pub async fn send_unsigned_chat(&self, message: &str, recipients: &[Arc<Self>]) {
+        let sender_name = self.display_name().await;
+        let chat_config = &ADVANCED_CONFIG.chat;
+        if chat_config.is_vanilla_format() {
+            let message = TextComponent::text(message.to_string());
+            let packet =
+                CDisguisedChatMessage::new(&message, (CHAT + 1).into(), &sender_name, None);
+            for player in recipients {
+                player.client.send_packet(&packet).await;
+            }
+            return;
+        }
+
+        // The vanilla chat types decorate the message themselves, the preformatted one shows it as it is
+        let formatted = chat_config.format(
+            &self.gameprofile.name,
+            &sender_name.to_plain_text(),
+            &self.world().dimension_type.name().path,
+            message,
+        );
+        let formatted = TextComponent::text(formatted);
+        let packet = CDisguisedChatMessage::new(
+            &formatted,
+            (preformatted_chat_type() + 1).into(),
+            &sender_name,
+            None,
+        );
+        for player in recipients {
+            player.client.send_packet(&packet).await;
+        }
+    }
+
+    pub async fn handle_chat_session_update(&self, session: SChatSessionUpdate) {
+        // TODO: Verify the key signature using Mojang's public key in online mode
+        let session = ChatSession {
+            session_id: session.session_id,
+            expires_at: session.expires_at,
+            public_key: session.public_key,
+            key_signature: session.key_signature,
+        };
+        self.chat_index
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+
+        self.world()
+            .broadcast_packet_all(&CPlayerInfoUpdate::new(
+                0x02,
+                &[pumpkin_protocol::client::play::Player {
+                    uuid: self.gameprofile.id,
+                    actions: vec![PlayerAction::InitializeChat(Some(session.to_protocol()))],
+                }],
             ))
             .await;
+        *self.chat_session.lock().await = Some(session);
+    }
 
-        /* server.broadcast_packet(
-            self,
-            &CDisguisedChatMessage::new(
-                TextComponent::from(message.clone()),
-                VarInt(0),
-               gameprofile.name.clone().into(),
-                None,
-            ),
-        ) */
+    pub async fn handle_chat_ack(&self, ack: SChatAck) {
+        let valid = self
+            .last_seen_messages
+            .lock()
+            .await
+            .apply_offset(ack.offset.0 as usize);
+        if !valid {
+            self.kick(TextComponent::translate(
+                "multiplayer.disconnect.chat_validation_failed",
+                [].into(),
+            ))
+            .await;
+        }
     }
 
     pub async fn handle_client_information(
@@ -710,7 +932,7 @@ impl Player {
                 let entity_id = interact.entity_id;
                 let config = &ADVANCED_CONFIG.pvp;
                 // TODO: do validation and stuff
-                if !config.enabled {
+                if !config.enabled || self.is_spectator() {
                     return;
                 }
 
@@ -728,6 +950,10 @@ impl Player {
                     if entity_victim.health.load() <= 0.0 {
                         return;
                     }
+                    let victim_pos = entity_victim.entity.block_pos.load();
+                    if world.is_protected(&victim_pos, self) {
+                        return;
+                    }
                     entity_victim.kill().await;
                     world.clone().remove_mob_entity(entity_victim).await;
                     // TODO: block entities should be checked here (signs)
@@ -766,7 +992,16 @@ impl Player {
             return;
         }
         match Status::try_from(player_action.status.0) {
+            Ok(Status::StartedDigging | Status::CancelledDigging | Status::FinishedDigging)
+                if !self.accept_block_sequence(player_action.sequence.0) =>
+            {
+                // Already handled, it is only acknowledged again below so the client stops waiting
+            }
             Ok(status) => match status {
+                Status::StartedDigging | Status::FinishedDigging if self.is_spectator() => {
+                    // Spectators can't break blocks, make sure the client didn't remove it
+                    self.resync_block(&player_action.location).await;
+                }
                 Status::StartedDigging => {
                     if !self.can_interact_with_block_at(&player_action.location, 1.0) {
                         log::warn!(
@@ -776,6 +1011,9 @@ impl Player {
                         );
                         return;
                     }
+                    if self.dig_with_selection_wand(&player_action, true).await {
+                        return;
+                    }
                     // TODO: do validation
                     // TODO: Config
                     if self.gamemode.load() == GameMode::Creative {
@@ -783,11 +1021,19 @@ impl Player {
                         // Block break & block break sound
                         let entity = &self.living_entity.entity;
                         let world = &entity.world;
+                        if world.is_protected(&location, &self) {
+                            self.resync_block(&location).await;
+                            self.client
+                                .send_packet(&CAcknowledgeBlockChange::new(player_action.sequence))
+                                .await;
+                            return;
+                        }
                         let block = world.get_block(&location).await;
 
-                        world.break_block(&location, Some(self.clone())).await;
-
-                        if let Ok(block) = block {
+                        if !world.break_block(&location, Some(self.clone())).await {
+                            // A plugin cancelled the break, the client already removed the block
+                            self.resync_block(&location).await;
+                        } else if let Ok(block) = block {
                             server
                                 .block_manager
                                 .on_broken(block, &self, location, server)
@@ -818,24 +1064,34 @@ impl Player {
                         );
                         return;
                     }
+                    if self.dig_with_selection_wand(&player_action, false).await {
+                        return;
+                    }
                     // Block break & block break sound
                     let entity = &self.living_entity.entity;
                     let world = &entity.world;
+                    if world.is_protected(&location, &self) {
+                        self.resync_block(&location).await;
+                        self.client
+                            .send_packet(&CAcknowledgeBlockChange::new(player_action.sequence))
+                            .await;
+                        return;
+                    }
                     let block = world.get_block(&location).await;
 
-                    world.break_block(&location, Some(self.clone())).await;
-
-                    if let Ok(block) = block {
+                    if !world.break_block(&location, Some(self.clone())).await {
+                        // A plugin cancelled the break, the client already removed the block
+                        self.resync_block(&location).await;
+                    } else if let Ok(block) = block {
                         server
                             .block_manager
                             .on_broken(block, &self, location, server)
                             .await;
                     }
                 }
-                Status::DropItemStack
-                | Status::DropItem
-                | Status::ShootArrowOrFinishEating
-                | Status::SwapItem => {
+                // Sent when the player lets go of the use key before they finished eating
+                Status::ShootArrowOrFinishEating => self.stop_eating().await,
+                Status::DropItemStack | Status::DropItem | Status::SwapItem => {
                     log::debug!("todo");
                 }
             },
@@ -847,6 +1103,54 @@ impl Player {
             .await;
     }
 
+    /// Builders holding the wand select regions instead of breaking or using blocks
+    async fn holds_selection_wand(&self) -> bool {
+        self.permission_lvl.load().ge(&SELECTION_PERMISSION_LVL)
+            && self
+                .inventory()
+                .lock()
+                .await
+                .held_item()
+                .and_then(|stack| ITEMS_REGISTRY_NAME_BY_ID.get(&stack.item_id))
+                .is_some_and(|name| name == SELECTION_WAND)
+    }
+
+    /// Digging with the selection wand sets the first corner once it starts, and never breaks blocks.
+    /// Returns whether the wand was used
+    async fn dig_with_selection_wand(&self, player_action: &SPlayerAction, started: bool) -> bool {
+        if !self.holds_selection_wand().await {
+            return false;
+        }
+        if started {
+            self.set_selection_corner(Corner::First, player_action.location)
+                .await;
+        }
+        self.resync_block(&player_action.location).await;
+        self.client
+            .send_packet(&CAcknowledgeBlockChange::new(
+                player_action.sequence.clone(),
+            ))
+            .await;
+        true
+    }
+
+    /// Returns whether the block change sequence is new. Duplicate or out of order sequences
+    /// were already handled and should only be acknowledged again
+    fn accept_block_sequence(&self, sequence: i32) -> bool {
+        self.last_block_sequence
+            .fetch_max(sequence, std::sync::atomic::Ordering::Relaxed)
+            < sequence
+    }
+
+    /// Sends the current state of a block to the client, reverting client-side predictions
+    async fn resync_block(&self, location: &BlockPos) {
+        if let Ok(state_id) = self.world().get_block_state_id(location).await {
+            self.client
+                .send_packet(&CBlockUpdate::new(location, i32::from(state_id).into()))
+                .await;
+        }
+    }
+
     pub async fn handle_keep_alive(&self, keep_alive: SKeepAlive) {
         if self
             .wait_for_keep_alive
@@ -863,17 +1167,42 @@ impl Player {
         }
     }
 
+    pub fn handle_player_input(&self, input: &SPlayerInput) {
+        // TODO: Steer the vehicle the player is riding once there are vehicles
+        // Applied once the client ends its tick, so all input of a tick takes effect at once
+        self.pending_input
+            .store(PlayerInput::from_flags(input.input));
+    }
+
+    pub fn handle_client_tick_end(&self) {
+        self.current_input.store(self.pending_input.load());
+        self.client_ticks
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
     pub async fn handle_player_abilities(&self, player_abilities: SPlayerAbilities) {
         let mut abilities = self.abilities.lock().await;
 
         // Set the flying ability
         let flying = player_abilities.flags & 0x02 != 0 && abilities.allow_flying;
-        if flying {
+        if flying != abilities.flying {
+            // Starting or stopping to fly both begin a new fall
             self.living_entity.fall_distance.store(0.0);
         }
         abilities.flying = flying;
     }
 
+    pub async fn handle_resource_pack_response(self: &Arc<Self>, packet: SResourcePackResponse) {
+        let Some(status) = self.client.handle_resource_pack_response(packet.result.0) else {
+            return;
+        };
+        PLUGIN_MANAGER
+            .lock()
+            .await
+            .fire(ResourcePackStatusEventImpl::new(self.clone(), status))
+            .await;
+    }
+
     pub async fn handle_play_ping_request(&self, request: SPlayPingRequest) {
         self.client
             .send_packet(&CPingResponse::new(request.payload))
@@ -881,7 +1210,7 @@ impl Player {
     }
 
     pub async fn handle_use_item_on(
-        &self,
+        self: &Arc<Self>,
         use_item_on: SUseItemOn,
         server: &Arc<Server>,
     ) -> Result<(), Box<dyn PumpkinError>> {
@@ -889,6 +1218,13 @@ impl Player {
             return Ok(());
         }
 
+        if !self.accept_block_sequence(use_item_on.sequence.0) {
+            self.client
+                .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
+                .await;
+            return Ok(());
+        }
+
         let location = use_item_on.location;
         let mut should_try_decrement = false;
 
@@ -933,6 +1269,11 @@ impl Player {
             return Err(BlockPlacingError::InventoryInvalid.into());
         };
 
+        if self.holds_selection_wand().await {
+            self.set_selection_corner(Corner::Second, location).await;
+            return Ok(());
+        }
+
         if !self
             .living_entity
             .entity
@@ -988,12 +1329,102 @@ impl Player {
         Ok(())
     }
 
-    pub fn handle_use_item(&self, _use_item: &SUseItem) {
+    pub async fn handle_use_item(&self, use_item: &SUseItem) {
         if !self.has_client_loaded() {
             return;
         }
-        // TODO: handle packet correctly
-        log::error!("An item was used(SUseItem), but the packet is not implemented yet");
+        let off_hand = match use_item.hand.0 {
+            0 => false,
+            1 => true,
+            _ => {
+                self.kick(TextComponent::text("Invalid hand")).await;
+                return;
+            }
+        };
+        if off_hand {
+            self.start_eating(true).await;
+            return;
+        }
+        let held_item = self
+            .inventory()
+            .lock()
+            .await
+            .held_item()
+            .and_then(|stack| ITEMS_REGISTRY_NAME_BY_ID.get(&stack.item_id));
+        // Buckets are used through this packet, as the client can not target fluids with use item on
+        if let Some(held_item) = held_item {
+            if held_item == EMPTY_BUCKET || Fluid::from_bucket(held_item).is_some() {
+                self.use_bucket(held_item).await;
+                return;
+            }
+        }
+        // Other items than food do nothing yet
+        self.start_eating(false).await;
+    }
+
+    /// Fills an empty bucket from the fluid source the player looks at, or places the fluid of a filled one
+    async fn use_bucket(&self, held_item: &str) {
+        if matches!(
+            self.gamemode.load(),
+            GameMode::Spectator | GameMode::Adventure
+        ) {
+            return;
+        }
+        let entity = &self.living_entity.entity;
+        let world = &entity.world;
+        let Some((target, face)) = world
+            .raycast_block_face(
+                entity.eye_position(),
+                entity.rotation_vector(),
+                self.block_interaction_range(),
+            )
+            .await
+        else {
+            return;
+        };
+        let Ok((block, state)) = world.get_block_and_block_state(&target).await else {
+            return;
+        };
+        // The source of a fluid is its default state, with a level of 0
+        let source = state.id == block.default_state_id;
+        let Some(action) = BucketAction::new(held_item, &block.name, source) else {
+            return;
+        };
+
+        let (position, block_state_id, new_item) = match action {
+            BucketAction::PickUp(fluid) => (target, 0, fluid.bucket_name()),
+            BucketAction::Place(fluid) => {
+                let position = if state.replaceable {
+                    target
+                } else {
+                    BlockPos(target.0 + face.to_offset())
+                };
+                if position.0.y < world.min_y || position.0.y >= world.max_y() {
+                    return;
+                }
+                let Ok(previous) = world.get_block_state(&position).await else {
+                    return;
+                };
+                let Some(fluid_block) = get_block(fluid.block_name()) else {
+                    return;
+                };
+                if !previous.replaceable {
+                    return;
+                }
+                (position, fluid_block.default_state_id, EMPTY_BUCKET)
+            }
+        };
+        if world.is_protected(&position, self) {
+            self.resync_block(&position).await;
+            return;
+        }
+        world.set_block_state(&position, block_state_id).await;
+
+        if self.gamemode.load() != GameMode::Creative {
+            if let Some(item) = get_item(new_item) {
+                self.exchange_held_item(item).await;
+            }
+        }
     }
 
     pub async fn handle_set_held_item(&self, held: SSetHeldItem) {
@@ -1003,6 +1434,7 @@ impl Player {
             return;
         }
         self.inventory().lock().await.set_selected(slot as u32);
+        self.held_item_attributes.invalidate();
     }
 
     pub async fn handle_set_creative_slot(
@@ -1012,15 +1444,19 @@ impl Player {
         if self.gamemode.load() != GameMode::Creative {
             return Err(InventoryError::PermissionError);
         }
+        let item = packet.clicked_item.to_item();
+        if item.is_some_and(|stack| !is_valid_creative_stack(stack)) {
+            return Err(InventoryError::InvalidPacket);
+        }
         let valid_slot = packet.slot >= 0 && packet.slot <= 45;
         if valid_slot {
-            self.inventory().lock().await.set_slot(
-                packet.slot as usize,
-                packet.clicked_item.to_item(),
-                true,
-            )?;
-        };
-        // TODO: The Item was dropped per drag and drop,
+            self.inventory()
+                .lock()
+                .await
+                .set_slot(packet.slot as usize, item, true)?;
+        }
+        // TODO: A slot of -1 means the Item was dropped per drag and drop. Spawn it in front of the player
+        // once item entities exist, until then it is discarded like an item put into the creative trash slot
         Ok(())
     }
 
@@ -1112,26 +1548,30 @@ impl Player {
                 f64::from(world_pos.0.y),
                 f64::from(world_pos.0.z) + 0.5,
             );
-            // create rotation like Vanilla
-            let yaw = wrap_degrees(rand::random::<f32>() * 360.0) % 360.0;
-
             let world = self.world();
-            // create new mob and uuid based on spawn egg id
-            let (mob, uuid) = mob::from_type(
-                EntityType::from_raw(*spawn_item_id).unwrap(),
-                server,
-                pos,
-                world,
-            )
-            .await;
-
-            // set the rotation
-            mob.living_entity.entity.set_rotation(yaw, 0.0);
-
-            // broadcast new mob to all players
-            world
-                .broadcast_packet_all(&mob.living_entity.entity.create_spawn_packet(uuid))
+            if world.is_protected(&world_pos, self) {
+                return Ok(false);
+            }
+            if let Some(limit) = world.entity_limit_reached(pos).await {
+                log::warn!(
+                    "{} could not use a spawn egg: {limit}",
+                    self.gameprofile.name
+                );
+                self.send_system_message(
+                    &TextComponent::text(limit.to_string()).color_named(NamedColor::Red),
+                )
                 .await;
+                return Ok(false);
+            }
+            // create new mob based on spawn egg id
+            let entity_type = EntityType::from_raw(*spawn_item_id).unwrap();
+            if world
+                .spawn_entity(server, entity_type, pos, SpawnReason::SpawnEgg)
+                .await
+                .is_none()
+            {
+                return Ok(false);
+            }
 
             // TODO: send/configure additional commands/data based on type of entity (horse, slime, etc)
         } else {
@@ -1143,7 +1583,7 @@ impl Player {
     }
 
     async fn run_is_block_place(
-        &self,
+        self: &Arc<Self>,
         block: Block,
         server: &Server,
         use_item_on: SUseItemOn,
@@ -1154,7 +1594,7 @@ impl Player {
         let world = &entity.world;
 
         // check block under the world
-        if location.0.y + face.to_offset().y < WORLD_LOWEST_Y.into() {
+        if location.0.y + face.to_offset().y < world.min_y {
             self.client
                 .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
                 .await;
@@ -1162,16 +1602,20 @@ impl Player {
         }
 
         //check max world build height
-        if location.0.y + face.to_offset().y >= WORLD_MAX_Y.into() {
-            self.send_system_message_raw(
-                &TextComponent::translate(
-                    "build.tooHigh",
-                    vec![TextComponent::text((WORLD_MAX_Y - 1).to_string())],
+        let build_limit = world.build_limit(self);
+        if location.0.y + face.to_offset().y >= build_limit {
+            // Players bypassing the limit can only reach the top of the world, which needs no message
+            if !self.bypasses_build_limit() {
+                self.send_system_message_raw(
+                    &TextComponent::translate(
+                        "build.tooHigh",
+                        vec![TextComponent::text((build_limit - 1).to_string())],
+                    )
+                    .color_named(NamedColor::Red),
+                    true,
                 )
-                .color_named(NamedColor::Red),
-                true,
-            )
-            .await;
+                .await;
+            }
             self.client
                 .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
                 .await;
@@ -1204,17 +1648,39 @@ impl Player {
             world_pos
         };
 
-        // To this point we must have the new block state
-        let block_bounding_box =
-            get_block_collision_shapes(block.default_state_id).unwrap_or_default();
-        let mut intersects = false;
-        for player in world.get_nearby_players(entity.pos.load(), 20.0).await {
-            let bounding_box = player.1.living_entity.entity.bounding_box.load();
-            if bounding_box.intersects_block(&world_pos, &block_bounding_box) {
-                intersects = true;
-            }
+        if world.is_protected(&world_pos, self) {
+            self.client
+                .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
+                .await;
+            return Ok(false);
         }
+
+        // To this point we must have the new block state
+        let block_boxes = BoundingBox::from_collision_shapes(
+            &world_pos,
+            &get_block_collision_shapes(block.default_state_id).unwrap_or_default(),
+        );
+        let intersects = world
+            .get_nearby_players(entity.pos.load(), 20.0)
+            .await
+            .values()
+            .any(|player| {
+                let bounding_box = player.living_entity.entity.bounding_box.load();
+                bounding_box.intersects_any(&block_boxes)
+            });
         if !intersects {
+            let clicked_block = world.get_block(&clicked_world_pos).await?.clone();
+            let event =
+                BlockPlaceEventImpl::new(self.clone(), block.clone(), clicked_block, world_pos);
+            let event = PLUGIN_MANAGER.lock().await.fire_cancellable(event).await;
+            if event.is_cancelled() || !event.can_build() {
+                // The client already shows the placed block
+                self.resync_block(&world_pos).await;
+                self.client
+                    .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
+                    .await;
+                return Ok(false);
+            }
             world
                 .set_block_state(&world_pos, block.default_state_id)
                 .await;
@@ -1229,3 +1695,10 @@ impl Player {
         Ok(true)
     }
 }
+
+/// Creative clients may create any item, but only as a stack that could exist in vanilla
+fn is_valid_creative_stack(stack: ItemStack) -> bool {
+    get_item_by_id(stack.item_id).is_some_and(|item| {
+        stack.item_count > 0 && stack.item_count <= item.components.max_stack_size
+    })
+}
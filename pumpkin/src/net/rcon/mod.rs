@@ -105,10 +105,19 @@ impl RCONClient {
                 if self.logged_in {
                     let output = Arc::new(tokio::sync::Mutex::new(Vec::new()));
 
+                    let Some(task) = server.command_tasks.start() else {
+                        self.send(
+                            ClientboundPacket::Output,
+                            packet.get_id(),
+                            "The server is shutting down",
+                        )
+                        .await?;
+                        return Ok(());
+                    };
                     let server_clone = server.clone();
                     let output_clone = output.clone();
                     let packet_body = packet.get_body().to_owned();
-                    tokio::spawn(async move {
+                    tokio::spawn(task.run(async move {
                         let dispatcher = server_clone.command_dispatcher.read().await;
                         dispatcher
                             .handle_command(
@@ -117,7 +126,7 @@ impl RCONClient {
                                 &packet_body,
                             )
                             .await;
-                    });
+                    }));
 
                     let output = output.lock().await;
                     for line in output.iter() {
@@ -1,15 +1,19 @@
-use std::{fs, path::Path, sync::Arc};
+use std::{fs, io, path::Path, sync::Arc, time::Duration};
 
-use pumpkin_util::PermissionLvl;
+use pumpkin_util::{math::position::BlockPos, text::TextComponent, PermissionLvl};
+use serde::{de::DeserializeOwned, Serialize};
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
 use crate::{
+    command::client_cmd_suggestions,
     entity::player::Player,
     plugin::{EventHandler, HandlerMap, TypedEventHandler},
     server::Server,
+    world::{bossbar::Bossbar, scheduled_tick::TickPriority, World},
 };
 
-use super::{Event, EventPriority, PermissionChecker, PluginMetadata};
+use super::{packet, Event, EventPriority, PermissionChecker, PluginMetadata, WorldEdit};
 
 pub struct Context {
     metadata: PluginMetadata<'static>,
@@ -36,15 +40,120 @@ impl Context {
     pub fn get_data_folder(&self) -> String {
         let path = format!("./plugins/{}", self.metadata.name);
         if !Path::new(&path).exists() {
-            fs::create_dir_all(&path).unwrap();
+            if let Err(err) = fs::create_dir_all(&path) {
+                log::error!("Failed to create data folder {path}: {err}");
+            }
         }
         path
     }
 
+    /// Loads the config `name` from `<data folder>/<name>.toml`, writing the default config there on the first run.
+    /// A config that can not be read or parsed is logged and the default is returned instead
+    #[must_use]
+    pub fn load_config<T: DeserializeOwned + Serialize + Default>(&self, name: &str) -> T {
+        load_config_file(&Path::new(&self.get_data_folder()).join(format!("{name}.toml")))
+    }
+
+    /// Saves the config `name` to `<data folder>/<name>.toml`, replacing the previous one
+    pub fn save_config<T: Serialize>(&self, name: &str, config: &T) -> io::Result<()> {
+        save_config_file(
+            &Path::new(&self.get_data_folder()).join(format!("{name}.toml")),
+            config,
+        )
+    }
+
     pub async fn get_player_by_name(&self, player_name: String) -> Option<Arc<Player>> {
         self.server.get_player_by_name(&player_name).await
     }
 
+    /// Changes the name shown for a player instead of their username, returns false if the player is not online
+    pub async fn set_display_name(
+        &self,
+        player_name: String,
+        display_name: Option<TextComponent>,
+    ) -> bool {
+        let Some(player) = self.get_player_by_name(player_name).await else {
+            return false;
+        };
+        player.set_display_name(display_name).await;
+        true
+    }
+
+    /// Shows a sidebar only this player sees, see [`Player::set_sidebar`]. Returns false if the player is not online
+    pub async fn set_sidebar(
+        &self,
+        player_name: String,
+        title: TextComponent,
+        lines: Vec<TextComponent>,
+    ) -> bool {
+        let Some(player) = self.get_player_by_name(player_name).await else {
+            return false;
+        };
+        player.set_sidebar(title, lines).await;
+        true
+    }
+
+    /// Hides the sidebar of a player, returns false if the player is not online
+    pub async fn clear_sidebar(&self, player_name: String) -> bool {
+        let Some(player) = self.get_player_by_name(player_name).await else {
+            return false;
+        };
+        player.clear_sidebar().await;
+        true
+    }
+
+    /// Shows a boss bar to a player, or updates it if they already see it.
+    /// Returns false if the player is not online
+    pub async fn show_bossbar(&self, player_name: String, bossbar: &Bossbar) -> bool {
+        let Some(player) = self.get_player_by_name(player_name).await else {
+            return false;
+        };
+        player.send_bossbar(bossbar).await;
+        true
+    }
+
+    /// Hides a boss bar from a player, returns false if the player is not online
+    pub async fn hide_bossbar(&self, player_name: String, uuid: Uuid) -> bool {
+        let Some(player) = self.get_player_by_name(player_name).await else {
+            return false;
+        };
+        player.remove_bossbar(uuid).await;
+        true
+    }
+
+    /// Sends the changes of a boss bar to every player seeing it
+    pub async fn update_bossbar(&self, bossbar: &Bossbar) {
+        for player in self.server.get_all_players().await {
+            player.update_bossbar(bossbar).await;
+        }
+    }
+
+    /// Hides a boss bar from every player seeing it
+    pub async fn remove_bossbar(&self, uuid: Uuid) {
+        for player in self.server.get_all_players().await {
+            player.remove_bossbar(uuid).await;
+        }
+    }
+
+    /// Schedules a tick of a block, which fires a `BlockTickEvent` once it is due.
+    /// Returns false if the block already has a tick scheduled
+    pub async fn schedule_block_tick(
+        &self,
+        world: &World,
+        position: &BlockPos,
+        delay: u32,
+        priority: TickPriority,
+    ) -> bool {
+        world.schedule_block_tick(position, delay, priority).await
+    }
+
+    /// Starts a batch of block changes in a world, which are applied together on [`WorldEdit::commit`].
+    /// Use this instead of setting many blocks one by one, so clients don't get a packet per block
+    #[must_use]
+    pub fn begin_world_edit(&self, world: Arc<World>) -> WorldEdit {
+        WorldEdit::new(world)
+    }
+
     pub async fn register_command(
         &self,
         tree: crate::command::tree::CommandTree,
@@ -54,6 +163,46 @@ impl Context {
         dispatcher_lock.register_with_plugin(tree, permission, &self.metadata.name);
     }
 
+    /// Removes a command this plugin registered together with all of its names.
+    /// Returns false if the plugin has no command called `name`
+    pub async fn unregister_command(&self, name: &str) -> bool {
+        let mut dispatcher_lock = self.server.command_dispatcher.write().await;
+        if dispatcher_lock.plugin_names.get(name).map(String::as_str) != Some(self.metadata.name) {
+            return false;
+        }
+        dispatcher_lock.unregister(name);
+        drop(dispatcher_lock);
+        resend_commands(&self.server).await;
+        true
+    }
+
+    /// Removes every command this plugin registered, used when it is unloaded.
+    /// Plugins are unloaded by `/plugin unload`, which holds the dispatcher until it returns, so the
+    /// commands are removed by a task of their own once the dispatcher is free again
+    pub(crate) fn unregister_all_commands(&self) {
+        let server = self.server.clone();
+        let plugin_name = self.metadata.name;
+        tokio::spawn(async move {
+            let removed = server
+                .command_dispatcher
+                .write()
+                .await
+                .unregister_all_for_plugin(plugin_name);
+            if removed > 0 {
+                resend_commands(&server).await;
+            }
+        });
+    }
+
+    /// Sets how long players have to wait between two uses of a command, zero removes the cooldown
+    pub async fn set_command_cooldown(&self, name: &str, cooldown: Duration) {
+        let mut dispatcher_lock = self.server.command_dispatcher.write().await;
+        dispatcher_lock.set_cooldown(name, cooldown);
+    }
+
+    /// Registers a handler for the event `E`. Blocking handlers run first, from the highest to the
+    /// lowest `priority`, and can modify the event. Once one of them cancels a [`crate::plugin::CancellableEvent`]
+    /// the remaining blocking handlers are skipped. Non-blocking handlers run afterwards and only observe the event
     pub async fn register_event<E: Event + 'static, H>(
         &self,
         handler: H,
@@ -75,6 +224,7 @@ impl Context {
             _phantom: std::marker::PhantomData,
         };
         handlers_vec.push(Box::new(typed_handler));
+        packet::on_handler_registered(E::get_name_static(), &self.handlers);
     }
 
     pub async fn register_permission_checker(&self, checker: Arc<dyn PermissionChecker>) {
@@ -87,3 +237,75 @@ impl Context {
         perm_checker.as_ref().cloned()
     }
 }
+
+/// Sends the changed commands to every player, so they are not suggested anymore
+async fn resend_commands(server: &Server) {
+    for player in server.get_all_players().await {
+        client_cmd_suggestions::send_c_commands_packet(&player, &server.command_dispatcher).await;
+    }
+}
+
+fn load_config_file<T: DeserializeOwned + Serialize + Default>(path: &Path) -> T {
+    if !path.exists() {
+        let config = T::default();
+        if let Err(err) = save_config_file(path, &config) {
+            log::warn!("Failed to write default config {}: {err}", path.display());
+        }
+        return config;
+    }
+    // The broken file is not overwritten, so it can still be fixed by hand
+    let config = fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|content| toml::from_str(&content).map_err(|err| err.message().to_string()));
+    config.unwrap_or_else(|err| {
+        log::error!(
+            "Failed to load config {}, using the default: {err}",
+            path.display()
+        );
+        T::default()
+    })
+}
+
+fn save_config_file<T: Serialize>(path: &Path, config: &T) -> io::Result<()> {
+    let content = toml::to_string_pretty(config).map_err(io::Error::other)?;
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::{load_config_file, save_config_file};
+    use crate::data::test_folder::TestFolder;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct TestConfig {
+        greeting: String,
+        max_homes: u32,
+        worlds: Vec<String>,
+    }
+
+    #[test]
+    fn config_round_trip() {
+        let folder = TestFolder::new("plugin-config");
+        fs::create_dir_all(&folder).unwrap();
+        let path = folder.join("config.toml");
+
+        // The first load writes the default
+        assert_eq!(load_config_file::<TestConfig>(&path), TestConfig::default());
+        assert!(path.exists());
+
+        let config = TestConfig {
+            greeting: "Hello".to_string(),
+            max_homes: 3,
+            worlds: vec!["overworld".to_string(), "nether".to_string()],
+        };
+        save_config_file(&path, &config).unwrap();
+        assert_eq!(load_config_file::<TestConfig>(&path), config);
+
+        fs::write(&path, "max_homes = \"three\"").unwrap();
+        assert_eq!(load_config_file::<TestConfig>(&path), TestConfig::default());
+    }
+}
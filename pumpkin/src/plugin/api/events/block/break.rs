@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::block::block_registry::Block;
 
 use crate::{
@@ -12,6 +13,7 @@ use super::{BlockBreakEvent, BlockEvent, BlockExpEvent};
 pub struct BlockBreakEventImpl {
     player: Option<Arc<Player>>,
     block: Block,
+    position: BlockPos,
     exp: u32,
     drop: bool,
     is_cancelled: bool,
@@ -19,10 +21,17 @@ pub struct BlockBreakEventImpl {
 
 impl BlockBreakEventImpl {
     #[must_use]
-    pub fn new(player: Option<Arc<Player>>, block: Block, exp: u32, drop: bool) -> Self {
+    pub fn new(
+        player: Option<Arc<Player>>,
+        block: Block,
+        position: BlockPos,
+        exp: u32,
+        drop: bool,
+    ) -> Self {
         Self {
             player,
             block,
+            position,
             exp,
             drop,
             is_cancelled: false,
@@ -35,6 +44,10 @@ impl BlockBreakEvent for BlockBreakEventImpl {
         self.player.clone()
     }
 
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
     fn will_drop(&self) -> bool {
         self.drop
     }
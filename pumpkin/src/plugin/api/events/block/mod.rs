@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::block::block_registry::Block;
 
 use crate::entity::player::Player;
@@ -10,6 +11,8 @@ pub mod r#break;
 pub mod burn;
 pub mod can_build;
 pub mod place;
+pub mod redstone;
+pub mod tick;
 
 pub trait BlockEvent: CancellableEvent {
     fn get_block(&self) -> &Block;
@@ -20,8 +23,10 @@ pub trait BlockExpEvent: BlockEvent {
     fn set_exp_to_drop(&mut self, exp: u32);
 }
 
+/// Fired before a block is broken, cancelling it keeps the block
 pub trait BlockBreakEvent: BlockExpEvent {
     fn get_player(&self) -> Option<Arc<Player>>;
+    fn get_position(&self) -> BlockPos;
     fn will_drop(&self) -> bool;
     fn set_drop(&mut self, drop: bool);
 }
@@ -37,10 +42,22 @@ pub trait BlockCanBuildEvent: BlockEvent {
     fn get_player(&self) -> Option<Arc<Player>>;
 }
 
+/// Fired before a player places a block, cancelling it or disallowing building keeps the old block
 pub trait BlockPlaceEvent: BlockEvent {
     fn get_player(&self) -> Option<Arc<Player>>;
+    fn get_position(&self) -> BlockPos;
     fn can_build(&self) -> bool;
     fn set_build(&mut self, build: bool);
     fn get_block_placed_against(&self) -> &Block;
     fn get_block_placed(&self) -> &Block;
 }
+
+pub trait BlockRedstoneEvent: BlockEvent {
+    fn get_position(&self) -> BlockPos;
+    fn get_old_current(&self) -> i32;
+    fn get_new_current(&self) -> i32;
+}
+
+pub trait BlockTickEvent: BlockEvent {
+    fn get_position(&self) -> BlockPos;
+}
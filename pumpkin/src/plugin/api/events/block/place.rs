@@ -1,3 +1,4 @@
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::block::block_registry::Block;
 use std::sync::Arc;
 
@@ -12,15 +13,39 @@ pub struct BlockPlaceEventImpl {
     player: Arc<Player>,
     block_placed: Block,
     block_placed_against: Block,
+    position: BlockPos,
     can_build: bool,
     is_cancelled: bool,
 }
 
+impl BlockPlaceEventImpl {
+    #[must_use]
+    pub fn new(
+        player: Arc<Player>,
+        block_placed: Block,
+        block_placed_against: Block,
+        position: BlockPos,
+    ) -> Self {
+        Self {
+            player,
+            block_placed,
+            block_placed_against,
+            position,
+            can_build: true,
+            is_cancelled: false,
+        }
+    }
+}
+
 impl BlockPlaceEvent for BlockPlaceEventImpl {
     fn get_player(&self) -> Option<Arc<Player>> {
         Some(self.player.clone())
     }
 
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
     fn can_build(&self) -> bool {
         self.can_build
     }
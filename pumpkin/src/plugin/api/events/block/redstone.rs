@@ -0,0 +1,77 @@
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::block_registry::Block;
+
+use crate::plugin::{CancellableEvent, Event};
+
+use super::{BlockEvent, BlockRedstoneEvent};
+
+/// Fired when a power source like a button, lever or pressure plate changes its redstone current.
+/// Cancelling it keeps the block as it was
+pub struct BlockRedstoneEventImpl {
+    block: Block,
+    position: BlockPos,
+    old_current: i32,
+    new_current: i32,
+    is_cancelled: bool,
+}
+
+impl BlockRedstoneEventImpl {
+    #[must_use]
+    pub fn new(block: Block, position: BlockPos, old_current: i32, new_current: i32) -> Self {
+        Self {
+            block,
+            position,
+            old_current,
+            new_current,
+            is_cancelled: false,
+        }
+    }
+}
+
+impl BlockRedstoneEvent for BlockRedstoneEventImpl {
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn get_old_current(&self) -> i32 {
+        self.old_current
+    }
+
+    fn get_new_current(&self) -> i32 {
+        self.new_current
+    }
+}
+
+impl BlockEvent for BlockRedstoneEventImpl {
+    fn get_block(&self) -> &Block {
+        &self.block
+    }
+}
+
+impl CancellableEvent for BlockRedstoneEventImpl {
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.is_cancelled = cancelled;
+    }
+}
+
+impl Event for BlockRedstoneEventImpl {
+    fn get_name_static() -> &'static str {
+        "BlockRedstoneEvent"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "BlockRedstoneEvent"
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,64 @@
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::block_registry::Block;
+
+use crate::plugin::{CancellableEvent, Event};
+
+use super::{BlockEvent, BlockTickEvent};
+
+/// Fired when a scheduled tick of a block is due, cancelling it skips the block's own behavior
+pub struct BlockTickEventImpl {
+    block: Block,
+    position: BlockPos,
+    is_cancelled: bool,
+}
+
+impl BlockTickEventImpl {
+    #[must_use]
+    pub fn new(block: Block, position: BlockPos) -> Self {
+        Self {
+            block,
+            position,
+            is_cancelled: false,
+        }
+    }
+}
+
+impl BlockTickEvent for BlockTickEventImpl {
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+}
+
+impl BlockEvent for BlockTickEventImpl {
+    fn get_block(&self) -> &Block {
+        &self.block
+    }
+}
+
+impl CancellableEvent for BlockTickEventImpl {
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.is_cancelled = cancelled;
+    }
+}
+
+impl Event for BlockTickEventImpl {
+    fn get_name_static() -> &'static str {
+        "BlockTickEvent"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "BlockTickEvent"
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
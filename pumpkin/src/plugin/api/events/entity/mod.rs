@@ -0,0 +1,31 @@
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::vector3::Vector3;
+
+use super::CancellableEvent;
+
+pub mod spawn;
+
+/// Why an entity is spawned, so plugins can only control some spawns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnReason {
+    /// Summoned by a command like `/summon`
+    Command,
+    /// A player used a spawn egg
+    SpawnEgg,
+    /// Spawned by the natural mob spawner
+    Natural,
+    /// Born from two breeding animals
+    Breeding,
+    /// Spawned by a plugin
+    Custom,
+}
+
+pub trait EntityEvent: CancellableEvent {
+    fn get_entity_type(&self) -> EntityType;
+}
+
+/// An entity is about to be added to a world, cancelling it prevents the entity from being created
+pub trait EntitySpawnEvent: EntityEvent {
+    fn get_position(&self) -> Vector3<f64>;
+    fn get_reason(&self) -> SpawnReason;
+}
@@ -0,0 +1,69 @@
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::plugin::{CancellableEvent, Event};
+
+use super::{EntityEvent, EntitySpawnEvent, SpawnReason};
+
+pub struct EntitySpawnEventImpl {
+    entity_type: EntityType,
+    position: Vector3<f64>,
+    reason: SpawnReason,
+    is_cancelled: bool,
+}
+
+impl EntitySpawnEventImpl {
+    #[must_use]
+    pub fn new(entity_type: EntityType, position: Vector3<f64>, reason: SpawnReason) -> Self {
+        Self {
+            entity_type,
+            position,
+            reason,
+            is_cancelled: false,
+        }
+    }
+}
+
+impl EntitySpawnEvent for EntitySpawnEventImpl {
+    fn get_position(&self) -> Vector3<f64> {
+        self.position
+    }
+
+    fn get_reason(&self) -> SpawnReason {
+        self.reason
+    }
+}
+
+impl EntityEvent for EntitySpawnEventImpl {
+    fn get_entity_type(&self) -> EntityType {
+        self.entity_type
+    }
+}
+
+impl CancellableEvent for EntitySpawnEventImpl {
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.is_cancelled = cancelled;
+    }
+}
+
+impl Event for EntitySpawnEventImpl {
+    fn get_name_static() -> &'static str {
+        "EntitySpawnEvent"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "EntitySpawnEvent"
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -1,6 +1,8 @@
 use std::any::Any;
 
 pub mod block;
+pub mod entity;
+pub mod packet;
 pub mod player;
 
 pub trait Event: Any + Send + Sync {
@@ -12,13 +14,15 @@ pub trait Event: Any + Send + Sync {
     fn as_any(&self) -> &dyn Any;
 }
 
+/// An event whose default action is skipped when a handler cancels it,
+/// fire it with [`crate::plugin::PluginManager::fire_cancellable`]
 pub trait CancellableEvent: Event {
     fn is_cancelled(&self) -> bool;
     fn set_cancelled(&mut self, cancelled: bool);
 }
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Clone)]
-// Lowest priority events are executed first, so that higher priority events can override their changes
+// Highest priority handlers are executed first, so they can cancel an event before lower priority handlers see it
 pub enum EventPriority {
     Highest,
     High,
@@ -0,0 +1,198 @@
+//! Events for the raw packets of a connection, for plugins like proxies or custom protocols.
+//!
+//! Packets are the hottest path of the server, so these events are only fired once a plugin
+//! registered a handler for them. After that, every packet of every connection creates an event
+//! and waits for all handlers on the connection's task, so slow handlers delay the whole connection.
+//! Handlers are never removed, unloading the plugin does not bring the cost back to zero.
+//! Packets sent by handlers of [`PacketSendEvent`] while handling it do not fire the event again,
+//! otherwise a handler sending a packet would fire itself forever.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, OnceLock,
+};
+
+use bytes::{Bytes, BytesMut};
+use pumpkin_protocol::{ClientPacket, ConnectionState, RawPacket};
+use tokio::sync::RwLock;
+
+use crate::{
+    entity::player::Player,
+    net::Client,
+    plugin::{fire_event, HandlerMap},
+};
+
+use super::{CancellableEvent, Event};
+
+pub mod receive;
+pub mod send;
+
+use receive::PacketReceiveEventImpl;
+use send::PacketSendEventImpl;
+
+pub trait PacketEvent: CancellableEvent {
+    /// The id of the packet, which depends on the connection state
+    fn get_packet_id(&self) -> i32;
+    /// The id of the connection, see [`Client::id`]
+    fn get_client_id(&self) -> u16;
+}
+
+/// A packet of a player was received and is about to be handled, cancelling drops it
+pub trait PacketReceiveEvent: PacketEvent {
+    fn get_player(&self) -> Arc<Player>;
+    /// The encoded packet after its id
+    fn get_payload(&self) -> &Bytes;
+}
+
+/// A packet is about to be sent to a connection, cancelling drops it
+pub trait PacketSendEvent: PacketEvent {
+    fn get_connection_state(&self) -> ConnectionState;
+    /// The encoded packet after its id
+    fn get_payload(&self) -> &Bytes;
+}
+
+static RECEIVE_HANDLED: AtomicBool = AtomicBool::new(false);
+static SEND_HANDLED: AtomicBool = AtomicBool::new(false);
+/// The handlers of all events, kept here so packets don't have to wait for the plugin manager
+static HANDLERS: OnceLock<Arc<RwLock<HandlerMap>>> = OnceLock::new();
+
+/// Enables firing the packet events once a handler for them is registered
+pub(crate) fn on_handler_registered(name: &str, handlers: &Arc<RwLock<HandlerMap>>) {
+    let handled = if name == PacketReceiveEventImpl::get_name_static() {
+        &RECEIVE_HANDLED
+    } else if name == PacketSendEventImpl::get_name_static() {
+        &SEND_HANDLED
+    } else {
+        return;
+    };
+    HANDLERS.get_or_init(|| handlers.clone());
+    handled.store(true, Ordering::Relaxed);
+}
+
+/// Fires a [`PacketReceiveEvent`] if a plugin handles them, returns whether the packet should still be handled
+pub(crate) async fn fire_receive(player: &Arc<Player>, packet: &RawPacket) -> bool {
+    if !RECEIVE_HANDLED.load(Ordering::Relaxed) {
+        return true;
+    }
+    let Some(handlers) = HANDLERS.get() else {
+        return true;
+    };
+    let event = PacketReceiveEventImpl::new(player.clone(), packet.id.0, packet.bytebuf.clone());
+    !fire_event(handlers, event, CancellableEvent::is_cancelled)
+        .await
+        .is_cancelled()
+}
+
+/// Fires a [`PacketSendEvent`] if a plugin handles them, returns whether the packet should still be sent
+pub(crate) async fn fire_send<P: ClientPacket>(client: &Client, packet: &P) -> bool {
+    if !SEND_HANDLED.load(Ordering::Relaxed) {
+        return true;
+    }
+    let Some(handlers) = HANDLERS.get() else {
+        return true;
+    };
+    fire_send_with(handlers, client, packet).await
+}
+
+tokio::task_local! {
+    /// Set while the handlers of a [`PacketSendEvent`] run on the task
+    static SENDING: ();
+}
+
+async fn fire_send_with<P: ClientPacket>(
+    handlers: &RwLock<HandlerMap>,
+    client: &Client,
+    packet: &P,
+) -> bool {
+    // Packets the handlers send themselves don't fire the event again, which would recurse forever
+    // and lock the handlers again while they are still locked
+    if SENDING.try_with(|()| ()).is_ok() {
+        return true;
+    }
+    let mut payload = BytesMut::new();
+    packet.write(&mut payload);
+    let event = PacketSendEventImpl::new(
+        client.id,
+        P::PACKET_ID,
+        client.connection_state.load(),
+        payload.freeze(),
+    );
+    !SENDING
+        .scope(
+            (),
+            fire_event(handlers, event, CancellableEvent::is_cancelled),
+        )
+        .await
+        .is_cancelled()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        marker::PhantomData,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            LazyLock,
+        },
+    };
+
+    use async_trait::async_trait;
+    use pumpkin_protocol::{bytebuf::packet_id::Packet, client::play::CSetHealth};
+    use tokio::sync::RwLock;
+
+    use super::{fire_send_with, send::PacketSendEventImpl, PacketEvent, PacketSendEvent};
+    use crate::{
+        net::Client,
+        plugin::{Event, EventHandler, EventPriority, HandlerMap, TypedEventHandler},
+    };
+
+    static HANDLERS: LazyLock<RwLock<HandlerMap>> = LazyLock::new(|| {
+        let handler = TypedEventHandler {
+            handler: Resend,
+            priority: EventPriority::Normal,
+            blocking: true,
+            _phantom: PhantomData,
+        };
+        let mut handlers: HandlerMap = HashMap::new();
+        handlers.insert(
+            PacketSendEventImpl::get_name_static(),
+            vec![Box::new(handler)],
+        );
+        RwLock::new(handlers)
+    });
+
+    static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+    fn test_client() -> Client {
+        Client::new(
+            tokio::sync::mpsc::channel(1).0,
+            "127.0.0.1:25565".parse().unwrap(),
+            0,
+        )
+    }
+
+    /// Sends another packet for every packet that is sent
+    struct Resend;
+
+    #[async_trait]
+    impl EventHandler<PacketSendEventImpl> for Resend {
+        async fn handle_blocking(&self, event: &mut PacketSendEventImpl) {
+            HANDLED.fetch_add(1, Ordering::Relaxed);
+            assert_eq!(event.get_packet_id(), CSetHealth::PACKET_ID);
+            assert!(!event.get_payload().is_empty());
+            let packet = CSetHealth::new(1.0, 1.into(), 1.0);
+            assert!(fire_send_with(&HANDLERS, &test_client(), &packet).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn handlers_sending_packets_do_not_fire_again() {
+        let packet = CSetHealth::new(20.0, 20.into(), 5.0);
+        assert!(fire_send_with(&HANDLERS, &test_client(), &packet).await);
+        assert_eq!(HANDLED.load(Ordering::Relaxed), 1);
+        // Packets sent later fire the event again
+        assert!(fire_send_with(&HANDLERS, &test_client(), &packet).await);
+        assert_eq!(HANDLED.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::{
+    entity::player::Player,
+    plugin::{CancellableEvent, Event},
+};
+
+use super::{PacketEvent, PacketReceiveEvent};
+
+pub struct PacketReceiveEventImpl {
+    player: Arc<Player>,
+    packet_id: i32,
+    payload: Bytes,
+    is_cancelled: bool,
+}
+
+impl PacketReceiveEventImpl {
+    #[must_use]
+    pub fn new(player: Arc<Player>, packet_id: i32, payload: Bytes) -> Self {
+        Self {
+            player,
+            packet_id,
+            payload,
+            is_cancelled: false,
+        }
+    }
+}
+
+impl PacketReceiveEvent for PacketReceiveEventImpl {
+    fn get_player(&self) -> Arc<Player> {
+        self.player.clone()
+    }
+
+    fn get_payload(&self) -> &Bytes {
+        &self.payload
+    }
+}
+
+impl PacketEvent for PacketReceiveEventImpl {
+    fn get_packet_id(&self) -> i32 {
+        self.packet_id
+    }
+
+    fn get_client_id(&self) -> u16 {
+        self.player.client.id
+    }
+}
+
+impl CancellableEvent for PacketReceiveEventImpl {
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.is_cancelled = cancelled;
+    }
+}
+
+impl Event for PacketReceiveEventImpl {
+    fn get_name_static() -> &'static str {
+        "PacketReceiveEvent"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "PacketReceiveEvent"
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
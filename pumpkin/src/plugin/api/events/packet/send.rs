@@ -0,0 +1,80 @@
+use bytes::Bytes;
+use pumpkin_protocol::ConnectionState;
+
+use crate::plugin::{CancellableEvent, Event};
+
+use super::{PacketEvent, PacketSendEvent};
+
+pub struct PacketSendEventImpl {
+    client_id: u16,
+    packet_id: i32,
+    connection_state: ConnectionState,
+    payload: Bytes,
+    is_cancelled: bool,
+}
+
+impl PacketSendEventImpl {
+    #[must_use]
+    pub fn new(
+        client_id: u16,
+        packet_id: i32,
+        connection_state: ConnectionState,
+        payload: Bytes,
+    ) -> Self {
+        Self {
+            client_id,
+            packet_id,
+            connection_state,
+            payload,
+            is_cancelled: false,
+        }
+    }
+}
+
+impl PacketSendEvent for PacketSendEventImpl {
+    fn get_connection_state(&self) -> ConnectionState {
+        self.connection_state
+    }
+
+    fn get_payload(&self) -> &Bytes {
+        &self.payload
+    }
+}
+
+impl PacketEvent for PacketSendEventImpl {
+    fn get_packet_id(&self) -> i32 {
+        self.packet_id
+    }
+
+    fn get_client_id(&self) -> u16 {
+        self.client_id
+    }
+}
+
+impl CancellableEvent for PacketSendEventImpl {
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.is_cancelled = cancelled;
+    }
+}
+
+impl Event for PacketSendEventImpl {
+    fn get_name_static() -> &'static str {
+        "PacketSendEvent"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "PacketSendEvent"
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
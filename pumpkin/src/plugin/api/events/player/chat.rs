@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::{
+    entity::player::Player,
+    plugin::{CancellableEvent, Event},
+};
+
+use super::{PlayerChatEvent, PlayerEvent};
+
+pub struct PlayerChatEventImpl {
+    player: Arc<Player>,
+    message: String,
+    is_cancelled: bool,
+}
+
+impl PlayerChatEventImpl {
+    pub fn new(player: Arc<Player>, message: String) -> Self {
+        Self {
+            player,
+            message,
+            is_cancelled: false,
+        }
+    }
+}
+
+impl PlayerChatEvent for PlayerChatEventImpl {
+    fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    fn set_message(&mut self, message: String) {
+        self.message = message;
+    }
+}
+
+impl PlayerEvent for PlayerChatEventImpl {
+    fn get_player(&self) -> Arc<Player> {
+        self.player.clone()
+    }
+}
+
+impl CancellableEvent for PlayerChatEventImpl {
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.is_cancelled = cancelled;
+    }
+}
+
+impl Event for PlayerChatEventImpl {
+    fn get_name_static() -> &'static str {
+        "PlayerChatEvent"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "PlayerChatEvent"
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use async_trait::async_trait;
+
+    use super::PlayerChatEventImpl;
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::test_player;
+    use crate::plugin::player::PlayerChatEvent;
+    use crate::plugin::{CancellableEvent, EventHandler, EventPriority, PluginManager};
+
+    struct Uppercase;
+
+    #[async_trait]
+    impl EventHandler<PlayerChatEventImpl> for Uppercase {
+        async fn handle_blocking(&self, event: &mut PlayerChatEventImpl) {
+            let message = event.get_message().to_uppercase();
+            event.set_message(message);
+        }
+    }
+
+    struct BannedWord;
+
+    #[async_trait]
+    impl EventHandler<PlayerChatEventImpl> for BannedWord {
+        async fn handle_blocking(&self, event: &mut PlayerChatEventImpl) {
+            if event.get_message().contains("CREEPER") {
+                event.set_cancelled(true);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn handlers_rewrite_and_block_messages() {
+        let folder = TestFolder::new("chat");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let plugins = PluginManager::new();
+        plugins.register(Uppercase, EventPriority::High, true).await;
+        // Runs after the message was uppercased
+        plugins.register(BannedWord, EventPriority::Low, true).await;
+
+        let event = plugins
+            .fire_cancellable(PlayerChatEventImpl::new(player.clone(), "hi".to_string()))
+            .await;
+        assert!(!event.is_cancelled());
+        assert_eq!(event.get_message(), "HI");
+
+        let event = plugins
+            .fire_cancellable(PlayerChatEventImpl::new(player, "a creeper!".to_string()))
+            .await;
+        assert!(event.is_cancelled());
+    }
+}
@@ -1,23 +1,39 @@
 use pumpkin_util::text::TextComponent;
 use std::sync::Arc;
 
-use crate::entity::player::Player;
+use crate::entity::player::{Player, ResourcePackStatus};
 
 use super::CancellableEvent;
 
+pub mod chat;
 pub mod join;
 pub mod leave;
+pub mod resource_pack_status;
 
 pub trait PlayerEvent: CancellableEvent {
     fn get_player(&self) -> Arc<Player>;
 }
 
+/// Fired before the chat message of a player is sent. Changing the message sends it unsigned,
+/// cancelling it does not send it at all
+pub trait PlayerChatEvent: PlayerEvent {
+    fn get_message(&self) -> &str;
+    fn set_message(&mut self, message: String);
+}
+
+/// Fired once a player joined a world. Cancelling it or changing the message changes what is broadcast,
+/// handle it with `context.register_event::<PlayerJoinEventImpl, _>(handler, EventPriority::Normal, true)`
 pub trait PlayerJoinEvent: PlayerEvent {
     fn get_join_message(&self) -> &TextComponent;
     fn set_join_message(&mut self, message: TextComponent);
 }
 
+/// Fired once a player left a world, like [`PlayerJoinEvent`] the leave message can be changed or suppressed
 pub trait PlayerLeaveEvent: PlayerEvent {
     fn get_leave_message(&self) -> &TextComponent;
     fn set_leave_message(&mut self, message: TextComponent);
 }
+
+pub trait ResourcePackStatusEvent: PlayerEvent {
+    fn get_status(&self) -> ResourcePackStatus;
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::{
+    entity::player::{Player, ResourcePackStatus},
+    plugin::{CancellableEvent, Event},
+};
+
+use super::{PlayerEvent, ResourcePackStatusEvent};
+
+pub struct ResourcePackStatusEventImpl {
+    player: Arc<Player>,
+    status: ResourcePackStatus,
+    is_cancelled: bool,
+}
+
+impl ResourcePackStatusEventImpl {
+    pub fn new(player: Arc<Player>, status: ResourcePackStatus) -> Self {
+        Self {
+            player,
+            status,
+            is_cancelled: false,
+        }
+    }
+}
+
+impl ResourcePackStatusEvent for ResourcePackStatusEventImpl {
+    fn get_status(&self) -> ResourcePackStatus {
+        self.status
+    }
+}
+
+impl PlayerEvent for ResourcePackStatusEventImpl {
+    fn get_player(&self) -> Arc<Player> {
+        self.player.clone()
+    }
+}
+
+impl CancellableEvent for ResourcePackStatusEventImpl {
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.is_cancelled = cancelled;
+    }
+}
+
+impl Event for ResourcePackStatusEventImpl {
+    fn get_name_static() -> &'static str {
+        "ResourcePackStatusEvent"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "ResourcePackStatusEvent"
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
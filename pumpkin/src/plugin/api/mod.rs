@@ -1,11 +1,13 @@
 pub mod context;
 pub mod events;
 pub mod permissions;
+pub mod world_edit;
 
 use async_trait::async_trait;
 pub use context::*;
 pub use events::*;
 pub use permissions::*;
+pub use world_edit::*;
 
 #[derive(Debug, Clone)]
 pub struct PluginMetadata<'s> {
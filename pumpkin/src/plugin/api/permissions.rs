@@ -1,13 +1,13 @@
-use uuid::Uuid;
 use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
 
 /// A trait for implementing permission checking logic
-/// 
+///
 /// This trait is used by plugins to provide custom permission checking functionality.
 /// Implementations should be thread-safe and efficient as they may be called frequently.
 pub trait PermissionChecker: Send + Sync {
     /// Check if a player has a specific permission
-    /// 
+    ///
     /// # Arguments
     /// * `uuid` - The UUID of the player to check
     /// * `permission` - The permission node to check
@@ -22,4 +22,4 @@ pub fn register_permission_checker(checker: Arc<dyn PermissionChecker>) {
 
 pub fn get_permission_checker() -> Option<&'static Arc<dyn PermissionChecker>> {
     PERMISSION_CHECKER.get()
-} 
\ No newline at end of file
+}
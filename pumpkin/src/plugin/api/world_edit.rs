@@ -0,0 +1,141 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_util::math::position::BlockPos;
+use thiserror::Error;
+
+use crate::world::World;
+
+/// A batch of block changes, started with [`super::Context::begin_world_edit`].
+///
+/// Nothing changes in the world until [`WorldEdit::commit`] is called, which applies every
+/// queued block like `/fill` does: each chunk is only locked once and clients get one packet
+/// per changed chunk section instead of one per block
+pub struct WorldEdit {
+    world: Arc<World>,
+    changes: Vec<(BlockPos, u16)>,
+}
+
+impl WorldEdit {
+    #[must_use]
+    pub const fn new(world: Arc<World>) -> Self {
+        Self {
+            world,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Queues a block change, a later change to the same position overrides this one.
+    /// Fails without queuing anything if the position is below or above the world
+    pub fn set_block(
+        &mut self,
+        position: BlockPos,
+        block_state_id: u16,
+    ) -> Result<&mut Self, OutOfWorldError> {
+        let y = position.0.y;
+        if y < self.world.min_y || y >= self.world.max_y() {
+            return Err(OutOfWorldError(position));
+        }
+        self.changes.push((position, block_state_id));
+        Ok(self)
+    }
+
+    /// How many block changes are queued, including overridden ones
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Applies the queued changes to the world, they may span any number of chunks.
+    /// Returns how many blocks were actually changed, blocks that already had their new state are not counted
+    pub async fn commit(self) -> usize {
+        let changes = last_change_per_position(self.changes);
+        if changes.is_empty() {
+            return 0;
+        }
+        let replaced = self.world.set_block_states(&changes).await;
+        count_changed(&changes, &replaced)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Block {0} is outside of the world height")]
+pub struct OutOfWorldError(pub BlockPos);
+
+/// Only keeps the last change to every position, in the order they were first queued
+fn last_change_per_position(changes: Vec<(BlockPos, u16)>) -> Vec<(BlockPos, u16)> {
+    let mut indices: HashMap<BlockPos, usize> = HashMap::with_capacity(changes.len());
+    let mut unique: Vec<(BlockPos, u16)> = Vec::with_capacity(changes.len());
+    for (position, block_state_id) in changes {
+        if let Some(&index) = indices.get(&position) {
+            unique[index].1 = block_state_id;
+        } else {
+            indices.insert(position, unique.len());
+            unique.push((position, block_state_id));
+        }
+    }
+    unique
+}
+
+fn count_changed(changes: &[(BlockPos, u16)], replaced: &[u16]) -> usize {
+    changes
+        .iter()
+        .zip(replaced)
+        .filter(|((_, new), old)| new != *old)
+        .count()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::dimension::Dimension;
+
+    use super::{count_changed, last_change_per_position, OutOfWorldError, WorldEdit};
+    use crate::data::test_folder::TestFolder;
+    use crate::world::World;
+
+    #[test]
+    fn rejects_blocks_outside_of_the_world() {
+        let folder = TestFolder::new("world-edit");
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.to_path_buf()),
+            DimensionType::Overworld,
+        ));
+        let (min_y, max_y) = (world.min_y, world.max_y());
+        let mut edit = WorldEdit::new(world);
+
+        let below = BlockPos(Vector3::new(0, min_y - 1, 0));
+        let above = BlockPos(Vector3::new(0, max_y, 0));
+        assert_eq!(edit.set_block(below, 1).err(), Some(OutOfWorldError(below)));
+        assert_eq!(edit.set_block(above, 1).err(), Some(OutOfWorldError(above)));
+        assert!(edit.is_empty());
+
+        edit.set_block(BlockPos(Vector3::new(0, min_y, 0)), 1)
+            .unwrap()
+            .set_block(BlockPos(Vector3::new(0, max_y - 1, 0)), 1)
+            .unwrap();
+        assert_eq!(edit.len(), 2);
+    }
+
+    #[test]
+    fn later_changes_override_earlier_ones() {
+        let a = BlockPos(Vector3::new(0, 0, 0));
+        let b = BlockPos(Vector3::new(16, 0, 0));
+        let changes = last_change_per_position(vec![(a, 1), (b, 2), (a, 3)]);
+        assert!(changes == [(a, 3), (b, 2)]);
+    }
+
+    #[test]
+    fn only_counts_blocks_that_changed() {
+        let a = BlockPos(Vector3::new(0, 0, 0));
+        let b = BlockPos(Vector3::new(-1, 5, -1));
+        assert_eq!(count_changed(&[(a, 1), (b, 2)], &[1, 0]), 1);
+    }
+}
@@ -201,6 +201,7 @@ impl PluginManager {
             );
             let res = plugin.on_unload(&context).await;
             res?;
+            context.unregister_all_commands();
             *loaded = false;
             Ok(())
         } else {
@@ -238,35 +239,232 @@ impl PluginManager {
         };
 
         handlers_vec.push(Box::new(typed_handler));
+        packet::on_handler_registered(E::get_name_static(), &self.handlers);
     }
 
-    pub async fn fire<E: Event + Send + Sync + 'static>(&self, mut event: E) -> E {
-        // Take a snapshot of handlers to avoid lifetime issues
-        let handlers = self.handlers.read().await;
-
-        log::debug!("Firing event: {}", E::get_name_static());
+    /// Runs every handler of the event, see [`Self::fire_cancellable`] for events that can be cancelled
+    pub async fn fire<E: Event + Send + Sync + 'static>(&self, event: E) -> E {
+        fire_event(&self.handlers, event, |_| false).await
+    }
 
-        if let Some(handlers_vec) = handlers.get(&E::get_name_static()) {
-            log::debug!(
-                "Found {} handlers for event: {}",
-                handlers_vec.len(),
-                E::get_name_static()
-            );
+    /// Runs the handlers of the event. Once a blocking handler cancels it, the blocking handlers
+    /// with a lower priority are skipped. Non-blocking handlers always run and see whether it was cancelled
+    pub async fn fire_cancellable<E: CancellableEvent + Send + Sync + 'static>(
+        &self,
+        event: E,
+    ) -> E {
+        fire_event(&self.handlers, event, E::is_cancelled).await
+    }
+}
 
-            let (blocking_handlers, non_blocking_handlers): (Vec<_>, Vec<_>) = handlers_vec
-                .iter()
-                .partition(|handler| handler.is_blocking());
+/// Runs the handlers registered for the event from the highest to the lowest priority,
+/// blocking handlers are skipped once `is_cancelled` returns true
+pub(crate) async fn fire_event<E: Event + Send + Sync + 'static>(
+    handlers: &RwLock<HandlerMap>,
+    mut event: E,
+    is_cancelled: fn(&E) -> bool,
+) -> E {
+    // Take a snapshot of handlers to avoid lifetime issues
+    let handlers = handlers.read().await;
+
+    log::debug!("Firing event: {}", E::get_name_static());
+
+    if let Some(handlers_vec) = handlers.get(&E::get_name_static()) {
+        log::debug!(
+            "Found {} handlers for event: {}",
+            handlers_vec.len(),
+            E::get_name_static()
+        );
 
-            for handler in blocking_handlers {
-                handler.handle_blocking_dyn(&mut event).await;
+        let (mut blocking_handlers, mut non_blocking_handlers): (Vec<_>, Vec<_>) = handlers_vec
+            .iter()
+            .partition(|handler| handler.is_blocking());
+        // Handlers with the same priority keep the order they were registered in
+        blocking_handlers.sort_by_key(|handler| handler.get_priority());
+        non_blocking_handlers.sort_by_key(|handler| handler.get_priority());
+
+        for handler in blocking_handlers {
+            if is_cancelled(&event) {
+                break;
             }
+            handler.handle_blocking_dyn(&mut event).await;
+        }
 
-            // TODO: Run non-blocking handlers in parallel
-            for handler in non_blocking_handlers {
-                handler.handle_dyn(&event).await;
-            }
+        // TODO: Run non-blocking handlers in parallel
+        for handler in non_blocking_handlers {
+            handler.handle_dyn(&event).await;
+        }
+    }
+
+    event
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use pumpkin_util::PermissionLvl;
+    use tokio::time::timeout;
+
+    use super::{
+        CancellableEvent, Context, Event, EventHandler, EventPriority, Plugin, PluginManager,
+        PluginMetadata,
+    };
+    use crate::{
+        command::{tree::CommandTree, CommandSender},
+        data::test_folder::TestFolder,
+        server::Server,
+        PLUGIN_MANAGER,
+    };
+
+    #[derive(Default)]
+    struct TestEvent {
+        cancelled: bool,
+    }
+
+    impl CancellableEvent for TestEvent {
+        fn is_cancelled(&self) -> bool {
+            self.cancelled
+        }
+
+        fn set_cancelled(&mut self, cancelled: bool) {
+            self.cancelled = cancelled;
+        }
+    }
+
+    impl Event for TestEvent {
+        fn get_name_static() -> &'static str {
+            "TestEvent"
+        }
+
+        fn get_name(&self) -> &'static str {
+            "TestEvent"
         }
 
-        event
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    struct Canceller;
+
+    #[async_trait]
+    impl EventHandler<TestEvent> for Canceller {
+        async fn handle_blocking(&self, event: &mut TestEvent) {
+            event.set_cancelled(true);
+        }
+    }
+
+    /// Remembers whether it ran and whether the event was cancelled by then
+    #[derive(Clone, Default)]
+    struct Observer {
+        ran: Arc<AtomicBool>,
+        saw_cancelled: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl EventHandler<TestEvent> for Observer {
+        async fn handle(&self, event: &TestEvent) {
+            self.ran.store(true, Ordering::Relaxed);
+            self.saw_cancelled
+                .store(event.is_cancelled(), Ordering::Relaxed);
+        }
+
+        async fn handle_blocking(&self, event: &mut TestEvent) {
+            self.handle(event).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelling_skips_lower_priority_handlers() {
+        let manager = PluginManager::new();
+        let skipped = Observer::default();
+        let observer = Observer::default();
+        // Registered first, but runs after the canceller because of its priority
+        manager
+            .register(skipped.clone(), EventPriority::Low, true)
+            .await;
+        manager
+            .register(observer.clone(), EventPriority::Lowest, false)
+            .await;
+        manager.register(Canceller, EventPriority::High, true).await;
+
+        let event = manager.fire_cancellable(TestEvent::default()).await;
+        assert!(event.is_cancelled());
+        assert!(!skipped.ran.load(Ordering::Relaxed));
+        assert!(observer.ran.load(Ordering::Relaxed));
+        assert!(observer.saw_cancelled.load(Ordering::Relaxed));
+    }
+
+    struct EmptyPlugin;
+
+    impl Plugin for EmptyPlugin {}
+
+    #[tokio::test]
+    async fn unload_command_removes_plugin_commands() {
+        let folder = TestFolder::new("plugin-unload");
+        let server = Arc::new(Server::with_world_folder(folder.to_path_buf()));
+        let metadata = PluginMetadata {
+            name: "unload-test",
+            version: "1.0.0",
+            authors: "",
+            description: "",
+        };
+        {
+            let mut manager = PLUGIN_MANAGER.lock().await;
+            manager.set_server(server.clone());
+            // The test binary itself stands in for the library of the plugin
+            let library = libloading::os::unix::Library::this().into();
+            let context = Context::new(metadata.clone(), server.clone(), manager.handlers.clone());
+            context
+                .register_command(
+                    CommandTree::new(["unloadtest"], "A command of the plugin"),
+                    PermissionLvl::Zero,
+                )
+                .await;
+            manager
+                .plugins
+                .push((metadata, Box::new(EmptyPlugin), library, true));
+        }
+
+        // Runs like a command of the console, which holds the dispatcher while the command runs
+        let dispatcher = server.command_dispatcher.read().await;
+        timeout(
+            Duration::from_secs(5),
+            dispatcher.handle_command(
+                &mut CommandSender::Console,
+                &server,
+                "plugin unload unload-test",
+            ),
+        )
+        .await
+        .expect("unloading a plugin by command must not wait for the dispatcher");
+        drop(dispatcher);
+
+        assert!(!PLUGIN_MANAGER.lock().await.is_plugin_loaded("unload-test"));
+        timeout(Duration::from_secs(5), async {
+            while server
+                .command_dispatcher
+                .read()
+                .await
+                .plugin_names
+                .contains_key("unloadtest")
+            {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("the commands of the plugin are removed once the command returned");
     }
 }
@@ -0,0 +1,86 @@
+use std::{
+    collections::VecDeque,
+    sync::{atomic::Ordering, Arc},
+};
+
+use pumpkin_config::{ADVANCED_CONFIG, BASIC_CONFIG};
+
+use crate::{data::last_seen_data::save_last_seen, entity::player::Player};
+
+use super::Server;
+
+/// Periodically saves all online players, modified chunks and when players were last seen.
+///
+/// Chunks are written all at once when an autosave starts, while players are saved a few per tick
+/// so a large amount of online players does not cause a lag spike.
+#[derive(Default)]
+pub struct Autosave {
+    ticks_since_save: u64,
+    pending_players: VecDeque<Arc<Player>>,
+    saved_players: usize,
+    saved_chunks: usize,
+}
+
+impl Autosave {
+    pub async fn tick(&mut self, server: &Server) {
+        let config = &ADVANCED_CONFIG.autosave;
+        if !self.pending_players.is_empty() {
+            self.save_pending_players(server, config.players_per_tick.max(1))
+                .await;
+            if self.pending_players.is_empty() {
+                self.finish();
+            }
+            return;
+        }
+
+        if !config.enabled || !server.is_autosave_enabled() {
+            self.ticks_since_save = 0;
+            return;
+        }
+
+        self.ticks_since_save += 1;
+        let interval = config.interval * BASIC_CONFIG.tps as u64;
+        if self.ticks_since_save < interval.max(1) {
+            return;
+        }
+        self.ticks_since_save = 0;
+
+        for world in server.worlds.read().await.iter() {
+            self.saved_chunks += world.level.save_dirty_chunks().await;
+        }
+        save_last_seen().await;
+        self.pending_players = server.get_all_players().await.into();
+        if self.pending_players.is_empty() {
+            self.finish();
+        }
+    }
+
+    async fn save_pending_players(&mut self, server: &Server, amount: usize) {
+        for _ in 0..amount {
+            let Some(player) = self.pending_players.pop_front() else {
+                break;
+            };
+            // The player may have left since the autosave started, it was saved when leaving then
+            if player.client.closed.load(Ordering::Relaxed) {
+                continue;
+            }
+            match server.player_data_storage.save(&player).await {
+                Ok(()) => self.saved_players += 1,
+                Err(err) => log::error!(
+                    "Failed to save player data of {}: {err}",
+                    player.gameprofile.name
+                ),
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        log::info!(
+            "Saved {} players, {} chunks",
+            self.saved_players,
+            self.saved_chunks
+        );
+        self.saved_players = 0;
+        self.saved_chunks = 0;
+    }
+}
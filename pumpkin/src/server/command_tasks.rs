@@ -0,0 +1,124 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::sync::Notify;
+
+tokio::task_local! {
+    /// Set while a command task started with [`CommandTaskGuard::run`] runs
+    static COMMAND_TASK: ();
+}
+
+/// Keeps track of the spawned command tasks, so the server can wait for them before shutting down
+#[derive(Default)]
+pub struct CommandTasks {
+    running: AtomicUsize,
+    finished: Notify,
+    shutting_down: AtomicBool,
+}
+
+/// Marks a command task as running until it is dropped
+pub struct CommandTaskGuard(Arc<CommandTasks>);
+
+impl CommandTaskGuard {
+    /// Runs the command task, it stops counting as running once the task finished
+    pub async fn run<F: Future>(self, task: F) -> F::Output {
+        let output = COMMAND_TASK.scope((), task).await;
+        drop(self);
+        output
+    }
+}
+
+impl Drop for CommandTaskGuard {
+    fn drop(&mut self) {
+        self.0.running.fetch_sub(1, Ordering::SeqCst);
+        self.0.finished.notify_waiters();
+    }
+}
+
+impl CommandTasks {
+    /// Registers a new command task, returns `None` once the server is shutting down
+    pub fn start(self: &Arc<Self>) -> Option<CommandTaskGuard> {
+        self.running.fetch_add(1, Ordering::SeqCst);
+        let guard = CommandTaskGuard(self.clone());
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return None;
+        }
+        Some(guard)
+    }
+
+    /// Rejects new command tasks and waits until all others finished. A command stopping the server
+    /// from its own task does not wait for itself. Returns false if the timeout passed first
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        let remaining = usize::from(Self::in_command_task());
+        tokio::time::timeout(timeout, async {
+            loop {
+                // Created before checking, so a task finishing in between still wakes us
+                let finished = self.finished.notified();
+                if self.running.load(Ordering::SeqCst) <= remaining {
+                    return;
+                }
+                finished.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    /// How many command tasks are still running, not counting the current one
+    pub fn running(&self) -> usize {
+        self.running.load(Ordering::SeqCst) - usize::from(Self::in_command_task())
+    }
+
+    /// Whether this is called from a command task, rather than from the console or a tick
+    fn in_command_task() -> bool {
+        COMMAND_TASK.try_with(|()| ()).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, time::Duration};
+
+    use super::CommandTasks;
+
+    #[tokio::test]
+    async fn shutdown_awaits_running_commands() {
+        let tasks = Arc::new(CommandTasks::default());
+        let running = tasks.start().unwrap();
+        let stopping = tasks.start().unwrap();
+
+        stopping
+            .run(async {
+                // the stop command itself is not waited for
+                assert!(!tasks.shutdown(Duration::from_millis(10)).await);
+                assert!(tasks.start().is_none());
+                assert_eq!(tasks.running(), 1);
+
+                let finish = tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    drop(running);
+                });
+                assert!(tasks.shutdown(Duration::from_secs(5)).await);
+                finish.await.unwrap();
+            })
+            .await;
+        assert_eq!(tasks.running(), 0);
+    }
+
+    #[tokio::test]
+    async fn console_shutdown_awaits_every_command() {
+        let tasks = Arc::new(CommandTasks::default());
+        let running = tasks.start().unwrap();
+        assert!(!tasks.shutdown(Duration::from_millis(10)).await);
+        assert_eq!(tasks.running(), 1);
+        drop(running);
+        assert!(tasks.shutdown(Duration::from_millis(10)).await);
+    }
+}
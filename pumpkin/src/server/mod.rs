@@ -1,3 +1,5 @@
+use autosave::Autosave;
+use command_tasks::CommandTasks;
 use connection_cache::{CachedBranding, CachedStatus};
 use crossbeam::atomic::AtomicCell;
 use key_store::KeyStore;
@@ -20,25 +22,29 @@ use pumpkin_world::entity::entity_registry::get_entity_by_id;
 use rand::prelude::SliceRandom;
 use std::collections::HashMap;
 use std::net::IpAddr;
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::{
     sync::{
         atomic::{AtomicI32, Ordering},
         Arc,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
+use tick_stats::TickStats;
 use tokio::sync::{Mutex, RwLock};
 use uuid::Uuid;
 
 use crate::block::block_manager::BlockManager;
 use crate::block::default_block_manager;
+use crate::command::function::FunctionHooks;
+use crate::data::{last_seen_data::save_last_seen, player_data::PlayerDataStorage};
 use crate::entity::ai::path::Navigator;
 use crate::entity::living::LivingEntity;
 use crate::entity::mob::MobEntity;
-use crate::entity::{Entity, EntityId};
+use crate::entity::{Entity, EntityId, NBTStorage};
 use crate::net::EncryptionError;
 use crate::world::custom_bossbar::CustomBossbars;
+use crate::world::entity_query::WorldEntity;
 use crate::{
     command::{default_dispatcher, dispatcher::CommandDispatcher},
     entity::player::Player,
@@ -46,8 +52,11 @@ use crate::{
     world::World,
 };
 
+mod autosave;
+pub mod command_tasks;
 mod connection_cache;
 mod key_store;
+pub mod tick_stats;
 pub mod ticker;
 
 pub const CURRENT_MC_VERSION: &str = "1.21.4";
@@ -82,12 +91,31 @@ pub struct Server {
     pub auth_client: Option<reqwest::Client>,
     /// The server's custom bossbars
     pub bossbars: Mutex<CustomBossbars>,
+    /// Saves and loads the data of players
+    pub player_data_storage: PlayerDataStorage,
+    /// Whether periodic autosaves are currently allowed, toggled by `/save-off` and `/save-on`
+    autosave_enabled: AtomicBool,
+    autosave: Mutex<Autosave>,
+    /// When the server was started, used to report its uptime
+    pub start_time: Instant,
+    /// The durations of the most recent ticks
+    pub tick_stats: Mutex<TickStats>,
+    /// The running command tasks of players and RCON, awaited when the server stops
+    pub command_tasks: Arc<CommandTasks>,
+    /// Runs the configured load and tick functions
+    function_hooks: Mutex<FunctionHooks>,
 }
 
 impl Server {
     #[allow(clippy::new_without_default)]
     #[must_use]
     pub fn new() -> Self {
+        // TODO: load form config
+        Self::with_world_folder("./world".parse().unwrap())
+    }
+
+    /// Creates a server whose world and player data are stored in `folder`
+    pub(crate) fn with_world_folder(folder: std::path::PathBuf) -> Self {
         let auth_client = BASIC_CONFIG.online_mode.then(|| {
             reqwest::Client::builder()
                 .connect_timeout(Duration::from_millis(u64::from(
@@ -103,11 +131,9 @@ impl Server {
         // First register default command, after that plugins can put in their own
         let command_dispatcher = RwLock::new(default_dispatcher());
 
+        let player_data_storage = PlayerDataStorage::new(folder.join("playerdata"));
         let world = World::load(
-            Dimension::OverWorld.into_level(
-                // TODO: load form config
-                "./world".parse().unwrap(),
-            ),
+            Dimension::OverWorld.into_level(folder),
             DimensionType::Overworld,
         );
 
@@ -139,6 +165,13 @@ impl Server {
             server_listing: Mutex::new(CachedStatus::new()),
             server_branding: CachedBranding::new(),
             bossbars: Mutex::new(CustomBossbars::new()),
+            player_data_storage,
+            autosave_enabled: AtomicBool::new(true),
+            autosave: Mutex::new(Autosave::default()),
+            start_time: Instant::now(),
+            tick_stats: Mutex::new(TickStats::default()),
+            command_tasks: Arc::new(CommandTasks::default()),
+            function_hooks: Mutex::new(FunctionHooks::default()),
         }
     }
 
@@ -177,7 +210,19 @@ impl Server {
         // TODO: select default from config
         let world = &self.worlds.read().await[0];
 
-        let player = Arc::new(Player::new(client, world.clone(), entity_id, gamemode).await);
+        let mut player = Player::new(client, world.clone(), entity_id, gamemode).await;
+        match self.player_data_storage.load(&player.gameprofile.id).await {
+            Ok(Some(mut nbt)) => {
+                player.read_nbt(&mut nbt).await;
+                player.restored_from_save = true;
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!(
+                "Failed to load player data of {}, using defaults: {err}",
+                player.gameprofile.name
+            ),
+        }
+        let player = Arc::new(player);
         world
             .add_player(player.gameprofile.id, player.clone())
             .await;
@@ -198,9 +243,29 @@ impl Server {
     }
 
     pub async fn save(&self) {
+        for player in self.get_all_players().await {
+            if let Err(err) = self.player_data_storage.save(&player).await {
+                log::error!(
+                    "Failed to save player data of {}: {err}",
+                    player.gameprofile.name
+                );
+            }
+        }
         for world in self.worlds.read().await.iter() {
             world.save().await;
         }
+        save_last_seen().await;
+    }
+
+    /// Returns whether periodic autosaves are currently allowed
+    pub fn is_autosave_enabled(&self) -> bool {
+        self.autosave_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Allows or prevents periodic autosaves, e.g. while backing up the world.
+    /// Returns false if they already were
+    pub fn set_autosave_enabled(&self, enabled: bool) -> bool {
+        self.autosave_enabled.swap(enabled, Ordering::Relaxed) != enabled
     }
 
     pub async fn add_mob_entity(
@@ -215,6 +280,8 @@ impl Server {
             living_entity,
             goals: Mutex::new(vec![]),
             navigator: Mutex::new(Navigator::default()),
+            persistence_required: AtomicBool::new(false),
+            far_ticks: AtomicU32::new(0),
         });
         world.add_mob_entity(uuid, mob.clone()).await;
         (mob, uuid)
@@ -351,6 +418,13 @@ impl Server {
         }
     }
 
+    /// Sends a system message to every player that has not hidden their chat
+    pub async fn broadcast_system_message(&self, message: &TextComponent) {
+        for world in self.worlds.read().await.iter() {
+            world.broadcast_system_message(message).await;
+        }
+    }
+
     /// Searches for a player by their username across all worlds.
     ///
     /// This function iterates through each world managed by the server and attempts to find a player with the specified username.
@@ -399,6 +473,15 @@ impl Server {
         players
     }
 
+    /// Returns all players and mobs from all worlds.
+    pub async fn get_all_entities(&self) -> Vec<WorldEntity> {
+        let mut entities = Vec::new();
+        for world in self.worlds.read().await.iter() {
+            entities.extend(world.get_all_entities().await);
+        }
+        entities
+    }
+
     /// Returns a random player from any of the worlds or None if all worlds are empty.
     pub async fn get_random_player(&self) -> Option<Arc<Player>> {
         let players = self.get_all_players().await;
@@ -491,8 +574,14 @@ impl Server {
     }
 
     async fn tick(&self) {
+        let start = Instant::now();
         for world in self.worlds.read().await.iter() {
-            world.tick().await;
+            world.tick(self).await;
+            world.tick_scheduled_blocks(self).await;
+            world.tick_spawning(self).await;
         }
+        self.function_hooks.lock().await.tick(self).await;
+        self.autosave.lock().await.tick(self).await;
+        self.tick_stats.lock().await.record(start.elapsed());
     }
 }
@@ -0,0 +1,65 @@
+use std::{collections::VecDeque, time::Duration};
+
+/// How many of the most recent ticks are averaged
+const SAMPLE_SIZE: usize = 100;
+
+/// Tracks how long the last ticks took, to report the server's MSPT and TPS
+#[derive(Default)]
+pub struct TickStats {
+    durations: VecDeque<Duration>,
+}
+
+impl TickStats {
+    pub fn record(&mut self, duration: Duration) {
+        if self.durations.len() == SAMPLE_SIZE {
+            self.durations.pop_front();
+        }
+        self.durations.push_back(duration);
+    }
+
+    /// The average milliseconds per tick of the recent ticks
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mspt(&self) -> f64 {
+        if self.durations.is_empty() {
+            return 0.0;
+        }
+        let total: Duration = self.durations.iter().sum();
+        total.as_secs_f64() * 1000.0 / self.durations.len() as f64
+    }
+
+    /// The ticks per second the server can keep up with, never more than the configured target
+    #[must_use]
+    pub fn tps(&self, target_tps: f32) -> f64 {
+        let target_tps = f64::from(target_tps);
+        let mspt = self.mspt();
+        if mspt <= 0.0 {
+            return target_tps;
+        }
+        (1000.0 / mspt).min(target_tps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{TickStats, SAMPLE_SIZE};
+
+    #[test]
+    fn tps_is_capped_by_target() {
+        let mut stats = TickStats::default();
+        assert!((stats.tps(20.0) - 20.0).abs() < f64::EPSILON);
+
+        stats.record(Duration::from_millis(10));
+        assert!((stats.mspt() - 10.0).abs() < 1e-9);
+        assert!((stats.tps(20.0) - 20.0).abs() < f64::EPSILON);
+
+        // Ticks taking 100ms only allow 10 ticks per second
+        for _ in 0..SAMPLE_SIZE {
+            stats.record(Duration::from_millis(100));
+        }
+        assert!((stats.mspt() - 100.0).abs() < 1e-9);
+        assert!((stats.tps(20.0) - 10.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,37 @@
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::entity::mob::MobEntity;
+
+/// How many mobs ticked normally and how many were skipped or slowed down in the last world tick
+#[derive(Clone, Copy, Default)]
+pub struct ActivationStats {
+    pub active: usize,
+    pub inactive: usize,
+}
+
+/// Whether a player is close enough to the mob for it to tick every tick
+pub fn is_active(mob: &MobEntity, players: &[Vector3<f64>]) -> bool {
+    let config = &ADVANCED_CONFIG.activation_range;
+    if !config.enabled {
+        return true;
+    }
+    let range = if mob.is_hostile() {
+        config.monsters
+    } else {
+        config.creatures
+    };
+    let pos = mob.living_entity.entity.pos.load();
+    players
+        .iter()
+        .any(|player| player.squared_distance_to_vec(pos) <= range * range)
+}
+
+/// Whether an inactive mob should still tick, the entity id spreads the mobs over different ticks
+pub fn is_inactive_tick(mob: &MobEntity, world_age: i64) -> bool {
+    let interval = ADVANCED_CONFIG
+        .activation_range
+        .inactive_tick_interval
+        .max(1) as i64;
+    (world_age + i64::from(mob.living_entity.entity.entity_id)).rem_euclid(interval) == 0
+}
@@ -1,3 +1,5 @@
+use std::collections::hash_map::Entry;
+
 use crate::entity::player::Player;
 use pumpkin_protocol::client::play::{BosseventAction, CBossEvent};
 use pumpkin_util::text::TextComponent;
@@ -23,7 +25,7 @@ pub enum BossbarDivisions {
     Notches20,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum BossbarFlags {
     NoFlags,
     DarkenSky = 0x01,
@@ -55,32 +57,111 @@ impl Bossbar {
             flags: BossbarFlags::NoFlags,
         }
     }
+
+    /// Sets how full the bar is, clamped to 0 (empty) to 1 (full)
+    pub fn set_progress(&mut self, progress: f32) {
+        self.health = clamp_progress(progress);
+    }
+}
+
+/// The client only accepts a progress from 0 to 1
+#[must_use]
+pub fn clamp_progress(progress: f32) -> f32 {
+    if progress.is_nan() {
+        0.0
+    } else {
+        progress.clamp(0.0, 1.0)
+    }
+}
+
+/// The actions that turn the `shown` bossbar into `bossbar`
+fn bossbar_changes(shown: &Bossbar, bossbar: &Bossbar) -> Vec<BosseventAction> {
+    let mut actions = Vec::new();
+    if shown.title != bossbar.title {
+        actions.push(BosseventAction::UpdateTile(bossbar.title.clone()));
+    }
+    if (shown.health - bossbar.health).abs() > f32::EPSILON {
+        actions.push(BosseventAction::UpdateHealth(bossbar.health));
+    }
+    if shown.color != bossbar.color || shown.division != bossbar.division {
+        actions.push(BosseventAction::UpdateStyle {
+            color: (bossbar.color.clone() as u8).into(),
+            dividers: (bossbar.division.clone() as u8).into(),
+        });
+    }
+    if shown.flags != bossbar.flags {
+        actions.push(BosseventAction::UpdateFlags(bossbar.flags.clone() as u8));
+    }
+    actions
 }
 
-/// Extension of the player to send the manage the bossbar
+/// Extension of the player to send the manage the bossbar.
+/// A player can see any number of bars at once, each is identified by its uuid
 impl Player {
+    /// Shows the bossbar to the player, or updates it if it is already shown
     pub async fn send_bossbar(&self, bossbar: &Bossbar) {
-        // Maybe this section could be implemented. feel free to change
-        let bossbar = bossbar.clone();
-        let boss_action = BosseventAction::Add {
-            title: bossbar.title,
-            health: bossbar.health,
-            color: (bossbar.color as u8).into(),
-            division: (bossbar.division as u8).into(),
-            flags: bossbar.flags as u8,
-        };
-
-        let packet = CBossEvent::new(&bossbar.uuid, boss_action);
-        self.client.send_packet(&packet).await;
+        self.sync_bossbar(bossbar, true).await;
     }
+
     pub async fn remove_bossbar(&self, uuid: Uuid) {
+        if self.bossbars.lock().await.remove(&uuid).is_none() {
+            return;
+        }
         let boss_action = BosseventAction::Remove;
 
         let packet = CBossEvent::new(&uuid, boss_action);
         self.client.send_packet(&packet).await;
     }
 
+    /// Whether the bossbar with this uuid is shown to the player
+    pub async fn has_bossbar(&self, uuid: &Uuid) -> bool {
+        self.bossbars.lock().await.contains_key(uuid)
+    }
+
+    /// Sends everything that changed since the bossbar was last sent, does nothing if it is not shown
+    pub async fn update_bossbar(&self, bossbar: &Bossbar) {
+        self.sync_bossbar(bossbar, false).await;
+    }
+
+    /// Brings the bossbar shown to the player up to date, adding it if `add` is set and it is not shown yet.
+    /// The lookup and the update happen under one lock, so concurrent updates can not overwrite each other
+    async fn sync_bossbar(&self, bossbar: &Bossbar, add: bool) {
+        let mut bossbar = bossbar.clone();
+        bossbar.health = clamp_progress(bossbar.health);
+        let actions = {
+            let mut bossbars = self.bossbars.lock().await;
+            match bossbars.entry(bossbar.uuid) {
+                Entry::Occupied(mut shown) => {
+                    let actions = bossbar_changes(shown.get(), &bossbar);
+                    shown.insert(bossbar.clone());
+                    actions
+                }
+                Entry::Vacant(entry) if add => {
+                    entry.insert(bossbar.clone());
+                    // Maybe this section could be implemented. feel free to change
+                    vec![BosseventAction::Add {
+                        title: bossbar.title.clone(),
+                        health: bossbar.health,
+                        color: (bossbar.color.clone() as u8).into(),
+                        division: (bossbar.division.clone() as u8).into(),
+                        flags: bossbar.flags.clone() as u8,
+                    }]
+                }
+                Entry::Vacant(_) => return,
+            }
+        };
+        for action in actions {
+            self.client
+                .send_packet(&CBossEvent::new(&bossbar.uuid, action))
+                .await;
+        }
+    }
+
     pub async fn update_bossbar_health(&self, uuid: &Uuid, health: f32) {
+        let health = clamp_progress(health);
+        if let Some(bossbar) = self.bossbars.lock().await.get_mut(uuid) {
+            bossbar.health = health;
+        }
         let boss_action = BosseventAction::UpdateHealth(health);
 
         let packet = CBossEvent::new(uuid, boss_action);
@@ -88,6 +169,9 @@ impl Player {
     }
 
     pub async fn update_bossbar_title(&self, uuid: &Uuid, title: TextComponent) {
+        if let Some(bossbar) = self.bossbars.lock().await.get_mut(uuid) {
+            bossbar.title = title.clone();
+        }
         let boss_action = BosseventAction::UpdateTile(title);
 
         let packet = CBossEvent::new(uuid, boss_action);
@@ -100,6 +184,10 @@ impl Player {
         color: BossbarColor,
         dividers: BossbarDivisions,
     ) {
+        if let Some(bossbar) = self.bossbars.lock().await.get_mut(uuid) {
+            bossbar.color = color.clone();
+            bossbar.division = dividers.clone();
+        }
         let boss_action = BosseventAction::UpdateStyle {
             color: (color as u8).into(),
             dividers: (dividers as u8).into(),
@@ -110,9 +198,50 @@ impl Player {
     }
 
     pub async fn update_bossbar_flags(&self, uuid: &Uuid, flags: BossbarFlags) {
+        if let Some(bossbar) = self.bossbars.lock().await.get_mut(uuid) {
+            bossbar.flags = flags.clone();
+        }
         let boss_action = BosseventAction::UpdateFlags(flags as u8);
 
         let packet = CBossEvent::new(uuid, boss_action);
         self.client.send_packet(&packet).await;
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use pumpkin_util::text::TextComponent;
+
+    use super::{clamp_progress, Bossbar};
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::test_player;
+
+    #[test]
+    fn progress_is_clamped() {
+        assert!(clamp_progress(-0.5).abs() < f32::EPSILON);
+        assert!((clamp_progress(0.25) - 0.25).abs() < f32::EPSILON);
+        assert!((clamp_progress(1.5) - 1.0).abs() < f32::EPSILON);
+        assert!(clamp_progress(f32::NAN).abs() < f32::EPSILON);
+        assert!((clamp_progress(f32::INFINITY) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn updates_only_shown_bars() {
+        let folder = TestFolder::new("bossbar");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let mut bossbar = Bossbar::new(TextComponent::text("Boss"));
+        player.update_bossbar(&bossbar).await;
+        assert!(!player.has_bossbar(&bossbar.uuid).await);
+
+        player.send_bossbar(&bossbar).await;
+        bossbar.set_progress(0.5);
+        player.send_bossbar(&bossbar).await;
+        let shown = player.bossbars.lock().await[&bossbar.uuid].clone();
+        assert!((shown.health - 0.5).abs() < f32::EPSILON);
+
+        player.remove_bossbar(bossbar.uuid).await;
+        player.update_bossbar(&bossbar).await;
+        assert!(!player.has_bossbar(&bossbar.uuid).await);
+    }
+}
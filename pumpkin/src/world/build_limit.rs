@@ -0,0 +1,80 @@
+use pumpkin_config::BASIC_CONFIG;
+
+use crate::{
+    command::has_permission_node, entity::player::Player,
+    plugin::api::permissions::get_permission_checker,
+};
+
+use super::World;
+
+impl World {
+    /// The y coordinate above the highest block the player can place.
+    /// Players with the bypass permission can build up to the top of the world
+    pub fn build_limit(&self, player: &Player) -> i32 {
+        build_limit(
+            self.max_y(),
+            BASIC_CONFIG.build_limit,
+            player.bypasses_build_limit(),
+        )
+    }
+}
+
+impl Player {
+    /// Whether the player has the permission, or a wildcard covering it, to build above the configured
+    /// build limit. Operators can always build up to the top of the world
+    pub fn bypasses_build_limit(&self) -> bool {
+        self.permission_lvl.load() >= BASIC_CONFIG.op_permission_level
+            || get_permission_checker().is_some_and(|checker| {
+                has_permission_node(
+                    checker.as_ref(),
+                    &self.gameprofile.id,
+                    &BASIC_CONFIG.build_limit_bypass_permission,
+                )
+            })
+    }
+}
+
+/// Blocks can never be placed above the top of the world, even when bypassing the configured limit
+fn build_limit(world_max_y: i32, configured: Option<i32>, bypass: bool) -> i32 {
+    match configured {
+        Some(limit) if !bypass => limit.min(world_max_y),
+        _ => world_max_y,
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pumpkin_util::permission::PermissionLvl;
+
+    use super::build_limit;
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::test_player;
+
+    const WORLD_MAX_Y: i32 = 320;
+
+    #[test]
+    fn bypass_at_limit() {
+        let limit = Some(256);
+        // A block at y 256 is right above the highest block normal players can place
+        assert!(256 >= build_limit(WORLD_MAX_Y, limit, false));
+        assert!(256 < build_limit(WORLD_MAX_Y, limit, true));
+        assert_eq!(build_limit(WORLD_MAX_Y, limit, true), WORLD_MAX_Y);
+    }
+
+    #[test]
+    fn defaults_to_top_of_world() {
+        assert_eq!(build_limit(WORLD_MAX_Y, None, false), WORLD_MAX_Y);
+        assert_eq!(build_limit(WORLD_MAX_Y, Some(1000), false), WORLD_MAX_Y);
+        assert_eq!(build_limit(WORLD_MAX_Y, Some(1000), true), WORLD_MAX_Y);
+    }
+
+    #[tokio::test]
+    async fn operators_bypass() {
+        let folder = TestFolder::new("build-limit");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        player.permission_lvl.store(PermissionLvl::Four);
+        assert!(player.bypasses_build_limit());
+        assert_eq!(player.world().build_limit(&player), player.world().max_y());
+    }
+}
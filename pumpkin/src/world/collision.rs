@@ -0,0 +1,206 @@
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3};
+use pumpkin_world::block::block_registry::get_block_collision_shapes;
+
+use super::World;
+
+/// Boxes closer than this are touching, like vanilla's collision epsilon
+const EPSILON: f64 = 1.0E-7;
+
+impl World {
+    /// Moves `bounding_box` by `velocity` and returns how far it gets before running into blocks.
+    /// Like in vanilla, the y axis is resolved first and then the horizontal axis with the larger movement
+    pub async fn sweep_collision(
+        &self,
+        bounding_box: &BoundingBox,
+        velocity: Vector3<f64>,
+    ) -> Vector3<f64> {
+        let area = bounding_box.stretch(velocity);
+        let mut obstacles = Vec::new();
+        // Blocks like fences and walls reach into the block above them. Outside of the world
+        // there are no blocks, only air
+        let min_y = (area.min_y.floor() as i32 - 1).max(self.min_y);
+        let max_y = (area.max_y.floor() as i32).min(self.max_y() - 1);
+        for y in min_y..=max_y {
+            for x in area.min_x.floor() as i32..=area.max_x.floor() as i32 {
+                for z in area.min_z.floor() as i32..=area.max_z.floor() as i32 {
+                    let position = BlockPos(Vector3::new(x, y, z));
+                    let Ok(state) = self.get_block_state(&position).await else {
+                        continue;
+                    };
+                    if state.collision_shapes.is_empty() {
+                        continue;
+                    }
+                    let shapes = get_block_collision_shapes(state.id).unwrap_or_default();
+                    obstacles.extend(BoundingBox::from_collision_shapes(&position, &shapes));
+                }
+            }
+        }
+        sweep(bounding_box, velocity, &obstacles)
+    }
+}
+
+/// Moves the box along one axis at a time, stopping at every obstacle in the way
+fn sweep(
+    bounding_box: &BoundingBox,
+    velocity: Vector3<f64>,
+    obstacles: &[BoundingBox],
+) -> Vector3<f64> {
+    let mut moving = *bounding_box;
+    let mut clip = |axis: Axis, mut distance: f64| {
+        if distance == 0.0 {
+            return 0.0;
+        }
+        for obstacle in obstacles {
+            distance = clip_axis(axis, &moving, obstacle, distance);
+        }
+        moving = match axis {
+            Axis::X => moving.offset(distance, 0.0, 0.0),
+            Axis::Y => moving.offset(0.0, distance, 0.0),
+            Axis::Z => moving.offset(0.0, 0.0, distance),
+        };
+        distance
+    };
+
+    let y = clip(Axis::Y, velocity.y);
+    if velocity.x.abs() < velocity.z.abs() {
+        let z = clip(Axis::Z, velocity.z);
+        let x = clip(Axis::X, velocity.x);
+        Vector3::new(x, y, z)
+    } else {
+        let x = clip(Axis::X, velocity.x);
+        let z = clip(Axis::Z, velocity.z);
+        Vector3::new(x, y, z)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// The minimum and maximum of a box on an axis
+fn bounds(bounding_box: &BoundingBox, axis: Axis) -> (f64, f64) {
+    match axis {
+        Axis::X => (bounding_box.min_x, bounding_box.max_x),
+        Axis::Y => (bounding_box.min_y, bounding_box.max_y),
+        Axis::Z => (bounding_box.min_z, bounding_box.max_z),
+    }
+}
+
+/// Shortens `distance` so `moving` stops at `obstacle` when moved along `axis`.
+/// Obstacles that do not overlap on the other axes or are already overlapped are ignored
+fn clip_axis(axis: Axis, moving: &BoundingBox, obstacle: &BoundingBox, distance: f64) -> f64 {
+    let others = match axis {
+        Axis::X => [Axis::Y, Axis::Z],
+        Axis::Y => [Axis::X, Axis::Z],
+        Axis::Z => [Axis::X, Axis::Y],
+    };
+    let overlaps = others.iter().all(|&other| {
+        let (min, max) = bounds(moving, other);
+        let (obstacle_min, obstacle_max) = bounds(obstacle, other);
+        max - EPSILON > obstacle_min && min + EPSILON < obstacle_max
+    });
+    if !overlaps {
+        return distance;
+    }
+    let (min, max) = bounds(moving, axis);
+    let (obstacle_min, obstacle_max) = bounds(obstacle, axis);
+    if distance > 0.0 && max <= obstacle_min + EPSILON {
+        distance.min(obstacle_min - max)
+    } else if distance < 0.0 && min >= obstacle_max - EPSILON {
+        distance.max(obstacle_max - min)
+    } else {
+        distance
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        position::BlockPos,
+        vector3::Vector3,
+    };
+
+    use pumpkin_registry::DimensionType;
+    use pumpkin_world::dimension::Dimension;
+
+    use super::sweep;
+    use crate::{data::test_folder::TestFolder, world::World};
+
+    const FULL_BLOCK: [f32; 6] = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+
+    fn block(x: i32, y: i32, z: i32) -> BoundingBox {
+        BoundingBox::from_collision_shapes(&BlockPos(Vector3::new(x, y, z)), &FULL_BLOCK)[0]
+    }
+
+    fn player(x: f64, y: f64, z: f64) -> BoundingBox {
+        BoundingBox::new_from_pos(
+            x,
+            y,
+            z,
+            &BoundingBoxSize {
+                width: 0.6,
+                height: 1.8,
+            },
+        )
+    }
+
+    #[test]
+    fn floor_stops_falling() {
+        let floor = [block(0, 63, 0)];
+        let moved = sweep(
+            &player(0.5, 64.5, 0.5),
+            Vector3::new(0.0, -2.0, 0.0),
+            &floor,
+        );
+        assert_eq!(moved, Vector3::new(0.0, -0.5, 0.0));
+    }
+
+    #[test]
+    fn open_axis_passes_through() {
+        let floor = [block(0, 63, 0), block(1, 63, 0), block(2, 63, 0)];
+        // Walking along the floor is not blocked by it
+        let velocity = Vector3::new(1.5, 0.0, 0.0);
+        assert_eq!(sweep(&player(0.5, 64.0, 0.5), velocity, &floor), velocity);
+        // But falling onto it while walking only stops the fall
+        let moved = sweep(
+            &player(0.5, 64.25, 0.5),
+            Vector3::new(1.5, -1.0, 0.0),
+            &floor,
+        );
+        assert_eq!(moved, Vector3::new(1.5, -0.25, 0.0));
+    }
+
+    #[test]
+    fn wall_stops_walking() {
+        let wall = [block(2, 64, 0)];
+        let moved = sweep(&player(0.5, 64.0, 0.5), Vector3::new(3.0, 0.0, 0.5), &wall);
+        assert!((moved.x - 1.2).abs() < 1.0E-9);
+        assert_eq!(moved.z, 0.5);
+    }
+
+    #[tokio::test]
+    async fn outside_of_the_world_is_air() {
+        let folder = TestFolder::new("collision-edges");
+        let world = World::load(
+            Dimension::OverWorld.into_level(folder.to_path_buf()),
+            DimensionType::Overworld,
+        );
+        let falling = Vector3::new(0.0, -2.0, 0.0);
+        // Flying above the build limit and falling through the void
+        for y in [
+            f64::from(world.max_y()) + 10.0,
+            f64::from(world.max_y()) - 1.0,
+        ] {
+            let moved = world.sweep_collision(&player(0.5, y, 0.5), falling).await;
+            assert_eq!(moved, falling);
+        }
+        for y in [f64::from(world.min_y) - 5.0, f64::from(world.min_y) - 100.0] {
+            let moved = world.sweep_collision(&player(0.5, y, 0.5), falling).await;
+            assert_eq!(moved, falling);
+        }
+    }
+}
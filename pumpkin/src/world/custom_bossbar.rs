@@ -151,20 +151,11 @@ impl CustomBossbars {
                 return Err(BossbarUpdateError::NoChanges("value", None));
             }
 
-            let ratio = f64::from(value) / f64::from(max_value);
-            let health: f32;
-
-            if ratio >= 1.0 {
-                health = 1.0;
-            } else if ratio <= 0.0 {
-                health = 0.0;
-            } else {
-                health = ratio as f32;
-            }
-
             bossbar.value = value;
             bossbar.max = max_value;
-            bossbar.bossbar_data.health = health;
+            bossbar
+                .bossbar_data
+                .set_progress((f64::from(value) / f64::from(max_value)) as f32);
 
             if !bossbar.visible {
                 return Ok(());
@@ -0,0 +1,167 @@
+use std::{collections::VecDeque, sync::Weak};
+
+use pumpkin_util::math::position::BlockPos;
+
+use super::World;
+
+/// The blocks a single command replaced, with the states they had before
+pub struct BlockEdit {
+    pub world: Weak<World>,
+    pub previous: Vec<(BlockPos, u16)>,
+}
+
+impl BlockEdit {
+    /// `replaced` are the old states of the `changes`, as returned by [`World::set_block_states`]
+    #[must_use]
+    pub fn new(world: Weak<World>, changes: &[(BlockPos, u16)], replaced: &[u16]) -> Self {
+        Self {
+            world,
+            previous: changes
+                .iter()
+                .zip(replaced)
+                .map(|((position, _), state)| (*position, *state))
+                .collect(),
+        }
+    }
+}
+
+/// The most recent block edits of a player that can be undone, oldest first.
+/// Old edits are forgotten once there are too many, or they hold too many blocks together
+#[derive(Default)]
+pub struct EditHistory {
+    edits: VecDeque<BlockEdit>,
+    blocks: usize,
+}
+
+impl EditHistory {
+    /// Records an edit, returns false if it alone has more than `max_blocks` blocks and can not be undone
+    pub fn push(&mut self, edit: BlockEdit, max_edits: usize, max_blocks: usize) -> bool {
+        if edit.previous.is_empty() {
+            return true;
+        }
+        if edit.previous.len() > max_blocks || max_edits == 0 {
+            return false;
+        }
+        self.blocks += edit.previous.len();
+        self.edits.push_back(edit);
+        while self.edits.len() > max_edits || self.blocks > max_blocks {
+            if let Some(oldest) = self.edits.pop_front() {
+                self.blocks -= oldest.previous.len();
+            }
+        }
+        true
+    }
+
+    /// Removes the most recent edit
+    pub fn pop(&mut self) -> Option<BlockEdit> {
+        let edit = self.edits.pop_back()?;
+        self.blocks -= edit.previous.len();
+        Some(edit)
+    }
+
+    pub fn clear(&mut self) {
+        self.edits.clear();
+        self.blocks = 0;
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Weak};
+
+    use pumpkin_util::{
+        math::{position::BlockPos, vector3::Vector3},
+        permission::PermissionLvl,
+    };
+    use pumpkin_world::block::block_registry::get_block;
+
+    use super::{BlockEdit, EditHistory};
+    use crate::data::test_folder::TestFolder;
+    use crate::{
+        command::{dispatcher::CommandError, CommandSender},
+        entity::player::{test_player, Player},
+        server::Server,
+        world::World,
+    };
+
+    fn edit(changes: &[(BlockPos, u16)], replaced: &[u16]) -> BlockEdit {
+        BlockEdit::new(Weak::new(), changes, replaced)
+    }
+
+    async fn run(server: &Server, player: &Arc<Player>, command: &str) -> Result<(), CommandError> {
+        let dispatcher = server.command_dispatcher.read().await;
+        let mut sender = CommandSender::Player(player.clone());
+        dispatcher.dispatch(&mut sender, server, command).await
+    }
+
+    async fn states(world: &World, positions: &[BlockPos]) -> Vec<u16> {
+        let mut states = Vec::new();
+        for position in positions {
+            states.push(world.get_block_state_id(position).await.unwrap());
+        }
+        states
+    }
+
+    #[tokio::test]
+    async fn fill_then_undo() {
+        let folder = TestFolder::new("undo");
+        let server = Server::with_world_folder(folder.join("server"));
+        let player = test_player(folder.join("world"), uuid::Uuid::new_v4()).await;
+        player.permission_lvl.store(PermissionLvl::Four);
+        let world = player.world().clone();
+
+        let stone = get_block("stone").unwrap().default_state_id;
+        let dirt = get_block("dirt").unwrap().default_state_id;
+        let positions: Vec<_> = (0..4).map(|x| BlockPos(Vector3::new(x, 100, 0))).collect();
+        world.set_block_state(&positions[1], dirt).await;
+        let original = states(&world, &positions).await;
+
+        run(&server, &player, "fill 0 100 0 3 100 0 stone")
+            .await
+            .unwrap();
+        assert_eq!(states(&world, &positions).await, [stone; 4]);
+        assert_eq!(player.edit_history.lock().await.len(), 1);
+
+        run(&server, &player, "undo").await.unwrap();
+        assert_eq!(states(&world, &positions).await, original);
+        assert!(player.edit_history.lock().await.is_empty());
+
+        // Destroyed blocks drop like broken ones, restoring them could duplicate items
+        run(&server, &player, "fill 0 100 0 3 100 0 stone destroy")
+            .await
+            .unwrap();
+        assert!(player.edit_history.lock().await.is_empty());
+        assert!(run(&server, &player, "undo").await.is_err());
+        assert_eq!(states(&world, &positions).await, [stone; 4]);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = EditHistory::default();
+        let changes = |count: i32| -> Vec<_> {
+            (0..count)
+                .map(|x| (BlockPos(Vector3::new(x, 0, 0)), 1))
+                .collect()
+        };
+        for _ in 0..5 {
+            assert!(history.push(edit(&changes(2), &[0, 0]), 3, 100));
+        }
+        assert_eq!(history.len(), 3);
+        // too many blocks together, the oldest edits are dropped
+        assert!(history.push(edit(&changes(5), &[0; 5]), 3, 8));
+        assert_eq!(history.len(), 2);
+        // too large to ever be undone
+        assert!(!history.push(edit(&changes(9), &[0; 9]), 3, 8));
+        assert_eq!(history.len(), 2);
+    }
+}
@@ -0,0 +1,89 @@
+use std::{collections::HashMap, fmt};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_util::math::{get_section_cord, vector2::Vector2, vector3::Vector3};
+
+use super::World;
+
+/// The limit that stopped a mob from spawning, see [`pumpkin_config::EntityLimitsConfig`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EntityLimit {
+    Chunk,
+    World,
+}
+
+impl fmt::Display for EntityLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Chunk => write!(
+                f,
+                "This chunk already has the maximum of {} entities",
+                ADVANCED_CONFIG.entity_limits.per_chunk
+            ),
+            Self::World => write!(
+                f,
+                "This world already has the maximum of {} entities",
+                ADVANCED_CONFIG.entity_limits.per_world
+            ),
+        }
+    }
+}
+
+impl World {
+    /// Returns the limit a new mob at `pos` would exceed, if any.
+    /// Every way of spawning a mob has to check this before creating it
+    pub async fn entity_limit_reached(&self, pos: Vector3<f64>) -> Option<EntityLimit> {
+        let chunk = Vector2::new(
+            get_section_cord(pos.x.floor() as i32),
+            get_section_cord(pos.z.floor() as i32),
+        );
+        let mobs = self.current_living_mobs.lock().await;
+        let in_chunk = mobs
+            .values()
+            .filter(|mob| mob.living_entity.entity.chunk_pos.load() == chunk)
+            .count();
+        let config = &ADVANCED_CONFIG.entity_limits;
+        exceeded_limit(mobs.len(), in_chunk, config.per_world, config.per_chunk)
+    }
+
+    /// How many mobs are in each chunk that has any
+    pub async fn mobs_per_chunk(&self) -> HashMap<Vector2<i32>, usize> {
+        let mut chunks = HashMap::new();
+        for mob in self.current_living_mobs.lock().await.values() {
+            *chunks
+                .entry(mob.living_entity.entity.chunk_pos.load())
+                .or_default() += 1;
+        }
+        chunks
+    }
+}
+
+/// Whether one more mob would go over the limit of the world or the chunk
+const fn exceeded_limit(
+    in_world: usize,
+    in_chunk: usize,
+    per_world: usize,
+    per_chunk: usize,
+) -> Option<EntityLimit> {
+    if in_world >= per_world {
+        Some(EntityLimit::World)
+    } else if in_chunk >= per_chunk {
+        Some(EntityLimit::Chunk)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{exceeded_limit, EntityLimit};
+
+    #[test]
+    fn spawning_over_the_cap() {
+        assert_eq!(exceeded_limit(10, 2, 100, 10), None);
+        assert_eq!(exceeded_limit(10, 10, 100, 10), Some(EntityLimit::Chunk));
+        assert_eq!(exceeded_limit(100, 2, 100, 10), Some(EntityLimit::World));
+        // a cap of zero disables spawning entirely
+        assert_eq!(exceeded_limit(0, 0, 0, 10), Some(EntityLimit::World));
+    }
+}
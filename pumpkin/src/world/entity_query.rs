@@ -0,0 +1,283 @@
+use std::sync::Arc;
+
+use pumpkin_util::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    vector3::Vector3,
+};
+
+use crate::entity::{living::LivingEntity, mob::MobEntity, player::Player, Entity, EntityId};
+
+use super::World;
+
+/// Any entity that is stored in a world
+#[derive(Clone)]
+pub enum WorldEntity {
+    Player(Arc<Player>),
+    Mob(Arc<MobEntity>),
+}
+
+impl WorldEntity {
+    #[must_use]
+    pub fn living_entity(&self) -> &LivingEntity {
+        match self {
+            Self::Player(player) => &player.living_entity,
+            Self::Mob(mob) => &mob.living_entity,
+        }
+    }
+
+    #[must_use]
+    pub fn entity(&self) -> &Entity {
+        &self.living_entity().entity
+    }
+
+    /// Spectators can not be touched and do not touch anything, like pressure plates
+    #[must_use]
+    pub fn is_spectator(&self) -> bool {
+        matches!(self, Self::Player(player) if player.is_spectator())
+    }
+}
+
+impl World {
+    /// All players and mobs of the world
+    pub async fn get_all_entities(&self) -> Vec<WorldEntity> {
+        let mut entities: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .map(WorldEntity::Player)
+            .collect();
+        entities.extend(
+            self.current_living_mobs
+                .lock()
+                .await
+                .values()
+                .cloned()
+                .map(WorldEntity::Mob),
+        );
+        entities
+    }
+
+    /// All players and mobs whose bounding box intersects with `area`.
+    /// Entities only touching the edge of the area are not included
+    pub async fn get_entities_in_box(&self, area: &BoundingBox) -> Vec<WorldEntity> {
+        let mut entities: Vec<_> = self
+            .get_players_in_box(area)
+            .await
+            .into_iter()
+            .map(WorldEntity::Player)
+            .collect();
+        entities.extend(
+            self.current_living_mobs
+                .lock()
+                .await
+                .values()
+                .filter(|mob| is_entity_in_box(&mob.living_entity.entity, area))
+                .cloned()
+                .map(WorldEntity::Mob),
+        );
+        entities
+    }
+
+    /// All players whose bounding box intersects with `area`, see [`World::get_entities_in_box`]
+    pub async fn get_players_in_box(&self, area: &BoundingBox) -> Vec<Arc<Player>> {
+        self.current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| is_entity_in_box(&player.living_entity.entity, area))
+            .cloned()
+            .collect()
+    }
+
+    /// All mobs whose bounding box intersects with `area`, see [`World::get_entities_in_box`]
+    pub async fn get_living_entities_in_box(&self, area: &BoundingBox) -> Vec<Arc<LivingEntity>> {
+        self.current_living_mobs
+            .lock()
+            .await
+            .values()
+            .filter(|mob| is_entity_in_box(&mob.living_entity.entity, area))
+            .map(|mob| mob.living_entity.clone())
+            .collect()
+    }
+
+    /// Returns the closest player or mob other than `except` whose bounding box is hit by a ray within `max_distance`.
+    /// `direction` has to be normalized
+    pub async fn raycast_entity(
+        &self,
+        start: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+        except: EntityId,
+    ) -> Option<WorldEntity> {
+        let end = start + direction * max_distance;
+        let area = BoundingBox::new(
+            Vector3::new(start.x.min(end.x), start.y.min(end.y), start.z.min(end.z)),
+            Vector3::new(start.x.max(end.x), start.y.max(end.y), start.z.max(end.z)),
+        );
+        // Entities only partially inside the area around the ray can still be hit
+        let area = BoundingBox::new(
+            Vector3::new(area.min_x - 1.0, area.min_y - 1.0, area.min_z - 1.0),
+            Vector3::new(area.max_x + 1.0, area.max_y + 1.0, area.max_z + 1.0),
+        );
+        self.get_entities_in_box(&area)
+            .await
+            .into_iter()
+            .filter(|entity| entity.entity().entity_id != except)
+            .filter_map(|entity| {
+                let inner = entity.entity();
+                let pos = inner.pos.load();
+                let hitbox =
+                    BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &inner.bounding_box_size.load());
+                ray_hit_distance(start, direction, &hitbox)
+                    .filter(|&distance| distance <= max_distance)
+                    .map(|distance| (distance, entity))
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, entity)| entity)
+    }
+}
+
+fn is_entity_in_box(entity: &Entity, area: &BoundingBox) -> bool {
+    is_in_box(entity.pos.load(), &entity.bounding_box_size.load(), area)
+}
+
+/// Whether the bounding box of an entity at `pos` intersects with `area`
+fn is_in_box(pos: Vector3<f64>, size: &BoundingBoxSize, area: &BoundingBox) -> bool {
+    BoundingBox::new_from_pos(pos.x, pos.y, pos.z, size).intersects(area)
+}
+
+/// Distance along the ray to where it enters `hitbox`, or 0 when it starts inside of it
+fn ray_hit_distance(
+    start: Vector3<f64>,
+    direction: Vector3<f64>,
+    hitbox: &BoundingBox,
+) -> Option<f64> {
+    let mut near: f64 = 0.0;
+    let mut far = f64::INFINITY;
+    for (origin, d, min, max) in [
+        (start.x, direction.x, hitbox.min_x, hitbox.max_x),
+        (start.y, direction.y, hitbox.min_y, hitbox.max_y),
+        (start.z, direction.z, hitbox.min_z, hitbox.max_z),
+    ] {
+        if d == 0.0 {
+            // Parallel to this axis, so the ray has to already be between both faces
+            if origin < min || origin > max {
+                return None;
+            }
+            continue;
+        }
+        let (a, b) = ((min - origin) / d, (max - origin) / d);
+        near = near.max(a.min(b));
+        far = far.min(a.max(b));
+    }
+    (near <= far).then_some(near)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        vector3::Vector3,
+    };
+    use pumpkin_world::dimension::Dimension;
+
+    use super::{is_in_box, ray_hit_distance};
+    use crate::data::test_folder::TestFolder;
+    use crate::{plugin::api::events::entity::SpawnReason, server::Server, world::World};
+
+    const SIZE: BoundingBoxSize = BoundingBoxSize {
+        width: 1.0,
+        height: 2.0,
+    };
+
+    fn area() -> BoundingBox {
+        BoundingBox::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(4.0, 4.0, 4.0))
+    }
+
+    #[test]
+    fn entity_inside_box() {
+        assert!(is_in_box(Vector3::new(2.0, 1.0, 2.0), &SIZE, &area()));
+        // partially overlapping is enough
+        assert!(is_in_box(Vector3::new(-0.4, 1.0, 2.0), &SIZE, &area()));
+        assert!(is_in_box(Vector3::new(2.0, -1.5, 2.0), &SIZE, &area()));
+    }
+
+    #[test]
+    fn entity_on_boundary_is_excluded() {
+        // touching the min x face from outside
+        assert!(!is_in_box(Vector3::new(-0.5, 1.0, 2.0), &SIZE, &area()));
+        // touching the max z face from outside
+        assert!(!is_in_box(Vector3::new(2.0, 1.0, 4.5), &SIZE, &area()));
+        // standing exactly on top of the area
+        assert!(!is_in_box(Vector3::new(2.0, 4.0, 2.0), &SIZE, &area()));
+        // feet exactly on the floor of the area, head below it
+        assert!(!is_in_box(Vector3::new(2.0, -2.0, 2.0), &SIZE, &area()));
+    }
+
+    #[test]
+    fn ray_hits_entity() {
+        let hitbox = BoundingBox::new_from_pos(0.0, 0.0, 5.0, &SIZE);
+        let distance = ray_hit_distance(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            &hitbox,
+        );
+        assert_eq!(distance, Some(4.5));
+        // looking the other way
+        let behind = ray_hit_distance(
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            &hitbox,
+        );
+        assert_eq!(behind, None);
+        // passing above the head
+        let above = ray_hit_distance(
+            Vector3::new(0.0, 3.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            &hitbox,
+        );
+        assert_eq!(above, None);
+    }
+
+    #[tokio::test]
+    async fn finds_spawned_entities() {
+        let folder = TestFolder::new("spawned-entities");
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        let server = Server::with_world_folder(folder.join("server"));
+        let position = Vector3::new(8.5, 100.0, 8.5);
+        let mob = world
+            .spawn_entity(&server, EntityType::Zombie, position, SpawnReason::Command)
+            .await
+            .unwrap();
+        let entity = &mob.living_entity.entity;
+
+        let found = world
+            .get_living_entity_by_entityid(entity.entity_id)
+            .await
+            .unwrap();
+        assert_eq!(found.entity.entity_uuid, entity.entity_uuid);
+
+        let around = BoundingBox::new(
+            Vector3::new(7.0, 99.0, 7.0),
+            Vector3::new(10.0, 102.0, 10.0),
+        );
+        let in_box = world.get_entities_in_box(&around).await;
+        assert_eq!(in_box.len(), 1);
+        assert_eq!(in_box[0].entity().entity_uuid, entity.entity_uuid);
+
+        let elsewhere = BoundingBox::new(
+            Vector3::new(20.0, 99.0, 20.0),
+            Vector3::new(22.0, 102.0, 22.0),
+        );
+        assert!(world.get_entities_in_box(&elsewhere).await.is_empty());
+    }
+}
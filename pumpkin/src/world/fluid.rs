@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{
+    block::{
+        block_registry::{get_block, get_block_by_state_id},
+        BlockFace,
+    },
+    coordinates::ChunkRelativeBlockCoordinates,
+};
+
+use super::{scheduled_tick::TickPriority, World};
+
+/// The item name of a bucket without any fluid
+pub const EMPTY_BUCKET: &str = "bucket";
+
+/// A fluid that can be picked up and placed with a bucket
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Fluid {
+    Water,
+    Lava,
+}
+
+impl Fluid {
+    #[must_use]
+    pub fn from_block(name: &str) -> Option<Self> {
+        match name {
+            "water" => Some(Self::Water),
+            "lava" => Some(Self::Lava),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn from_bucket(item_name: &str) -> Option<Self> {
+        match item_name {
+            "water_bucket" => Some(Self::Water),
+            "lava_bucket" => Some(Self::Lava),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn block_name(self) -> &'static str {
+        match self {
+            Self::Water => "water",
+            Self::Lava => "lava",
+        }
+    }
+
+    #[must_use]
+    pub const fn bucket_name(self) -> &'static str {
+        match self {
+            Self::Water => "water_bucket",
+            Self::Lava => "lava_bucket",
+        }
+    }
+}
+
+/// What using a bucket on a block does
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BucketAction {
+    /// Places the fluid of a filled bucket, leaving an empty bucket
+    Place(Fluid),
+    /// Fills an empty bucket with the fluid, removing its source block
+    PickUp(Fluid),
+}
+
+impl BucketAction {
+    /// `source` tells whether the targeted block is a fluid source, only those can be picked up
+    #[must_use]
+    pub fn new(held_item: &str, target_block: &str, source: bool) -> Option<Self> {
+        if held_item == EMPTY_BUCKET {
+            return Fluid::from_block(target_block)
+                .filter(|_| source)
+                .map(Self::PickUp);
+        }
+        Fluid::from_bucket(held_item).map(Self::Place)
+    }
+}
+
+/// Ticks between every step of flowing water
+pub const WATER_FLOW_DELAY: u32 = 5;
+/// Water flows this many blocks away from its source
+const MAX_FLOW_DISTANCE: u16 = 7;
+/// Flowing water with this level or above is falling down
+const FALLING: u16 = 8;
+
+const HORIZONTAL: [BlockFace; 4] = [
+    BlockFace::North,
+    BlockFace::South,
+    BlockFace::West,
+    BlockFace::East,
+];
+
+impl World {
+    /// Lets fluids at and next to changed blocks flow.
+    ///
+    /// Only positions that hold a fluid get a tick, and only chunks that are already loaded are
+    /// looked at, so changing blocks away from any fluid costs no ticks and loads no chunks
+    pub async fn schedule_fluid_ticks(&self, changed: impl IntoIterator<Item = &BlockPos>) {
+        let mut by_chunk: HashMap<Vector2<i32>, HashSet<BlockPos>> = HashMap::new();
+        for position in changed {
+            let neighbors = HORIZONTAL
+                .into_iter()
+                .chain([BlockFace::Bottom, BlockFace::Top])
+                .map(|face| BlockPos(position.0 + face.to_offset()));
+            for candidate in std::iter::once(*position).chain(neighbors) {
+                if candidate.0.y < self.min_y || candidate.0.y >= self.max_y() {
+                    continue;
+                }
+                let (chunk, _) = candidate.chunk_and_chunk_relative_position();
+                by_chunk.entry(chunk).or_default().insert(candidate);
+            }
+        }
+
+        let mut fluids = Vec::new();
+        for (chunk, candidates) in by_chunk {
+            let Some(chunk) = self.level.get_loaded_chunk(&chunk) else {
+                continue;
+            };
+            let chunk = chunk.read().await;
+            for candidate in candidates {
+                let (_, relative) = candidate.chunk_and_chunk_relative_position();
+                let is_fluid = chunk
+                    .subchunks
+                    .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                    .and_then(get_block_by_state_id)
+                    .is_some_and(|block| Fluid::from_block(&block.name).is_some());
+                if is_fluid {
+                    fluids.push(candidate);
+                }
+            }
+        }
+        if fluids.is_empty() {
+            return;
+        }
+
+        let mut fluid_ticks = self.fluid_ticks.lock().await;
+        for position in fluids {
+            fluid_ticks.schedule(position, WATER_FLOW_DELAY, TickPriority::Normal);
+        }
+    }
+
+    /// Moves the water at `position` one step, only water flows for now.
+    /// Every block changed by this schedules the next step, until the water leveled off
+    pub async fn tick_fluid(&self, position: BlockPos) {
+        let Some(mut level) = self.water_level(&position).await else {
+            return;
+        };
+        if level != 0 {
+            let above = BlockPos(position.0 + Vector3::new(0, 1, 0));
+            let mut neighbors = Vec::with_capacity(HORIZONTAL.len());
+            for face in HORIZONTAL {
+                let neighbor = BlockPos(position.0 + face.to_offset());
+                if let Some(neighbor_level) = self.water_level(&neighbor).await {
+                    neighbors.push(neighbor_level);
+                }
+            }
+            let Some(new_level) =
+                flowing_level(self.water_level(&above).await.is_some(), neighbors)
+            else {
+                self.set_block_state(&position, 0).await;
+                return;
+            };
+            if new_level != level {
+                self.set_water(&position, new_level).await;
+                level = new_level;
+            }
+        }
+
+        let below = BlockPos(position.0 + Vector3::new(0, -1, 0));
+        if below.0.y >= self.min_y && self.can_flow_into(&below, FALLING).await {
+            self.set_water(&below, FALLING).await;
+            // Only sources also spread to the sides while they can flow down
+            if level != 0 {
+                return;
+            }
+        }
+        let Some(next_level) = spread_level(level) else {
+            return;
+        };
+        for face in HORIZONTAL {
+            let neighbor = BlockPos(position.0 + face.to_offset());
+            if self.can_flow_into(&neighbor, next_level).await {
+                self.set_water(&neighbor, next_level).await;
+            }
+        }
+    }
+
+    /// The level of the water at `position`, none if there is no water
+    async fn water_level(&self, position: &BlockPos) -> Option<u16> {
+        let (block, state) = self.get_block_and_block_state(position).await.ok()?;
+        (Fluid::from_block(&block.name) == Some(Fluid::Water))
+            .then(|| state.id - block.default_state_id)
+    }
+
+    /// Water stops at solid blocks, replaceable blocks like grass are washed away
+    async fn can_flow_into(&self, position: &BlockPos, level: u16) -> bool {
+        if position.0.y < self.min_y || position.0.y >= self.max_y() {
+            return false;
+        }
+        let Ok((block, state)) = self.get_block_and_block_state(position).await else {
+            return false;
+        };
+        match Fluid::from_block(&block.name) {
+            Some(Fluid::Water) => replaces(state.id - block.default_state_id, level),
+            Some(Fluid::Lava) => false,
+            None => state.replaceable,
+        }
+    }
+
+    async fn set_water(&self, position: &BlockPos, level: u16) {
+        if let Some(water) = get_block(Fluid::Water.block_name()) {
+            self.set_block_state(position, water.default_state_id + level)
+                .await;
+        }
+    }
+}
+
+/// How far flowing water of the given level is from its source, falling water acts like a source
+const fn distance(level: u16) -> u16 {
+    if level >= FALLING {
+        0
+    } else {
+        level
+    }
+}
+
+/// The level water of the given level flows to the sides with, none if it is too far from its source
+fn spread_level(level: u16) -> Option<u16> {
+    let next = distance(level) + 1;
+    (next <= MAX_FLOW_DISTANCE).then_some(next)
+}
+
+/// The level flowing water should have with water above it or the given water levels next to it.
+/// Returns none if nothing feeds it anymore and it dries up
+fn flowing_level(water_above: bool, neighbors: impl IntoIterator<Item = u16>) -> Option<u16> {
+    if water_above {
+        return Some(FALLING);
+    }
+    neighbors.into_iter().filter_map(spread_level).min()
+}
+
+/// Whether flowing water of `new` level replaces water of the `existing` level.
+/// Sources and falling water are never replaced
+const fn replaces(existing: u16, new: u16) -> bool {
+    if existing == 0 || existing >= FALLING {
+        false
+    } else {
+        new >= FALLING || new < existing
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::{block::block_registry::get_block, dimension::Dimension};
+
+    use super::{
+        flowing_level, replaces, spread_level, BucketAction, Fluid, EMPTY_BUCKET, FALLING,
+    };
+    use crate::data::test_folder::TestFolder;
+    use crate::world::World;
+
+    #[test]
+    fn filling_buckets() {
+        assert_eq!(
+            BucketAction::new(EMPTY_BUCKET, "water", true),
+            Some(BucketAction::PickUp(Fluid::Water))
+        );
+        assert_eq!(
+            BucketAction::new(EMPTY_BUCKET, "lava", true),
+            Some(BucketAction::PickUp(Fluid::Lava))
+        );
+        // flowing fluids and other blocks can not be picked up
+        assert_eq!(BucketAction::new(EMPTY_BUCKET, "water", false), None);
+        assert_eq!(BucketAction::new(EMPTY_BUCKET, "stone", true), None);
+    }
+
+    #[test]
+    fn emptying_buckets() {
+        assert_eq!(
+            BucketAction::new("water_bucket", "stone", false),
+            Some(BucketAction::Place(Fluid::Water))
+        );
+        assert_eq!(
+            BucketAction::new("lava_bucket", "water", true),
+            Some(BucketAction::Place(Fluid::Lava))
+        );
+        assert_eq!(BucketAction::new("stone", "water", true), None);
+        for fluid in [Fluid::Water, Fluid::Lava] {
+            assert_eq!(Fluid::from_bucket(fluid.bucket_name()), Some(fluid));
+            assert_eq!(Fluid::from_block(fluid.block_name()), Some(fluid));
+        }
+    }
+
+    #[test]
+    fn spread_distance() {
+        let mut levels = vec![0];
+        while let Some(next) = spread_level(*levels.last().unwrap()) {
+            levels.push(next);
+        }
+        // a source and seven flowing blocks
+        assert_eq!(levels, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        // water landing after falling spreads like a source
+        assert_eq!(spread_level(FALLING), Some(1));
+    }
+
+    #[test]
+    fn leveling_off() {
+        assert_eq!(flowing_level(false, [0]), Some(1));
+        assert_eq!(flowing_level(false, [4, 2, 6]), Some(3));
+        assert_eq!(flowing_level(true, []), Some(FALLING));
+        // nothing feeds it anymore, it dries up
+        assert_eq!(flowing_level(false, []), None);
+        assert_eq!(flowing_level(false, [7]), None);
+
+        assert!(replaces(5, 2));
+        assert!(!replaces(2, 2));
+        assert!(replaces(3, FALLING));
+        assert!(!replaces(0, 1));
+        assert!(!replaces(FALLING, 1));
+    }
+
+    #[tokio::test]
+    async fn only_ticks_next_to_fluids() {
+        let folder = TestFolder::new("fluid-ticks");
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.to_path_buf()),
+            DimensionType::Overworld,
+        ));
+        let stone = get_block("stone").unwrap().default_state_id;
+        let water = get_block("water").unwrap().default_state_id;
+        let at = |x| BlockPos(Vector3::new(x, 200, 0));
+
+        world.set_block_state(&at(0), stone).await;
+        world
+            .set_block_states(&[(at(2), stone), (at(3), stone)])
+            .await;
+        assert!(world.fluid_ticks.lock().await.is_empty());
+
+        world.set_block_states(&[(at(5), water)]).await;
+        world.set_block_state(&at(6), stone).await;
+        let fluid_ticks = world.fluid_ticks.lock().await;
+        assert!(fluid_ticks.is_scheduled(&at(5)));
+        assert!(!fluid_ticks.is_scheduled(&at(6)));
+    }
+}
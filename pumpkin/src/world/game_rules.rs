@@ -0,0 +1,196 @@
+use std::{collections::HashMap, fmt};
+
+/// The name of every game rule, as used by `/gamerule` and in the `GameRules` of `level.dat`
+pub const GAME_RULE_NAMES: [&str; 7] = [
+    "doDaylightCycle",
+    "doWeatherCycle",
+    "doMobSpawning",
+    "keepInventory",
+    "sendCommandFeedback",
+    "naturalRegeneration",
+    "maxCommandChainLength",
+];
+
+/// Rules that change how a single world behaves, every world has its own
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameRules {
+    /// Whether the time of day advances
+    pub do_daylight_cycle: bool,
+    /// Whether the weather changes on its own
+    pub do_weather_cycle: bool,
+    /// Whether mobs spawn on their own
+    pub do_mob_spawning: bool,
+    /// Whether players keep their experience when dying
+    pub keep_inventory: bool,
+    /// Whether players see the messages of the commands they run
+    pub send_command_feedback: bool,
+    /// Whether players regenerate health on their own
+    pub natural_regeneration: bool,
+    /// How many commands a function and all the functions it calls may run together
+    pub max_command_chain_length: i32,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            do_daylight_cycle: true,
+            do_weather_cycle: true,
+            do_mob_spawning: true,
+            keep_inventory: false,
+            send_command_feedback: true,
+            natural_regeneration: true,
+            max_command_chain_length: 65536,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameRuleValue {
+    Bool(bool),
+    Int(i32),
+}
+
+impl fmt::Display for GameRuleValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Int(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameRuleError {
+    UnknownRule,
+    /// The value does not have the type of the rule, e.g. a number for a boolean rule
+    InvalidValue,
+}
+
+enum RuleMut<'a> {
+    Bool(&'a mut bool),
+    Int(&'a mut i32),
+}
+
+impl GameRules {
+    fn rule_mut(&mut self, name: &str) -> Option<RuleMut<'_>> {
+        Some(match name {
+            "doDaylightCycle" => RuleMut::Bool(&mut self.do_daylight_cycle),
+            "doWeatherCycle" => RuleMut::Bool(&mut self.do_weather_cycle),
+            "doMobSpawning" => RuleMut::Bool(&mut self.do_mob_spawning),
+            "keepInventory" => RuleMut::Bool(&mut self.keep_inventory),
+            "sendCommandFeedback" => RuleMut::Bool(&mut self.send_command_feedback),
+            "naturalRegeneration" => RuleMut::Bool(&mut self.natural_regeneration),
+            "maxCommandChainLength" => RuleMut::Int(&mut self.max_command_chain_length),
+            _ => return None,
+        })
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<GameRuleValue> {
+        let mut rules = *self;
+        Some(match rules.rule_mut(name)? {
+            RuleMut::Bool(value) => GameRuleValue::Bool(*value),
+            RuleMut::Int(value) => GameRuleValue::Int(*value),
+        })
+    }
+
+    /// Parses the value with the type of the rule and sets it, returning the new value
+    pub fn set(&mut self, name: &str, value: &str) -> Result<GameRuleValue, GameRuleError> {
+        match self.rule_mut(name).ok_or(GameRuleError::UnknownRule)? {
+            RuleMut::Bool(rule) => {
+                *rule = value.parse().map_err(|_| GameRuleError::InvalidValue)?;
+                Ok(GameRuleValue::Bool(*rule))
+            }
+            RuleMut::Int(rule) => {
+                *rule = value.parse().map_err(|_| GameRuleError::InvalidValue)?;
+                Ok(GameRuleValue::Int(*rule))
+            }
+        }
+    }
+
+    /// The rules like they are stored in `level.dat`, where vanilla keeps every value as a string
+    #[must_use]
+    pub fn to_level_data(&self) -> HashMap<String, String> {
+        GAME_RULE_NAMES
+            .iter()
+            .filter_map(|name| Some((name.to_string(), self.get(name)?.to_string())))
+            .collect()
+    }
+
+    /// Reads the rules stored in `level.dat`, missing rules keep their default.
+    /// Rules that are not supported yet are skipped, invalid values are skipped with a warning
+    #[must_use]
+    pub fn from_level_data(rules: &HashMap<String, String>) -> Self {
+        let mut game_rules = Self::default();
+        for (name, value) in rules {
+            if game_rules.set(name, value) == Err(GameRuleError::InvalidValue) {
+                log::warn!("Skipping invalid value {value} of game rule {name} in level.dat");
+            }
+        }
+        game_rules
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{GameRuleError, GameRuleValue, GameRules, GAME_RULE_NAMES};
+
+    #[test]
+    fn values_must_match_the_rule_type() {
+        let mut rules = GameRules::default();
+        assert_eq!(
+            rules.set("keepInventory", "true"),
+            Ok(GameRuleValue::Bool(true))
+        );
+        assert!(rules.keep_inventory);
+        assert_eq!(
+            rules.set("maxCommandChainLength", "10"),
+            Ok(GameRuleValue::Int(10))
+        );
+        assert_eq!(
+            rules.set("keepInventory", "10"),
+            Err(GameRuleError::InvalidValue)
+        );
+        assert_eq!(
+            rules.set("maxCommandChainLength", "true"),
+            Err(GameRuleError::InvalidValue)
+        );
+        assert_eq!(
+            rules.set("flyFaster", "true"),
+            Err(GameRuleError::UnknownRule)
+        );
+        // failed changes keep the previous value
+        assert_eq!(
+            rules.get("maxCommandChainLength"),
+            Some(GameRuleValue::Int(10))
+        );
+        for name in GAME_RULE_NAMES {
+            assert!(rules.get(name).is_some());
+        }
+    }
+
+    #[test]
+    fn level_data_round_trip() {
+        let rules = GameRules {
+            send_command_feedback: false,
+            max_command_chain_length: 0,
+            ..Default::default()
+        };
+        let stored = rules.to_level_data();
+        assert_eq!(stored["sendCommandFeedback"], "false");
+        assert_eq!(stored["maxCommandChainLength"], "0");
+        assert_eq!(GameRules::from_level_data(&stored), rules);
+
+        // Rules of other versions and broken values don't prevent the others from loading
+        let stored = HashMap::from([
+            ("keepInventory".to_string(), "true".to_string()),
+            ("doFireTick".to_string(), "false".to_string()),
+            ("doMobSpawning".to_string(), "maybe".to_string()),
+        ]);
+        let rules = GameRules::from_level_data(&stored);
+        assert!(rules.keep_inventory);
+        assert!(rules.do_mob_spawning);
+    }
+}
@@ -5,7 +5,6 @@ use super::World;
 pub struct LevelTime {
     pub world_age: i64,
     pub time_of_day: i64,
-    pub rain_time: i64,
 }
 
 pub enum DayTime {
@@ -27,19 +26,27 @@ impl LevelTime {
         Self {
             world_age: 0,
             time_of_day: 0,
-            rain_time: 0,
         }
     }
 
-    pub fn tick_time(&mut self) {
+    /// Advances the world by one tick, the time of day only when the `do_daylight_cycle` rule allows it.
+    /// The weather is counted down by [`World::tick_weather`]
+    pub fn tick_time(&mut self, do_daylight_cycle: bool) {
         self.world_age += 1;
-        self.time_of_day += 1;
-        self.rain_time += 1;
+        if do_daylight_cycle {
+            self.time_of_day += 1;
+        }
     }
 
     pub async fn send_time(&self, world: &World) {
+        // The client only advances the time itself if the daylight cycle is on
+        let do_daylight_cycle = world.game_rules.lock().await.do_daylight_cycle;
         world
-            .broadcast_packet_all(&CUpdateTime::new(self.world_age, self.time_of_day, true))
+            .broadcast_packet_all(&CUpdateTime::new(
+                self.world_age,
+                self.time_of_day,
+                do_daylight_cycle,
+            ))
             .await;
     }
 
@@ -66,3 +73,25 @@ impl LevelTime {
         self.time_of_day / 24000
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::LevelTime;
+
+    #[test]
+    fn worlds_advance_time_independently() {
+        let mut overworld = LevelTime::new();
+        let mut nether = LevelTime::new();
+        overworld.set_time(6000);
+        for _ in 0..100 {
+            overworld.tick_time(true);
+            // a world with the daylight cycle turned off keeps its time of day
+            nether.tick_time(false);
+        }
+        assert_eq!(overworld.query_daytime(), 6100);
+        assert_eq!(nether.query_daytime(), 0);
+        // the age of both worlds still counts up
+        assert_eq!(overworld.query_gametime(), 100);
+        assert_eq!(nether.query_gametime(), 100);
+    }
+}
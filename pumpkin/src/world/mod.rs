@@ -1,31 +1,63 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, MutexGuard, PoisonError},
+};
 
+pub mod activation_range;
+mod build_limit;
+mod collision;
+pub mod edit_history;
+pub mod entity_limit;
+pub mod entity_query;
+pub mod fluid;
+pub mod game_rules;
 pub mod level_time;
+pub mod natural_spawner;
 pub mod player_chunker;
+mod player_collision;
+mod respawn_point;
+pub mod scheduled_tick;
+pub mod selection;
+mod spawn_protection;
+mod spawn_radius;
+pub mod weather;
 
 use crate::{
     command::client_cmd_suggestions,
-    entity::{living::LivingEntity, mob::MobEntity, player::Player, Entity, EntityId},
+    entity::{
+        living::LivingEntity,
+        mob::{self, MobEntity},
+        player::{ChatMode, Player},
+        Entity, EntityId,
+    },
     error::PumpkinError,
+    net::chat::ChatSession,
     plugin::{
         block::r#break::BlockBreakEventImpl,
+        entity::{spawn::EntitySpawnEventImpl, SpawnReason},
         player::{
             join::PlayerJoinEventImpl, leave::PlayerLeaveEventImpl, PlayerJoinEvent,
             PlayerLeaveEvent,
         },
-        CancellableEvent,
+        CancellableEvent, PluginManager,
     },
     server::Server,
     PLUGIN_MANAGER,
 };
+use activation_range::ActivationStats;
+use crossbeam::atomic::AtomicCell;
+use game_rules::GameRules;
 use level_time::LevelTime;
-use pumpkin_config::BasicConfiguration;
+use pumpkin_config::{BasicConfiguration, BASIC_CONFIG};
 use pumpkin_data::{
     entity::{EntityPose, EntityType},
     sound::{Sound, SoundCategory},
     world::WorldEvent,
 };
-use pumpkin_protocol::client::play::{CBlockUpdate, CDisguisedChatMessage, CRespawn, CWorldEvent};
+use pumpkin_protocol::client::play::{
+    CBlockUpdate, CChangeDifficulty, CDisguisedChatMessage, CRespawn, CSectionBlocksUpdate,
+    CWorldEvent,
+};
 use pumpkin_protocol::{
     client::play::CLevelEvent,
     codec::{identifier::Identifier, var_int::VarInt},
@@ -39,17 +71,22 @@ use pumpkin_protocol::{
 };
 use pumpkin_registry::DimensionType;
 use pumpkin_util::math::vector2::Vector2;
-use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3, wrap_degrees};
+use pumpkin_util::random::{java_string_hash, xoroshiro128::Xoroshiro, RandomImpl};
 use pumpkin_util::text::{color::NamedColor, TextComponent};
+use pumpkin_util::Difficulty;
 use pumpkin_world::chunk::ChunkData;
 use pumpkin_world::level::Level;
 use pumpkin_world::{
-    block::block_registry::{
-        get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id,
+    block::{
+        block_registry::{
+            get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id,
+        },
+        BlockFace,
     },
     coordinates::ChunkRelativeBlockCoordinates,
 };
-use rand::{thread_rng, Rng};
+use scheduled_tick::ScheduledTicks;
 use scoreboard::Scoreboard;
 use thiserror::Error;
 use tokio::sync::{mpsc::Receiver, Mutex};
@@ -57,6 +94,7 @@ use tokio::{
     runtime::Handle,
     sync::{mpsc, RwLock},
 };
+use weather::Weather;
 use worldborder::Worldborder;
 
 pub mod bossbar;
@@ -90,6 +128,9 @@ impl PumpkinError for GetBlockError {
     }
 }
 
+/// The world spawn, until it is stored in the level data
+pub const WORLD_SPAWN: Vector2<i32> = Vector2::new(10, 10);
+
 /// Represents a Minecraft world, containing entities, players, and the underlying level data.
 ///
 /// Each dimension (Overworld, Nether, End) typically has its own `World`.
@@ -110,16 +151,40 @@ pub struct World {
     pub scoreboard: Mutex<Scoreboard>,
     /// The world's worldborder, defining the playable area and controlling its expansion or contraction.
     pub worldborder: Mutex<Worldborder>,
-    /// The world's time, including counting ticks for weather, time cycles and statistics
+    /// The world's time, including counting ticks for time cycles and statistics
     pub level_time: Mutex<LevelTime>,
+    /// The rules of this world, like whether its time and weather advance
+    pub game_rules: Mutex<GameRules>,
+    /// The world's weather and how long it lasts, the only place the weather is kept
+    pub weather: Mutex<Weather>,
+    /// Blocks waiting for a delayed update, see [`World::schedule_block_tick`]
+    pub block_ticks: Mutex<ScheduledTicks>,
+    /// Fluids waiting to flow, kept apart from the block ticks like in vanilla
+    pub fluid_ticks: Mutex<ScheduledTicks>,
     /// The type of dimension the world is in
     pub dimension_type: DimensionType,
+    /// The lowest y coordinate blocks can be placed at
+    pub min_y: i32,
+    /// The total height in blocks of the world, starting at `min_y`
+    pub height: i32,
+    /// How many blocks around the spawn are protected, see [`World::is_protected`]
+    pub spawn_protection: u32,
+    /// Players dying in a hardcore world can only spectate afterwards
+    pub hardcore: bool,
+    /// The current difficulty of the world
+    difficulty: AtomicCell<Difficulty>,
+    /// Mob counts of the last tick, split by whether a player was in their activation range
+    activation_stats: AtomicCell<ActivationStats>,
+    /// Randomness outside of world generation, see [`seeded_rng`]
+    rng: std::sync::Mutex<Xoroshiro>,
     // TODO: entities
 }
 
 impl World {
     #[must_use]
     pub fn load(level: Level, dimension_type: DimensionType) -> Self {
+        let rng = seeded_rng(level.seed.0, dimension_type, level.level_info.last_played);
+        let game_rules = GameRules::from_level_data(&level.level_info.game_rules);
         Self {
             level: Arc::new(level),
             current_players: Arc::new(Mutex::new(HashMap::new())),
@@ -127,12 +192,50 @@ impl World {
             scoreboard: Mutex::new(Scoreboard::new()),
             worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 29_999_984.0, 0, 0, 0)),
             level_time: Mutex::new(LevelTime::new()),
+            game_rules: Mutex::new(game_rules),
+            weather: Mutex::new(Weather::default()),
+            block_ticks: Mutex::new(ScheduledTicks::default()),
+            fluid_ticks: Mutex::new(ScheduledTicks::default()),
+            min_y: dimension_type.min_y(),
+            height: dimension_type.height(),
+            spawn_protection: BASIC_CONFIG.spawn_protection,
+            hardcore: BASIC_CONFIG.hardcore,
             dimension_type,
+            difficulty: AtomicCell::new(BASIC_CONFIG.default_difficulty),
+            activation_stats: AtomicCell::new(ActivationStats::default()),
+            rng: std::sync::Mutex::new(rng),
         }
     }
 
     pub async fn save(&self) {
-        self.level.save().await;
+        let game_rules = self.game_rules.lock().await.to_level_data();
+        self.level.save(game_rules).await;
+    }
+
+    /// The y coordinate above the highest block that can be placed
+    pub const fn max_y(&self) -> i32 {
+        self.min_y + self.height
+    }
+
+    /// The random generator for everything happening in the world, like mob spawns and drops.
+    /// The guard can not be held across an await
+    pub fn rng(&self) -> MutexGuard<'_, Xoroshiro> {
+        self.rng.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty.load()
+    }
+
+    /// Changes the difficulty of the world and informs all players about it.
+    /// Switching to Peaceful removes the monsters right away
+    pub async fn set_difficulty(&self, difficulty: Difficulty) {
+        self.difficulty.store(difficulty);
+        self.broadcast_packet_all(&CChangeDifficulty::new(difficulty as u8, false))
+            .await;
+        if difficulty == Difficulty::Peaceful {
+            self.remove_hostile_mobs().await;
+        }
     }
 
     /// Broadcasts a packet to all connected players within the world.
@@ -150,6 +253,7 @@ impl World {
         }
     }
 
+    /// Sends a message of a command like `/say` to every player that has not hidden their chat
     pub async fn broadcast_message(
         &self,
         message: &TextComponent,
@@ -157,13 +261,24 @@ impl World {
         chat_type: u32,
         target_name: Option<&TextComponent>,
     ) {
-        self.broadcast_packet_all(&CDisguisedChatMessage::new(
-            message,
-            (chat_type + 1).into(),
-            sender_name,
-            target_name,
-        ))
-        .await;
+        let packet =
+            CDisguisedChatMessage::new(message, (chat_type + 1).into(), sender_name, target_name);
+        for player in self.current_players.lock().await.values() {
+            // Only commands like `/say` and `/me` broadcast messages
+            if player.config.lock().await.chat_mode.shows_chat(true) {
+                player.client.send_packet(&packet).await;
+            }
+        }
+    }
+
+    /// Sends a system message to every player in the world that has not hidden their chat
+    pub async fn broadcast_system_message(&self, message: &TextComponent) {
+        for player in self.current_players.lock().await.values() {
+            if matches!(player.config.lock().await.chat_mode, ChatMode::Hidden) {
+                continue;
+            }
+            player.send_system_message(message).await;
+        }
     }
 
     /// Broadcasts a packet to all connected players within the world, excluding the specified players.
@@ -194,7 +309,7 @@ impl World {
         volume: f32,
         pitch: f32,
     ) {
-        let seed = thread_rng().gen::<f64>();
+        let seed = self.rng().next_f64();
         let players = self.current_players.lock().await;
         for (_, player) in players.iter() {
             player
@@ -227,28 +342,133 @@ impl World {
             .await;
     }
 
-    pub async fn tick(&self) {
+    pub async fn tick(&self, server: &Server) {
         // world ticks
-        {
+        let game_rules = *self.game_rules.lock().await;
+        let world_age = {
             let mut level_time = self.level_time.lock().await;
-            level_time.tick_time();
+            level_time.tick_time(game_rules.do_daylight_cycle);
             if level_time.world_age % 20 == 0 {
                 level_time.send_time(self).await;
             }
+            level_time.world_age
+        };
+        self.tick_weather(game_rules.do_weather_cycle).await;
+        let due = self.fluid_ticks.lock().await.advance();
+        for position in due {
+            self.tick_fluid(position).await;
         }
         // player ticks
         for player in self.current_players.lock().await.values() {
             player.tick().await;
         }
-        // entites tick
-        for entity in self.current_living_mobs.lock().await.values() {
+        // Spectators look up the entity they follow in the world, which needs the players unlocked
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in players {
+            player.tick_spectating().await;
+        }
+        self.push_colliding_players().await;
+        // Monsters are not allowed to exist on peaceful
+        if self.difficulty() == Difficulty::Peaceful {
+            self.remove_hostile_mobs().await;
+        }
+        // entites tick, mobs without a player nearby only tick every few ticks
+        let player_positions: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| !player.is_spectator())
+            .map(|player| player.living_entity.entity.pos.load())
+            .collect();
+        let mut stats = ActivationStats::default();
+        let mut despawned = Vec::new();
+        let mut entered_blocks = Vec::new();
+        for (uuid, entity) in self.current_living_mobs.lock().await.iter() {
+            // Without any players nothing despawns, like in vanilla
+            if !player_positions.is_empty() && entity.should_despawn().await {
+                despawned.push((*uuid, entity.living_entity.entity.entity_id));
+                continue;
+            }
+            if activation_range::is_active(entity, &player_positions) {
+                stats.active += 1;
+            } else {
+                stats.inactive += 1;
+                if !activation_range::is_inactive_tick(entity, world_age) {
+                    continue;
+                }
+            }
+            let block_pos = entity.living_entity.entity.block_pos.load();
             entity.tick().await;
+            let new_block_pos = entity.living_entity.entity.block_pos.load();
+            if new_block_pos != block_pos {
+                entered_blocks.push(new_block_pos);
+            }
+        }
+        self.activation_stats.store(stats);
+        self.remove_mobs(despawned).await;
+        // Blocks like pressure plates look for the mobs on them, so this waits until they are unlocked
+        for location in entered_blocks {
+            self.enter_block(location, server).await;
+        }
+    }
+
+    /// Lets the block an entity just walked into react to it, like pressure plates
+    pub async fn enter_block(&self, location: BlockPos, server: &Server) {
+        // Entities falling through the void or flying above the build limit are in no block
+        if !(self.min_y..self.max_y()).contains(&location.0.y) {
+            return;
         }
+        if let Ok(block) = self.get_block(&location).await {
+            server
+                .block_manager
+                .on_entity_inside(block, self, location, server)
+                .await;
+        }
+    }
+
+    pub fn activation_stats(&self) -> ActivationStats {
+        self.activation_stats.load()
+    }
+
+    /// Instantly removes all hostile mobs from the world, without any death animation
+    async fn remove_hostile_mobs(&self) {
+        let hostile: Vec<_> = self
+            .current_living_mobs
+            .lock()
+            .await
+            .iter()
+            .filter(|(_, mob)| mob.is_hostile())
+            .map(|(uuid, mob)| (*uuid, mob.living_entity.entity.entity_id))
+            .collect();
+        self.remove_mobs(hostile).await;
+    }
+
+    /// Instantly removes the mobs from the world, without any death animation
+    async fn remove_mobs(&self, mobs: Vec<(uuid::Uuid, EntityId)>) {
+        if mobs.is_empty() {
+            return;
+        }
+        let mut current_living_mobs = self.current_living_mobs.lock().await;
+        let mut entity_ids: Vec<VarInt> = Vec::with_capacity(mobs.len());
+        for (uuid, entity_id) in mobs {
+            current_living_mobs.remove(&uuid);
+            entity_ids.push(entity_id.into());
+        }
+        drop(current_living_mobs);
+        self.broadcast_packet_all(&CRemoveEntities::new(&entity_ids))
+            .await;
     }
 
     /// Gets the y position of the first non air block from the top down
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
-        for y in (-64..=319).rev() {
+        for y in (self.min_y..self.max_y()).rev() {
             let pos = BlockPos(Vector3::new(position.x, y, position.z));
             let block = self.get_block_state(&pos).await;
             if let Ok(block) = block {
@@ -258,7 +478,7 @@ impl World {
             }
             return y;
         }
-        319
+        self.max_y() - 1
     }
 
     #[expect(clippy::too_many_lines)]
@@ -285,12 +505,12 @@ impl World {
             .client
             .send_packet(&CLogin::new(
                 entity_id,
-                base_config.hardcore,
+                self.hardcore,
                 &dimensions,
                 base_config.max_players.into(),
                 base_config.view_distance.get().into(), //  TODO: view distance
                 base_config.simulation_distance.get().into(), // TODO: sim view dinstance
-                false,
+                base_config.reduced_debug_info,
                 true,
                 false,
                 (self.dimension_type as u8).into(),
@@ -306,18 +526,14 @@ impl World {
                 false,
             ))
             .await;
+        player
+            .client
+            .send_packet(&CChangeDifficulty::new(self.difficulty() as u8, false))
+            .await;
         // permissions, i. e. the commands a player may use
         player.send_permission_lvl_update().await;
         client_cmd_suggestions::send_c_commands_packet(&player, &server.command_dispatcher).await;
-        // teleport
-        let mut position = Vector3::new(10.0, 120.0, 10.0);
-        let yaw = 10.0;
-        let pitch = 10.0;
-
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
+        let (position, yaw, pitch) = self.join_position(&player, base_config.spawn_radius).await;
 
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
@@ -328,8 +544,9 @@ impl World {
         // first send info update to our new player, So he can see his Skin
         // also send his info to everyone else
         log::debug!("Broadcasting player info for {}", player.gameprofile.name);
+        let display_name = player.custom_display_name().await;
         self.broadcast_packet_all(&CPlayerInfoUpdate::new(
-            0x01 | 0x08,
+            0x01 | 0x04 | 0x08 | 0x20,
             &[pumpkin_protocol::client::play::Player {
                 uuid: gameprofile.id,
                 actions: vec![
@@ -337,7 +554,10 @@ impl World {
                         name: &gameprofile.name,
                         properties: &gameprofile.properties,
                     },
+                    // Clients use the gamemode to hide spectators from non spectators
+                    PlayerAction::UpdateGameMode((gamemode as i32).into()),
                     PlayerAction::UpdateListed(true),
+                    PlayerAction::UpdateDisplayName(display_name.as_ref()),
                 ],
             }],
         ))
@@ -348,10 +568,18 @@ impl World {
         let mut entries = Vec::new();
         {
             let current_players = self.current_players.lock().await;
+            let mut player_infos = Vec::new();
             for (_, playerr) in current_players
                 .iter()
                 .filter(|(c, _)| **c != player.gameprofile.id)
             {
+                player_infos.push((
+                    playerr,
+                    playerr.chat_session.lock().await.clone(),
+                    playerr.custom_display_name().await,
+                ));
+            }
+            for (playerr, chat_session, display_name) in &player_infos {
                 let gameprofile = &playerr.gameprofile;
                 entries.push(pumpkin_protocol::client::play::Player {
                     uuid: gameprofile.id,
@@ -360,14 +588,23 @@ impl World {
                             name: &gameprofile.name,
                             properties: &gameprofile.properties,
                         },
+                        // Needed to show their signed chat messages as secure
+                        PlayerAction::InitializeChat(
+                            chat_session.as_ref().map(ChatSession::to_protocol),
+                        ),
+                        PlayerAction::UpdateGameMode((playerr.gamemode.load() as i32).into()),
                         PlayerAction::UpdateListed(true),
+                        PlayerAction::UpdateDisplayName(display_name.as_ref()),
                     ],
                 });
             }
             log::debug!("Sending player info to {}", player.gameprofile.name);
             player
                 .client
-                .send_packet(&CPlayerInfoUpdate::new(0x01 | 0x08, &entries))
+                .send_packet(&CPlayerInfoUpdate::new(
+                    0x01 | 0x02 | 0x04 | 0x08 | 0x20,
+                    &entries,
+                ))
                 .await;
         };
 
@@ -426,7 +663,13 @@ impl World {
                     0.0,
                 ))
                 .await;
+            existing_player
+                .living_entity
+                .send_effects(&player.client)
+                .await;
         }
+        // The effects the player had when leaving
+        player.living_entity.broadcast_effects().await;
         // entity meta data
         // set skin parts
         if let Some(config) = player.client.config.lock().await.as_ref() {
@@ -453,9 +696,12 @@ impl World {
 
         // Sends initial time
         player.send_time(self).await;
+        player.send_weather(self).await;
+        player.send_experience().await;
 
         // Spawn in initial chunks
         player_chunker::player_join(&player).await;
+        player.start_spawn_grace().await;
 
         // if let Some(bossbars) = self..lock().await.get_player_bars(&player.gameprofile.id) {
         //     for bossbar in bossbars {
@@ -502,21 +748,31 @@ impl World {
         player.send_permission_lvl_update().await;
 
         // teleport
-        let mut position = Vector3::new(10.0, 120.0, 10.0);
         let yaw = 10.0;
         let pitch = 10.0;
-
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
+        let position = if let Some(position) = self.validate_respawn_point(player).await {
+            position
+        } else {
+            let top = self.get_top_block(WORLD_SPAWN).await;
+            Vector3::new(
+                f64::from(WORLD_SPAWN.x),
+                f64::from(top + 1),
+                f64::from(WORLD_SPAWN.z),
+            )
+        };
 
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
 
         player.living_entity.last_pos.store(position);
 
-        // TODO: difficulty, exp bar, status effect
+        player
+            .client
+            .send_packet(&CChangeDifficulty::new(self.difficulty() as u8, false))
+            .await;
+
+        // Respawning resets the effects on the client, players who died have none left
+        player.living_entity.send_effects(&player.client).await;
 
         self.worldborder
             .lock()
@@ -563,7 +819,17 @@ impl World {
         self.broadcast_packet_all(&entity_metadata_packet).await;
         // update commands
 
+        // The player may come from a world with a different time
+        player.send_time(self).await;
+        player.send_weather(self).await;
+        if alive || self.game_rules.lock().await.keep_inventory {
+            player.send_experience().await;
+        } else {
+            // Experience is lost when dying
+            player.set_experience(0, 0.0).await;
+        }
         player.set_health(20.0, 20, 20.0).await;
+        player.start_spawn_grace().await;
     }
 
     /// IMPORTANT: Chunks have to be non-empty
@@ -774,26 +1040,13 @@ impl World {
 
         let current_players = self.current_players.clone();
         tokio::spawn(async move {
-            let msg_comp = TextComponent::translate(
-                "multiplayer.player.joined",
-                [TextComponent::text(player.gameprofile.name.clone())].into(),
-            )
-            .color_named(NamedColor::Yellow);
-            let event = PlayerJoinEventImpl::new(player.clone(), msg_comp);
-
-            let event = PLUGIN_MANAGER
-                .lock()
-                .await
-                .fire::<PlayerJoinEventImpl>(event)
-                .await;
-
-            if !event.is_cancelled() {
-                let current_players = current_players.clone();
+            let join_message = fire_player_join(&*PLUGIN_MANAGER.lock().await, player).await;
+            if let Some(join_message) = join_message {
                 let players = current_players.lock().await;
                 for player in players.values() {
-                    player.send_system_message(event.get_join_message()).await;
+                    player.send_system_message(&join_message).await;
                 }
-                log::info!("{}", event.get_join_message().clone().to_pretty_console());
+                log::info!("{}", join_message.to_pretty_console());
             }
         });
     }
@@ -830,26 +1083,46 @@ impl World {
         .await;
         self.remove_entity(&player.living_entity.entity).await;
 
-        let msg_comp = TextComponent::translate(
-            "multiplayer.player.left",
-            [TextComponent::text(player.gameprofile.name.clone())].into(),
-        )
-        .color_named(NamedColor::Yellow);
-        let event = PlayerLeaveEventImpl::new(player.clone(), msg_comp);
+        let leave_message = fire_player_leave(&*PLUGIN_MANAGER.lock().await, player).await;
+        if let Some(leave_message) = leave_message {
+            let players = self.current_players.lock().await;
+            for player in players.values() {
+                player.send_system_message(&leave_message).await;
+            }
+            log::info!("{}", leave_message.to_pretty_console());
+        }
+    }
 
+    /// Creates a mob, adds it to the world and shows it to the players. Every spawn should go
+    /// through here so plugins see it in an [`EntitySpawnEvent`](crate::plugin::entity::EntitySpawnEvent).
+    /// Returns `None` if a plugin cancelled the spawn
+    pub async fn spawn_entity(
+        self: &Arc<Self>,
+        server: &Server,
+        entity_type: EntityType,
+        position: Vector3<f64>,
+        reason: SpawnReason,
+    ) -> Option<Arc<MobEntity>> {
         let event = PLUGIN_MANAGER
             .lock()
             .await
-            .fire::<PlayerLeaveEventImpl>(event)
+            .fire_cancellable(EntitySpawnEventImpl::new(entity_type, position, reason))
             .await;
+        if event.is_cancelled() {
+            return None;
+        }
 
-        if !event.is_cancelled() {
-            let players = self.current_players.lock().await;
-            for player in players.values() {
-                player.send_system_message(event.get_leave_message()).await;
-            }
-            log::info!("{}", event.get_leave_message().clone().to_pretty_console());
+        let (mob, uuid) = mob::from_type(entity_type, server, position, self).await;
+        let entity = &mob.living_entity.entity;
+        // Like in vanilla, mobs look in a random direction when they spawn
+        entity.set_rotation(wrap_degrees(self.rng().next_f32() * 360.0), 0.0);
+
+        self.broadcast_packet_all(&entity.create_spawn_packet(uuid))
+            .await;
+        if let Some(packet) = entity.create_custom_name_packet().await {
+            self.broadcast_packet_all(&packet).await;
         }
+        Some(mob)
     }
 
     /// Adds a living entity to the world.
@@ -896,21 +1169,195 @@ impl World {
 
         let chunk = self.receive_chunk(chunk_coordinate).await;
         let replaced_block_state_id = chunk.read().await.subchunks.get_block(relative).unwrap();
-        chunk
-            .write()
-            .await
-            .subchunks
-            .set_block(relative, block_state_id);
+        let mut chunk_data = chunk.write().await;
+        chunk_data.subchunks.set_block(relative, block_state_id);
+        chunk_data.dirty = true;
+        drop(chunk_data);
 
         self.broadcast_packet_all(&CBlockUpdate::new(
             position,
             i32::from(block_state_id).into(),
         ))
         .await;
+        self.schedule_fluid_ticks([position]).await;
 
         replaced_block_state_id
     }
 
+    /// Sets many blocks at once.
+    ///
+    /// Every affected chunk is only loaded and locked once, and clients receive a single
+    /// multi block update per changed chunk section instead of one packet per block.
+    /// Returns the replaced block state ids, in the same order as `changes`
+    pub async fn set_block_states(&self, changes: &[(BlockPos, u16)]) -> Vec<u16> {
+        let mut by_chunk: HashMap<Vector2<i32>, Vec<usize>> = HashMap::new();
+        for (index, (position, _)) in changes.iter().enumerate() {
+            let (chunk_coordinate, _) = position.chunk_and_chunk_relative_position();
+            by_chunk.entry(chunk_coordinate).or_default().push(index);
+        }
+
+        let mut replaced = vec![0; changes.len()];
+        for (chunk_coordinate, indices) in by_chunk {
+            let chunk = self.receive_chunk(chunk_coordinate).await;
+            let mut chunk_data = chunk.write().await;
+            for index in indices {
+                let (position, block_state_id) = &changes[index];
+                let (_, relative_coordinates) = position.chunk_and_chunk_relative_position();
+                let relative = ChunkRelativeBlockCoordinates::from(relative_coordinates);
+                replaced[index] = chunk_data.subchunks.get_block(relative).unwrap_or(0);
+                chunk_data.subchunks.set_block(relative, *block_state_id);
+            }
+            chunk_data.dirty = true;
+        }
+
+        for (section, blocks) in group_by_section(changes) {
+            if let [(position, block_state_id)] = blocks.as_slice() {
+                self.broadcast_packet_all(&CBlockUpdate::new(
+                    position,
+                    i32::from(*block_state_id).into(),
+                ))
+                .await;
+            } else {
+                self.broadcast_packet_all(&CSectionBlocksUpdate::new(section, &blocks))
+                    .await;
+            }
+        }
+        self.schedule_fluid_ticks(changes.iter().map(|(position, _)| position))
+            .await;
+
+        replaced
+    }
+
+    /// Walks along a ray block by block and returns the first non air block hit, if any within `max_distance`.
+    /// `direction` has to be normalized
+    pub async fn raycast_block(
+        &self,
+        start: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+    ) -> Option<BlockPos> {
+        self.raycast_block_face(start, direction, max_distance)
+            .await
+            .map(|(pos, _)| pos)
+    }
+
+    /// Like [`World::raycast_block`], but also returns the face of the block the ray entered through
+    pub async fn raycast_block_face(
+        &self,
+        start: Vector3<f64>,
+        direction: Vector3<f64>,
+        max_distance: f64,
+    ) -> Option<(BlockPos, BlockFace)> {
+        let mut block = Vector3::new(
+            start.x.floor() as i32,
+            start.y.floor() as i32,
+            start.z.floor() as i32,
+        );
+        let step = |d: f64| {
+            if d > 0.0 {
+                1
+            } else if d < 0.0 {
+                -1
+            } else {
+                0
+            }
+        };
+        // Distance along the ray to the first boundary crossing and between two crossings, per axis
+        let first_crossing = |origin: f64, block: i32, d: f64| {
+            if d > 0.0 {
+                (f64::from(block) + 1.0 - origin) / d
+            } else if d < 0.0 {
+                (origin - f64::from(block)) / -d
+            } else {
+                f64::INFINITY
+            }
+        };
+        // Dividing by zero results in infinity, so axes the ray is parallel to are never crossed
+        let crossing_distance = |d: f64| (1.0 / d).abs();
+
+        let step = Vector3::new(step(direction.x), step(direction.y), step(direction.z));
+        let delta = Vector3::new(
+            crossing_distance(direction.x),
+            crossing_distance(direction.y),
+            crossing_distance(direction.z),
+        );
+        let mut next = Vector3::new(
+            first_crossing(start.x, block.x, direction.x),
+            first_crossing(start.y, block.y, direction.y),
+            first_crossing(start.z, block.z, direction.z),
+        );
+
+        // The ray starting inside a block hits it from above
+        let mut face = BlockFace::Top;
+        loop {
+            if self.min_y <= block.y && block.y < self.max_y() {
+                if let Ok(state) = self.get_block_state(&BlockPos(block)).await {
+                    if !state.air {
+                        return Some((BlockPos(block), face));
+                    }
+                }
+            }
+
+            let distance = if next.x < next.y && next.x < next.z {
+                block.x += step.x;
+                next.x += delta.x;
+                face = if step.x > 0 {
+                    BlockFace::West
+                } else {
+                    BlockFace::East
+                };
+                next.x - delta.x
+            } else if next.y < next.z {
+                block.y += step.y;
+                next.y += delta.y;
+                face = if step.y > 0 {
+                    BlockFace::Bottom
+                } else {
+                    BlockFace::Top
+                };
+                next.y - delta.y
+            } else {
+                block.z += step.z;
+                next.z += delta.z;
+                face = if step.z > 0 {
+                    BlockFace::North
+                } else {
+                    BlockFace::South
+                };
+                next.z - delta.z
+            };
+            if distance > max_distance {
+                return None;
+            }
+        }
+    }
+
+    /// Finds the closest position in the column of `position` a player can stand at without suffocating,
+    /// searching at most `range` blocks up and down
+    pub async fn find_safe_position(&self, position: BlockPos, range: i32) -> Option<BlockPos> {
+        // One extra block on both ends for the floor and the head
+        let bottom = position.0.y - range - 1;
+        let mut solid = Vec::with_capacity(range as usize * 2 + 3);
+        for y in bottom..=position.0.y + range + 1 {
+            // Outside of the world there is nothing to collide with nor to stand on
+            let is_solid = if self.min_y <= y && y < self.max_y() {
+                let block = BlockPos(Vector3::new(position.0.x, y, position.0.z));
+                self.get_block_state(&block)
+                    .await
+                    .is_ok_and(|state| !state.collision_shapes.is_empty())
+            } else {
+                false
+            };
+            solid.push(is_solid);
+        }
+        let index = nearest_safe_index(&solid, range as usize + 1)?;
+        Some(BlockPos(Vector3::new(
+            position.0.x,
+            bottom + index as i32,
+            position.0.z,
+        )))
+    }
+
     // Stream the chunks (don't collect them and then do stuff with them)
     /// Important: must be called from an async function (or changed to accept a tokio runtime
     /// handle)
@@ -943,32 +1390,73 @@ impl World {
         chunk
     }
 
-    pub async fn break_block(&self, position: &BlockPos, cause: Option<Arc<Player>>) {
-        let block = self.get_block(position).await.unwrap();
-        let event = BlockBreakEventImpl::new(cause.clone(), block.clone(), 0, false);
-
-        let event = PLUGIN_MANAGER
-            .lock()
+    /// Breaks the block unless a plugin cancels the [`BlockBreakEvent`](crate::plugin::block::BlockBreakEvent).
+    /// Returns whether the block was broken
+    pub async fn break_block(&self, position: &BlockPos, cause: Option<Arc<Player>>) -> bool {
+        self.break_block_with(&PLUGIN_MANAGER, position, cause)
             .await
-            .fire::<BlockBreakEventImpl>(event)
-            .await;
+    }
 
-        if !event.is_cancelled() {
-            let broken_block_state_id = self.set_block_state(position, 0).await;
+    async fn break_block_with(
+        &self,
+        plugins: &Mutex<PluginManager>,
+        position: &BlockPos,
+        cause: Option<Arc<Player>>,
+    ) -> bool {
+        let Ok(block) = self.get_block(position).await else {
+            return false;
+        };
+        let event = BlockBreakEventImpl::new(cause.clone(), block.clone(), *position, 0, false);
+        // The plugins are only locked while firing the event, not while the world is changed
+        let cancelled = {
+            let plugins = plugins.lock().await;
+            plugins.fire_cancellable(event).await.is_cancelled()
+        };
+        if cancelled {
+            return false;
+        }
 
-            let particles_packet = CWorldEvent::new(
-                WorldEvent::BlockBroken as i32,
-                position,
-                broken_block_state_id.into(),
-                false,
-            );
+        let broken_block_state_id = self.set_block_state(position, 0).await;
 
-            match cause {
-                Some(player) => {
-                    self.broadcast_packet_except(&[player.gameprofile.id], &particles_packet)
-                        .await;
-                }
-                None => self.broadcast_packet_all(&particles_packet).await,
+        let particles_packet = CWorldEvent::new(
+            WorldEvent::BlockBroken as i32,
+            position,
+            broken_block_state_id.into(),
+            false,
+        );
+
+        match cause {
+            Some(player) => {
+                self.broadcast_packet_except(&[player.gameprofile.id], &particles_packet)
+                    .await;
+            }
+            None => self.broadcast_packet_all(&particles_packet).await,
+        }
+        true
+    }
+
+    /// Shows the break particles of many blocks at once, like after a `set_block_states` with the
+    /// replaced block state ids. Air was not broken, so it gets no particles
+    pub async fn broadcast_broken_blocks(&self, broken: &[(BlockPos, u16)]) {
+        let packets: Vec<_> = broken
+            .iter()
+            .filter(|(_, state_id)| get_state_by_state_id(*state_id).is_some_and(|s| !s.air))
+            .map(|(position, state_id)| {
+                CWorldEvent::new(
+                    WorldEvent::BlockBroken as i32,
+                    position,
+                    (*state_id).into(),
+                    false,
+                )
+            })
+            .collect();
+        if packets.is_empty() {
+            return;
+        }
+        let current_players = self.current_players.lock().await;
+        for player in current_players.values() {
+            for packet in &packets {
+                player.client.send_packet(packet).await;
             }
         }
     }
@@ -1019,3 +1507,261 @@ impl World {
         get_block_and_state_by_state_id(id).ok_or(GetBlockError::InvalidBlockId)
     }
 }
+
+/// Groups block changes by the chunk section they are in, keeping their order within a section
+fn group_by_section(changes: &[(BlockPos, u16)]) -> HashMap<Vector3<i32>, Vec<(BlockPos, u16)>> {
+    let mut sections: HashMap<Vector3<i32>, Vec<(BlockPos, u16)>> = HashMap::new();
+    for &(position, block_state_id) in changes {
+        let section = Vector3::new(
+            position.0.x.div_euclid(16),
+            position.0.y.div_euclid(16),
+            position.0.z.div_euclid(16),
+        );
+        sections
+            .entry(section)
+            .or_default()
+            .push((position, block_state_id));
+    }
+    sections
+}
+
+/// Returns the index closest to `start` with a solid block below and two passable blocks for the feet and head,
+/// preferring lower positions on a tie
+fn nearest_safe_index(solid: &[bool], start: usize) -> Option<usize> {
+    let is_safe = |index: usize| {
+        index > 0
+            && index + 1 < solid.len()
+            && solid[index - 1]
+            && !solid[index]
+            && !solid[index + 1]
+    };
+    (0..solid.len()).find_map(|offset| {
+        [start.checked_sub(offset), start.checked_add(offset)]
+            .into_iter()
+            .flatten()
+            .find(|&index| is_safe(index))
+    })
+}
+
+/// Mixes the dimension into the world seed, so every dimension has its own random values, and
+/// the time the level was last saved, so they do not repeat after every restart
+fn seeded_rng(seed: u64, dimension_type: DimensionType, last_played: i64) -> Xoroshiro {
+    let dimension = java_string_hash(&dimension_type.name().path).unsigned_abs();
+    Xoroshiro::from_seed(seed ^ u64::from(dimension) ^ last_played.unsigned_abs())
+}
+
+/// Fires a [`PlayerJoinEvent`](crate::plugin::player::PlayerJoinEvent) for a player that joined,
+/// returns the join message to broadcast unless a plugin cancelled it
+async fn fire_player_join(plugins: &PluginManager, player: Arc<Player>) -> Option<TextComponent> {
+    let join_message = TextComponent::translate(
+        "multiplayer.player.joined",
+        [TextComponent::text(player.gameprofile.name.clone())].into(),
+    )
+    .color_named(NamedColor::Yellow);
+    let event = plugins
+        .fire_cancellable(PlayerJoinEventImpl::new(player, join_message))
+        .await;
+    (!event.is_cancelled()).then(|| event.get_join_message().clone())
+}
+
+/// Fires a [`PlayerLeaveEvent`](crate::plugin::player::PlayerLeaveEvent) for a player that left,
+/// returns the leave message to broadcast unless a plugin cancelled it
+async fn fire_player_leave(plugins: &PluginManager, player: Arc<Player>) -> Option<TextComponent> {
+    let leave_message = TextComponent::translate(
+        "multiplayer.player.left",
+        [TextComponent::text(player.gameprofile.name.clone())].into(),
+    )
+    .color_named(NamedColor::Yellow);
+    let event = plugins
+        .fire_cancellable(PlayerLeaveEventImpl::new(player, leave_message))
+        .await;
+    (!event.is_cancelled()).then(|| event.get_leave_message().clone())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_util::random::RandomImpl;
+    use pumpkin_util::Difficulty;
+    use pumpkin_world::{block::block_registry::get_block, dimension::Dimension};
+    use tokio::sync::Mutex;
+
+    use super::{fire_player_join, group_by_section, nearest_safe_index, seeded_rng, World};
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::test_player;
+    use crate::plugin::block::r#break::BlockBreakEventImpl;
+    use crate::plugin::entity::SpawnReason;
+    use crate::plugin::player::{join::PlayerJoinEventImpl, PlayerEvent};
+    use crate::plugin::{CancellableEvent, EventHandler, EventPriority, PluginManager};
+    use crate::server::Server;
+
+    /// Remembers the uuids of the players that joined
+    #[derive(Clone, Default)]
+    struct JoinRecorder(Arc<Mutex<Vec<uuid::Uuid>>>);
+
+    #[async_trait]
+    impl EventHandler<PlayerJoinEventImpl> for JoinRecorder {
+        async fn handle(&self, event: &PlayerJoinEventImpl) {
+            self.0.lock().await.push(event.get_player().gameprofile.id);
+        }
+    }
+
+    #[tokio::test]
+    async fn join_handler_receives_player() {
+        let folder = TestFolder::new("join");
+        let uuid = uuid::Uuid::new_v4();
+        let player = test_player(folder.to_path_buf(), uuid).await;
+
+        let plugins = PluginManager::new();
+        let recorder = JoinRecorder::default();
+        plugins
+            .register(recorder.clone(), EventPriority::Normal, false)
+            .await;
+        assert!(fire_player_join(&plugins, player).await.is_some());
+        assert_eq!(*recorder.0.lock().await, [uuid]);
+    }
+
+    struct CancelBreaking;
+
+    #[async_trait]
+    impl EventHandler<BlockBreakEventImpl> for CancelBreaking {
+        async fn handle_blocking(&self, event: &mut BlockBreakEventImpl) {
+            event.set_cancelled(true);
+        }
+    }
+
+    #[tokio::test]
+    async fn peaceful_removes_hostile_mobs() {
+        let folder = TestFolder::new("peaceful");
+        let server = Server::with_world_folder(folder.join("server"));
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        world.set_difficulty(Difficulty::Normal).await;
+        let position = Vector3::new(0.5, 100.0, 0.5);
+        for entity_type in [EntityType::Zombie, EntityType::Pig] {
+            world
+                .spawn_entity(&server, entity_type, position, SpawnReason::Command)
+                .await
+                .unwrap();
+        }
+        assert_eq!(world.current_living_mobs.lock().await.len(), 2);
+
+        world.set_difficulty(Difficulty::Peaceful).await;
+        assert_eq!(world.difficulty(), Difficulty::Peaceful);
+        let mobs = world.current_living_mobs.lock().await;
+        let remaining: Vec<_> = mobs
+            .values()
+            .map(|mob| mob.living_entity.entity.entity_type)
+            .collect();
+        assert_eq!(remaining, [EntityType::Pig]);
+    }
+
+    #[tokio::test]
+    async fn cancelled_break_keeps_block() {
+        let folder = TestFolder::new("break");
+        let player = test_player(folder.to_path_buf(), uuid::Uuid::new_v4()).await;
+        let world = player.world().clone();
+        let position = BlockPos(Vector3::new(0, 100, 0));
+        let stone = get_block("minecraft:stone").unwrap().default_state_id;
+        world.set_block_state(&position, stone).await;
+
+        let plugins = PluginManager::new();
+        plugins
+            .register(CancelBreaking, EventPriority::Normal, true)
+            .await;
+        let plugins = Mutex::new(plugins);
+        assert!(
+            !world
+                .break_block_with(&plugins, &position, Some(player.clone()))
+                .await
+        );
+        assert_eq!(world.get_block_state_id(&position).await.unwrap(), stone);
+
+        // Without the plugin the block is broken
+        let plugins = Mutex::new(PluginManager::new());
+        assert!(
+            world
+                .break_block_with(&plugins, &position, Some(player))
+                .await
+        );
+        assert_eq!(world.get_block_state_id(&position).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn entering_blocks_outside_of_the_world() {
+        let folder = TestFolder::new("enter-block");
+        let server = Server::with_world_folder(folder.join("server"));
+        let player = test_player(folder.join("world"), uuid::Uuid::new_v4()).await;
+        let world = player.world();
+        // Falling through the void and flying above the build limit
+        for y in [
+            world.min_y - 1,
+            world.min_y - 100,
+            world.max_y(),
+            world.max_y() + 100,
+        ] {
+            world
+                .enter_block(BlockPos(Vector3::new(0, y, 0)), &server)
+                .await;
+        }
+    }
+
+    #[test]
+    fn rng_differs_per_dimension_and_load() {
+        let sequence = |dimension_type, last_played| {
+            let mut rng = seeded_rng(1234, dimension_type, last_played);
+            (0..16).map(|_| rng.next_i64()).collect::<Vec<_>>()
+        };
+        let overworld = sequence(DimensionType::Overworld, 1000);
+        assert_ne!(overworld, sequence(DimensionType::TheNether, 1000));
+        assert_ne!(overworld, sequence(DimensionType::TheEnd, 1000));
+        // A restart after the level was saved again continues differently
+        assert_ne!(overworld, sequence(DimensionType::Overworld, 2000));
+    }
+
+    #[test]
+    fn coalesces_changes_per_section() {
+        let mut changes = Vec::new();
+        for x in 0..16 {
+            for y in -16..16 {
+                changes.push((BlockPos(Vector3::new(x, y, -1)), 1));
+            }
+        }
+        changes.push((BlockPos(Vector3::new(16, 0, 0)), 2));
+
+        let sections = group_by_section(&changes);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[&Vector3::new(0, -1, -1)].len(), 256);
+        assert_eq!(sections[&Vector3::new(0, 0, -1)].len(), 256);
+        assert!(sections[&Vector3::new(1, 0, 0)] == [(BlockPos(Vector3::new(16, 0, 0)), 2)]);
+    }
+
+    #[test]
+    fn safe_position_in_open_air_falls_to_ground() {
+        // ground at index 1, the target is 3 blocks above where a player can stand
+        let solid = [true, true, false, false, false, false, false];
+        assert_eq!(nearest_safe_index(&solid, 5), Some(2));
+        assert_eq!(nearest_safe_index(&solid, 2), Some(2));
+    }
+
+    #[test]
+    fn safe_position_in_wall_moves_out() {
+        // two blocks of air on top of a wall
+        let solid = [true, true, true, true, false, false];
+        assert_eq!(nearest_safe_index(&solid, 2), Some(4));
+        // the whole column is solid
+        assert_eq!(nearest_safe_index(&[true; 7], 3), None);
+        // a one block gap is not enough to stand in
+        assert_eq!(
+            nearest_safe_index(&[true, true, false, true, true], 2),
+            None
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use std::{collections::HashSet, sync::Arc};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    random::RandomImpl,
+    Difficulty,
+};
+
+use pumpkin_world::{
+    block::block_registry::get_block_and_state_by_state_id,
+    coordinates::ChunkRelativeBlockCoordinates,
+};
+
+use crate::{plugin::entity::SpawnReason, server::Server};
+
+use super::World;
+
+/// Chunks within this distance of a player are spawned in, making up a 17x17 area around them
+const SPAWN_CHUNK_RADIUS: i32 = 8;
+/// Mobs never spawn closer than this to a player
+const MIN_PLAYER_DISTANCE: f64 = 24.0;
+/// Monsters spawn on the surface between these times of the day
+const NIGHT: std::ops::Range<i64> = 13_000..23_000;
+/// Each category tries at most this many positions per tick, in random chunks around players
+const SPAWN_ATTEMPTS_PER_TICK: usize = 32;
+
+const MONSTERS: [EntityType; 4] = [
+    EntityType::Zombie,
+    EntityType::Skeleton,
+    EntityType::Spider,
+    EntityType::Creeper,
+];
+const CREATURES: [EntityType; 4] = [
+    EntityType::Pig,
+    EntityType::Cow,
+    EntityType::Sheep,
+    EntityType::Chicken,
+];
+
+/// Mobs are spawned and capped separately per category
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SpawnCategory {
+    Monster,
+    Creature,
+}
+
+impl SpawnCategory {
+    /// How many mobs of this category may exist around a single player
+    fn base_cap(self) -> usize {
+        let config = &ADVANCED_CONFIG.mob_spawning;
+        match self {
+            Self::Monster => config.monsters,
+            Self::Creature => config.creatures,
+        }
+    }
+
+    /// Only every this many ticks this category tries to spawn
+    fn spawn_interval(self) -> i64 {
+        match self {
+            Self::Monster => 1,
+            Self::Creature => ADVANCED_CONFIG.mob_spawning.creature_spawn_interval.max(1) as i64,
+        }
+    }
+
+    const fn entity_types(self) -> &'static [EntityType] {
+        match self {
+            Self::Monster => &MONSTERS,
+            Self::Creature => &CREATURES,
+        }
+    }
+}
+
+/// What is known about a position a mob may spawn at
+struct SpawnPosition<'a> {
+    /// The block the mob would stand on can be stood on
+    solid_floor: bool,
+    /// Name of the block the mob would stand on
+    floor: &'a str,
+    /// Both blocks the mob would occupy are air
+    free: bool,
+    /// There is no block above the position
+    sky_exposed: bool,
+}
+
+impl World {
+    /// Tries to spawn monsters and passive mobs in the chunks around players, until the mob cap is reached
+    pub async fn tick_spawning(self: &Arc<Self>, server: &Server) {
+        if !self.game_rules.lock().await.do_mob_spawning {
+            return;
+        }
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| !player.is_spectator())
+            .map(|player| {
+                let entity = &player.living_entity.entity;
+                (entity.pos.load(), entity.chunk_pos.load())
+            })
+            .collect();
+        if players.is_empty() {
+            return;
+        }
+        let chunks = self.spawnable_chunks(players.iter().map(|(_, chunk)| *chunk));
+        let players: Vec<_> = players.into_iter().map(|(pos, _)| pos).collect();
+
+        let (monsters, creatures) = {
+            let mobs = self.current_living_mobs.lock().await;
+            let monsters = mobs.values().filter(|mob| mob.is_hostile()).count();
+            (monsters, mobs.len() - monsters)
+        };
+        let (world_age, is_night) = {
+            let level_time = self.level_time.lock().await;
+            (
+                level_time.world_age,
+                NIGHT.contains(&level_time.query_daytime()),
+            )
+        };
+
+        for (category, mut count) in [
+            (SpawnCategory::Monster, monsters),
+            (SpawnCategory::Creature, creatures),
+        ] {
+            if category == SpawnCategory::Monster && self.difficulty() == Difficulty::Peaceful {
+                continue;
+            }
+            if world_age % category.spawn_interval() != 0 {
+                continue;
+            }
+            let cap = mob_cap(category.base_cap(), chunks.len());
+            if count >= cap {
+                continue;
+            }
+            // Random positions in random chunks, picked up front as the rng can not be held across awaits
+            let candidates: Vec<_> = {
+                let mut rng = self.rng();
+                let entity_types = category.entity_types();
+                (0..chunks.len().min(SPAWN_ATTEMPTS_PER_TICK))
+                    .map(|_| {
+                        let chunk = chunks[rng.next_bounded_i32(chunks.len() as i32) as usize];
+                        let pos = BlockPos(Vector3::new(
+                            chunk.x * 16 + rng.next_bounded_i32(16),
+                            rng.next_inbetween_i32_exclusive(self.min_y + 1, self.max_y() - 1),
+                            chunk.z * 16 + rng.next_bounded_i32(16),
+                        ));
+                        let index = rng.next_bounded_i32(entity_types.len() as i32) as usize;
+                        (pos, entity_types[index])
+                    })
+                    .collect()
+            };
+            for (pos, entity_type) in candidates {
+                if count >= cap {
+                    break;
+                }
+                if self
+                    .try_spawn(server, category, entity_type, pos, &players, is_night)
+                    .await
+                {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    /// Spawns the mob at the position if it is allowed to spawn there
+    async fn try_spawn(
+        self: &Arc<Self>,
+        server: &Server,
+        category: SpawnCategory,
+        entity_type: EntityType,
+        pos: BlockPos,
+        players: &[Vector3<f64>],
+        is_night: bool,
+    ) -> bool {
+        let spawn_pos = Vector3::new(
+            f64::from(pos.0.x) + 0.5,
+            f64::from(pos.0.y),
+            f64::from(pos.0.z) + 0.5,
+        );
+        if players.iter().any(|player| {
+            player.squared_distance_to_vec(spawn_pos) < MIN_PLAYER_DISTANCE * MIN_PLAYER_DISTANCE
+        }) {
+            return false;
+        }
+        let Some(position) = self.spawn_position(pos).await else {
+            return false;
+        };
+        if !can_spawn(category, &position, is_night) {
+            return false;
+        }
+        if self.entity_limit_reached(spawn_pos).await.is_some() {
+            return false;
+        }
+        self.spawn_entity(server, entity_type, spawn_pos, SpawnReason::Natural)
+            .await
+            .is_some()
+    }
+
+    /// All watched chunks close enough to a player to spawn mobs in
+    fn spawnable_chunks(&self, players: impl Iterator<Item = Vector2<i32>>) -> Vec<Vector2<i32>> {
+        let mut chunks = HashSet::new();
+        for player in players {
+            for x in -SPAWN_CHUNK_RADIUS..=SPAWN_CHUNK_RADIUS {
+                for z in -SPAWN_CHUNK_RADIUS..=SPAWN_CHUNK_RADIUS {
+                    let chunk = Vector2::new(player.x + x, player.z + z);
+                    if self.level.is_chunk_watched(&chunk) {
+                        chunks.insert(chunk);
+                    }
+                }
+            }
+        }
+        chunks.into_iter().collect()
+    }
+
+    /// Looks at the column of the position, its chunk is only read once for all of the blocks
+    async fn spawn_position(&self, pos: BlockPos) -> Option<SpawnPosition<'static>> {
+        // The floor and the head have to be inside of the world
+        if pos.0.y <= self.min_y || pos.0.y + 1 >= self.max_y() {
+            return None;
+        }
+        let (chunk_pos, relative) = pos.chunk_and_chunk_relative_position();
+        let chunk = match self.level.get_loaded_chunk(&chunk_pos) {
+            Some(chunk) => chunk,
+            None => self.receive_chunk(chunk_pos).await,
+        };
+        let chunk = chunk.read().await;
+        let block_at = |y: i32| {
+            let relative = Vector3::new(relative.x, y, relative.z);
+            let id = chunk
+                .subchunks
+                .get_block(ChunkRelativeBlockCoordinates::from(relative))?;
+            get_block_and_state_by_state_id(id)
+        };
+        let is_air = |y: i32| block_at(y).is_some_and(|(_, state)| state.air);
+
+        let free = is_air(pos.0.y) && is_air(pos.0.y + 1);
+        if !free {
+            return None;
+        }
+        let (floor, floor_state) = block_at(pos.0.y - 1)?;
+        let sky_exposed = (pos.0.y + 2..self.max_y()).all(is_air);
+        Some(SpawnPosition {
+            solid_floor: !floor_state.collision_shapes.is_empty(),
+            floor: &floor.name,
+            free,
+            sky_exposed,
+        })
+    }
+}
+
+/// The mob cap grows with the amount of chunks around players, `base_cap` applies to the chunks around a single player
+fn mob_cap(base_cap: usize, chunks: usize) -> usize {
+    const CHUNKS_PER_PLAYER: usize = (SPAWN_CHUNK_RADIUS as usize * 2 + 1).pow(2);
+    base_cap * chunks / CHUNKS_PER_PLAYER
+}
+
+/// Monsters spawn in the dark, which is underground or at night.
+/// Passive mobs spawn on grass under the open sky
+fn can_spawn(category: SpawnCategory, position: &SpawnPosition, is_night: bool) -> bool {
+    if !position.free || !position.solid_floor {
+        return false;
+    }
+    match category {
+        SpawnCategory::Monster => !position.sky_exposed || is_night,
+        SpawnCategory::Creature => position.sky_exposed && position.floor == "grass_block",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::{block::block_registry::get_block, dimension::Dimension};
+
+    use super::{can_spawn, mob_cap, SpawnCategory, SpawnPosition};
+    use crate::data::test_folder::TestFolder;
+    use crate::{server::Server, world::World};
+
+    fn surface(floor: &str) -> SpawnPosition<'_> {
+        SpawnPosition {
+            solid_floor: true,
+            floor,
+            free: true,
+            sky_exposed: true,
+        }
+    }
+
+    #[test]
+    fn mob_cap_scales_with_chunks() {
+        assert_eq!(mob_cap(70, 289), 70);
+        // two players far apart
+        assert_eq!(mob_cap(70, 578), 140);
+        assert_eq!(mob_cap(70, 0), 0);
+    }
+
+    #[test]
+    fn monsters_spawn_in_the_dark() {
+        assert!(!can_spawn(SpawnCategory::Monster, &surface("stone"), false));
+        assert!(can_spawn(SpawnCategory::Monster, &surface("stone"), true));
+        let cave = SpawnPosition {
+            sky_exposed: false,
+            ..surface("stone")
+        };
+        assert!(can_spawn(SpawnCategory::Monster, &cave, false));
+    }
+
+    #[test]
+    fn creatures_spawn_on_grass() {
+        assert!(can_spawn(
+            SpawnCategory::Creature,
+            &surface("grass_block"),
+            false
+        ));
+        assert!(!can_spawn(
+            SpawnCategory::Creature,
+            &surface("stone"),
+            false
+        ));
+        let floating = SpawnPosition {
+            solid_floor: false,
+            ..surface("grass_block")
+        };
+        assert!(!can_spawn(SpawnCategory::Creature, &floating, true));
+    }
+
+    #[tokio::test]
+    async fn spawns_on_free_positions() {
+        let folder = TestFolder::new("natural-spawning");
+        let server = Server::with_world_folder(folder.join("server"));
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        let floor = BlockPos(Vector3::new(8, 250, 8));
+        let grass = get_block("grass_block").unwrap().default_state_id;
+        world.set_block_state(&floor, grass).await;
+        let pos = BlockPos(floor.0 + Vector3::new(0, 1, 0));
+        let zombie = (SpawnCategory::Monster, EntityType::Zombie);
+        let pig = (SpawnCategory::Creature, EntityType::Pig);
+        let player = Vector3::new(8.5, 251.0, 20.5);
+
+        // Monsters only spawn on the surface at night
+        for ((category, entity_type), players, is_night, spawned) in [
+            (zombie, vec![], false, false),
+            (pig, vec![], false, true),
+            // Too close to a player
+            (zombie, vec![player], true, false),
+            (zombie, vec![], true, true),
+        ] {
+            assert_eq!(
+                world
+                    .try_spawn(&server, category, entity_type, pos, &players, is_night)
+                    .await,
+                spawned
+            );
+        }
+        assert_eq!(world.current_living_mobs.lock().await.len(), 2);
+
+        // There is no room for the head
+        let stone = get_block("stone").unwrap().default_state_id;
+        world
+            .set_block_state(&BlockPos(pos.0 + Vector3::new(0, 1, 0)), stone)
+            .await;
+        let (category, entity_type) = zombie;
+        assert!(
+            !world
+                .try_spawn(&server, category, entity_type, pos, &[], true)
+                .await
+        );
+    }
+}
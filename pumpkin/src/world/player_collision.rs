@@ -0,0 +1,145 @@
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_protocol::{client::play::CEntityVelocity, codec::var_int::VarInt};
+use pumpkin_util::math::{boundingbox::BoundingBox, vector3::Vector3};
+
+use super::World;
+
+impl World {
+    /// Pushes players whose bounding boxes overlap away from each other
+    // TODO: Respect the collision rule of teams once they exist
+    pub(super) async fn push_colliding_players(&self) {
+        let config = &ADVANCED_CONFIG.pvp;
+        if !config.player_collision {
+            return;
+        }
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| !player.is_spectator())
+            .cloned()
+            .collect();
+        let boxes: Vec<_> = players
+            .iter()
+            .map(|player| {
+                let entity = &player.living_entity.entity;
+                let pos = entity.pos.load();
+                let bounding_box = BoundingBox::new_from_pos(
+                    pos.x,
+                    pos.y,
+                    pos.z,
+                    &entity.bounding_box_size.load(),
+                );
+                (pos, bounding_box)
+            })
+            .collect();
+
+        let mut pushes: Vec<Option<Vector3<f64>>> = vec![None; players.len()];
+        for a in 0..players.len() {
+            for b in a + 1..players.len() {
+                let (a_pos, a_box) = boxes[a];
+                let (b_pos, b_box) = boxes[b];
+                if let Some(push) = separating_push(a_pos, &a_box, b_pos, &b_box) {
+                    let a_push = pushes[a].get_or_insert(Vector3::new(0.0, 0.0, 0.0));
+                    *a_push = a_push.sub(&push);
+                    *pushes[b].get_or_insert(Vector3::new(0.0, 0.0, 0.0)) += push;
+                }
+            }
+        }
+
+        for (player, push) in players.iter().zip(pushes) {
+            let Some(push) = push else {
+                continue;
+            };
+            let push = cap_push(push, config.max_collision_push);
+            let entity_id = VarInt(player.entity_id());
+            player
+                .client
+                .send_packet(&CEntityVelocity::new(&entity_id, push.x, 0.0, push.z))
+                .await;
+        }
+    }
+}
+
+/// The horizontal push applied to `b` when its bounding box overlaps with the one of `a`, `a` is pushed the opposite way.
+/// Works like vanilla's entity pushing
+fn separating_push(
+    a_pos: Vector3<f64>,
+    a_box: &BoundingBox,
+    b_pos: Vector3<f64>,
+    b_box: &BoundingBox,
+) -> Option<Vector3<f64>> {
+    if !a_box.intersects(b_box) {
+        return None;
+    }
+    let mut dx = b_pos.x - a_pos.x;
+    let mut dz = b_pos.z - a_pos.z;
+    let distance = dx.abs().max(dz.abs());
+    // Entities at the exact same spot have no direction to be pushed in
+    if distance < 0.01 {
+        return None;
+    }
+    let distance = distance.sqrt();
+    let strength = (1.0 / distance).min(1.0) * 0.05 / distance;
+    dx *= strength;
+    dz *= strength;
+    Some(Vector3::new(dx, 0.0, dz))
+}
+
+fn cap_push(push: Vector3<f64>, max: f64) -> Vector3<f64> {
+    let length = push.x.hypot(push.z);
+    if length > max {
+        push * (max / length)
+    } else {
+        push
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        vector3::Vector3,
+    };
+
+    use super::{cap_push, separating_push};
+
+    fn player_box(pos: Vector3<f64>) -> BoundingBox {
+        BoundingBox::new_from_pos(
+            pos.x,
+            pos.y,
+            pos.z,
+            &BoundingBoxSize {
+                width: 0.6,
+                height: 1.8,
+            },
+        )
+    }
+
+    #[test]
+    fn overlapping_boxes_push_apart() {
+        let a = Vector3::new(0.0, 64.0, 0.0);
+        let b = Vector3::new(0.3, 64.0, 0.0);
+        let push = separating_push(a, &player_box(a), b, &player_box(b)).unwrap();
+        // b is pushed away from a, along the axis they are apart on
+        assert!(push.x > 0.0);
+        assert!(push.z.abs() < f64::EPSILON);
+        // and a is pushed the other way by the same amount
+        let opposite = separating_push(b, &player_box(b), a, &player_box(a)).unwrap();
+        assert!((opposite.x + push.x).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn separate_boxes_do_not_push() {
+        let a = Vector3::new(0.0, 64.0, 0.0);
+        let b = Vector3::new(2.0, 64.0, 0.0);
+        assert!(separating_push(a, &player_box(a), b, &player_box(b)).is_none());
+    }
+
+    #[test]
+    fn push_is_capped() {
+        let push = cap_push(Vector3::new(3.0, 0.0, 4.0), 0.5);
+        assert!((push.x.hypot(push.z) - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use pumpkin_util::{
+    math::{position::BlockPos, vector3::Vector3},
+    text::TextComponent,
+};
+
+use crate::entity::player::Player;
+
+use super::World;
+
+/// Positions next to and on top of the respawn block a player can respawn at, in the order they are tried
+const STANDING_OFFSETS: [(i32, i32, i32); 9] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+    (-1, 0, -1),
+    (-1, 0, 1),
+    (1, 0, -1),
+    (1, 0, 1),
+    (0, 1, 0),
+];
+
+impl World {
+    /// Returns where the player respawns at their bed or respawn anchor.
+    /// If it was destroyed or is obstructed, the respawn point is cleared and the player is notified
+    pub async fn validate_respawn_point(&self, player: &Player) -> Option<Vector3<f64>> {
+        let respawn_point = player.respawn_point.load()?;
+        if let Some(position) = self.find_respawn_position(respawn_point).await {
+            return Some(position);
+        }
+        player.respawn_point.store(None);
+        player
+            .send_system_message(&TextComponent::translate(
+                "block.minecraft.spawn.not_valid",
+                [].into(),
+            ))
+            .await;
+        None
+    }
+
+    async fn find_respawn_position(&self, respawn_point: BlockPos) -> Option<Vector3<f64>> {
+        let (block, state) = self.get_block_and_block_state(&respawn_point).await.ok()?;
+        if !is_respawn_block(&block.name, state.id, block.default_state_id) {
+            return None;
+        }
+
+        let mut solid = HashSet::new();
+        for x in -1..=1 {
+            for y in -1..=2 {
+                for z in -1..=1 {
+                    let offset = Vector3::new(x, y, z);
+                    let position = respawn_point.0 + offset;
+                    if position.y < self.min_y || position.y >= self.max_y() {
+                        continue;
+                    }
+                    if self
+                        .get_block_state(&BlockPos(position))
+                        .await
+                        .is_ok_and(|state| !state.collision_shapes.is_empty())
+                    {
+                        solid.insert(offset);
+                    }
+                }
+            }
+        }
+
+        let feet = respawn_point.0 + find_standing_offset(|offset| solid.contains(&offset))?;
+        Some(Vector3::new(
+            f64::from(feet.x) + 0.5,
+            f64::from(feet.y),
+            f64::from(feet.z) + 0.5,
+        ))
+    }
+}
+
+/// Beds always work, respawn anchors only when they are charged, which is any state but the default one
+fn is_respawn_block(name: &str, state_id: u16, default_state_id: u16) -> bool {
+    name.ends_with("_bed") || (name == "respawn_anchor" && state_id != default_state_id)
+}
+
+/// Finds the first offset with a solid block below and room for the feet and head
+fn find_standing_offset(is_solid: impl Fn(Vector3<i32>) -> bool) -> Option<Vector3<i32>> {
+    STANDING_OFFSETS
+        .iter()
+        .map(|&(x, y, z)| Vector3::new(x, y, z))
+        .find(|&offset| {
+            is_solid(offset + Vector3::new(0, -1, 0))
+                && !is_solid(offset)
+                && !is_solid(offset + Vector3::new(0, 1, 0))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{find_standing_offset, is_respawn_block};
+
+    /// A flat floor below the respawn block
+    fn floor() -> HashSet<Vector3<i32>> {
+        let mut solid = HashSet::new();
+        for x in -1..=1 {
+            for z in -1..=1 {
+                solid.insert(Vector3::new(x, -1, z));
+            }
+        }
+        solid
+    }
+
+    #[test]
+    fn destroyed_respawn_block() {
+        assert!(is_respawn_block("red_bed", 1700, 1688));
+        assert!(!is_respawn_block("air", 0, 0));
+        assert!(!is_respawn_block("stone", 1, 1));
+        // an uncharged anchor does not work either
+        assert!(!is_respawn_block("respawn_anchor", 19000, 19000));
+        assert!(is_respawn_block("respawn_anchor", 19001, 19000));
+    }
+
+    #[test]
+    fn unobstructed_respawn_block() {
+        let mut solid = floor();
+        // the bed itself
+        solid.insert(Vector3::new(0, 0, 0));
+        let offset = find_standing_offset(|offset| solid.contains(&offset));
+        assert_eq!(offset, Some(Vector3::new(-1, 0, 0)));
+    }
+
+    #[test]
+    fn obstructed_respawn_block() {
+        let mut solid = floor();
+        // walled in on every side and covered
+        for x in -1..=1 {
+            for y in 0..=2 {
+                for z in -1..=1 {
+                    solid.insert(Vector3::new(x, y, z));
+                }
+            }
+        }
+        assert_eq!(find_standing_offset(|offset| solid.contains(&offset)), None);
+
+        // a free spot on top of the bed is still valid
+        solid.remove(&Vector3::new(0, 1, 0));
+        solid.remove(&Vector3::new(0, 2, 0));
+        assert_eq!(
+            find_standing_offset(|offset| solid.contains(&offset)),
+            Some(Vector3::new(0, 1, 0))
+        );
+    }
+}
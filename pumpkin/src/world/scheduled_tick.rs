@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, HashSet};
+
+use pumpkin_util::math::position::BlockPos;
+
+use crate::{
+    plugin::{block::tick::BlockTickEventImpl, CancellableEvent},
+    server::Server,
+    PLUGIN_MANAGER,
+};
+
+use super::World;
+
+/// Ticks due in the same game tick run in this order, earlier scheduled ticks first within a priority
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum TickPriority {
+    ExtremelyHigh,
+    VeryHigh,
+    High,
+    #[default]
+    Normal,
+    Low,
+    VeryLow,
+    ExtremelyLow,
+}
+
+/// Blocks waiting for a delayed update, like flowing fluids
+#[derive(Default)]
+pub struct ScheduledTicks {
+    /// How many times the queue was advanced, delays are relative to this
+    current_tick: u64,
+    /// Counts up with every scheduled tick, keeping ticks of the same priority in order
+    scheduled_count: u64,
+    due: BTreeMap<(u64, TickPriority, u64), BlockPos>,
+    scheduled: HashSet<BlockPos>,
+}
+
+impl ScheduledTicks {
+    /// Schedules an update of the block in `delay` ticks.
+    /// Returns false if the block already has an update scheduled, which is kept
+    pub fn schedule(&mut self, position: BlockPos, delay: u32, priority: TickPriority) -> bool {
+        if !self.scheduled.insert(position) {
+            return false;
+        }
+        let due_tick = self.current_tick + u64::from(delay.max(1));
+        self.due
+            .insert((due_tick, priority, self.scheduled_count), position);
+        self.scheduled_count += 1;
+        true
+    }
+
+    /// Advances the queue by a tick and returns the blocks that are due, in the order they should run
+    pub fn advance(&mut self) -> Vec<BlockPos> {
+        self.current_tick += 1;
+        let mut positions = Vec::new();
+        while let Some(entry) = self.due.first_entry() {
+            if entry.key().0 > self.current_tick {
+                break;
+            }
+            let position = entry.remove();
+            self.scheduled.remove(&position);
+            positions.push(position);
+        }
+        positions
+    }
+
+    #[must_use]
+    pub fn is_scheduled(&self, position: &BlockPos) -> bool {
+        self.scheduled.contains(position)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.scheduled.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.scheduled.is_empty()
+    }
+}
+
+impl World {
+    /// Schedules a tick of the block at `position` in `delay` ticks, which is passed to its
+    /// [`crate::block::pumpkin_block::PumpkinBlock`]. Returns false if the block already has a tick scheduled
+    pub async fn schedule_block_tick(
+        &self,
+        position: &BlockPos,
+        delay: u32,
+        priority: TickPriority,
+    ) -> bool {
+        self.block_ticks
+            .lock()
+            .await
+            .schedule(*position, delay, priority)
+    }
+
+    /// Runs the block ticks that are due, plugins can cancel them
+    pub async fn tick_scheduled_blocks(&self, server: &Server) {
+        let due = self.block_ticks.lock().await.advance();
+        for position in due {
+            let Ok(block) = self.get_block(&position).await else {
+                continue;
+            };
+            let event = PLUGIN_MANAGER
+                .lock()
+                .await
+                .fire_cancellable(BlockTickEventImpl::new(block.clone(), position))
+                .await;
+            if event.is_cancelled() {
+                continue;
+            }
+            server
+                .block_manager
+                .on_scheduled_tick(block, self, position, server)
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::{ScheduledTicks, TickPriority};
+
+    fn pos(x: i32) -> BlockPos {
+        BlockPos(Vector3::new(x, 64, 0))
+    }
+
+    #[test]
+    fn ticks_are_due_after_their_delay() {
+        let mut ticks = ScheduledTicks::default();
+        assert!(ticks.schedule(pos(0), 2, TickPriority::Normal));
+        assert!(ticks.schedule(pos(1), 1, TickPriority::Normal));
+        assert!(ticks.schedule(pos(2), 2, TickPriority::Normal));
+        assert_eq!(ticks.advance(), vec![pos(1)]);
+        assert_eq!(ticks.advance(), vec![pos(0), pos(2)]);
+        assert!(ticks.advance().is_empty());
+        assert!(ticks.is_empty());
+    }
+
+    #[test]
+    fn ticks_are_ordered_by_priority() {
+        let mut ticks = ScheduledTicks::default();
+        ticks.schedule(pos(0), 1, TickPriority::Low);
+        ticks.schedule(pos(1), 1, TickPriority::Normal);
+        ticks.schedule(pos(2), 1, TickPriority::ExtremelyHigh);
+        ticks.schedule(pos(3), 1, TickPriority::Normal);
+        // a higher priority does not make a later tick run earlier
+        ticks.schedule(pos(4), 2, TickPriority::ExtremelyHigh);
+        assert_eq!(ticks.advance(), vec![pos(2), pos(1), pos(3), pos(0)]);
+        assert_eq!(ticks.advance(), vec![pos(4)]);
+    }
+
+    #[test]
+    fn duplicate_ticks_are_ignored() {
+        let mut ticks = ScheduledTicks::default();
+        assert!(ticks.schedule(pos(0), 1, TickPriority::Normal));
+        assert!(!ticks.schedule(pos(0), 3, TickPriority::High));
+        assert!(ticks.is_scheduled(&pos(0)));
+        assert_eq!(ticks.len(), 1);
+        assert_eq!(ticks.advance(), vec![pos(0)]);
+        // once it ran, the block can be scheduled again
+        assert!(ticks.schedule(pos(0), 1, TickPriority::Normal));
+    }
+}
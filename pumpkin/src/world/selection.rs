@@ -0,0 +1,310 @@
+use std::fmt;
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    PermissionLvl,
+};
+use pumpkin_world::coordinates::ChunkRelativeBlockCoordinates;
+
+use super::World;
+
+/// Holding this item, builders set the first corner of their selection with a left click and the second with a right click
+pub const SELECTION_WAND: &str = "wooden_axe";
+
+/// The permission level needed for the selection wand and the commands editing selections
+pub const SELECTION_PERMISSION_LVL: PermissionLvl = PermissionLvl::Two;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Corner {
+    First,
+    Second,
+}
+
+/// The two corners of a player's selection, either may not be set yet
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Selection {
+    /// The world the corners were set in, the selection can only be edited there
+    pub world: Option<DimensionType>,
+    pub first: Option<BlockPos>,
+    pub second: Option<BlockPos>,
+}
+
+impl Selection {
+    /// Sets a corner in the given world, a corner set in another world before is dropped
+    #[must_use]
+    pub fn with_corner(self, world: DimensionType, corner: Corner, position: BlockPos) -> Self {
+        let selection = if self.world == Some(world) {
+            self
+        } else {
+            Self {
+                world: Some(world),
+                ..Self::default()
+            }
+        };
+        match corner {
+            Corner::First => Self {
+                first: Some(position),
+                ..selection
+            },
+            Corner::Second => Self {
+                second: Some(position),
+                ..selection
+            },
+        }
+    }
+
+    /// The selected region, once both corners are set
+    #[must_use]
+    pub fn region(&self) -> Option<Region> {
+        Some(Region::new(self.first?, self.second?))
+    }
+
+    /// The selected region, if it is complete, in the given world, inside of its height and not larger
+    /// than the configured maximum
+    pub fn editable_region(&self, world: DimensionType) -> Result<Region, SelectionError> {
+        let region = self.region().ok_or(SelectionError::Incomplete)?;
+        if self.world != Some(world) {
+            return Err(SelectionError::OtherWorld);
+        }
+        // Players standing above the build limit or in the void can set corners out there
+        if region.min.0.y < world.min_y() || region.max.0.y >= world.min_y() + world.height() {
+            return Err(SelectionError::OutOfWorld);
+        }
+        if region.volume() > ADVANCED_CONFIG.commands.max_selection_blocks as u64 {
+            return Err(SelectionError::TooLarge(region.volume()));
+        }
+        Ok(region)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SelectionError {
+    Incomplete,
+    OtherWorld,
+    OutOfWorld,
+    TooLarge(u64),
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Incomplete => write!(
+                f,
+                "Set both corners of your selection with /pos1 and /pos2 first"
+            ),
+            Self::OtherWorld => write!(f, "Your selection is in another world"),
+            Self::OutOfWorld => write!(f, "Your selection reaches outside of the world"),
+            Self::TooLarge(volume) => write!(
+                f,
+                "Your selection has {volume} blocks, at most {} can be edited at once",
+                ADVANCED_CONFIG.commands.max_selection_blocks
+            ),
+        }
+    }
+}
+
+impl World {
+    /// Calls `f` with the state id of every block in the region. Each chunk is looked up once,
+    /// instead of once per block like with [`World::get_block_state_id`]. Blocks outside of the
+    /// height of the world are skipped
+    pub async fn for_each_block_state(&self, region: &Region, mut f: impl FnMut(BlockPos, u16)) {
+        let (mut min, mut max) = (region.min.0, region.max.0);
+        min.y = min.y.max(self.min_y);
+        max.y = max.y.min(self.max_y() - 1);
+        if min.y > max.y {
+            return;
+        }
+        for chunk_x in (min.x >> 4)..=(max.x >> 4) {
+            for chunk_z in (min.z >> 4)..=(max.z >> 4) {
+                let chunk_pos = Vector2::new(chunk_x, chunk_z);
+                let chunk = match self.level.get_loaded_chunk(&chunk_pos) {
+                    Some(chunk) => chunk,
+                    None => self.receive_chunk(chunk_pos).await,
+                };
+                let chunk = chunk.read().await;
+                let chunk_region = Region::new(
+                    BlockPos(Vector3::new(
+                        min.x.max(chunk_x << 4),
+                        min.y,
+                        min.z.max(chunk_z << 4),
+                    )),
+                    BlockPos(Vector3::new(
+                        max.x.min((chunk_x << 4) + 15),
+                        max.y,
+                        max.z.min((chunk_z << 4) + 15),
+                    )),
+                );
+                for position in chunk_region.positions() {
+                    let (_, relative) = position.chunk_and_chunk_relative_position();
+                    if let Some(state_id) = chunk
+                        .subchunks
+                        .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                    {
+                        f(position, state_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A box of blocks, both corners are included
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Region {
+    pub min: BlockPos,
+    pub max: BlockPos,
+}
+
+impl Region {
+    /// The region between two opposite corners, in any order
+    #[must_use]
+    pub fn new(a: BlockPos, b: BlockPos) -> Self {
+        let (a, b) = (a.0, b.0);
+        Self {
+            min: BlockPos(Vector3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z))),
+            max: BlockPos(Vector3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z))),
+        }
+    }
+
+    #[must_use]
+    pub fn volume(&self) -> u64 {
+        let length = |min: i32, max: i32| u64::from(min.abs_diff(max)) + 1;
+        length(self.min.0.x, self.max.0.x)
+            * length(self.min.0.y, self.max.0.y)
+            * length(self.min.0.z, self.max.0.z)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = BlockPos> {
+        let (min, max) = (self.min.0, self.max.0);
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y)
+                .flat_map(move |y| (min.z..=max.z).map(move |z| BlockPos(Vector3::new(x, y, z))))
+        })
+    }
+
+    /// Whether the position is on one of the four vertical sides of the region
+    #[must_use]
+    pub const fn is_wall(&self, position: BlockPos) -> bool {
+        let (min, max, position) = (self.min.0, self.max.0, position.0);
+        position.x == min.x || position.x == max.x || position.z == min.z || position.z == max.z
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::{block::block_registry::get_block, dimension::Dimension};
+
+    use super::{Corner, Region, Selection, SelectionError};
+    use crate::data::test_folder::TestFolder;
+    use crate::world::World;
+
+    fn pos(x: i32, y: i32, z: i32) -> BlockPos {
+        BlockPos(Vector3::new(x, y, z))
+    }
+
+    #[test]
+    fn region_bounds_are_normalized() {
+        let region = Region::new(pos(5, -3, 2), pos(-1, 10, 2));
+        assert_eq!(region.min, pos(-1, -3, 2));
+        assert_eq!(region.max, pos(5, 10, 2));
+        assert_eq!(region, Region::new(pos(-1, 10, 2), pos(5, -3, 2)));
+        assert_eq!(region.volume(), 7 * 14);
+        assert_eq!(region.positions().count(), 7 * 14);
+        assert!(region.positions().all(|p| p.0.x >= -1 && p.0.x <= 5));
+    }
+
+    #[test]
+    fn selection_needs_both_corners() {
+        let selection = Selection::default().with_corner(
+            DimensionType::Overworld,
+            Corner::Second,
+            pos(1, 2, 3),
+        );
+        assert_eq!(selection.region(), None);
+        let selection =
+            selection.with_corner(DimensionType::Overworld, Corner::First, pos(1, 2, 3));
+        assert_eq!(selection.region().map(|region| region.volume()), Some(1));
+    }
+
+    #[test]
+    fn selection_is_bound_to_its_world() {
+        let selection = Selection::default()
+            .with_corner(DimensionType::Overworld, Corner::First, pos(0, 0, 0))
+            .with_corner(DimensionType::Overworld, Corner::Second, pos(1, 1, 1));
+        assert!(selection.editable_region(DimensionType::Overworld).is_ok());
+        assert_eq!(
+            selection.editable_region(DimensionType::TheNether),
+            Err(SelectionError::OtherWorld)
+        );
+        // a corner set in another world starts a new selection
+        let selection =
+            selection.with_corner(DimensionType::TheNether, Corner::First, pos(0, 0, 0));
+        assert_eq!(selection.region(), None);
+    }
+
+    #[test]
+    fn selection_stays_inside_the_world() {
+        let world = DimensionType::Overworld;
+        let max_y = world.min_y() + world.height();
+        let selection = Selection::default()
+            .with_corner(world, Corner::First, pos(0, world.min_y(), 0))
+            .with_corner(world, Corner::Second, pos(1, max_y - 1, 1));
+        assert!(selection.editable_region(world).is_ok());
+        // corners set standing above the build limit or in the void
+        for y in [max_y, world.min_y() - 1] {
+            assert_eq!(
+                selection
+                    .with_corner(world, Corner::Second, pos(1, y, 1))
+                    .editable_region(world),
+                Err(SelectionError::OutOfWorld)
+            );
+        }
+    }
+
+    #[test]
+    fn region_walls() {
+        let region = Region::new(pos(0, 0, 0), pos(4, 3, 4));
+        assert!(region.is_wall(pos(0, 2, 2)));
+        assert!(region.is_wall(pos(3, 1, 4)));
+        assert!(!region.is_wall(pos(2, 0, 2)));
+        assert_eq!(
+            region.positions().filter(|p| region.is_wall(*p)).count(),
+            16 * 4
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_block_states_across_chunks() {
+        let folder = TestFolder::new("selection");
+        let world = World::load(
+            Dimension::OverWorld.into_level(folder.to_path_buf()),
+            DimensionType::Overworld,
+        );
+        let stone = get_block("stone").unwrap().default_state_id;
+        world.set_block_state(&pos(-1, 100, 15), stone).await;
+        world.set_block_state(&pos(16, 101, 0), stone).await;
+
+        let region = Region::new(pos(-2, 100, 14), pos(17, 101, 17));
+        let mut states = Vec::new();
+        world
+            .for_each_block_state(&region, |position, state_id| {
+                states.push((position, state_id));
+            })
+            .await;
+        assert_eq!(states.len() as u64, region.volume());
+        for (position, state_id) in states {
+            assert_eq!(world.get_block_state_id(&position).await.unwrap(), state_id);
+        }
+
+        // Only the blocks inside of the world are read
+        let region = Region::new(pos(0, world.max_y() - 1, 0), pos(0, world.max_y() + 5, 0));
+        let mut read = 0;
+        world.for_each_block_state(&region, |_, _| read += 1).await;
+        assert_eq!(read, 1);
+    }
+}
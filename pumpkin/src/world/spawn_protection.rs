@@ -0,0 +1,210 @@
+use pumpkin_config::BASIC_CONFIG;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2},
+    permission::PermissionLvl,
+};
+
+use crate::entity::player::Player;
+
+use super::{World, WORLD_SPAWN};
+
+impl World {
+    /// Whether the spawn protection prevents the player from changing the world at `pos`.
+    /// Every action changing blocks or entities has to check this, like breaking and placing blocks,
+    /// attacking entities and using buckets
+    pub fn is_protected(&self, pos: &BlockPos, player: &Player) -> bool {
+        // Like in vanilla only the spawn of the overworld is protected
+        matches!(self.dimension_type, DimensionType::Overworld)
+            && protects(
+                WORLD_SPAWN,
+                self.spawn_protection,
+                pos,
+                player.permission_lvl.load(),
+                BASIC_CONFIG.spawn_protection_bypass_level,
+            )
+    }
+}
+
+/// The protected area is a square around the spawn, operators can always change it
+fn protects(
+    spawn: Vector2<i32>,
+    radius: u32,
+    pos: &BlockPos,
+    permission_lvl: PermissionLvl,
+    bypass_level: PermissionLvl,
+) -> bool {
+    if radius == 0 || permission_lvl >= bypass_level {
+        return false;
+    }
+    let distance = (pos.0.x - spawn.x)
+        .unsigned_abs()
+        .max((pos.0.z - spawn.z).unsigned_abs());
+    distance <= radius
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_protocol::{
+        server::play::{SInteract, SPlayerAction},
+        VarInt,
+    };
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::{
+        math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+        permission::PermissionLvl,
+        GameMode,
+    };
+    use pumpkin_world::{block::block_registry::get_block, dimension::Dimension};
+
+    use super::protects;
+    use crate::data::test_folder::TestFolder;
+    use crate::{
+        entity::player::test_player_in,
+        server::Server,
+        world::{World, WORLD_SPAWN},
+    };
+
+    const SPAWN: Vector2<i32> = Vector2::new(10, 10);
+
+    fn protected_for(x: i32, z: i32, permission_lvl: PermissionLvl) -> bool {
+        protects(
+            SPAWN,
+            16,
+            &BlockPos(Vector3::new(x, 64, z)),
+            permission_lvl,
+            PermissionLvl::One,
+        )
+    }
+
+    #[test]
+    fn breaking_and_placing_blocks() {
+        assert!(protected_for(10, 10, PermissionLvl::Zero));
+        // the height does not matter, only the distance to the spawn
+        assert!(protects(
+            SPAWN,
+            16,
+            &BlockPos(Vector3::new(26, -64, -6)),
+            PermissionLvl::Zero,
+            PermissionLvl::One
+        ));
+        assert!(!protected_for(27, 10, PermissionLvl::Zero));
+        assert!(!protected_for(10, -7, PermissionLvl::Zero));
+    }
+
+    #[test]
+    fn operators_bypass() {
+        assert!(!protected_for(10, 10, PermissionLvl::One));
+        assert!(!protected_for(10, 10, PermissionLvl::Four));
+        assert!(protects(
+            SPAWN,
+            16,
+            &BlockPos(Vector3::new(10, 64, 10)),
+            PermissionLvl::Two,
+            PermissionLvl::Three
+        ));
+    }
+
+    #[test]
+    fn disabled_protection() {
+        assert!(!protects(
+            SPAWN,
+            0,
+            &BlockPos(Vector3::new(10, 64, 10)),
+            PermissionLvl::Zero,
+            PermissionLvl::One
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_breaking_blocks_at_spawn() {
+        let folder = TestFolder::new("spawn-protection");
+        let mut world = World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        );
+        world.spawn_protection = 16;
+        let world = Arc::new(world);
+        let server = Server::with_world_folder(folder.join("server"));
+        let player = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        player.gamemode.store(GameMode::Creative);
+        player.permission_lvl.store(PermissionLvl::Zero);
+        player.set_client_loaded(true);
+
+        let position = BlockPos(Vector3::new(WORLD_SPAWN.x, 100, WORLD_SPAWN.z));
+        player.living_entity.set_pos(Vector3::new(
+            f64::from(WORLD_SPAWN.x) + 0.5,
+            101.0,
+            f64::from(WORLD_SPAWN.z) + 2.5,
+        ));
+        let stone = get_block("stone").unwrap().default_state_id;
+        world.set_block_state(&position, stone).await;
+        let start_digging = |sequence| SPlayerAction {
+            status: VarInt(0),
+            location: position,
+            face: 1,
+            sequence: VarInt(sequence),
+        };
+
+        player
+            .clone()
+            .handle_player_action(start_digging(1), &server)
+            .await;
+        assert_eq!(world.get_block_state_id(&position).await.unwrap(), stone);
+
+        // Operators can still build at the spawn
+        player.permission_lvl.store(PermissionLvl::Four);
+        player
+            .clone()
+            .handle_player_action(start_digging(2), &server)
+            .await;
+        assert_eq!(world.get_block_state_id(&position).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn rejects_attacking_players_at_spawn() {
+        let folder = TestFolder::new("spawn-protection-attack");
+        let mut world = World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        );
+        world.spawn_protection = 16;
+        let world = Arc::new(world);
+        let attacker = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        attacker.permission_lvl.store(PermissionLvl::Zero);
+        attacker.set_client_loaded(true);
+        let victim = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        world
+            .current_players
+            .lock()
+            .await
+            .insert(victim.gameprofile.id, victim.clone());
+        let health = victim.living_entity.health.load();
+        let attack = || SInteract {
+            entity_id: VarInt(victim.entity_id()),
+            typ: VarInt(1),
+            target_position: None,
+            hand: None,
+            sneaking: false,
+        };
+
+        // Players are protected by the block they are in
+        victim.living_entity.set_pos(Vector3::new(
+            f64::from(WORLD_SPAWN.x),
+            100.0,
+            f64::from(WORLD_SPAWN.z + 16),
+        ));
+        attacker.handle_interact(attack()).await;
+        assert_eq!(victim.living_entity.health.load(), health);
+
+        victim.living_entity.set_pos(Vector3::new(
+            f64::from(WORLD_SPAWN.x),
+            100.0,
+            f64::from(WORLD_SPAWN.z + 17),
+        ));
+        attacker.handle_interact(attack()).await;
+        assert!(victim.living_entity.health.load() < health);
+    }
+}
@@ -0,0 +1,116 @@
+use pumpkin_util::{
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+    random::RandomImpl,
+};
+
+use crate::entity::player::Player;
+
+use super::{World, WORLD_SPAWN};
+
+/// How many random columns are tried before falling back to the exact spawn
+const ATTEMPTS: usize = 10;
+/// How far up and down from the surface a safe position is searched for
+const SAFE_RANGE: i32 = 4;
+
+impl World {
+    /// Where a joining player is teleported to with which rotation. Returning players continue
+    /// where they left, every other player is spread around the spawn, also when data of them
+    /// exists that could not be restored
+    pub async fn join_position(&self, player: &Player, radius: u32) -> (Vector3<f64>, f32, f32) {
+        if player.restored_from_save {
+            let entity = &player.living_entity.entity;
+            return (entity.pos.load(), entity.yaw.load(), entity.pitch.load());
+        }
+        (
+            self.find_join_position(WORLD_SPAWN, radius).await,
+            10.0,
+            10.0,
+        )
+    }
+
+    /// Finds where a player joining for the first time is placed, on a safe surface block within
+    /// `radius` blocks around the spawn so players do not pile up
+    pub async fn find_join_position(&self, spawn: Vector2<i32>, radius: u32) -> Vector3<f64> {
+        for _ in 0..if radius == 0 { 0 } else { ATTEMPTS } {
+            let offset = spread_offset(&mut *self.rng(), radius);
+            let column = Vector2::new(spawn.x + offset.x, spawn.z + offset.z);
+            let top = self.get_top_block(column).await;
+            let surface = BlockPos(Vector3::new(column.x, top + 1, column.z));
+            if let Some(pos) = self.find_safe_position(surface, SAFE_RANGE).await {
+                return Vector3::new(
+                    f64::from(pos.0.x) + 0.5,
+                    f64::from(pos.0.y),
+                    f64::from(pos.0.z) + 0.5,
+                );
+            }
+        }
+        let top = self.get_top_block(spawn).await;
+        Vector3::new(f64::from(spawn.x), f64::from(top + 1), f64::from(spawn.z))
+    }
+}
+
+/// A random offset within a circle of `radius` blocks
+fn spread_offset(rng: &mut impl RandomImpl, radius: u32) -> Vector2<i32> {
+    let radius = radius.min(i32::MAX as u32 / 2) as i32;
+    loop {
+        let x = rng.next_inbetween_i32(-radius, radius);
+        let z = rng.next_inbetween_i32(-radius, radius);
+        if i64::from(x).pow(2) + i64::from(z).pow(2) <= i64::from(radius).pow(2) {
+            return Vector2::new(x, z);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::random::{xoroshiro128::Xoroshiro, RandomImpl};
+    use pumpkin_world::dimension::Dimension;
+
+    use super::spread_offset;
+    use crate::data::test_folder::TestFolder;
+    use crate::entity::player::test_player_in;
+    use crate::server::Server;
+    use crate::world::{World, WORLD_SPAWN};
+
+    #[test]
+    fn placement_within_radius() {
+        let mut rng = Xoroshiro::from_seed(42);
+        for radius in [1, 5, 10, 100] {
+            for _ in 0..1000 {
+                let offset = spread_offset(&mut rng, radius);
+                let radius = i64::from(radius);
+                assert!(i64::from(offset.x).pow(2) + i64::from(offset.z).pow(2) <= radius * radius);
+            }
+        }
+        assert_eq!(spread_offset(&mut rng, 0).x, 0);
+    }
+
+    #[tokio::test]
+    async fn spreads_players_that_were_not_restored() {
+        let folder = TestFolder::new("spawn-radius");
+        let server = Server::with_world_folder(folder.join("server"));
+        let world = Arc::new(World::load(
+            Dimension::OverWorld.into_level(folder.join("world")),
+            DimensionType::Overworld,
+        ));
+        let player = test_player_in(world.clone(), uuid::Uuid::new_v4()).await;
+        // Data was saved before, but the player was not restored from it
+        server.player_data_storage.save(&player).await.unwrap();
+        assert!(server.player_data_storage.has_data(&player.gameprofile.id));
+        assert!(!player.restored_from_save);
+
+        let mut spread = false;
+        for _ in 0..4 {
+            let (position, _, _) = world.join_position(&player, 10).await;
+            let x = position.x - f64::from(WORLD_SPAWN.x);
+            let z = position.z - f64::from(WORLD_SPAWN.z);
+            // the middle of the block may be up to half a block further away
+            assert!(x.hypot(z) <= 11.0);
+            spread |= x != 0.0 || z != 0.0;
+        }
+        assert!(spread);
+    }
+}
@@ -0,0 +1,136 @@
+use std::ops::RangeInclusive;
+
+use pumpkin_protocol::client::play::{CGameEvent, GameEvent};
+use pumpkin_util::random::RandomImpl;
+
+use super::World;
+
+/// How long each weather lasts when it changes on its own or no duration is given, in ticks
+const CLEAR_DURATION: RangeInclusive<i32> = 12_000..=180_000;
+const RAIN_DURATION: RangeInclusive<i32> = 12_000..=24_000;
+const THUNDER_DURATION: RangeInclusive<i32> = 3_600..=15_600;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeatherType {
+    Clear,
+    Rain,
+    Thunder,
+}
+
+impl WeatherType {
+    /// The weather that follows once this one runs out
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Clear => Self::Rain,
+            Self::Rain | Self::Thunder => Self::Clear,
+        }
+    }
+
+    /// A vanilla-like random duration in ticks
+    pub fn random_duration(self, rng: &mut impl RandomImpl) -> i32 {
+        let range = match self {
+            Self::Clear => CLEAR_DURATION,
+            Self::Rain => RAIN_DURATION,
+            Self::Thunder => THUNDER_DURATION,
+        };
+        rng.next_inbetween_i32(*range.start(), *range.end())
+    }
+
+    /// The game events making the client show this weather
+    #[must_use]
+    pub fn game_events(self) -> [CGameEvent; 3] {
+        let (raining, thundering) = match self {
+            Self::Clear => (false, false),
+            Self::Rain => (true, false),
+            Self::Thunder => (true, true),
+        };
+        [
+            CGameEvent::new(
+                if raining {
+                    GameEvent::BeginRaining
+                } else {
+                    GameEvent::EndRaining
+                },
+                0.0,
+            ),
+            CGameEvent::new(GameEvent::RainLevelChange, f32::from(u8::from(raining))),
+            CGameEvent::new(
+                GameEvent::ThunderLevelChange,
+                f32::from(u8::from(thundering)),
+            ),
+        ]
+    }
+}
+
+/// The current weather of a world
+pub struct Weather {
+    pub weather_type: WeatherType,
+    /// Ticks until the weather changes on its own, like vanilla's `rainTime` and `clearWeatherTime`
+    pub duration: i32,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            weather_type: WeatherType::Clear,
+            duration: *CLEAR_DURATION.start(),
+        }
+    }
+}
+
+impl Weather {
+    /// Counts down the duration, returns the weather that follows once it ran out
+    pub fn tick(&mut self) -> Option<WeatherType> {
+        self.duration -= 1;
+        (self.duration <= 0).then(|| self.weather_type.next())
+    }
+}
+
+impl World {
+    /// Changes the weather for `duration` ticks, or a random duration if none is given
+    pub async fn set_weather(&self, weather_type: WeatherType, duration: Option<i32>) {
+        let duration = duration.unwrap_or_else(|| weather_type.random_duration(&mut *self.rng()));
+        let previous = {
+            let mut weather = self.weather.lock().await;
+            let previous = weather.weather_type;
+            *weather = Weather {
+                weather_type,
+                duration,
+            };
+            previous
+        };
+        if previous != weather_type {
+            for packet in weather_type.game_events() {
+                self.broadcast_packet_all(&packet).await;
+            }
+        }
+    }
+
+    /// Lets the weather change on its own, if the `do_weather_cycle` rule allows it
+    pub async fn tick_weather(&self, do_weather_cycle: bool) {
+        if !do_weather_cycle {
+            return;
+        }
+        let next = self.weather.lock().await.tick();
+        if let Some(next) = next {
+            self.set_weather(next, None).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Weather, WeatherType};
+
+    #[test]
+    fn weather_runs_out() {
+        let mut weather = Weather {
+            weather_type: WeatherType::Thunder,
+            duration: 2,
+        };
+        assert_eq!(weather.tick(), None);
+        assert_eq!(weather.tick(), Some(WeatherType::Clear));
+        assert_eq!(WeatherType::Clear.next(), WeatherType::Rain);
+    }
+}